@@ -0,0 +1,55 @@
+//! Generated input shared by the benches
+
+const WORDS: &[&str] = &[
+    "stocks",
+    "rally",
+    "fed",
+    "rates",
+    "earnings",
+    "beat",
+    "oil",
+    "slides",
+    "bitcoin",
+    "jumps",
+    "inflation",
+    "cools",
+    "chipmaker",
+    "guidance",
+    "merger",
+    "talks",
+    "yields",
+    "climb",
+];
+
+/// An RSS feed with `items` entries carrying permalink GUIDs, HTML
+/// descriptions and categories, like the larger publisher feeds
+pub fn feed(items: usize) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\"?><rss version=\"2.0\"><channel>\
+         <title>Bench</title><link>https://example.com</link>\
+         <description>Bench feed</description><language>en-us</language>",
+    );
+    for i in 0..items {
+        xml.push_str(&format!(
+            "<item><title>{} story {i}</title>\
+             <link>https://example.com/markets/{i}.html</link>\
+             <guid isPermaLink=\"true\">https://example.com/markets/{i}.html</guid>\
+             <pubDate>Tue, 01 Oct 2024 12:{:02}:00 GMT</pubDate>\
+             <description>&lt;p&gt;Shares of &lt;b&gt;$AAPL&lt;/b&gt; moved, \
+             story {i}.&lt;/p&gt;</description>\
+             <category>Markets</category><category>Earnings</category></item>",
+            headline(i),
+            i % 60
+        ));
+    }
+    xml.push_str("</channel></rss>");
+    xml
+}
+
+/// A headline of four words that varies with `i`
+pub fn headline(i: usize) -> String {
+    (0..4)
+        .map(|k| WORDS[(i * 7 + k * 5) % WORDS.len()])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
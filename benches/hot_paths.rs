@@ -0,0 +1,149 @@
+//! Criterion benchmarks for the paths a refresh and a keystroke go through:
+//! fetching, parsing and storing a feed, filtering a large list, and drawing
+//! it. Run before and after a performance change and compare:
+//!
+//! ```text
+//! cargo bench --bench hot_paths
+//! ```
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+use chrono::{Duration, TimeZone, Utc};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+use news_hub::adaptors::{NewsAdaptor, RssAdaptor};
+use news_hub::clock::ManualClock;
+use news_hub::ui::{Action, Component, NewsListComponent, RenderState};
+use news_hub::{NewsDB, NewsItem};
+
+mod common;
+
+use common::{feed, headline};
+
+/// Entries in the fetched feed
+const FEED_ITEMS: usize = 1_000;
+/// Articles in the list being filtered and drawn
+const STORED_ITEMS: usize = 50_000;
+
+const SOURCES: &[&str] = &["CNBC", "Reuters", "MarketWatch", "Hacker News", "Benzinga"];
+
+/// Serve `body` to every request on a local port; returns the feed URL
+fn serve(body: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+    url
+}
+
+fn stored_items(count: usize) -> Vec<NewsItem> {
+    let now = Utc.with_ymd_and_hms(2024, 10, 1, 12, 0, 0).unwrap();
+    (0..count)
+        .map(|i| {
+            let source = SOURCES[i % SOURCES.len()];
+            NewsItem {
+                id: format!("bench-{}", i),
+                source: source.to_string(),
+                source_id: source.to_lowercase().replace(' ', "-"),
+                title: format!("{} story {}", headline(i), i),
+                url: format!("https://example.com/{}", i),
+                summary: format!("{} and {}", headline(i + 3), headline(i + 11)),
+                published: now - Duration::minutes(i as i64),
+                updated_at: now,
+                read: i % 3 == 0,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn ingest(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let adaptor = RssAdaptor::new(serve(feed(FEED_ITEMS)), "Bench".to_string());
+
+    let mut group = c.benchmark_group("ingest");
+    group.throughput(Throughput::Elements(FEED_ITEMS as u64));
+    group.sample_size(20);
+    group.bench_function("fetch_parse_insert_1k", |b| {
+        b.iter_batched(
+            || {
+                let dir = tempfile::tempdir().unwrap();
+                let db = NewsDB::new(dir.path().join("news.db").to_str().unwrap()).unwrap();
+                (dir, db)
+            },
+            |(_dir, db)| {
+                let (items, _) = runtime.block_on(adaptor.fetch()).unwrap();
+                assert_eq!(items.len(), FEED_ITEMS);
+                for item in &items {
+                    db.insert(item).unwrap();
+                }
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+fn filter(c: &mut Criterion) {
+    let items = stored_items(STORED_ITEMS);
+    let mut list = NewsListComponent::new(items.clone());
+
+    let mut group = c.benchmark_group("filter");
+    group.throughput(Throughput::Elements(STORED_ITEMS as u64));
+    // Alternate queries so each iteration refilters the whole list
+    let mut flip = false;
+    group.bench_function("list_search_50k", |b| {
+        b.iter(|| {
+            flip = !flip;
+            let query = if flip { "earnings beat" } else { "reuters" };
+            list.update(&Action::SearchQueryChanged(query.to_string()));
+            list.filtered_items().len()
+        })
+    });
+
+    let dir = tempfile::tempdir().unwrap();
+    let db = NewsDB::new(dir.path().join("news.db").to_str().unwrap()).unwrap();
+    for item in &items {
+        db.insert(item).unwrap();
+    }
+    let since = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+    group.sample_size(20);
+    group.bench_function("db_search_50k", |b| {
+        b.iter(|| db.search("earnings beat", since).unwrap().len())
+    });
+    group.finish();
+}
+
+fn render(c: &mut Criterion) {
+    let mut list = NewsListComponent::new(stored_items(STORED_ITEMS));
+    list.set_clock(ManualClock::new(Utc.with_ymd_and_hms(2024, 10, 1, 12, 0, 0).unwrap()).shared());
+    let mut term = Terminal::new(TestBackend::new(160, 50)).unwrap();
+    let state = RenderState::default();
+
+    c.bench_function("render/news_list_50k", |b| {
+        b.iter(|| {
+            term.draw(|f| {
+                let area = f.size();
+                list.render(f, area, &state)
+            })
+            .unwrap();
+        })
+    });
+}
+
+criterion_group!(benches, ingest, filter, render);
+criterion_main!(benches);
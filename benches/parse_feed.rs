@@ -0,0 +1,34 @@
+//! Time to parse a large catch-all feed, the CPU-bound part of a refresh.
+//!
+//! ```text
+//! cargo bench --bench parse_feed
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use news_hub::adaptors::parse_feed;
+
+mod common;
+
+use common::feed;
+
+const ITEMS: usize = 5_000;
+
+fn parse(c: &mut Criterion) {
+    let xml = feed(ITEMS);
+
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(xml.len() as u64));
+    group.sample_size(20);
+    group.bench_function("parse_feed_5k", |b| {
+        b.iter(|| {
+            let (items, dropped) =
+                parse_feed(xml.as_bytes(), "application/rss+xml", "Bench", true).unwrap();
+            assert_eq!((items.len(), dropped), (ITEMS, 0));
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
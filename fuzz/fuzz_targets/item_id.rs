@@ -0,0 +1,19 @@
+//! Ids for feed entries from any source name, GUID and link.
+//!
+//! ```text
+//! cargo +nightly fuzz run item_id
+//! ```
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use news_hub::models::{feed_item_id, source_id};
+
+fuzz_target!(|input: (&str, Option<&str>, bool, &str)| {
+    let (source, guid, permalink, url) = input;
+    let id = feed_item_id(source, guid, permalink, url);
+    assert_eq!(id, feed_item_id(source, guid, permalink, url));
+    let rest = id
+        .strip_prefix(&format!("{}-", source_id(source)))
+        .expect("id is prefixed by the source id");
+    assert!(rest.starts_with("guid-") || rest.starts_with("hash-"));
+});
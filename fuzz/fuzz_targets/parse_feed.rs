@@ -0,0 +1,22 @@
+//! Feed bodies as fetched, with and without HTML descriptions. Seed it with
+//! the corpus of real-world quirks:
+//!
+//! ```text
+//! cargo +nightly fuzz run parse_feed testdata/feeds
+//! ```
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use news_hub::adaptors::parse_feed;
+
+fuzz_target!(|data: &[u8]| {
+    for html in [false, true] {
+        if let Ok((items, _)) = parse_feed(data, "application/rss+xml", "Fuzz", html) {
+            for item in &items {
+                assert!(!item.title.trim().is_empty());
+                assert!(!item.url.trim().is_empty());
+                assert!(item.id.starts_with("fuzz-"));
+            }
+        }
+    }
+});
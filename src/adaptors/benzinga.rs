@@ -4,9 +4,21 @@ use chrono::{TimeZone, Utc};
 use reqwest::Client;
 use serde::Deserialize;
 
-use crate::models::NewsItem;
+use crate::error::NewsHubError;
+use crate::models::{api_item_id, source_id, NewsItem, SourceKind};
 use super::NewsAdaptor;
 
+#[derive(Deserialize)]
+struct BenzName {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct BenzImage {
+    size: String,
+    url: String,
+}
+
 #[derive(Deserialize)]
 struct BenzArticle {
     id: i64,
@@ -14,6 +26,20 @@ struct BenzArticle {
     url: String,
     description: Option<String>,
     updated: i64,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    teaser: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    channels: Vec<BenzName>,
+    #[serde(default)]
+    tags: Vec<BenzName>,
+    #[serde(default)]
+    stocks: Vec<BenzName>,
+    #[serde(default)]
+    image: Vec<BenzImage>,
 }
 
 #[derive(Deserialize)]
@@ -48,12 +74,18 @@ impl NewsAdaptor for BenzingaAdaptor {
     async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
         let url = "https://api.benzinga.com/api/v2/news";
 
-        let resp: BenzResp = self.client
+        let response = self.client
             .get(url)
             .query(&[("token", self.api_key.as_str()), ("pagesize", "50")])
             .send()
             .await
-            .context("Failed to connect to Benzinga API")?
+            .context("Failed to connect to Benzinga API")?;
+        match response.status().as_u16() {
+            200..=299 => {}
+            401 | 403 => return Err(NewsHubError::Auth("invalid API key".to_string()).into()),
+            _ => return Err(NewsHubError::from_response(&response, None).into()),
+        }
+        let resp: BenzResp = response
             .json()
             .await
             .context("Failed to parse Benzinga response")?;
@@ -63,15 +95,27 @@ impl NewsAdaptor for BenzingaAdaptor {
         let items = resp.articles
             .into_iter()
             .map(|n| NewsItem {
-                id: format!("benzinga-{}", n.id),
+                id: api_item_id("Benzinga", n.id),
                 source: "Benzinga".into(),
+                source_id: source_id("Benzinga"),
                 title: n.title,
                 url: n.url,
-                summary: n.description.unwrap_or_default(),
+                summary: n.description.or(n.teaser).unwrap_or_default(),
                 published: Utc.timestamp_opt(n.updated, 0)
                     .single()
-                    .unwrap_or_else(|| now),
+                    .unwrap_or(now),
                 updated_at: now,
+                author: n.author.filter(|a| !a.is_empty()),
+                tags: n.channels.into_iter().chain(n.tags).map(|c| c.name).collect(),
+                tickers: n.stocks.into_iter().map(|s| s.name).collect(),
+                // Prefer the large rendition when several sizes are listed
+                image_url: n.image.iter()
+                    .find(|i| i.size == "large")
+                    .or(n.image.first())
+                    .map(|i| i.url.clone()),
+                content: n.body.filter(|b| !b.is_empty()),
+                source_kind: SourceKind::Api,
+                ..Default::default()
             })
             .collect();
 
@@ -0,0 +1,148 @@
+/// A curated set of RSS feeds, selected by name under `[app] bundles`
+#[derive(Debug)]
+pub struct SourceBundle {
+    pub name: &'static str,
+    /// Bumped whenever the bundle's feeds change
+    pub version: u32,
+    pub description: &'static str,
+    /// Items are tagged with the coins they mention
+    pub tag_coins: bool,
+    /// URL and source name of each feed
+    pub feeds: &'static [(&'static str, &'static str)],
+}
+
+/// Bundles used when `[app] bundles` is not set
+pub const DEFAULT_BUNDLES: &[&str] = &["us-markets", "world", "crypto", "macro"];
+
+/// Built-in bundles; none of them need an API key
+pub const BUNDLES: &[SourceBundle] = &[
+    SourceBundle {
+        name: "us-markets",
+        version: 1,
+        description: "US market news and earnings",
+        tag_coins: false,
+        feeds: &[
+            ("https://www.marketwatch.com/rss/topstories", "MarketWatch"),
+            ("https://feeds.bloomberg.com/markets/news.rss", "Bloomberg"),
+            (
+                "https://www.cnbc.com/id/100003114/device/rss/rss.html",
+                "CNBC",
+            ),
+            ("https://www.barrons.com/rss/topstories", "Barrons"),
+            (
+                "https://www.investing.com/rss/news_25.rss",
+                "Investing.com Stocks",
+            ),
+            (
+                "https://www.investing.com/rss/news_1062.rss",
+                "Investing.com Earnings",
+            ),
+            // investing.com catch-all endpoint
+            (
+                "https://www.investing.com/rss/news.rss",
+                "Investing.com Latest",
+            ),
+        ],
+    },
+    SourceBundle {
+        name: "world",
+        version: 1,
+        description: "World news from the major papers",
+        tag_coins: false,
+        feeds: &[
+            ("https://www.ft.com/rss/home/world", "Financial Times"),
+            // looks discontinued
+            // ("https://www.reuters.com/rss/worldNews", "Reuters"),
+            (
+                "https://feeds.a.dj.com/rss/RSSWorldNews.xml",
+                "Wall Street Journal",
+            ),
+            (
+                "https://rss.nytimes.com/services/xml/rss/nyt/World.xml",
+                "New York Times",
+            ),
+        ],
+    },
+    SourceBundle {
+        name: "crypto",
+        version: 2,
+        description: "Crypto markets, tagged by coin",
+        tag_coins: true,
+        feeds: &[
+            (
+                "https://www.investing.com/rss/news_301.rss",
+                "Investing.com Crypto",
+            ),
+            (
+                "https://www.coindesk.com/arc/outboundfeeds/rss/",
+                "CoinDesk",
+            ),
+            ("https://cointelegraph.com/rss", "Cointelegraph"),
+            ("https://decrypt.co/feed", "Decrypt"),
+        ],
+    },
+    SourceBundle {
+        name: "tech",
+        version: 1,
+        description: "Technology industry news",
+        tag_coins: false,
+        feeds: &[
+            ("https://hnrss.org/frontpage", "Hacker News"),
+            ("https://www.theverge.com/rss/index.xml", "The Verge"),
+            (
+                "https://feeds.arstechnica.com/arstechnica/index",
+                "Ars Technica",
+            ),
+        ],
+    },
+    SourceBundle {
+        name: "macro",
+        version: 1,
+        description: "Central banks, rates and currencies",
+        tag_coins: false,
+        feeds: &[
+            (
+                "https://www.investing.com/rss/news_1.rss",
+                "Investing.com Forex",
+            ),
+            (
+                "https://www.federalreserve.gov/feeds/press_all.xml",
+                "Federal Reserve",
+            ),
+            ("https://www.ecb.europa.eu/rss/press.html", "ECB"),
+        ],
+    },
+];
+
+pub fn find_bundle(name: &str) -> Option<&'static SourceBundle> {
+    BUNDLES.iter().find(|b| b.name == name)
+}
+
+/// Feeds of the named bundles in order, each once. Unknown names are
+/// skipped; `config check` reports them.
+pub fn bundle_feeds(names: &[String]) -> Vec<(&'static str, &'static str)> {
+    let mut feeds: Vec<(&str, &str)> = Vec::new();
+    for bundle in names.iter().filter_map(|name| find_bundle(name)) {
+        for feed in bundle.feeds {
+            if !feeds.iter().any(|(_, name)| *name == feed.1) {
+                feeds.push(*feed);
+            }
+        }
+    }
+    feeds
+}
+
+/// Whether `name` is a feed of a bundle that tags items by coin
+pub fn is_coin_tagged(name: &str) -> bool {
+    BUNDLES
+        .iter()
+        .filter(|b| b.tag_coins)
+        .any(|b| b.feeds.iter().any(|(_, feed)| *feed == name))
+}
+
+/// Whether `name` is a feed in any built-in bundle
+pub fn is_builtin_feed(name: &str) -> bool {
+    BUNDLES
+        .iter()
+        .any(|b| b.feeds.iter().any(|(_, feed)| *feed == name))
+}
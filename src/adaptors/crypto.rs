@@ -0,0 +1,54 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::{NewsAdaptor, RssAdaptor};
+use crate::models::{detect_coins, NewsItem, SourceKind};
+
+/// A crypto news feed whose items are tagged with the coins they mention,
+/// so tag and ticker filters can narrow the feed to one asset. Coin symbols
+/// come first in the tags, where `t` picks the filter from.
+pub struct CryptoAdaptor {
+    feed: RssAdaptor,
+}
+
+impl CryptoAdaptor {
+    pub fn new(url: String, source_name: String) -> Self {
+        Self {
+            feed: RssAdaptor::new(url, source_name),
+        }
+    }
+}
+
+#[async_trait]
+impl NewsAdaptor for CryptoAdaptor {
+    fn name(&self) -> &str {
+        self.feed.name()
+    }
+
+    fn kind(&self) -> SourceKind {
+        SourceKind::Rss
+    }
+
+    fn url(&self) -> Option<&str> {
+        self.feed.url()
+    }
+
+    fn moved_to(&self) -> Option<String> {
+        self.feed.moved_to()
+    }
+
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let (mut items, warnings) = self.feed.fetch().await?;
+        for item in &mut items {
+            let coins = detect_coins(&[&item.title, &item.summary]);
+            item.tags.retain(|tag| !coins.contains(tag));
+            item.tags.splice(0..0, coins.iter().cloned());
+            for coin in coins {
+                if !item.tickers.contains(&coin) {
+                    item.tickers.push(coin);
+                }
+            }
+        }
+        Ok((items, warnings))
+    }
+}
@@ -0,0 +1,293 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{Days, NaiveDate, NaiveTime, Utc};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::NewsAdaptor;
+use crate::config::FredConfig;
+use crate::error::NewsHubError;
+use crate::models::{api_item_id, source_id, NewsItem, SourceKind};
+
+/// Source name of the FRED adaptor
+pub const FRED: &str = "FRED";
+
+const API_ROOT: &str = "https://api.stlouisfed.org/fred";
+
+#[derive(Deserialize)]
+struct SeriesResp {
+    seriess: Vec<SeriesMeta>,
+}
+
+#[derive(Deserialize)]
+struct SeriesMeta {
+    title: String,
+    #[serde(default)]
+    units_short: String,
+}
+
+#[derive(Deserialize)]
+struct ReleasesResp {
+    releases: Vec<Release>,
+}
+
+#[derive(Deserialize)]
+struct Release {
+    id: i64,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ReleaseDatesResp {
+    release_dates: Vec<ReleaseDate>,
+}
+
+#[derive(Deserialize)]
+struct ReleaseDate {
+    date: NaiveDate,
+}
+
+#[derive(Deserialize)]
+struct ObservationsResp {
+    observations: Vec<Observation>,
+}
+
+#[derive(Deserialize)]
+struct Observation {
+    date: NaiveDate,
+    /// "." when the value is missing
+    value: String,
+}
+
+/// A configured series and the release that publishes it
+#[derive(Clone)]
+struct SeriesInfo {
+    id: String,
+    title: String,
+    units: String,
+    release: i64,
+    release_name: String,
+}
+
+/// Upcoming and recent release dates of the releases that publish the
+/// configured FRED series. Once a release is out, the latest values of its
+/// series go in the item's summary.
+pub struct FredAdaptor {
+    api_key: String,
+    series: Vec<String>,
+    days_ahead: u64,
+    days_back: u64,
+    client: Client,
+    /// Series metadata by id; it doesn't change, so it is looked up once
+    info: Mutex<HashMap<String, SeriesInfo>>,
+}
+
+impl FredAdaptor {
+    pub fn new(api_key: String, cfg: &FredConfig) -> Self {
+        Self {
+            api_key,
+            series: cfg.series.clone(),
+            days_ahead: cfg.days_ahead,
+            days_back: cfg.days_back,
+            client: Client::new(),
+            info: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, &str)]) -> Result<T> {
+        let response = self
+            .client
+            .get(format!("{}/{}", API_ROOT, path))
+            .query(&[("api_key", self.api_key.as_str()), ("file_type", "json")])
+            .query(query)
+            .send()
+            .await
+            .context("Failed to connect to FRED API")?;
+        if !response.status().is_success() {
+            return Err(NewsHubError::from_response(&response, Some(path)).into());
+        }
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse FRED {} response", path))
+    }
+
+    async fn series_info(&self, id: &str) -> Result<SeriesInfo> {
+        if let Some(info) = self.info.lock().unwrap().get(id) {
+            return Ok(info.clone());
+        }
+        let meta: SeriesResp = self.get("series", &[("series_id", id)]).await?;
+        let meta = meta
+            .seriess
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("unknown series {}", id))?;
+        let releases: ReleasesResp = self.get("series/release", &[("series_id", id)]).await?;
+        let release = releases
+            .releases
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("series {} has no release", id))?;
+        let info = SeriesInfo {
+            id: id.to_string(),
+            title: meta.title,
+            units: meta.units_short,
+            release: release.id,
+            release_name: release.name,
+        };
+        self.info
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), info.clone());
+        Ok(info)
+    }
+
+    /// "CPI: 310.3 Index for Jan 2024 (previous 309.7)"
+    async fn latest_value(&self, info: &SeriesInfo) -> Result<String> {
+        let resp: ObservationsResp = self
+            .get(
+                "series/observations",
+                &[
+                    ("series_id", info.id.as_str()),
+                    ("sort_order", "desc"),
+                    ("limit", "2"),
+                ],
+            )
+            .await?;
+        let mut values = resp.observations.iter().filter(|o| o.value != ".");
+        let latest = values
+            .next()
+            .ok_or_else(|| anyhow!("series {} has no observations", info.id))?;
+        let mut line = format!(
+            "{}: {} {} for {}",
+            info.title,
+            latest.value,
+            info.units,
+            latest.date.format("%b %Y")
+        );
+        if let Some(previous) = values.next() {
+            line.push_str(&format!(" (previous {})", previous.value));
+        }
+        Ok(line)
+    }
+}
+
+#[async_trait]
+impl NewsAdaptor for FredAdaptor {
+    fn name(&self) -> &str {
+        FRED
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.api_key.is_empty() && !self.series.is_empty()
+    }
+
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let mut warnings = Vec::new();
+        // Series grouped by the release that publishes them, in config order
+        let mut releases: Vec<(i64, String, Vec<SeriesInfo>)> = Vec::new();
+        for id in &self.series {
+            let info = match self.series_info(id).await {
+                Ok(info) => info,
+                Err(e) => {
+                    warnings.push(format!("{}: {:#}", id, e));
+                    continue;
+                }
+            };
+            match releases
+                .iter_mut()
+                .find(|(release, _, _)| *release == info.release)
+            {
+                Some((_, _, series)) => series.push(info),
+                None => releases.push((info.release, info.release_name.clone(), vec![info])),
+            }
+        }
+        if releases.is_empty() {
+            return Err(anyhow!("No configured FRED series could be loaded"));
+        }
+
+        let now = Utc::now();
+        let today = now.date_naive();
+        let start = today - Days::new(self.days_back);
+        let end = today + Days::new(self.days_ahead);
+        let start_arg = start.to_string();
+
+        let mut items = Vec::new();
+        for (release, name, series) in &releases {
+            let release_arg = release.to_string();
+            let dates: ReleaseDatesResp = self
+                .get(
+                    "release/dates",
+                    &[
+                        ("release_id", release_arg.as_str()),
+                        ("realtime_start", start_arg.as_str()),
+                        ("realtime_end", "9999-12-31"),
+                        ("include_release_dates_with_no_data", "true"),
+                        ("sort_order", "asc"),
+                    ],
+                )
+                .await?;
+            let dates: Vec<NaiveDate> = dates
+                .release_dates
+                .into_iter()
+                .map(|d| d.date)
+                .filter(|date| (start..=end).contains(date))
+                .collect();
+            // Only the latest release's numbers are the series' current values
+            let last_released = dates.iter().filter(|date| **date <= today).max().copied();
+
+            for date in dates {
+                let released = date <= today;
+                let mut summary = if released {
+                    format!("{} released {}.", name, date.format("%b %-d"))
+                } else {
+                    format!(
+                        "{} scheduled for {}. Series: {}.",
+                        name,
+                        date.format("%b %-d"),
+                        series
+                            .iter()
+                            .map(|s| s.title.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                };
+                if Some(date) == last_released {
+                    for info in series {
+                        match self.latest_value(info).await {
+                            Ok(line) => {
+                                summary.push('\n');
+                                summary.push_str(&line);
+                            }
+                            Err(e) => warnings.push(format!("{}: {:#}", info.id, e)),
+                        }
+                    }
+                }
+                // Past releases are dated on their day; upcoming ones when first seen
+                let published = if released {
+                    date.and_time(NaiveTime::MIN).and_utc()
+                } else {
+                    now
+                };
+                items.push(NewsItem {
+                    id: api_item_id(FRED, format!("{}-{}", release, date)),
+                    source: FRED.to_string(),
+                    source_id: source_id(FRED),
+                    title: format!("{} release, {}", name, date.format("%b %-d")),
+                    url: format!("https://fred.stlouisfed.org/release?rid={}", release),
+                    summary,
+                    published,
+                    updated_at: now,
+                    tags: vec!["economic data".to_string()],
+                    event_date: Some(date),
+                    source_kind: SourceKind::Api,
+                    ..Default::default()
+                });
+            }
+        }
+        Ok((items, warnings))
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use super::rss::parse_date;
+use super::NewsAdaptor;
+use crate::config::{JsonFieldsConfig, JsonSourceConfig};
+use crate::error::NewsHubError;
+use crate::models::{api_item_id, feed_item_id, source_id, NewsItem, SourceKind};
+
+/// Unix timestamps above this are taken as milliseconds
+const MAX_UNIX_SECS: i64 = 100_000_000_000;
+
+/// Any JSON (or GraphQL) API whose items are mapped to articles by the
+/// field paths of a `[[json]]` entry
+pub struct JsonAdaptor {
+    name: String,
+    url: String,
+    query: Option<String>,
+    items: String,
+    fields: JsonFieldsConfig,
+    /// Header name and value sent with each request
+    auth: Option<(String, String)>,
+    client: Client,
+}
+
+impl JsonAdaptor {
+    pub fn new(cfg: &JsonSourceConfig, auth_value: Option<String>) -> Self {
+        Self {
+            name: cfg.name.clone(),
+            url: cfg.url.clone(),
+            query: cfg.query.clone(),
+            items: cfg.items.clone(),
+            fields: cfg.fields.clone(),
+            auth: auth_value.map(|value| (cfg.auth_header.clone(), value)),
+            client: Client::new(),
+        }
+    }
+
+    /// Article for one element of the items array; `None` when a required
+    /// field is missing
+    fn item(&self, value: &Value, now: DateTime<Utc>) -> Option<NewsItem> {
+        let text = |path: &str| lookup(value, path).and_then(as_text);
+        let title = text(&self.fields.title)?;
+        let url = text(&self.fields.url)?;
+        let published = lookup(value, &self.fields.date).and_then(as_date)?;
+        let id = match self.fields.id.as_deref().and_then(text) {
+            Some(id) => api_item_id(&self.name, id),
+            None => feed_item_id(&self.name, None, false, &url),
+        };
+        Some(NewsItem {
+            id,
+            source: self.name.clone(),
+            source_id: source_id(&self.name),
+            title,
+            url,
+            summary: self
+                .fields
+                .summary
+                .as_deref()
+                .and_then(text)
+                .unwrap_or_default(),
+            published,
+            updated_at: now,
+            author: self.fields.author.as_deref().and_then(text),
+            source_kind: SourceKind::Api,
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait]
+impl NewsAdaptor for JsonAdaptor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn url(&self) -> Option<&str> {
+        Some(&self.url)
+    }
+
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let mut request = match &self.query {
+            Some(query) => self.client.post(&self.url).json(&json!({ "query": query })),
+            None => self.client.get(&self.url),
+        };
+        if let Some((header, value)) = &self.auth {
+            request = request.header(header.as_str(), value.as_str());
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", self.name))?;
+        if !response.status().is_success() {
+            return Err(NewsHubError::from_response(&response, None).into());
+        }
+        let body: Value = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} response", self.name))?;
+        // GraphQL reports failed queries in the body of a 200 response
+        if let Some(message) = lookup(&body, "errors[0].message").and_then(Value::as_str) {
+            return Err(anyhow!("{}: {}", self.name, message));
+        }
+        let entries = lookup(&body, &self.items)
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("No array at `{}` in the response", self.items))?;
+
+        let now = Utc::now();
+        let items: Vec<NewsItem> = entries.iter().filter_map(|e| self.item(e, now)).collect();
+        let dropped = entries.len() - items.len();
+        let warnings = if dropped > 0 {
+            vec![format!(
+                "Dropped {} items without a title, url or valid date",
+                dropped
+            )]
+        } else {
+            Vec::new()
+        };
+        Ok((items, warnings))
+    }
+}
+
+/// Value at a dotted path with optional `[n]` indexes, e.g. `data.items` or
+/// `links[0].href`. An empty path (or `$`) is the value itself.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = value;
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, indexes) = segment.split_once('[').unwrap_or((segment, ""));
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indexes.split('[').filter(|s| !s.is_empty()) {
+            let index: usize = index.trim_end_matches(']').parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+    Some(current)
+}
+
+/// A string, or a number written out, trimmed and non-empty
+fn as_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    Some(text).filter(|t| !t.is_empty())
+}
+
+/// A date string, or a Unix timestamp in seconds or milliseconds
+fn as_date(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::String(s) => parse_date(s).or_else(|| s.trim().parse().ok().and_then(unix_date)),
+        Value::Number(n) => n.as_i64().and_then(unix_date),
+        _ => None,
+    }
+}
+
+fn unix_date(timestamp: i64) -> Option<DateTime<Utc>> {
+    if timestamp > MAX_UNIX_SECS {
+        DateTime::from_timestamp_millis(timestamp)
+    } else {
+        DateTime::from_timestamp(timestamp, 0)
+    }
+}
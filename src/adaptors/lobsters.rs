@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::NewsAdaptor;
+use crate::error::NewsHubError;
+use crate::models::{api_item_id, source_id, NewsItem, SourceKind};
+
+#[derive(Deserialize)]
+struct Story {
+    short_id: String,
+    title: String,
+    /// Empty for text posts
+    #[serde(default)]
+    url: String,
+    created_at: String,
+    #[serde(default)]
+    score: i64,
+    #[serde(default)]
+    comment_count: u32,
+    #[serde(default)]
+    description_plain: String,
+    comments_url: String,
+    /// A name, or an object with `username` on older versions
+    #[serde(default)]
+    submitter_user: serde_json::Value,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Front page of Lobsters or another site running its software (e.g.
+/// tilde.news), with each story's votes as the score and its comment count
+pub struct LobstersAdaptor {
+    name: String,
+    /// `hottest.json` of the site
+    url: String,
+    client: Client,
+}
+
+impl LobstersAdaptor {
+    pub fn new(name: String, url: String) -> Self {
+        Self {
+            name,
+            url,
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl NewsAdaptor for LobstersAdaptor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> SourceKind {
+        SourceKind::Api
+    }
+
+    fn url(&self) -> Option<&str> {
+        Some(&self.url)
+    }
+
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let response = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", self.name))?;
+        if !response.status().is_success() {
+            return Err(NewsHubError::from_response(&response, None).into());
+        }
+        let stories: Vec<Story> = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} response", self.name))?;
+
+        let now = Utc::now();
+        let mut dropped = 0;
+        let items: Vec<NewsItem> = stories
+            .into_iter()
+            .filter_map(|s| {
+                let Ok(published) = DateTime::parse_from_rfc3339(&s.created_at) else {
+                    dropped += 1;
+                    return None;
+                };
+                let author = s
+                    .submitter_user
+                    .as_str()
+                    .or_else(|| s.submitter_user.get("username")?.as_str())
+                    .map(str::to_string);
+                Some(NewsItem {
+                    id: api_item_id(&self.name, &s.short_id),
+                    source: self.name.clone(),
+                    source_id: source_id(&self.name),
+                    title: s.title,
+                    // Text posts link to their discussion
+                    url: if s.url.is_empty() {
+                        s.comments_url
+                    } else {
+                        s.url
+                    },
+                    summary: s.description_plain,
+                    published: published.with_timezone(&Utc),
+                    updated_at: now,
+                    author,
+                    tags: s.tags,
+                    score: s.score as f64,
+                    comments: s.comment_count,
+                    source_kind: SourceKind::Api,
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        let warnings = if dropped > 0 {
+            vec![format!("Dropped {} stories without a valid date", dropped)]
+        } else {
+            Vec::new()
+        };
+        Ok((items, warnings))
+    }
+}
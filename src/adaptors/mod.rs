@@ -1,13 +1,52 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::models::NewsItem;
+use crate::error::NewsHubError;
+use crate::models::{collapse_duplicates, scheduled_date, Duplicate, NewsItem, SourceKind};
 
 mod benzinga;
+mod bundles;
+mod crypto;
+mod fred;
+mod greader;
+mod json;
+mod lobsters;
+mod nextcloud;
+mod oauth;
+mod quote;
+mod reddit;
+mod registry;
+mod rewrite;
 mod rss;
+mod transcripts;
+mod ttrss;
+mod watch;
+mod yahoo;
 
 pub use benzinga::BenzingaAdaptor;
-pub use rss::{RssAdaptor, DEFAULT_RSS_FEEDS};
+pub use bundles::{
+    bundle_feeds, find_bundle, is_builtin_feed, is_coin_tagged, SourceBundle, BUNDLES,
+    DEFAULT_BUNDLES,
+};
+pub use crypto::CryptoAdaptor;
+pub use fred::{FredAdaptor, FRED};
+pub use greader::GReaderAdaptor;
+pub use json::JsonAdaptor;
+pub use lobsters::LobstersAdaptor;
+pub use nextcloud::NextcloudAdaptor;
+pub use oauth::{code_from_redirect, OAuthProvider, TokenManager, TokenStore, TOKEN_PATH};
+pub use quote::{build_quote_adaptor, QuoteAdaptor, YahooQuoteAdaptor};
+pub use reddit::{reddit_tokens, RedditAdaptor, REDDIT, REDDIT_OAUTH};
+pub use registry::{
+    AdaptorRegistry, AdaptorRegistryBuilder, RegisteredAdaptor, SourceOptions,
+    DEFAULT_FETCH_TIMEOUT, MIN_REFRESH_INTERVAL,
+};
+pub use rewrite::{ItemRewrites, Rewrite};
+pub use rss::{parse_feed, RssAdaptor};
+pub use transcripts::{TranscriptsAdaptor, TRANSCRIPTS};
+pub use ttrss::TtRssAdaptor;
+pub use watch::{WatchAdaptor, WATCH_SNAPSHOT_DIR};
+pub use yahoo::{YahooFinanceAdaptor, YAHOO_FINANCE};
 
 /// Diagnostic information for a single fetch operation
 #[derive(Debug, Clone)]
@@ -16,6 +55,10 @@ pub struct FetchDiagnostic {
     pub success: bool,
     pub message: String,
     pub warnings: Vec<String>,
+    /// Where the source's endpoint has permanently moved, if it has
+    pub moved_to: Option<String>,
+    /// Why the fetch failed; `message` is its text
+    pub error: Option<NewsHubError>,
 }
 
 /// Result of fetching from all adaptors, including diagnostics
@@ -23,6 +66,21 @@ pub struct FetchDiagnostic {
 pub struct FetchResult {
     pub items: Vec<NewsItem>,
     pub diagnostics: Vec<FetchDiagnostic>,
+    /// Copies of stories another source also carried, moved out of `items`
+    /// by [`FetchResult::collapse_duplicates`]
+    pub duplicates: Vec<Duplicate>,
+}
+
+impl FetchResult {
+    /// Keep one item per story carried by several sources, the earliest
+    /// published; the others move to `duplicates`. Headlines at least
+    /// `threshold` similar, or the same article URL, count as one story.
+    pub fn collapse_duplicates(&mut self, threshold: f32) {
+        let items = std::mem::take(&mut self.items);
+        let (kept, duplicates) = collapse_duplicates(items, threshold);
+        self.items = kept;
+        self.duplicates.extend(duplicates);
+    }
 }
 
 /// Trait for news adaptors - requires Send + Sync for tokio::spawn (thread safety)
@@ -39,66 +97,83 @@ pub trait NewsAdaptor: Send + Sync {
     fn is_enabled(&self) -> bool {
         true
     }
+
+    /// Kind recorded on the source's database row (default: API)
+    fn kind(&self) -> SourceKind {
+        SourceKind::Api
+    }
+
+    /// Endpoint recorded on the source's database row (default: none)
+    fn url(&self) -> Option<&str> {
+        None
+    }
+
+    /// New endpoint after a permanent redirect, once one has been seen
+    /// (default: none)
+    fn moved_to(&self) -> Option<String> {
+        None
+    }
+
+    /// Send an item's read/starred state to the source, for sources that keep
+    /// their own (default: nothing to do)
+    async fn push_state(&self, _item: &NewsItem) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Upper bound on sources fetched at the same time during a refresh
+pub const MAX_CONCURRENT_FETCHES: usize = 4;
+
+/// Fetch from a single adaptor, folding errors into its diagnostic
+pub async fn fetch_one(adaptor: &dyn NewsAdaptor) -> (Vec<NewsItem>, FetchDiagnostic) {
+    match adaptor.fetch().await {
+        Ok((mut items, warnings)) => {
+            for item in items.iter_mut().filter(|i| i.event_date.is_none()) {
+                item.event_date = scheduled_date(&[&item.title, &item.summary], item.published);
+            }
+            let diagnostic = FetchDiagnostic {
+                source: adaptor.name().to_string(),
+                success: true,
+                message: format!("Fetched {} items", items.len()),
+                warnings,
+                moved_to: adaptor.moved_to(),
+                error: None,
+            };
+            (items, diagnostic)
+        }
+        Err(e) => {
+            let error = NewsHubError::classify(&e);
+            let diagnostic = FetchDiagnostic {
+                source: adaptor.name().to_string(),
+                success: false,
+                message: error.to_string(),
+                warnings: Vec::new(),
+                moved_to: adaptor.moved_to(),
+                error: Some(error),
+            };
+            (Vec::new(), diagnostic)
+        }
+    }
 }
 
-/// Fetch from all enabled adaptors
-pub async fn fetch_all(adaptors: &[Box<dyn NewsAdaptor>]) -> FetchResult {
+/// Fetch from all enabled sources in the registry, one at a time
+pub async fn fetch_all(registry: &AdaptorRegistry) -> FetchResult {
     let mut all_items = Vec::new();
     let mut diagnostics = Vec::new();
 
-    for adaptor in adaptors {
-        if !adaptor.is_enabled() {
+    for source in registry.iter() {
+        if !source.is_enabled() {
             continue;
         }
 
-        match adaptor.fetch().await {
-            Ok((items, warnings)) => {
-                diagnostics.push(FetchDiagnostic {
-                    source: adaptor.name().to_string(),
-                    success: true,
-                    message: format!("Fetched {} items", items.len()),
-                    warnings,
-                });
-                all_items.extend(items);
-            }
-            Err(e) => {
-                diagnostics.push(FetchDiagnostic {
-                    source: adaptor.name().to_string(),
-                    success: false,
-                    message: format!("Failed: {}", e),
-                    warnings: Vec::new(),
-                });
-            }
-        }
+        let (items, diagnostic) = source.fetch().await;
+        diagnostics.push(diagnostic);
+        all_items.extend(items);
     }
 
     FetchResult {
         items: all_items,
         diagnostics,
+        duplicates: Vec::new(),
     }
 }
-
-/// Build adaptors dynamically based on available API keys
-pub fn build_adaptors(
-    benzinga_key: Option<String>,
-    // Future: add more API keys here
-) -> Vec<Box<dyn NewsAdaptor>> {
-    let mut adaptors: Vec<Box<dyn NewsAdaptor>> = Vec::new();
-
-    // Always add RSS feeds (no API key required)
-    for (url, name) in DEFAULT_RSS_FEEDS {
-        adaptors.push(Box::new(RssAdaptor::new(
-            url.to_string(),
-            name.to_string(),
-        )));
-    }
-
-    // Conditionally add API-based adaptors
-    if let Some(key) = benzinga_key {
-        adaptors.push(Box::new(BenzingaAdaptor::new(key)));
-    }
-
-    // Future: Add MarketAux, Reddit, etc.
-
-    adaptors
-}
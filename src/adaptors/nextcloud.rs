@@ -0,0 +1,230 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::NewsAdaptor;
+use crate::models::{api_item_id, native_item_id, source_id, NewsItem, SourceKind};
+
+/// API path below the Nextcloud server root
+const API_PATH: &str = "/index.php/apps/news/api/v1-3";
+
+#[derive(Deserialize)]
+struct Feeds {
+    #[serde(default)]
+    feeds: Vec<Feed>,
+}
+
+#[derive(Deserialize)]
+struct Feed {
+    id: i64,
+    #[serde(default)]
+    title: String,
+    #[serde(rename = "folderId", default)]
+    folder_id: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct Folders {
+    #[serde(default)]
+    folders: Vec<Folder>,
+}
+
+#[derive(Deserialize)]
+struct Folder {
+    id: i64,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Items {
+    #[serde(default)]
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Item {
+    id: i64,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    author: Option<String>,
+    pub_date: i64,
+    #[serde(default)]
+    updated_date: Option<i64>,
+    #[serde(default)]
+    body: Option<String>,
+    feed_id: i64,
+    unread: bool,
+    starred: bool,
+    #[serde(default)]
+    enclosure_link: Option<String>,
+    #[serde(default)]
+    enclosure_mime: Option<String>,
+}
+
+/// Client for the Nextcloud News app (API v1.3). Pulls the server's items
+/// with their read/starred state and pushes local state changes back.
+pub struct NextcloudAdaptor {
+    name: String,
+    /// Server root, e.g. `https://cloud.example.com`
+    server_url: String,
+    username: String,
+    password: String,
+    /// Items requested per fetch
+    limit: usize,
+    client: Client,
+}
+
+impl NextcloudAdaptor {
+    pub fn new(name: String, server_url: String, username: String, password: String) -> Self {
+        Self {
+            name,
+            server_url: server_url.trim_end_matches('/').to_string(),
+            username,
+            password,
+            limit: 200,
+            client: Client::new(),
+        }
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit.max(1);
+    }
+
+    fn api(&self, path: &str) -> String {
+        format!("{}{}/{}", self.server_url, API_PATH, path)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        Ok(self
+            .client
+            .get(self.api(path))
+            .basic_auth(&self.username, Some(&self.password))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    async fn put(&self, path: &str) -> Result<()> {
+        self.client
+            .put(self.api(path))
+            .basic_auth(&self.username, Some(&self.password))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NewsAdaptor for NextcloudAdaptor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> SourceKind {
+        SourceKind::Remote
+    }
+
+    fn url(&self) -> Option<&str> {
+        Some(&self.server_url)
+    }
+
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let feeds: Feeds = self
+            .get_json("feeds")
+            .await
+            .context("Failed to load feeds")?;
+        let folders: Folders = self
+            .get_json("folders")
+            .await
+            .context("Failed to load folders")?;
+        let folders: HashMap<i64, String> = folders
+            .folders
+            .into_iter()
+            .map(|f| (f.id, f.name))
+            .collect();
+        let feeds: HashMap<i64, Feed> = feeds.feeds.into_iter().map(|f| (f.id, f)).collect();
+
+        // type=3 is "all items"; getRead includes read ones so their state syncs
+        let items: Items = self
+            .get_json(&format!(
+                "items?batchSize={}&offset=0&type=3&id=0&getRead=true",
+                self.limit
+            ))
+            .await
+            .context("Failed to load items")?;
+
+        let now = Utc::now();
+        let total = items.items.len();
+        let items: Vec<NewsItem> = items
+            .items
+            .into_iter()
+            .filter_map(|entry| {
+                let url = entry.url.filter(|u| !u.is_empty())?;
+                let published = Utc.timestamp_opt(entry.pub_date, 0).single()?;
+                let feed = feeds.get(&entry.feed_id);
+                let source = feed
+                    .map(|f| f.title.clone())
+                    .filter(|title| !title.is_empty())
+                    .unwrap_or_else(|| self.name.clone());
+                let folder = feed
+                    .and_then(|f| f.folder_id)
+                    .and_then(|id| folders.get(&id));
+                let is_image = entry
+                    .enclosure_mime
+                    .as_deref()
+                    .is_some_and(|mime| mime.starts_with("image/"));
+
+                Some(NewsItem {
+                    id: api_item_id(&self.name, entry.id),
+                    source_id: source_id(&source),
+                    source,
+                    title: entry.title,
+                    url,
+                    published,
+                    updated_at: entry
+                        .updated_date
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single())
+                        .unwrap_or(now),
+                    author: entry.author.filter(|a| !a.is_empty()),
+                    tags: folder.cloned().into_iter().collect(),
+                    image_url: entry.enclosure_link.filter(|_| is_image),
+                    content: entry.body,
+                    source_kind: SourceKind::Remote,
+                    read: !entry.unread,
+                    starred: entry.starred,
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        let mut warnings = Vec::new();
+        if items.len() < total {
+            warnings.push(format!(
+                "Dropped {} items without a link",
+                total - items.len()
+            ));
+        }
+        Ok((items, warnings))
+    }
+
+    async fn push_state(&self, item: &NewsItem) -> Result<()> {
+        let Some(remote_id) = native_item_id(&self.name, &item.id) else {
+            return Ok(());
+        };
+        let read = if item.read { "read" } else { "unread" };
+        self.put(&format!("items/{}/{}", remote_id, read)).await?;
+        let star = if item.starred { "star" } else { "unstar" };
+        self.put(&format!("items/{}/{}", remote_id, star)).await?;
+        Ok(())
+    }
+}
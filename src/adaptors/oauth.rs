@@ -0,0 +1,414 @@
+//! OAuth2 access tokens for APIs behind a user login. A user authorizes
+//! news-hub once with `news-hub auth <source>`; the refresh token that
+//! yields is kept in [`TOKEN_PATH`], readable by its owner only, and
+//! [`TokenManager`] trades it for short-lived access tokens as fetches need
+//! them. When the refresh token stops working the fetch fails with an
+//! auth error naming the command to run again.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::error::NewsHubError;
+
+/// Refresh tokens of authorized sources
+pub const TOKEN_PATH: &str = "data/tokens.json";
+/// Token lifetime given up early so a request never carries an expired one
+const TOKEN_MARGIN: Duration = Duration::from_secs(60);
+
+/// Where an API hands out tokens
+#[derive(Debug)]
+pub struct OAuthProvider {
+    /// Key in the token store and argument of `news-hub auth`
+    pub name: &'static str,
+    pub authorize_url: &'static str,
+    pub token_url: &'static str,
+    pub scope: &'static str,
+    /// Extra parameters of the authorize URL, e.g. to ask for a refresh token
+    pub authorize_params: &'static [(&'static str, &'static str)],
+    /// Use the client credentials grant until a user authorizes
+    pub app_only: bool,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    /// Present after authorizing, and when the provider rotates it
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredToken {
+    refresh_token: String,
+    authorized_at: DateTime<Utc>,
+}
+
+/// Refresh tokens by provider, in a JSON file only its owner can read
+pub struct TokenStore {
+    path: PathBuf,
+}
+
+impl TokenStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, StoredToken>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", self.path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", self.path.display())),
+        }
+    }
+
+    /// Replace the file in one step, so a crash never leaves half of it
+    fn write(&self, tokens: &BTreeMap<String, StoredToken>) -> Result<()> {
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options
+            .open(&tmp)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        file.write_all(serde_json::to_string_pretty(tokens)?.as_bytes())
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn refresh_token(&self, provider: &str) -> Result<Option<String>> {
+        Ok(self.read()?.remove(provider).map(|t| t.refresh_token))
+    }
+
+    pub fn save(&self, provider: &str, refresh_token: &str) -> Result<()> {
+        let mut tokens = self.read()?;
+        tokens.insert(
+            provider.to_string(),
+            StoredToken {
+                refresh_token: refresh_token.to_string(),
+                authorized_at: Utc::now(),
+            },
+        );
+        self.write(&tokens)
+    }
+
+    /// Forget a provider's token; true if there was one
+    pub fn remove(&self, provider: &str) -> Result<bool> {
+        let mut tokens = self.read()?;
+        let removed = tokens.remove(provider).is_some();
+        if removed {
+            self.write(&tokens)?;
+        }
+        Ok(removed)
+    }
+}
+
+/// Access tokens for one provider, renewed before they expire. Requests
+/// that get a 401 anyway call [`invalidate`](Self::invalidate) and ask again.
+pub struct TokenManager {
+    provider: &'static OAuthProvider,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    store: TokenStore,
+    client: Client,
+    /// Access token and when it stops being used. Held while renewing so
+    /// concurrent fetches don't spend a rotating refresh token twice.
+    access: Mutex<Option<(String, Instant)>>,
+}
+
+impl TokenManager {
+    pub fn new(
+        provider: &'static OAuthProvider,
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+        store: TokenStore,
+        client: Client,
+    ) -> Self {
+        Self {
+            provider,
+            client_id,
+            client_secret,
+            redirect_uri,
+            store,
+            client,
+            access: Mutex::new(None),
+        }
+    }
+
+    pub fn provider(&self) -> &'static OAuthProvider {
+        self.provider
+    }
+
+    pub fn store(&self) -> &TokenStore {
+        &self.store
+    }
+
+    /// Page where the user grants access; it redirects to the redirect URI
+    /// with a code and `state`
+    pub fn authorize_url(&self, state: &str) -> String {
+        let mut params = vec![
+            ("client_id", self.client_id.as_str()),
+            ("response_type", "code"),
+            ("state", state),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("scope", self.provider.scope),
+        ];
+        params.extend(self.provider.authorize_params.iter().copied());
+        Url::parse_with_params(self.provider.authorize_url, &params)
+            .map(String::from)
+            .unwrap_or_default()
+    }
+
+    /// Trade the code from the authorize redirect for a refresh token and
+    /// keep it
+    pub async fn authorize(&self, code: &str) -> Result<()> {
+        let mut response = self
+            .request(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &self.redirect_uri),
+            ])
+            .await?;
+        let Some(refresh) = response.refresh_token.take() else {
+            bail!("{} returned no refresh token", self.provider.name);
+        };
+        self.store.save(self.provider.name, &refresh)?;
+        *self.access.lock().await = Some(self.cache(response));
+        Ok(())
+    }
+
+    /// A fresh access token, renewed first if the cached one is close to
+    /// expiring
+    pub async fn access_token(&self) -> Result<String> {
+        let mut access = self.access.lock().await;
+        if let Some((token, expires)) = access.as_ref() {
+            if Instant::now() < *expires {
+                return Ok(token.clone());
+            }
+        }
+        let response = match self.store.refresh_token(self.provider.name)? {
+            Some(refresh) => {
+                self.request(&[("grant_type", "refresh_token"), ("refresh_token", &refresh)])
+                    .await?
+            }
+            None if self.provider.app_only => {
+                self.request(&[("grant_type", "client_credentials")])
+                    .await?
+            }
+            None => return Err(self.reauthorize("not authorized").into()),
+        };
+        let token = response.access_token.clone();
+        *access = Some(self.cache(response));
+        Ok(token)
+    }
+
+    /// Drop the cached access token after the API rejected it
+    pub async fn invalidate(&self) {
+        *self.access.lock().await = None;
+    }
+
+    /// Store a rotated refresh token and the access token's deadline
+    fn cache(&self, response: TokenResponse) -> (String, Instant) {
+        if let Some(refresh) = &response.refresh_token {
+            if let Err(e) = self.store.save(self.provider.name, refresh) {
+                tracing::warn!(
+                    "Failed to save {} refresh token: {:#}",
+                    self.provider.name,
+                    e
+                );
+            }
+        }
+        let lifetime = Duration::from_secs(response.expires_in.unwrap_or(3600));
+        (
+            response.access_token,
+            Instant::now() + lifetime.saturating_sub(TOKEN_MARGIN),
+        )
+    }
+
+    async fn request(&self, form: &[(&str, &str)]) -> Result<TokenResponse> {
+        let response = self
+            .client
+            .post(self.provider.token_url)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(form)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", self.provider.name))?;
+        match response.status() {
+            status if status.is_success() => {}
+            StatusCode::UNAUTHORIZED => {
+                return Err(NewsHubError::Auth("invalid client id or secret".to_string()).into())
+            }
+            StatusCode::BAD_REQUEST => {
+                let body = response.text().await.unwrap_or_default();
+                return Err(if body.contains("invalid_grant") {
+                    self.reauthorize("authorization expired or was revoked")
+                } else {
+                    NewsHubError::from_status(400, Some(body.trim()))
+                }
+                .into());
+            }
+            _ => return Err(NewsHubError::from_response(&response, None).into()),
+        }
+        // Some providers answer errors with 200 and an `error` field
+        let value: serde_json::Value = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} token response", self.provider.name))?;
+        if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+            return Err(match error {
+                "invalid_grant" => self.reauthorize("authorization expired or was revoked"),
+                "unauthorized_client" | "invalid_client" => {
+                    NewsHubError::Auth("invalid client id or secret".to_string())
+                }
+                other => NewsHubError::Auth(format!("token request refused: {}", other)),
+            }
+            .into());
+        }
+        serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse {} token response", self.provider.name))
+    }
+
+    fn reauthorize(&self, problem: &str) -> NewsHubError {
+        NewsHubError::Auth(format!(
+            "{}; run `news-hub auth {}`",
+            problem, self.provider.name
+        ))
+    }
+}
+
+/// The code in what the user pasted after authorizing: the address they
+/// were redirected to, or the bare code
+pub fn code_from_redirect(input: &str, state: &str) -> Result<String> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("Nothing was pasted");
+    }
+    let Ok(url) = Url::parse(input) else {
+        return Ok(input.to_string());
+    };
+    let params: BTreeMap<_, _> = url.query_pairs().collect();
+    if let Some(error) = params.get("error") {
+        bail!("Authorization was refused: {}", error);
+    }
+    if params.get("state").map(|s| s.as_ref()) != Some(state) {
+        bail!("The address is from a different authorization attempt; start again");
+    }
+    params
+        .get("code")
+        .map(|c| c.to_string())
+        .ok_or_else(|| anyhow!("No code in the address"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROVIDER: OAuthProvider = OAuthProvider {
+        name: "example",
+        authorize_url: "https://example.com/authorize",
+        token_url: "https://example.com/token",
+        scope: "read",
+        authorize_params: &[("duration", "permanent")],
+        app_only: false,
+    };
+
+    #[test]
+    fn store_keeps_tokens_per_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = TokenStore::new(dir.path().join("data/tokens.json"));
+        assert_eq!(store.refresh_token("reddit").unwrap(), None);
+
+        store.save("reddit", "r1").unwrap();
+        store.save("other", "o1").unwrap();
+        store.save("reddit", "r2").unwrap();
+        assert_eq!(
+            store.refresh_token("reddit").unwrap().as_deref(),
+            Some("r2")
+        );
+        assert_eq!(store.refresh_token("other").unwrap().as_deref(), Some("o1"));
+
+        assert!(store.remove("reddit").unwrap());
+        assert!(!store.remove("reddit").unwrap());
+        assert_eq!(store.refresh_token("other").unwrap().as_deref(), Some("o1"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(store.path()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn authorize_url_carries_the_request() {
+        let tokens = TokenManager::new(
+            &PROVIDER,
+            "abc".to_string(),
+            "secret".to_string(),
+            "http://localhost:65010/callback".to_string(),
+            TokenStore::new("unused.json"),
+            Client::new(),
+        );
+        let url = Url::parse(&tokens.authorize_url("s1")).unwrap();
+        let params: BTreeMap<_, _> = url.query_pairs().collect();
+        assert_eq!(params["client_id"], "abc");
+        assert_eq!(params["state"], "s1");
+        assert_eq!(params["redirect_uri"], "http://localhost:65010/callback");
+        assert_eq!(params["duration"], "permanent");
+        assert!(!params.contains_key("client_secret"));
+    }
+
+    #[tokio::test]
+    async fn missing_authorization_names_the_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let tokens = TokenManager::new(
+            &PROVIDER,
+            "abc".to_string(),
+            "secret".to_string(),
+            String::new(),
+            TokenStore::new(dir.path().join("tokens.json")),
+            Client::new(),
+        );
+        let error = tokens.access_token().await.unwrap_err();
+        assert_eq!(
+            NewsHubError::classify(&error),
+            NewsHubError::Auth("not authorized; run `news-hub auth example`".to_string())
+        );
+    }
+
+    #[test]
+    fn code_comes_from_the_redirect() {
+        let redirect = "http://localhost:65010/callback?state=s1&code=xyz";
+        assert_eq!(code_from_redirect(redirect, "s1").unwrap(), "xyz");
+        assert_eq!(code_from_redirect(" xyz\n", "s1").unwrap(), "xyz");
+        assert!(code_from_redirect(redirect, "s2").is_err());
+        let refused = "http://localhost:65010/callback?state=s1&error=access_denied";
+        assert!(code_from_redirect(refused, "s1")
+            .unwrap_err()
+            .to_string()
+            .contains("access_denied"));
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::models::Quote;
+
+/// Trait for quote providers - same Send + Sync requirements as NewsAdaptor
+#[async_trait]
+pub trait QuoteAdaptor: Send + Sync {
+    /// Unique identifier for this provider
+    fn name(&self) -> &str;
+
+    /// Fetch the latest quotes for the given symbols.
+    /// Symbols the provider cannot resolve are skipped rather than failing the batch.
+    async fn fetch_quotes(&self, symbols: &[String]) -> Result<Vec<Quote>>;
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChartMeta {
+    symbol: String,
+    regular_market_price: Option<f64>,
+    chart_previous_close: Option<f64>,
+    previous_close: Option<f64>,
+    regular_market_time: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct ChartResult {
+    meta: ChartMeta,
+}
+
+#[derive(Deserialize)]
+struct Chart {
+    result: Option<Vec<ChartResult>>,
+}
+
+#[derive(Deserialize)]
+struct ChartResp {
+    chart: Chart,
+}
+
+/// Quotes from Yahoo Finance's public chart endpoint (no API key required)
+pub struct YahooQuoteAdaptor {
+    client: Client,
+}
+
+impl Default for YahooQuoteAdaptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl YahooQuoteAdaptor {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("news-hub/0.1")
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    async fn fetch_one(&self, symbol: &str) -> Result<Quote> {
+        let url = format!(
+            "https://query1.finance.yahoo.com/v8/finance/chart/{}",
+            symbol
+        );
+
+        let resp: ChartResp = self
+            .client
+            .get(&url)
+            .query(&[("range", "1d"), ("interval", "1d")])
+            .send()
+            .await
+            .context("Failed to connect to Yahoo Finance")?
+            .json()
+            .await
+            .context("Failed to parse Yahoo Finance response")?;
+
+        let meta = resp
+            .chart
+            .result
+            .and_then(|r| r.into_iter().next())
+            .map(|r| r.meta)
+            .with_context(|| format!("No quote data for {}", symbol))?;
+
+        let price = meta
+            .regular_market_price
+            .with_context(|| format!("No price for {}", symbol))?;
+        let previous = meta
+            .chart_previous_close
+            .or(meta.previous_close)
+            .unwrap_or(price);
+        let change = price - previous;
+        let change_percent = if previous != 0.0 {
+            change / previous * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(Quote {
+            symbol: meta.symbol,
+            price,
+            change,
+            change_percent,
+            as_of: meta
+                .regular_market_time
+                .and_then(|t| Utc.timestamp_opt(t, 0).single())
+                .unwrap_or_else(Utc::now),
+        })
+    }
+}
+
+#[async_trait]
+impl QuoteAdaptor for YahooQuoteAdaptor {
+    fn name(&self) -> &str {
+        "Yahoo Finance"
+    }
+
+    async fn fetch_quotes(&self, symbols: &[String]) -> Result<Vec<Quote>> {
+        let mut quotes = Vec::new();
+        let mut last_err = None;
+
+        for symbol in symbols {
+            match self.fetch_one(symbol).await {
+                Ok(quote) => quotes.push(quote),
+                Err(e) => last_err = Some(e),
+            }
+        }
+
+        // Only fail when nothing could be fetched at all
+        match last_err {
+            Some(e) if quotes.is_empty() && !symbols.is_empty() => Err(e),
+            _ => Ok(quotes),
+        }
+    }
+}
+
+/// Build the configured quote provider
+pub fn build_quote_adaptor(provider: &str) -> Result<Box<dyn QuoteAdaptor>> {
+    match provider.to_lowercase().as_str() {
+        "yahoo" => Ok(Box::new(YahooQuoteAdaptor::new())),
+        other => anyhow::bail!("Unknown quote provider '{}'", other),
+    }
+}
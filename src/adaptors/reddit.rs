@@ -0,0 +1,283 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+
+use super::oauth::{OAuthProvider, TokenManager, TokenStore, TOKEN_PATH};
+use super::NewsAdaptor;
+use crate::config::RedditConfig;
+use crate::error::NewsHubError;
+use crate::models::{api_item_id, extract_tickers, source_id, NewsItem, SourceKind};
+
+/// Source name of the Reddit adaptor
+pub const REDDIT: &str = "Reddit";
+
+/// Reddit's OAuth endpoints. Until a user authorizes with `news-hub auth
+/// reddit` the adaptor logs in as the application alone.
+pub const REDDIT_OAUTH: OAuthProvider = OAuthProvider {
+    name: "reddit",
+    authorize_url: "https://www.reddit.com/api/v1/authorize",
+    token_url: "https://www.reddit.com/api/v1/access_token",
+    scope: "read",
+    authorize_params: &[("duration", "permanent")],
+    app_only: true,
+};
+const API_ROOT: &str = "https://oauth.reddit.com";
+const SITE_ROOT: &str = "https://www.reddit.com";
+/// Characters of a self post's text kept as the summary
+const SUMMARY_CHARS: usize = 400;
+
+#[derive(Deserialize)]
+struct Listing {
+    data: ListingData,
+}
+
+#[derive(Deserialize)]
+struct ListingData {
+    children: Vec<Child>,
+}
+
+#[derive(Deserialize)]
+struct Child {
+    data: Post,
+}
+
+#[derive(Deserialize)]
+struct Post {
+    id: String,
+    title: String,
+    subreddit: String,
+    /// Link target, or the post itself for self posts
+    url: String,
+    permalink: String,
+    #[serde(default)]
+    selftext: String,
+    #[serde(default)]
+    is_self: bool,
+    created_utc: f64,
+    #[serde(default)]
+    score: i64,
+    #[serde(default)]
+    num_comments: u32,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    link_flair_text: Option<String>,
+    /// Moderator announcements pinned to the top
+    #[serde(default)]
+    stickied: bool,
+}
+
+/// Hot posts of the configured subreddits through Reddit's OAuth API, as
+/// the authorized user or else as an application-only client. Each post's upvotes are its score; link
+/// posts point at the linked article and self posts at their discussion.
+pub struct RedditAdaptor {
+    subreddits: Vec<String>,
+    limit: u32,
+    client: Client,
+    tokens: TokenManager,
+}
+
+impl RedditAdaptor {
+    pub fn new(client_id: String, client_secret: String, cfg: &RedditConfig) -> Self {
+        let subreddits = cfg
+            .subreddits
+            .iter()
+            .map(|s| {
+                let s = s.trim();
+                s.strip_prefix("r/").unwrap_or(s).to_string()
+            })
+            .filter(|s| !s.is_empty())
+            .collect();
+        Self {
+            subreddits,
+            limit: cfg.limit.clamp(1, 100),
+            client: reddit_client(cfg),
+            tokens: reddit_tokens(client_id, client_secret, cfg),
+        }
+    }
+
+    /// Hot posts of one subreddit, logging in again once if the token was revoked
+    async fn fetch_subreddit(&self, subreddit: &str) -> Result<Vec<Post>> {
+        let url = format!("{}/r/{}/hot", API_ROOT, subreddit);
+        let limit = self.limit.to_string();
+        let request = |token: &str| {
+            self.client
+                .get(&url)
+                .bearer_auth(token)
+                .query(&[("limit", limit.as_str()), ("raw_json", "1")])
+                .send()
+        };
+        let mut response = request(&self.tokens.access_token().await?)
+            .await
+            .with_context(|| format!("Failed to fetch r/{}", subreddit))?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            self.tokens.invalidate().await;
+            response = request(&self.tokens.access_token().await?)
+                .await
+                .with_context(|| format!("Failed to fetch r/{}", subreddit))?;
+        }
+        if !response.status().is_success() {
+            return Err(
+                NewsHubError::from_response(&response, Some(&format!("r/{}", subreddit))).into(),
+            );
+        }
+        let listing: Listing = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse r/{} listing", subreddit))?;
+        Ok(listing.data.children.into_iter().map(|c| c.data).collect())
+    }
+
+    fn item(&self, post: Post, now: DateTime<Utc>) -> NewsItem {
+        let discussion = format!("{}{}", SITE_ROOT, post.permalink);
+        let summary: String = post.selftext.chars().take(SUMMARY_CHARS).collect();
+        let mut tags = vec![format!("r/{}", post.subreddit)];
+        tags.extend(post.link_flair_text.filter(|f| !f.trim().is_empty()));
+        NewsItem {
+            id: api_item_id(REDDIT, &post.id),
+            source: REDDIT.to_string(),
+            source_id: source_id(REDDIT),
+            tickers: extract_tickers(&[&post.title, &summary]),
+            title: post.title,
+            url: if post.is_self { discussion } else { post.url },
+            summary,
+            published: DateTime::from_timestamp(post.created_utc as i64, 0).unwrap_or(now),
+            updated_at: now,
+            author: post.author.filter(|a| a != "[deleted]"),
+            tags,
+            score: post.score as f64,
+            comments: post.num_comments,
+            source_kind: SourceKind::Api,
+            ..Default::default()
+        }
+    }
+}
+
+/// Reddit asks every client for a unique, descriptive user agent
+fn reddit_client(cfg: &RedditConfig) -> Client {
+    Client::builder()
+        .user_agent(cfg.user_agent.clone())
+        .build()
+        .unwrap_or_default()
+}
+
+/// Tokens for Reddit's API, with refresh tokens kept in [`TOKEN_PATH`]
+pub fn reddit_tokens(client_id: String, client_secret: String, cfg: &RedditConfig) -> TokenManager {
+    TokenManager::new(
+        &REDDIT_OAUTH,
+        client_id,
+        client_secret,
+        cfg.redirect_uri.clone(),
+        TokenStore::new(TOKEN_PATH),
+        reddit_client(cfg),
+    )
+}
+
+#[async_trait]
+impl NewsAdaptor for RedditAdaptor {
+    fn name(&self) -> &str {
+        REDDIT
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.subreddits.is_empty()
+    }
+
+    /// Fails only when every subreddit does; a failing one is a warning
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let now = Utc::now();
+        let mut items: Vec<NewsItem> = Vec::new();
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+        for subreddit in &self.subreddits {
+            match self.fetch_subreddit(subreddit).await {
+                Ok(posts) => {
+                    for post in posts.into_iter().filter(|p| !p.stickied) {
+                        // Crossposted to several of the subreddits
+                        if !items.iter().any(|i| i.url == post.url && !post.is_self) {
+                            items.push(self.item(post, now));
+                        }
+                    }
+                }
+                Err(e) => {
+                    warnings.push(format!("r/{}: {}", subreddit, NewsHubError::classify(&e)));
+                    errors.push(e);
+                }
+            }
+        }
+
+        if errors.len() == self.subreddits.len() {
+            // Keep the first error whole so a rate limit or bad login is reported as such
+            return Err(errors.remove(0));
+        }
+        Ok((items, warnings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LISTING: &str = r#"{"kind": "Listing", "data": {"children": [
+        {"kind": "t3", "data": {"id": "1b2c3d", "title": "Daily discussion thread",
+            "subreddit": "stocks", "url": "https://www.reddit.com/r/stocks/comments/1b2c3d/daily/",
+            "permalink": "/r/stocks/comments/1b2c3d/daily/", "selftext": "", "is_self": true,
+            "created_utc": 1710424800.0, "score": 12, "num_comments": 3400,
+            "author": "AutoModerator", "stickied": true}},
+        {"kind": "t3", "data": {"id": "1b2c4e", "title": "NVDA guidance beats, shares up 8% after hours",
+            "subreddit": "stocks", "url": "https://www.reuters.com/technology/nvidia-results",
+            "permalink": "/r/stocks/comments/1b2c4e/nvda_guidance/", "selftext": "",
+            "is_self": false, "created_utc": 1710428400.0, "score": 5120, "num_comments": 812,
+            "author": "trader42", "link_flair_text": "Company News", "stickied": false}},
+        {"kind": "t3", "data": {"id": "1b2c5f", "title": "Is $TSLA overvalued?",
+            "subreddit": "stocks", "url": "https://www.reddit.com/r/stocks/comments/1b2c5f/tsla/",
+            "permalink": "/r/stocks/comments/1b2c5f/tsla/", "selftext": "Genuine question, P/E & all.",
+            "is_self": true, "created_utc": 1710432000.5, "score": 87, "num_comments": 240,
+            "author": "[deleted]", "stickied": false}}
+    ]}}"#;
+
+    fn adaptor() -> RedditAdaptor {
+        let cfg = RedditConfig {
+            subreddits: vec!["r/stocks".to_string(), " ".to_string()],
+            ..Default::default()
+        };
+        RedditAdaptor::new("id".to_string(), "secret".to_string(), &cfg)
+    }
+
+    #[test]
+    fn posts_become_items() {
+        let adaptor = adaptor();
+        assert_eq!(adaptor.subreddits, ["stocks"]);
+
+        let listing: Listing = serde_json::from_str(LISTING).unwrap();
+        let now = Utc::now();
+        let items: Vec<NewsItem> = listing
+            .data
+            .children
+            .into_iter()
+            .map(|c| adaptor.item(c.data, now))
+            .collect();
+
+        let link = &items[1];
+        assert_eq!(link.id, "reddit-1b2c4e");
+        assert_eq!(
+            link.url,
+            "https://www.reuters.com/technology/nvidia-results"
+        );
+        assert_eq!(link.tags, ["r/stocks", "Company News"]);
+        assert_eq!((link.score, link.comments), (5120.0, 812));
+        assert_eq!(link.published.timestamp(), 1710428400);
+        assert_eq!(link.author.as_deref(), Some("trader42"));
+
+        let self_post = &items[2];
+        assert_eq!(
+            self_post.url,
+            "https://www.reddit.com/r/stocks/comments/1b2c5f/tsla/"
+        );
+        assert_eq!(self_post.summary, "Genuine question, P/E & all.");
+        assert_eq!(self_post.tickers, ["TSLA"]);
+        assert_eq!(self_post.author, None);
+    }
+}
@@ -0,0 +1,564 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use super::{bundle_feeds, is_coin_tagged, CryptoAdaptor, ItemRewrites, Rewrite};
+use super::{
+    fetch_one, BenzingaAdaptor, FetchDiagnostic, FredAdaptor, GReaderAdaptor, JsonAdaptor,
+    LobstersAdaptor, NewsAdaptor, NextcloudAdaptor, RedditAdaptor, RssAdaptor, TranscriptsAdaptor,
+    TtRssAdaptor, WatchAdaptor, YahooFinanceAdaptor, WATCH_SNAPSHOT_DIR,
+};
+use crate::config::{Config, FeedKind, RemoteKind, SourceConfig};
+use crate::error::NewsHubError;
+use crate::models::{source_id, NewsItem, Source};
+use crate::profile::{self, Phase};
+
+/// Default per-fetch timeout
+pub const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+/// Shortest allowed scheduled refresh interval; smaller values are raised to this
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How the app treats a registered source
+#[derive(Debug, Clone)]
+pub struct SourceOptions {
+    /// Disabled sources are skipped by refreshes
+    pub enabled: bool,
+    /// A fetch taking longer than this fails
+    pub timeout: Duration,
+    /// Refresh automatically this often; `None` means manual refresh only
+    pub interval: Option<Duration>,
+    /// Refresh at an interval learned from the source's publishing cadence,
+    /// with `interval` used until there is enough history
+    pub adaptive: bool,
+    /// Free-form labels for grouping sources
+    pub tags: Vec<String>,
+    pub group: Option<String>,
+    /// Higher sorts first when sources are listed
+    pub priority: i32,
+    /// Display colour name or hex code
+    pub color: Option<String>,
+    /// Keep at most this many of the newest items per fetch
+    pub max_items: Option<usize>,
+    /// Skip items published longer ago than this
+    pub max_age: Option<Duration>,
+    /// Tags added to every item fetched from the source
+    pub item_tags: Vec<String>,
+    /// Drop items repeating a headline the source published this close to
+    pub title_dedup: Option<Duration>,
+    /// Title and URL clean-up applied to every item fetched from the source
+    pub rewrites: ItemRewrites,
+}
+
+impl Default for SourceOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout: DEFAULT_FETCH_TIMEOUT,
+            interval: None,
+            adaptive: false,
+            tags: Vec::new(),
+            group: None,
+            priority: 0,
+            color: None,
+            max_items: None,
+            max_age: None,
+            item_tags: Vec::new(),
+            title_dedup: None,
+            rewrites: ItemRewrites::default(),
+        }
+    }
+}
+
+impl SourceOptions {
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval.max(MIN_REFRESH_INTERVAL));
+        self
+    }
+
+    pub fn adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items.max(1));
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn item_tag(mut self, tag: impl Into<String>) -> Self {
+        self.item_tags.push(tag.into());
+        self
+    }
+
+    pub fn title_dedup(mut self, window: Duration) -> Self {
+        self.title_dedup = Some(window);
+        self
+    }
+
+    pub fn strip_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.rewrites.strip_prefixes.push(prefix.into());
+        self
+    }
+
+    pub fn title_rewrite(mut self, rewrite: Rewrite) -> Self {
+        self.rewrites.titles.push(rewrite);
+        self
+    }
+
+    pub fn url_rewrite(mut self, rewrite: Rewrite) -> Self {
+        self.rewrites.urls.push(rewrite);
+        self
+    }
+
+    /// Apply `[sources.<name>]` overrides from config
+    fn merge(mut self, cfg: &SourceConfig) -> Self {
+        if let Some(enabled) = cfg.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(secs) = cfg.timeout_secs {
+            self.timeout = Duration::from_secs(secs.max(1));
+        }
+        if let Some(secs) = cfg.interval_secs {
+            self = self.interval(Duration::from_secs(secs));
+        }
+        if let Some(adaptive) = cfg.adaptive {
+            self.adaptive = adaptive;
+        }
+        self.tags.extend(cfg.tags.iter().cloned());
+        if let Some(group) = &cfg.group {
+            self.group = Some(group.clone());
+        }
+        if let Some(priority) = cfg.priority {
+            self.priority = priority;
+        }
+        if let Some(color) = &cfg.color {
+            self.color = Some(color.clone());
+        }
+        if let Some(max_items) = cfg.max_items {
+            self = self.max_items(max_items);
+        }
+        if let Some(hours) = cfg.max_age_hours {
+            self = self.max_age(Duration::from_secs(hours.max(1) * 3600));
+        }
+        if let Some(mins) = cfg.title_dedup_mins {
+            self = self.title_dedup(Duration::from_secs(mins.max(1) * 60));
+        }
+        for tag in cfg.item_tags.iter().filter(|t| !t.trim().is_empty()) {
+            self = self.item_tag(tag.trim());
+        }
+        for prefix in cfg.strip_prefixes.iter().filter(|p| !p.trim().is_empty()) {
+            self = self.strip_prefix(prefix.trim());
+        }
+        // Invalid patterns are reported by `config check`
+        for rule in &cfg.title_rewrites {
+            if let Ok(rewrite) = Rewrite::from_config(rule) {
+                self = self.title_rewrite(rewrite);
+            }
+        }
+        for rule in &cfg.url_rewrites {
+            if let Ok(rewrite) = Rewrite::from_config(rule) {
+                self = self.url_rewrite(rewrite);
+            }
+        }
+        self
+    }
+}
+
+/// An adaptor together with its options
+pub struct RegisteredAdaptor {
+    adaptor: Box<dyn NewsAdaptor>,
+    pub options: SourceOptions,
+}
+
+impl RegisteredAdaptor {
+    pub fn name(&self) -> &str {
+        self.adaptor.name()
+    }
+
+    pub fn adaptor(&self) -> &dyn NewsAdaptor {
+        self.adaptor.as_ref()
+    }
+
+    /// Enabled by its options and configured (e.g. has an API key)
+    pub fn is_enabled(&self) -> bool {
+        self.options.enabled && self.adaptor.is_enabled()
+    }
+
+    /// The database row describing this source; `last_fetch`, the stats,
+    /// `moved_to` and `cooldown_until` are left unset
+    pub fn source(&self) -> Source {
+        Source {
+            id: source_id(self.name()),
+            name: self.name().to_string(),
+            kind: self.adaptor.kind(),
+            url: self.adaptor.url().map(str::to_string),
+            group: self.options.group.clone(),
+            enabled: self.is_enabled(),
+            priority: self.options.priority,
+            color: self.options.color.clone(),
+            last_fetch: None,
+            stats: Default::default(),
+            moved_to: None,
+            cooldown_until: None,
+        }
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.options
+            .tags
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Apply `max_age` and `max_items`, newest items first. Returns the kept
+    /// items and a description of each group skipped.
+    fn trim(&self, mut items: Vec<NewsItem>, now: DateTime<Utc>) -> (Vec<NewsItem>, Vec<String>) {
+        let mut skipped = Vec::new();
+        if let Some(max_age) = self.options.max_age {
+            let cutoff = now - chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+            let before = items.len();
+            items.retain(|item| item.published >= cutoff);
+            if items.len() < before {
+                skipped.push(format!(
+                    "{} older than {}h",
+                    before - items.len(),
+                    max_age.as_secs() / 3600
+                ));
+            }
+        }
+        if let Some(max_items) = self.options.max_items.filter(|&max| items.len() > max) {
+            items.sort_by_key(|item| std::cmp::Reverse(item.published));
+            skipped.push(format!(
+                "{} over the limit of {}",
+                items.len() - max_items,
+                max_items
+            ));
+            items.truncate(max_items);
+        }
+        (items, skipped)
+    }
+
+    /// Add the source's item tags to each item that doesn't have them yet
+    fn tag(&self, items: &mut [NewsItem]) {
+        for item in items {
+            for tag in &self.options.item_tags {
+                if !item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    item.tags.push(tag.clone());
+                }
+            }
+        }
+    }
+
+    /// Fetch with the configured timeout, folding errors into the diagnostic.
+    /// Titles and URLs are rewritten first. Items beyond the age cutoff or
+    /// item limit are then dropped and counted in the diagnostic's message;
+    /// the rest get the source's item tags.
+    pub async fn fetch(&self) -> (Vec<NewsItem>, FetchDiagnostic) {
+        let _span = profile::span(Phase::Fetch, self.name());
+        match tokio::time::timeout(self.options.timeout, fetch_one(self.adaptor())).await {
+            Ok((mut items, mut diagnostic)) if diagnostic.success => {
+                if !self.options.rewrites.is_empty() {
+                    items
+                        .iter_mut()
+                        .for_each(|item| self.options.rewrites.apply(item));
+                }
+                let (mut items, skipped) = self.trim(items, Utc::now());
+                self.tag(&mut items);
+                if !skipped.is_empty() {
+                    diagnostic.message = format!(
+                        "Fetched {} items (skipped {})",
+                        items.len(),
+                        skipped.join(", ")
+                    );
+                }
+                (items, diagnostic)
+            }
+            Ok(result) => result,
+            Err(_) => {
+                let error = NewsHubError::Network(format!(
+                    "timed out after {}s",
+                    self.options.timeout.as_secs()
+                ));
+                let diagnostic = FetchDiagnostic {
+                    source: self.name().to_string(),
+                    success: false,
+                    message: error.to_string(),
+                    warnings: Vec::new(),
+                    moved_to: None,
+                    error: Some(error),
+                };
+                (Vec::new(), diagnostic)
+            }
+        }
+    }
+}
+
+/// The set of news sources, in registration order. Names are unique; registering
+/// a name again replaces the earlier adaptor.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use news_hub::adaptors::{AdaptorRegistry, RssAdaptor, SourceOptions};
+///
+/// let registry = AdaptorRegistry::builder()
+///     .register(RssAdaptor::new(
+///         "https://www.cnbc.com/id/100003114/device/rss/rss.html".to_string(),
+///         "CNBC".to_string(),
+///     ))
+///     .register_with(
+///         RssAdaptor::new("https://example.com/feed.xml".to_string(), "Example".to_string()),
+///         SourceOptions::default()
+///             .timeout(Duration::from_secs(10))
+///             .interval(Duration::from_secs(300))
+///             .tag("blogs"),
+///     )
+///     .build();
+/// ```
+#[derive(Default)]
+pub struct AdaptorRegistry {
+    entries: Vec<RegisteredAdaptor>,
+}
+
+impl AdaptorRegistry {
+    pub fn builder() -> AdaptorRegistryBuilder {
+        AdaptorRegistryBuilder::default()
+    }
+
+    /// RSS feeds of the selected bundles, `[[feed]]`, `[[watch]]` and `[[json]]` entries, Yahoo
+    /// Finance for the watchlist,
+    /// and API sources that have credentials, with `[sources.<name>]` overrides applied
+    pub fn from_config(config: &Config) -> Self {
+        let mut builder = Self::builder();
+
+        // Feeds from the selected bundles (no API key required)
+        for (url, name) in bundle_feeds(&config.app.bundles) {
+            let url = config
+                .sources
+                .get(name)
+                .and_then(|s| s.url.clone())
+                .unwrap_or_else(|| url.to_string());
+            builder = if is_coin_tagged(name) {
+                builder.register(CryptoAdaptor::new(url, name.to_string()))
+            } else {
+                builder.register(RssAdaptor::new(url, name.to_string()))
+            };
+        }
+
+        // `[[feed]]` publisher and bridge feeds; invalid entries are reported by `config check`
+        for feed in &config.feed {
+            let Ok((url, name)) = feed.resolve(&config.bridge) else {
+                continue;
+            };
+            builder = match feed.kind {
+                FeedKind::Lobsters => builder.register(LobstersAdaptor::new(name, url)),
+                _ => {
+                    let mut adaptor = RssAdaptor::new(url, name);
+                    adaptor.set_html_descriptions(true);
+                    builder.register(adaptor)
+                }
+            };
+        }
+
+        // `[[watch]]` pages diffed against their last snapshot
+        for page in &config.watch {
+            builder = builder.register(WatchAdaptor::new(page.name(), page, WATCH_SNAPSHOT_DIR));
+        }
+
+        // `[[json]]` APIs; those whose auth variable is unset are skipped
+        for source in &config.json {
+            let auth = config.credentials.json_auth.get(&source.name).cloned();
+            if source.auth_env.is_some() && auth.is_none() {
+                continue;
+            }
+            builder = builder.register(JsonAdaptor::new(source, auth));
+        }
+
+        // Yahoo Finance markets feed plus one per watchlist symbol
+        builder = builder.register(YahooFinanceAdaptor::new(&config.watchlist.symbols));
+
+        // Conditionally add API-based adaptors
+        if let Some(key) = &config.credentials.benzinga_key {
+            builder = builder.register(BenzingaAdaptor::new(key.clone()));
+        }
+        if let Some(key) = &config.credentials.fred_key {
+            builder = builder.register(FredAdaptor::new(key.clone(), &config.fred));
+        }
+        if let Some(key) = &config.credentials.fmp_key {
+            builder = builder.register(TranscriptsAdaptor::new(
+                key.clone(),
+                &config.watchlist.symbols,
+                &config.transcripts,
+            ));
+        }
+        if let (Some(id), Some(secret)) = (
+            &config.credentials.reddit_client_id,
+            &config.credentials.reddit_client_secret,
+        ) {
+            builder = builder.register(RedditAdaptor::new(
+                id.clone(),
+                secret.clone(),
+                &config.reddit,
+            ));
+        }
+
+        // Remote servers whose password is set
+        for remote in &config.remote {
+            let name = remote.name();
+            let Some(password) = config.credentials.remote_passwords.get(&name) else {
+                continue;
+            };
+            let (url, username) = (remote.url.clone(), remote.username.clone());
+            let password = password.clone();
+            builder = match remote.kind {
+                RemoteKind::FreshRss | RemoteKind::Miniflux => {
+                    let mut adaptor = GReaderAdaptor::new(name, url, username, password);
+                    adaptor.set_limit(remote.limit);
+                    builder.register(adaptor)
+                }
+                RemoteKind::Nextcloud => {
+                    let mut adaptor = NextcloudAdaptor::new(name, url, username, password);
+                    adaptor.set_limit(remote.limit);
+                    builder.register(adaptor)
+                }
+                RemoteKind::TtRss => {
+                    let mut adaptor = TtRssAdaptor::new(name, url, username, password);
+                    adaptor.set_limit(remote.limit);
+                    builder.register(adaptor)
+                }
+            };
+        }
+
+        builder
+            .adaptive(config.refresh.adaptive)
+            .apply_config(&config.sources)
+            .build()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RegisteredAdaptor> {
+        self.entries.iter().find(|e| e.name() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredAdaptor> {
+        self.entries.iter()
+    }
+
+    /// Names of sources a full refresh should fetch
+    pub fn enabled_names(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.is_enabled())
+            .map(|e| e.name().to_string())
+            .collect()
+    }
+
+    /// Database rows for every registered source
+    pub fn sources(&self) -> Vec<Source> {
+        self.entries.iter().map(RegisteredAdaptor::source).collect()
+    }
+
+    /// Enabled sources with a scheduled refresh interval
+    pub fn scheduled(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.entries
+            .iter()
+            .filter(|e| e.is_enabled())
+            .filter_map(|e| e.options.interval.map(|interval| (e.name(), interval)))
+    }
+
+    /// Enabled sources whose interval is learned from their publishing cadence
+    pub fn adaptive(&self) -> impl Iterator<Item = &RegisteredAdaptor> {
+        self.entries
+            .iter()
+            .filter(|e| e.is_enabled() && e.options.adaptive)
+    }
+}
+
+#[derive(Default)]
+pub struct AdaptorRegistryBuilder {
+    entries: Vec<RegisteredAdaptor>,
+}
+
+impl AdaptorRegistryBuilder {
+    /// Register a source with default options
+    pub fn register(self, adaptor: impl NewsAdaptor + 'static) -> Self {
+        self.register_with(adaptor, SourceOptions::default())
+    }
+
+    pub fn register_with(
+        mut self,
+        adaptor: impl NewsAdaptor + 'static,
+        options: SourceOptions,
+    ) -> Self {
+        let entry = RegisteredAdaptor {
+            adaptor: Box::new(adaptor),
+            options,
+        };
+        match self.entries.iter_mut().find(|e| e.name() == entry.name()) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+        self
+    }
+
+    /// Learn every registered source's refresh interval, or none's; the
+    /// per-source `adaptive` override applies after this
+    pub fn adaptive(mut self, adaptive: bool) -> Self {
+        for entry in &mut self.entries {
+            entry.options.adaptive = adaptive;
+        }
+        self
+    }
+
+    /// Apply per-source overrides to already registered sources.
+    /// Overrides for names that aren't registered are ignored.
+    pub fn apply_config<'a>(
+        mut self,
+        sources: impl IntoIterator<Item = (&'a String, &'a SourceConfig)>,
+    ) -> Self {
+        for (name, cfg) in sources {
+            if let Some(entry) = self.entries.iter_mut().find(|e| e.name() == name) {
+                entry.options = entry.options.clone().merge(cfg);
+            }
+        }
+        self
+    }
+
+    pub fn build(self) -> AdaptorRegistry {
+        AdaptorRegistry {
+            entries: self.entries,
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+
+use crate::config::RewriteConfig;
+use crate::models::NewsItem;
+
+/// A compiled `pattern` → `replace` rule for titles or URLs. `replace` may
+/// refer to capture groups as `$1` or `${name}`.
+#[derive(Debug, Clone)]
+pub struct Rewrite {
+    pattern: Regex,
+    replace: String,
+}
+
+impl Rewrite {
+    pub fn from_config(cfg: &RewriteConfig) -> Result<Self> {
+        let pattern = Regex::new(&cfg.pattern)
+            .map_err(|e| anyhow!("Invalid rewrite pattern '{}': {}", cfg.pattern, e))?;
+        Ok(Self {
+            pattern,
+            replace: cfg.replace.clone(),
+        })
+    }
+
+    /// Every match in `text` replaced
+    pub fn apply(&self, text: &str) -> String {
+        self.pattern
+            .replace_all(text, self.replace.as_str())
+            .into_owned()
+    }
+}
+
+/// How a source's items are cleaned up as they are fetched
+#[derive(Debug, Clone, Default)]
+pub struct ItemRewrites {
+    /// Removed from the start of titles, case-insensitively, e.g. "BREAKING:"
+    pub strip_prefixes: Vec<String>,
+    pub titles: Vec<Rewrite>,
+    pub urls: Vec<Rewrite>,
+}
+
+impl ItemRewrites {
+    pub fn is_empty(&self) -> bool {
+        self.strip_prefixes.is_empty() && self.titles.is_empty() && self.urls.is_empty()
+    }
+
+    /// Rewrite the title and URL of `item`. A rewrite that would leave
+    /// either empty is not applied.
+    pub fn apply(&self, item: &mut NewsItem) {
+        let mut title = item.title.trim();
+        // Prefixes are stripped repeatedly, e.g. "BREAKING: WATCH: ..."
+        while let Some(rest) = self
+            .strip_prefixes
+            .iter()
+            .find_map(|prefix| strip_prefix_ignore_case(title, prefix))
+        {
+            title = rest.trim_start();
+        }
+        let title = self
+            .titles
+            .iter()
+            .fold(title.to_string(), |title, rule| rule.apply(&title));
+        if !title.trim().is_empty() {
+            item.title = title.trim().to_string();
+        }
+
+        let url = self
+            .urls
+            .iter()
+            .fold(item.url.clone(), |url, rule| rule.apply(&url));
+        if !url.trim().is_empty() {
+            item.url = url;
+        }
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let prefix = prefix.trim();
+    if prefix.is_empty() {
+        return None;
+    }
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, replace: &str) -> Rewrite {
+        Rewrite::from_config(&RewriteConfig {
+            pattern: pattern.to_string(),
+            replace: replace.to_string(),
+        })
+        .unwrap()
+    }
+
+    fn item(title: &str, url: &str) -> NewsItem {
+        NewsItem {
+            title: title.to_string(),
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn prefixes_are_stripped_repeatedly_ignoring_case() {
+        let rewrites = ItemRewrites {
+            strip_prefixes: vec!["BREAKING:".to_string(), "Watch:".to_string()],
+            ..Default::default()
+        };
+        let mut news = item("breaking: WATCH:  Fed holds rates", "https://example.com/a");
+        rewrites.apply(&mut news);
+        assert_eq!(news.title, "Fed holds rates");
+    }
+
+    #[test]
+    fn title_and_url_rules_apply_in_order() {
+        let rewrites = ItemRewrites {
+            titles: vec![rule(r"\s+- Reuters$", ""), rule("Fed", "Federal Reserve")],
+            urls: vec![rule("/amp/(.*)$", "/$1")],
+            ..Default::default()
+        };
+        let mut news = item(
+            "Fed holds rates - Reuters",
+            "https://example.com/amp/markets/fed",
+        );
+        rewrites.apply(&mut news);
+        assert_eq!(news.title, "Federal Reserve holds rates");
+        assert_eq!(news.url, "https://example.com/markets/fed");
+    }
+
+    #[test]
+    fn rewrite_leaving_title_empty_is_skipped() {
+        let rewrites = ItemRewrites {
+            strip_prefixes: vec!["BREAKING:".to_string()],
+            ..Default::default()
+        };
+        let mut news = item("BREAKING:", "https://example.com/a");
+        rewrites.apply(&mut news);
+        assert_eq!(news.title, "BREAKING:");
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        let cfg = RewriteConfig {
+            pattern: "(unclosed".to_string(),
+            replace: String::new(),
+        };
+        assert!(Rewrite::from_config(&cfg).is_err());
+    }
+}
@@ -1,101 +1,412 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use reqwest::redirect::Policy;
+use reqwest::{Client, Response, StatusCode};
 use rss::Channel;
-use sha2::{Digest, Sha256};
+use std::sync::Mutex;
 
 use super::NewsAdaptor;
-use crate::models::NewsItem;
+use crate::enrich::html_to_text;
+use crate::error::{retry_after, NewsHubError};
+use crate::models::{extract_tickers, feed_item_id, source_id, NewsItem, SourceKind};
+use crate::profile::{self, Phase};
 
 use tracing::warn;
 
-// Hardcoded default RSS feeds (no API keys required)
-pub const DEFAULT_RSS_FEEDS: &[(&str, &str)] = &[
-    ("https://www.marketwatch.com/rss/topstories", "MarketWatch"),
-    ("https://feeds.bloomberg.com/markets/news.rss", "Bloomberg"),
-    (
-        "https://www.cnbc.com/id/100003114/device/rss/rss.html",
-        "CNBC",
-    ),
-    ("https://www.barrons.com/rss/topstories", "Barrons"),
-    ("https://www.ft.com/rss/home/world", "Financial Times"),
-    // looks discontinued
-    // ("https://www.reuters.com/rss/worldNews", "Reuters"),
-    ("https://www.wsj.com/news/world", "Wall Street Journal"),
-    ("https://www.nytimes.com/rss/world", "New York Times"),
-    (
-        "https://www.investing.com/rss/news_25.rss",
-        "Investing.com Stocks",
-    ),
-    (
-        "https://www.investing.com/rss/news_301.rss",
-        "Investing.com Crypto",
-    ),
-    (
-        "https://www.investing.com/rss/news_1.rss",
-        "Investing.com Forex",
-    ),
-    (
-        "https://www.investing.com/rss/news_1062.rss",
-        "Investing.com Earnings",
-    ),
-    // investing.com catch-all endpoint
-    (
-        "https://www.investing.com/rss/news.rss",
-        "Investing.com Latest",
-    ),
+/// Image for an item: an image enclosure, else a Media RSS thumbnail or image content
+fn image_url(item: &rss::Item) -> Option<String> {
+    if let Some(enclosure) = item.enclosure() {
+        if enclosure.mime_type().starts_with("image/") {
+            return Some(enclosure.url().to_string());
+        }
+    }
+    let media = item.extensions().get("media")?;
+    ["thumbnail", "content"]
+        .iter()
+        .filter_map(|name| media.get(*name))
+        .flatten()
+        .find(|ext| ext.attrs().get("medium").is_none_or(|m| m == "image"))
+        .and_then(|ext| ext.attrs().get("url").cloned())
+}
+
+/// Longest response excerpt quoted in an error
+const SNIPPET_LEN: usize = 80;
+/// Redirects followed before a fetch gives up
+const MAX_REDIRECTS: usize = 10;
+
+/// Whether a response is an HTML page rather than a feed, by content type or,
+/// for servers that mislabel it, by its first tag
+fn is_html(content_type: &str, body: &[u8]) -> bool {
+    if content_type.contains("text/html") || content_type.contains("application/xhtml") {
+        return true;
+    }
+    let start = String::from_utf8_lossy(&body[..body.len().min(512)]).to_lowercase();
+    let start = start.trim_start_matches('\u{feff}').trim_start();
+    start.starts_with("<!doctype html") || start.starts_with("<html")
+}
+
+/// Short excerpt of a response for error messages: the page title if it has
+/// one, else the start of its text with whitespace collapsed
+fn snippet(body: &[u8]) -> String {
+    let text = String::from_utf8_lossy(body);
+    let lower = text.to_ascii_lowercase();
+    let title = lower.find("<title").and_then(|start| {
+        let open_end = start + lower[start..].find('>')? + 1;
+        let close = open_end + lower[open_end..].find("</title")?;
+        Some(&text[open_end..close])
+    });
+    let excerpt: String = title
+        .unwrap_or(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if excerpt.chars().count() > SNIPPET_LEN {
+        let cut: String = excerpt.chars().take(SNIPPET_LEN).collect();
+        format!("{}…", cut)
+    } else {
+        excerpt
+    }
+}
+
+/// Date layouts seen in feeds besides RFC 2822 and RFC 3339; zoneless ones are UTC
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S %z", "%a, %d %b %Y %H:%M %z"];
+const NAIVE_DATE_FORMATS: &[&str] = &[
+    "%a, %d %b %Y %H:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
 ];
 
-/// Generate URL hash for ID fallback
-fn url_to_hash(url: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(url.as_bytes());
-    // CR jyuan: truncate the hash to 16 chars. Collision should be extremely
-    // unlikely, but there may be a smarter way to generate and compress
-    format!("{:x}", hasher.finalize())[..16].to_string()
+/// Publish date of an item, tolerating the layouts some publishers use
+pub(super) fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    DateTime::parse_from_rfc2822(text)
+        .or_else(|_| DateTime::parse_from_rfc3339(text))
+        .ok()
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| DateTime::parse_from_str(text, format).ok())
+        })
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|| {
+            NAIVE_DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+                .map(|dt| dt.and_utc())
+        })
+}
+
+/// Link of an item; feeds that leave out `<link>` often carry the article
+/// URL as a permalink GUID instead
+fn item_link(item: &rss::Item) -> Option<String> {
+    let link = item.link().map(str::trim).filter(|l| !l.is_empty());
+    let permalink = || {
+        item.guid()
+            .filter(|g| g.is_permalink())
+            .map(|g| g.value().trim())
+            .filter(|g| g.starts_with("https://") || g.starts_with("http://"))
+    };
+    link.or_else(permalink).map(str::to_string)
 }
 
-/// Generate stable ID from source, GUID (if available), or URL hash
-/// Uses hybrid approach: hash if isPermaLink=true OR length > 16
-fn generate_stable_id(source_name: &str, guid_obj: Option<rss::Guid>, url: &str) -> String {
-    let source_slug = source_name.to_lowercase().replace(" ", "-");
+/// Image of an Atom entry: an image enclosure link, else a Media RSS
+/// thumbnail or image content
+fn atom_image_url(entry: &atom_syndication::Entry) -> Option<String> {
+    let enclosure = entry
+        .links()
+        .iter()
+        .find(|l| l.rel() == "enclosure" && l.mime_type().is_some_and(|m| m.starts_with("image/")));
+    if let Some(link) = enclosure {
+        return Some(link.href().to_string());
+    }
+    let media = entry.extensions().get("media")?;
+    ["thumbnail", "content"]
+        .iter()
+        .filter_map(|name| media.get(*name))
+        .flatten()
+        .find(|ext| ext.attrs().get("medium").is_none_or(|m| m == "image"))
+        .and_then(|ext| ext.attrs().get("url").cloned())
+}
 
-    let identifier = if let Some(guid) = guid_obj {
-        let guid_str = guid.value();
+/// Text of an Atom text construct; HTML is reduced to plain text
+fn atom_text(text: &atom_syndication::Text) -> String {
+    match text.r#type {
+        atom_syndication::TextType::Text => text.value.trim().to_string(),
+        _ => html_to_text(&text.value),
+    }
+}
 
-        // Hash if it's a permalink OR if it's too long (>16 chars)
-        if guid.is_permalink() || guid_str.len() > 16 {
-            format!("hash-{}", url_to_hash(guid_str))
-        } else {
-            // Keep original GUID, sanitized and normalized to 16 chars
-            let sanitized = guid_str
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
-                .take(16) // Normalize to 16 chars max
-                .collect::<String>();
-
-            if sanitized.is_empty() {
-                format!("hash-{}", url_to_hash(url))
-            } else {
-                format!("guid-{}", sanitized)
+/// Link of an Atom entry: its alternate link (the default relation), else
+/// an id that is a web address
+fn atom_link(entry: &atom_syndication::Entry) -> Option<String> {
+    let alternate = entry
+        .links()
+        .iter()
+        .find(|l| l.rel() == "alternate")
+        .map(|l| l.href().trim())
+        .filter(|href| !href.is_empty());
+    let id = entry.id().trim();
+    let permalink = (id.starts_with("https://") || id.starts_with("http://")).then_some(id);
+    alternate.or(permalink).map(str::to_string)
+}
+
+/// Items of an Atom feed, and the number of entries dropped for lacking a
+/// title or link. Entries are dated by `published`, else `updated`.
+fn parse_atom(
+    feed: &atom_syndication::Feed,
+    source_name: &str,
+    html_descriptions: bool,
+) -> (Vec<NewsItem>, usize) {
+    let now = Utc::now();
+    let language = feed.lang().map(str::to_string);
+    let items: Vec<NewsItem> = feed
+        .entries()
+        .iter()
+        .filter_map(|entry| {
+            let title = atom_text(entry.title());
+            if title.is_empty() {
+                return None;
             }
+            let link = atom_link(entry)?;
+            let published = entry.published().unwrap_or(entry.updated());
+
+            // Ids that are web addresses are hashed like permalink GUIDs
+            let id = feed_item_id(
+                source_name,
+                Some(entry.id()),
+                entry.id().starts_with("http"),
+                &link,
+            );
+
+            let summary = match entry.summary() {
+                Some(text) if html_descriptions => html_to_text(&text.value),
+                Some(text) => atom_text(text),
+                None => String::new(),
+            };
+            let content = entry.content().and_then(|c| c.value()).map(str::to_string);
+            // Entries without their own author inherit the feed's
+            let author = entry
+                .authors()
+                .first()
+                .or_else(|| feed.authors().first())
+                .map(|a| a.name().to_string());
+
+            Some(NewsItem {
+                id,
+                source: source_name.to_string(),
+                source_id: source_id(source_name),
+                tickers: extract_tickers(&[&title, &summary]),
+                title,
+                url: link,
+                summary,
+                published: published.with_timezone(&Utc),
+                updated_at: now,
+                author,
+                tags: entry
+                    .categories()
+                    .iter()
+                    .map(|c| c.label().unwrap_or(c.term()).to_string())
+                    .collect(),
+                image_url: atom_image_url(entry),
+                content,
+                language: language.clone(),
+                source_kind: SourceKind::Rss,
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    let dropped = feed.entries().len() - items.len();
+    (items, dropped)
+}
+
+/// Items of an RSS or Atom feed body, and the number of entries dropped for
+/// lacking a title, link or valid date. CPU-bound; [`RssAdaptor`] runs it
+/// on a blocking thread.
+pub fn parse_feed(
+    content: &[u8],
+    content_type: &str,
+    source_name: &str,
+    html_descriptions: bool,
+) -> Result<(Vec<NewsItem>, usize)> {
+    let parse_error = |e: &dyn std::fmt::Display| {
+        let kind = if content_type.is_empty() {
+            "no content type"
+        } else {
+            content_type
+        };
+        anyhow!(
+            "Failed to parse feed XML from {} ({}): {}",
+            source_name,
+            kind,
+            e
+        )
+    };
+    let channel = match Channel::read_from(content) {
+        Ok(channel) => channel,
+        // Not an <rss> document; Atom feeds start with <feed>
+        Err(rss::Error::InvalidStartTag) => {
+            return match atom_syndication::Feed::read_from(content) {
+                Ok(feed) => Ok(parse_atom(&feed, source_name, html_descriptions)),
+                Err(atom_syndication::Error::InvalidStartTag) => Err(parse_error(
+                    &"the document is neither an RSS nor an Atom feed",
+                )),
+                Err(e) => Err(parse_error(&e)),
+            };
         }
-    } else {
-        // No GUID, hash the URL
-        format!("hash-{}", url_to_hash(url))
+        Err(e) => return Err(parse_error(&e)),
     };
 
-    format!("{}-{}", source_slug, identifier)
+    let now = Utc::now();
+    let language = channel.language().map(str::to_string);
+
+    let items: Vec<NewsItem> = channel
+        .items()
+        .iter()
+        .filter_map(|item| {
+            let title = item.title().map(str::trim).filter(|t| !t.is_empty())?;
+            let title = title.to_string();
+            let link = item_link(item)?;
+            // RSS 1.0 style feeds date items with dc:date instead of pubDate
+            let pub_date = item.pub_date().or_else(|| {
+                item.dublin_core_ext()
+                    .and_then(|dc| dc.dates().first())
+                    .map(String::as_str)
+            })?;
+
+            // if we cannot parse the date, skip this entry
+            let published = parse_date(pub_date)?;
+
+            // Permalink GUIDs are hashed, short opaque ones kept
+            let guid = item.guid();
+            let id = feed_item_id(
+                source_name,
+                guid.map(|g| g.value()),
+                guid.is_some_and(|g| g.is_permalink()),
+                &link,
+            );
+
+            let description = item.description().unwrap_or("");
+            let summary = if html_descriptions {
+                html_to_text(description)
+            } else {
+                description.to_string()
+            };
+            let author = item.author().map(str::to_string).or_else(|| {
+                item.dublin_core_ext()
+                    .and_then(|dc| dc.creators().first().cloned())
+            });
+
+            Some(NewsItem {
+                id,
+                source: source_name.to_string(),
+                source_id: source_id(source_name),
+                tickers: extract_tickers(&[&title, &summary]),
+                title,
+                url: link,
+                summary,
+                published,
+                updated_at: now,
+                author,
+                tags: item
+                    .categories()
+                    .iter()
+                    .map(|c| c.name().to_string())
+                    .collect(),
+                image_url: image_url(item),
+                content: item.content().map(str::to_string),
+                language: language.clone(),
+                source_kind: SourceKind::Rss,
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    let dropped = channel.items().len() - items.len();
+    Ok((items, dropped))
 }
 
 pub struct RssAdaptor {
     url: String,
     source_name: String,
+    /// Descriptions are HTML and are reduced to plain text
+    html_descriptions: bool,
+    /// Follows redirects by hand, to tell permanent moves from temporary ones
+    client: Client,
+    /// Where the feed permanently moved; later fetches go there directly
+    moved_to: Mutex<Option<String>>,
 }
 
 impl RssAdaptor {
     pub fn new(url: String, source_name: String) -> Self {
-        Self { url, source_name }
+        let client = Client::builder()
+            .redirect(Policy::none())
+            .build()
+            .unwrap_or_default();
+        Self {
+            url,
+            source_name,
+            html_descriptions: false,
+            client,
+            moved_to: Mutex::new(None),
+        }
+    }
+
+    /// Convert item descriptions from HTML to plain text, for publishers
+    /// that put markup in them
+    pub fn set_html_descriptions(&mut self, html: bool) {
+        self.html_descriptions = html;
+    }
+
+    /// GET the feed, following redirects. Also returns the final URL when
+    /// every redirect on the way was permanent (301/308).
+    async fn get(&self) -> Result<(Response, Option<String>)> {
+        let mut url = self.moved_to().unwrap_or_else(|| self.url.clone());
+        let mut permanent = true;
+        let mut moved = None;
+        for _ in 0..=MAX_REDIRECTS {
+            let response = self
+                .client
+                .get(&url)
+                .send()
+                .await
+                .context("Failed to fetch RSS feed")?;
+            let status = response.status();
+            let is_redirect = matches!(
+                status,
+                StatusCode::MOVED_PERMANENTLY
+                    | StatusCode::FOUND
+                    | StatusCode::SEE_OTHER
+                    | StatusCode::TEMPORARY_REDIRECT
+                    | StatusCode::PERMANENT_REDIRECT
+            );
+            if !is_redirect {
+                return Ok((response, moved));
+            }
+
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| anyhow!("{} redirected without a location", self.source_name))?;
+            url = response
+                .url()
+                .join(location)
+                .with_context(|| format!("Invalid redirect to '{}'", location))?
+                .to_string();
+            permanent &= matches!(
+                status,
+                StatusCode::MOVED_PERMANENTLY | StatusCode::PERMANENT_REDIRECT
+            );
+            moved = permanent.then(|| url.clone());
+        }
+        Err(anyhow!(
+            "{} redirected more than {} times",
+            self.source_name,
+            MAX_REDIRECTS
+        ))
     }
 }
 
@@ -105,50 +416,68 @@ impl NewsAdaptor for RssAdaptor {
         &self.source_name
     }
 
+    fn kind(&self) -> SourceKind {
+        SourceKind::Rss
+    }
+
+    fn url(&self) -> Option<&str> {
+        Some(&self.url)
+    }
+
+    fn moved_to(&self) -> Option<String> {
+        self.moved_to.lock().unwrap().clone()
+    }
+
     async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
-        let content = reqwest::get(&self.url)
-            .await
-            .context("Failed to fetch RSS feed")?
+        let mut warnings = Vec::new();
+        let (response, moved) = self.get().await?;
+        if let Some(new_url) = moved {
+            warnings.push(format!(
+                "Feed moved permanently to {}; set `url` under [sources.\"{}\"] to update it",
+                new_url, self.source_name
+            ));
+            *self.moved_to.lock().unwrap() = Some(new_url);
+        }
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_lowercase();
+        let retry_after = retry_after(response.headers(), Utc::now());
+        let content = response
             .bytes()
             .await
             .context("Failed to read RSS response")?;
 
-        let channel = Channel::read_from(&content[..]).context("Failed to parse RSS XML")?;
-
-        let now = Utc::now();
+        // Login pages and interstitials don't parse; say what came back instead
+        if !status.is_success() {
+            return Err(
+                NewsHubError::from_status(status.as_u16(), Some(&snippet(&content)))
+                    .with_retry_after(retry_after)
+                    .into(),
+            );
+        }
+        if is_html(&content_type, &content) {
+            return Err(NewsHubError::Parse(format!(
+                "returned HTML, not a feed (likely moved or blocked): {}",
+                snippet(&content)
+            ))
+            .into());
+        }
 
-        let items: Vec<NewsItem> = channel
-            .items()
-            .iter()
-            .filter_map(|item| {
-                let title = item.title()?.to_string();
-                let link = item.link()?.to_string();
-                let pub_date = item.pub_date()?;
-
-                // if we cannot parse the date, skip this entry
-                let published = DateTime::parse_from_rfc2822(pub_date)
-                    .or_else(|_| DateTime::parse_from_rfc3339(pub_date))
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .ok()?;
-
-                // Extract GUID object (not just string value) for hybrid handling
-                let guid_obj = item.guid().cloned();
-
-                Some(NewsItem {
-                    id: generate_stable_id(&self.source_name, guid_obj, &link),
-                    source: "RSS_".to_string() + &self.source_name.clone(),
-                    title,
-                    url: link,
-                    summary: item.description().unwrap_or("").to_string(),
-                    published,
-                    updated_at: now,
-                })
-            })
-            .collect();
+        // XML parsing and id hashing of a large feed take long enough to
+        // hold up other fetches on the runtime's threads
+        let source_name = self.source_name.clone();
+        let html_descriptions = self.html_descriptions;
+        let (items, dropped_count) = tokio::task::spawn_blocking(move || {
+            let _span = profile::span(Phase::Parse, &source_name);
+            parse_feed(&content, &content_type, &source_name, html_descriptions)
+        })
+        .await
+        .context("RSS parser stopped unexpectedly")??;
 
-        // Count how many items we dropped and build warnings
-        let mut warnings = Vec::new();
-        let dropped_count = channel.items().len() - items.len();
         if dropped_count > 0 {
             warn!("Dropped {} unparsable RSS items.", dropped_count);
             warnings.push(format!("Dropped {} unparsable items", dropped_count));
@@ -157,3 +486,165 @@ impl NewsAdaptor for RssAdaptor {
         Ok((items, warnings))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Real-world feed quirks; also the seed corpus for the fuzz targets
+    const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/feeds");
+
+    fn parse(name: &str) -> Result<(Vec<NewsItem>, usize)> {
+        let body = std::fs::read(format!("{}/{}", CORPUS, name)).unwrap();
+        parse_feed(&body, "application/rss+xml", "Corpus", true)
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    #[test]
+    fn corpus_entries_are_kept_or_counted() {
+        for (name, kept, dropped) in [
+            ("atom_entries.xml", 3, 1),
+            ("atom_feed.xml", 1, 0),
+            ("atom_self_link.xml", 1, 0),
+            ("bom_prolog.xml", 1, 0),
+            ("cdata_and_entities.xml", 2, 0),
+            ("dates.xml", 7, 2),
+            ("duplicate_guids.xml", 3, 0),
+            ("latin1.xml", 1, 0),
+            ("missing_fields.xml", 2, 4),
+        ] {
+            let (items, n) = parse(name).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert_eq!((items.len(), n), (kept, dropped), "{}", name);
+        }
+    }
+
+    #[test]
+    fn broken_documents_are_errors() {
+        for name in ["html_page.xml", "truncated.xml"] {
+            assert!(parse(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn atom_entries_become_items() {
+        let (items, _) = parse("atom_entries.xml").unwrap();
+        let html = &items[0];
+        assert_eq!(html.title, "Fed holds rates & signals cuts");
+        assert_eq!(html.url, "https://example.com/markets/fed-holds");
+        assert_eq!(html.summary, "Powell said $SPY may rally.");
+        assert_eq!(html.tickers, ["SPY"]);
+        assert_eq!(html.published.to_rfc3339(), "2024-10-01T12:00:00+00:00");
+        assert_eq!(html.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(html.tags, ["Markets", "rates"]);
+        assert_eq!(html.language.as_deref(), Some("en"));
+        assert_eq!(
+            html.image_url.as_deref(),
+            Some("https://example.com/img/fed.jpg")
+        );
+
+        // Dated by `updated`, linked by its id, written by the feed's author
+        let plain = &items[1];
+        assert_eq!(plain.url, "https://example.com/posts/2");
+        assert_eq!(plain.published.to_rfc3339(), "2024-10-02T08:30:00+00:00");
+        assert_eq!(plain.author.as_deref(), Some("Example Research"));
+        assert_ne!(items[1].id, items[2].id);
+    }
+
+    #[test]
+    fn missing_link_falls_back_to_permalink_guid() {
+        let (items, _) = parse("missing_fields.xml").unwrap();
+        let urls: Vec<&str> = items.iter().map(|i| i.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/complete",
+                "https://example.com/guid-only"
+            ]
+        );
+    }
+
+    #[test]
+    fn dublin_core_date_stands_in_for_pub_date() {
+        let (items, _) = parse("dates.xml").unwrap();
+        let dc = items
+            .iter()
+            .find(|i| i.title == "Dublin Core only")
+            .unwrap();
+        assert_eq!(dc.published.to_rfc3339(), "2024-10-01T12:00:00+00:00");
+        // Every layout in the file means the same instant
+        assert!(items.iter().all(|i| i.published == dc.published));
+    }
+
+    #[test]
+    fn entities_and_encodings_are_decoded() {
+        let (items, _) = parse("cdata_and_entities.xml").unwrap();
+        assert_eq!(items[0].url, "https://example.com/markets/fed?a=1&b=2");
+        assert_eq!(items[0].summary, "Shares of Apple & $NVDA rose sharply.");
+        assert_eq!(items[0].tickers, ["NVDA"]);
+        assert_eq!(items[1].title, "Café chain “Blue Bottle” sold");
+
+        let (items, _) = parse("latin1.xml").unwrap();
+        assert_eq!(items[0].title, "Café prices rise £1");
+    }
+
+    #[test]
+    fn every_corpus_file_parses_without_panicking() {
+        for entry in std::fs::read_dir(CORPUS).unwrap() {
+            let body = std::fs::read(entry.unwrap().path()).unwrap();
+            let _ = parse_feed(&body, "", "Corpus", true);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_bytes_never_panic(body in proptest::collection::vec(any::<u8>(), 0..2048)) {
+            let _ = parse_feed(&body, "", "Fuzz", true);
+        }
+
+        #[test]
+        fn arbitrary_dates_never_panic(text in "\\PC{0,64}") {
+            let _ = parse_date(&text);
+        }
+
+        #[test]
+        fn generated_entries_are_kept_or_counted(
+            entries in proptest::collection::vec(
+                ("\\PC{0,40}", proptest::option::of("\\PC{0,40}"), any::<bool>()),
+                0..20,
+            )
+        ) {
+            let mut xml = String::from(
+                "<rss version=\"2.0\"><channel><title>T</title>\
+                 <link>https://example.com</link><description>D</description>",
+            );
+            for (i, (title, description, dated)) in entries.iter().enumerate() {
+                xml.push_str(&format!(
+                    "<item><title>{}</title><link>https://example.com/{}</link>",
+                    escape(title),
+                    i
+                ));
+                if let Some(description) = description {
+                    xml.push_str(&format!("<description>{}</description>", escape(description)));
+                }
+                if *dated {
+                    xml.push_str("<pubDate>Tue, 01 Oct 2024 12:00:00 GMT</pubDate>");
+                }
+                xml.push_str("</item>");
+            }
+            xml.push_str("</channel></rss>");
+
+            let (items, dropped) = parse_feed(xml.as_bytes(), "", "Fuzz", true).unwrap();
+            prop_assert_eq!(items.len() + dropped, entries.len());
+            for item in &items {
+                prop_assert!(!item.title.is_empty());
+                prop_assert!(item.id.starts_with("fuzz-"));
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use super::NewsAdaptor;
+use crate::config::TranscriptsConfig;
+use crate::error::NewsHubError;
+use crate::models::{api_item_id, source_id, NewsItem, SourceKind};
+
+/// Source name of the transcripts adaptor
+pub const TRANSCRIPTS: &str = "Earnings Calls";
+
+const API_ROOT: &str = "https://financialmodelingprep.com/api";
+/// Characters of the transcript shown as the summary
+const SUMMARY_CHARS: usize = 400;
+
+/// Full transcript of one call
+#[derive(Deserialize)]
+struct Transcript {
+    quarter: u32,
+    year: i32,
+    date: String,
+    content: String,
+}
+
+/// Earnings-call transcripts of watchlist tickers from Financial Modeling
+/// Prep, as items whose content is the whole call. Only calls held within
+/// `max_age_days` are fetched, each once per session.
+pub struct TranscriptsAdaptor {
+    api_key: String,
+    symbols: Vec<String>,
+    max_age: Duration,
+    client: Client,
+    /// Items already returned; transcripts don't change once published
+    seen: Mutex<HashSet<String>>,
+}
+
+impl TranscriptsAdaptor {
+    pub fn new(api_key: String, symbols: &[String], cfg: &TranscriptsConfig) -> Self {
+        Self {
+            api_key,
+            symbols: symbols.iter().map(|s| s.trim().to_uppercase()).collect(),
+            max_age: Duration::days(cfg.max_age_days as i64),
+            client: Client::new(),
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, &str)]) -> Result<T> {
+        let response = self
+            .client
+            .get(format!("{}/{}", API_ROOT, path))
+            .query(&[("apikey", self.api_key.as_str())])
+            .query(query)
+            .send()
+            .await
+            .context("Failed to connect to Financial Modeling Prep")?;
+        if !response.status().is_success() {
+            return Err(NewsHubError::from_response(&response, None).into());
+        }
+        response
+            .json()
+            .await
+            .context("Failed to parse transcript response")
+    }
+
+    /// New transcripts of one symbol
+    async fn fetch_symbol(&self, symbol: &str) -> Result<Vec<NewsItem>> {
+        // [quarter, year, "YYYY-MM-DD HH:MM:SS"], newest first
+        let calls: Vec<(u32, i32, String)> = self
+            .get("v4/earning_call_transcript", &[("symbol", symbol)])
+            .await?;
+        let now = Utc::now();
+        let mut items = Vec::new();
+        for (quarter, year, date) in calls {
+            let Some(held) = parse_time(&date) else {
+                continue;
+            };
+            if now - held > self.max_age {
+                break;
+            }
+            let id = api_item_id(TRANSCRIPTS, format!("{}-{}-Q{}", symbol, year, quarter));
+            if self.seen.lock().unwrap().contains(&id) {
+                continue;
+            }
+            let (quarter_arg, year_arg) = (quarter.to_string(), year.to_string());
+            let transcripts: Vec<Transcript> = self
+                .get(
+                    &format!("v3/earning_call_transcript/{}", symbol),
+                    &[
+                        ("quarter", quarter_arg.as_str()),
+                        ("year", year_arg.as_str()),
+                    ],
+                )
+                .await?;
+            let Some(transcript) = transcripts.into_iter().next() else {
+                continue;
+            };
+            let summary: String = transcript.content.chars().take(SUMMARY_CHARS).collect();
+            items.push(NewsItem {
+                id: id.clone(),
+                source: TRANSCRIPTS.to_string(),
+                source_id: source_id(TRANSCRIPTS),
+                title: format!(
+                    "{} Q{} {} earnings call transcript",
+                    symbol, transcript.quarter, transcript.year
+                ),
+                url: format!(
+                    "https://financialmodelingprep.com/financial-summary/{}",
+                    symbol
+                ),
+                summary: if summary.len() < transcript.content.len() {
+                    format!("{}…", summary.trim_end())
+                } else {
+                    summary
+                },
+                published: parse_time(&transcript.date).unwrap_or(held),
+                updated_at: now,
+                tags: vec!["transcript".to_string()],
+                tickers: vec![symbol.to_string()],
+                content: Some(transcript.content),
+                source_kind: SourceKind::Api,
+                ..Default::default()
+            });
+            self.seen.lock().unwrap().insert(id);
+        }
+        Ok(items)
+    }
+}
+
+/// Call time as given by the API, taken as UTC
+fn parse_time(text: &str) -> Option<chrono::DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+#[async_trait]
+impl NewsAdaptor for TranscriptsAdaptor {
+    fn name(&self) -> &str {
+        TRANSCRIPTS
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.api_key.is_empty() && !self.symbols.is_empty()
+    }
+
+    /// Fails only when every symbol does
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+        for symbol in &self.symbols {
+            match self.fetch_symbol(symbol).await {
+                Ok(found) => items.extend(found),
+                Err(e) => errors.push(format!("{}: {:#}", symbol, e)),
+            }
+        }
+        if !self.symbols.is_empty() && errors.len() == self.symbols.len() {
+            return Err(anyhow!(errors.join("; ")));
+        }
+        Ok((items, errors))
+    }
+}
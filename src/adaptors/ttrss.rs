@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+
+use super::NewsAdaptor;
+use crate::error::NewsHubError;
+use crate::models::{api_item_id, native_item_id, source_id, NewsItem, SourceKind};
+
+/// getHeadlines returns at most this many items per call
+const MAX_HEADLINES: usize = 200;
+/// Virtual feed containing every article
+const ALL_ARTICLES_FEED: i64 = -4;
+
+#[derive(Deserialize)]
+struct Response {
+    status: i64,
+    content: Value,
+}
+
+#[derive(Deserialize)]
+struct Session {
+    session_id: String,
+}
+
+#[derive(Deserialize)]
+struct Headline {
+    id: i64,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    link: String,
+    updated: i64,
+    unread: bool,
+    marked: bool,
+    #[serde(default)]
+    feed_title: String,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    excerpt: Option<String>,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Client for the Tiny Tiny RSS JSON API. Pulls the server's headlines with
+/// their read/starred ("marked") state and pushes local state changes back.
+pub struct TtRssAdaptor {
+    name: String,
+    /// Installation root, e.g. `https://example.com/tt-rss`
+    server_url: String,
+    username: String,
+    password: String,
+    /// Items requested per fetch
+    limit: usize,
+    client: Client,
+    /// API session, obtained on first use and after it expires
+    session: Mutex<Option<String>>,
+}
+
+impl TtRssAdaptor {
+    pub fn new(name: String, server_url: String, username: String, password: String) -> Self {
+        Self {
+            name,
+            server_url: server_url.trim_end_matches('/').to_string(),
+            username,
+            password,
+            limit: MAX_HEADLINES,
+            client: Client::new(),
+            session: Mutex::new(None),
+        }
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit.clamp(1, MAX_HEADLINES);
+    }
+
+    async fn post(&self, body: &Value) -> Result<Response> {
+        Ok(self
+            .client
+            .post(format!("{}/api/", self.server_url))
+            .json(body)
+            .send()
+            .await
+            .context("Failed to reach server")?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    async fn login(&self) -> Result<String> {
+        let response = self
+            .post(&json!({
+                "op": "login",
+                "user": self.username,
+                "password": self.password,
+            }))
+            .await?;
+        if response.status != 0 {
+            return Err(NewsHubError::Auth(format!("login failed: {}", response.content)).into());
+        }
+        let session: Session = serde_json::from_value(response.content)?;
+        *self.session.lock().unwrap() = Some(session.session_id.clone());
+        Ok(session.session_id)
+    }
+
+    /// Call an API operation, logging in again once if the session expired
+    async fn call<T: DeserializeOwned>(&self, op: &str, mut params: Value) -> Result<T> {
+        let cached = self.session.lock().unwrap().clone();
+        let sid = match cached {
+            Some(sid) => sid,
+            None => self.login().await?,
+        };
+        params["op"] = json!(op);
+        params["sid"] = json!(sid);
+        let mut response = self.post(&params).await?;
+        if response.status != 0 && response.content["error"] == "NOT_LOGGED_IN" {
+            params["sid"] = json!(self.login().await?);
+            response = self.post(&params).await?;
+        }
+        if response.status != 0 {
+            return Err(anyhow!("{} failed: {}", op, response.content));
+        }
+        Ok(serde_json::from_value(response.content)?)
+    }
+}
+
+#[async_trait]
+impl NewsAdaptor for TtRssAdaptor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> SourceKind {
+        SourceKind::Remote
+    }
+
+    fn url(&self) -> Option<&str> {
+        Some(&self.server_url)
+    }
+
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let headlines: Vec<Headline> = self
+            .call(
+                "getHeadlines",
+                json!({
+                    "feed_id": ALL_ARTICLES_FEED,
+                    "limit": self.limit,
+                    "view_mode": "all_articles",
+                    "show_excerpt": true,
+                    "show_content": true,
+                }),
+            )
+            .await
+            .context("Failed to load headlines")?;
+
+        let now = Utc::now();
+        let total = headlines.len();
+        let items: Vec<NewsItem> = headlines
+            .into_iter()
+            .filter_map(|entry| {
+                if entry.link.is_empty() {
+                    return None;
+                }
+                let published = Utc.timestamp_opt(entry.updated, 0).single()?;
+                let source = if entry.feed_title.is_empty() {
+                    self.name.clone()
+                } else {
+                    entry.feed_title
+                };
+
+                Some(NewsItem {
+                    id: api_item_id(&self.name, entry.id),
+                    source_id: source_id(&source),
+                    source,
+                    title: entry.title,
+                    url: entry.link,
+                    summary: entry.excerpt.unwrap_or_default(),
+                    published,
+                    updated_at: now,
+                    author: entry.author.filter(|a| !a.is_empty()),
+                    tags: entry.tags.into_iter().filter(|t| !t.is_empty()).collect(),
+                    content: entry.content,
+                    source_kind: SourceKind::Remote,
+                    read: !entry.unread,
+                    starred: entry.marked,
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        let mut warnings = Vec::new();
+        if items.len() < total {
+            warnings.push(format!(
+                "Dropped {} items without a link",
+                total - items.len()
+            ));
+        }
+        Ok((items, warnings))
+    }
+
+    async fn push_state(&self, item: &NewsItem) -> Result<()> {
+        let Some(article_id) = native_item_id(&self.name, &item.id) else {
+            return Ok(());
+        };
+        // field 0 is "marked" (starred), field 2 is "unread"
+        for (field, mode) in [(0, item.starred), (2, !item.read)] {
+            let _: Value = self
+                .call(
+                    "updateArticle",
+                    json!({
+                        "article_ids": article_id,
+                        "mode": mode as i64,
+                        "field": field,
+                    }),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
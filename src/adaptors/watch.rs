@@ -0,0 +1,234 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use super::NewsAdaptor;
+use crate::config::WatchConfig;
+use crate::enrich::html_to_text;
+use crate::error::NewsHubError;
+use crate::models::id::hash_hex;
+use crate::models::{api_item_id, source_id, NewsItem, SourceKind};
+
+/// Directory holding the last seen text of each watched page
+pub const WATCH_SNAPSHOT_DIR: &str = "data/watch";
+
+/// Elements that start a new block of text
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "div",
+    "li",
+    "tr",
+    "td",
+    "th",
+    "br",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "section",
+    "article",
+    "header",
+    "footer",
+    "dt",
+    "dd",
+    "blockquote",
+    "table",
+    "ul",
+    "ol",
+];
+/// Elements whose contents are never page text
+const SKIPPED_TAGS: &[&str] = &["script", "style", "noscript", "svg", "template"];
+/// Characters of the first added block shown in the title
+const TITLE_CHARS: usize = 120;
+/// Characters of the added text shown as the summary
+const SUMMARY_CHARS: usize = 400;
+
+/// A page without a feed, e.g. an investor relations or government
+/// announcements page. Each fetch compares the page's text with the last
+/// snapshot, block by block, and returns an item holding the added and
+/// removed text once at least `min_words` words changed. The first fetch
+/// only stores the snapshot.
+pub struct WatchAdaptor {
+    name: String,
+    url: String,
+    min_words: usize,
+    snapshot: PathBuf,
+    client: Client,
+}
+
+impl WatchAdaptor {
+    pub fn new(name: String, cfg: &WatchConfig, snapshot_dir: impl Into<PathBuf>) -> Self {
+        let snapshot = snapshot_dir
+            .into()
+            .join(format!("{}.txt", hash_hex(&cfg.url)));
+        Self {
+            name,
+            url: cfg.url.clone(),
+            min_words: cfg.min_words.max(1),
+            snapshot,
+            client: Client::new(),
+        }
+    }
+
+    fn save(&self, blocks: &[String]) -> Result<()> {
+        if let Some(dir) = self.snapshot.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(&self.snapshot, blocks.join("\n"))
+            .with_context(|| format!("Failed to write {}", self.snapshot.display()))
+    }
+
+    /// The item describing how `old` became `new`
+    fn change_item(&self, added: &[&str], removed: &[&str], new_text: &str) -> NewsItem {
+        let now = Utc::now();
+        let headline = match added.first() {
+            Some(first) => truncate(first, TITLE_CHARS),
+            None => "text removed".to_string(),
+        };
+        let mut content = added.join("\n\n");
+        if !removed.is_empty() {
+            if !content.is_empty() {
+                content.push_str("\n\n");
+            }
+            content.push_str("Removed:\n\n");
+            content.push_str(&removed.join("\n\n"));
+        }
+        let hash = hash_hex(new_text);
+        // Items of a source are unique by URL, so each change gets its own
+        let page = self.url.split('#').next().unwrap_or(&self.url);
+        NewsItem {
+            id: api_item_id(&self.name, hash),
+            source: self.name.clone(),
+            source_id: source_id(&self.name),
+            title: format!("{} changed: {}", self.name, headline),
+            url: format!("{}#changed-{}", page, now.format("%Y%m%d%H%M")),
+            summary: truncate(&added.join(" "), SUMMARY_CHARS),
+            published: now,
+            updated_at: now,
+            tags: vec!["page-change".to_string()],
+            content: Some(content),
+            source_kind: SourceKind::Rss,
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl NewsAdaptor for WatchAdaptor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> SourceKind {
+        SourceKind::Rss
+    }
+
+    fn url(&self) -> Option<&str> {
+        Some(&self.url)
+    }
+
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let response = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", self.name))?;
+        if !response.status().is_success() {
+            return Err(NewsHubError::from_response(&response, None).into());
+        }
+        let html = response
+            .text()
+            .await
+            .with_context(|| format!("Failed to read {}", self.name))?;
+        let blocks = page_blocks(&html);
+        if blocks.is_empty() {
+            return Err(anyhow!("{} has no text", self.name));
+        }
+
+        let Ok(old) = fs::read_to_string(&self.snapshot) else {
+            self.save(&blocks)?;
+            return Ok((Vec::new(), vec!["Saved the first snapshot".to_string()]));
+        };
+        let old_blocks: HashSet<&str> = old.lines().collect();
+        let new_blocks: HashSet<&str> = blocks.iter().map(String::as_str).collect();
+        let added: Vec<&str> = blocks
+            .iter()
+            .map(String::as_str)
+            .filter(|b| !old_blocks.contains(b))
+            .collect();
+        let removed: Vec<&str> = old.lines().filter(|b| !new_blocks.contains(b)).collect();
+
+        let changed_words: usize = added
+            .iter()
+            .chain(&removed)
+            .map(|b| b.split_whitespace().count())
+            .sum();
+        // Small edits (e.g. a "last updated" time) keep the old snapshot, so
+        // they add up against it rather than being lost one by one
+        if changed_words < self.min_words {
+            return Ok((Vec::new(), Vec::new()));
+        }
+        let item = self.change_item(&added, &removed, &blocks.join("\n"));
+        self.save(&blocks)?;
+        Ok((vec![item], Vec::new()))
+    }
+}
+
+/// Text of a page split into blocks at block-level elements, one line each,
+/// without scripts and styles
+fn page_blocks(html: &str) -> Vec<String> {
+    // ASCII lowercasing keeps byte offsets, so indices map back to `html`
+    let lower = html.to_ascii_lowercase();
+    let mut blocks = Vec::new();
+    let mut block = String::new();
+    let mut pos = 0;
+    while let Some(start) = lower[pos..].find('<').map(|i| pos + i) {
+        block.push_str(&html[pos..start]);
+        let Some(end) = lower[start..].find('>').map(|i| start + i + 1) else {
+            pos = lower.len();
+            break;
+        };
+        let tag = &lower[start + 1..end - 1];
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect();
+        pos = end;
+        if SKIPPED_TAGS.contains(&name.as_str()) && !tag.starts_with('/') {
+            let close = format!("</{}", name);
+            pos = lower[end..]
+                .find(&close)
+                .and_then(|i| lower[end + i..].find('>').map(|j| end + i + j + 1))
+                .unwrap_or(lower.len());
+            continue;
+        }
+        if BLOCK_TAGS.contains(&name.as_str()) {
+            blocks.push(std::mem::take(&mut block));
+        }
+    }
+    block.push_str(&html[pos..]);
+    blocks.push(block);
+    blocks
+        .iter()
+        .map(|b| html_to_text(b))
+        .filter(|b| !b.is_empty())
+        .collect()
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() > max_chars {
+        let cut: String = text.chars().take(max_chars).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use super::{NewsAdaptor, RssAdaptor};
+use crate::models::{NewsItem, SourceKind};
+
+/// Source name of the Yahoo Finance adaptor
+pub const YAHOO_FINANCE: &str = "Yahoo Finance";
+
+const MARKETS_FEED: &str = "https://finance.yahoo.com/news/rssindex";
+/// Headline feed for one symbol; `{}` is replaced by the symbol
+const TICKER_FEED: &str =
+    "https://feeds.finance.yahoo.com/rss/2.0/headline?s={}&region=US&lang=en-US";
+
+/// Yahoo Finance's markets feed plus a headline feed per watchlist symbol.
+/// Items from a symbol's feed are tagged with the symbol; an article in
+/// several feeds becomes one item with all their symbols.
+pub struct YahooFinanceAdaptor {
+    /// Each feed with the symbol it covers, the markets feed first
+    feeds: Vec<(Option<String>, RssAdaptor)>,
+}
+
+impl YahooFinanceAdaptor {
+    pub fn new(watchlist: &[String]) -> Self {
+        let mut feeds = vec![(
+            None,
+            RssAdaptor::new(MARKETS_FEED.to_string(), YAHOO_FINANCE.to_string()),
+        )];
+        for symbol in watchlist {
+            let symbol = symbol.trim().to_uppercase();
+            if symbol.is_empty() || feeds.iter().any(|(s, _)| s.as_ref() == Some(&symbol)) {
+                continue;
+            }
+            // Index symbols start with ^, currencies end in =X
+            let encoded = symbol.replace('^', "%5E").replace('=', "%3D");
+            let url = TICKER_FEED.replace("{}", &encoded);
+            feeds.push((
+                Some(symbol),
+                RssAdaptor::new(url, YAHOO_FINANCE.to_string()),
+            ));
+        }
+        Self { feeds }
+    }
+}
+
+#[async_trait]
+impl NewsAdaptor for YahooFinanceAdaptor {
+    fn name(&self) -> &str {
+        YAHOO_FINANCE
+    }
+
+    fn kind(&self) -> SourceKind {
+        SourceKind::Rss
+    }
+
+    fn url(&self) -> Option<&str> {
+        Some(MARKETS_FEED)
+    }
+
+    /// Fails only when every feed does; a failing symbol feed is a warning
+    async fn fetch(&self) -> Result<(Vec<NewsItem>, Vec<String>)> {
+        let mut items: Vec<NewsItem> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+        for (symbol, feed) in &self.feeds {
+            let label = symbol.as_deref().unwrap_or("markets");
+            let fetched = match feed.fetch().await {
+                Ok((fetched, feed_warnings)) => {
+                    warnings.extend(
+                        feed_warnings
+                            .into_iter()
+                            .map(|w| format!("{}: {}", label, w)),
+                    );
+                    fetched
+                }
+                Err(e) => {
+                    errors.push(format!("{}: {:#}", label, e));
+                    continue;
+                }
+            };
+            for mut item in fetched {
+                let position = match index.get(&item.id) {
+                    Some(&i) => i,
+                    None => {
+                        index.insert(item.id.clone(), items.len());
+                        items.push(std::mem::take(&mut item));
+                        items.len() - 1
+                    }
+                };
+                if let Some(symbol) = symbol {
+                    let tickers = &mut items[position].tickers;
+                    if !tickers.contains(symbol) {
+                        tickers.push(symbol.clone());
+                    }
+                }
+            }
+        }
+
+        if errors.len() == self.feeds.len() {
+            return Err(anyhow!(errors.join("; ")));
+        }
+        warnings.extend(errors);
+        Ok((items, warnings))
+    }
+}
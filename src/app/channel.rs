@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, Notify};
+
+use super::AppMessage;
+
+/// Messages waiting for the main loop before senders have to wait
+pub const APP_CHANNEL_CAPACITY: usize = 256;
+
+/// Undelivered messages that only matter in their newest version, by kind
+type Latest = Arc<Mutex<HashMap<&'static str, AppMessage>>>;
+
+/// Channel from background tasks to the main loop. Messages go through a
+/// bounded queue, so a burst makes senders wait instead of growing memory.
+/// Messages where only the newest matters (quotes, file changes) skip the
+/// queue: an undelivered one is replaced by its successor, so a fast
+/// producer can't crowd out fetch results.
+pub fn app_channel() -> (AppSender, AppReceiver) {
+    let (tx, rx) = mpsc::channel(APP_CHANNEL_CAPACITY);
+    let latest = Latest::default();
+    let notify = Arc::new(Notify::new());
+    (
+        AppSender {
+            tx,
+            latest: latest.clone(),
+            notify: notify.clone(),
+        },
+        AppReceiver { rx, latest, notify },
+    )
+}
+
+#[derive(Clone)]
+pub struct AppSender {
+    tx: mpsc::Sender<AppMessage>,
+    latest: Latest,
+    notify: Arc<Notify>,
+}
+
+impl AppSender {
+    /// Queue `msg`, waiting while the queue is full. Returns false once the
+    /// main loop has stopped.
+    pub async fn send(&self, msg: AppMessage) -> bool {
+        match msg.coalesce_key() {
+            Some(key) => self.replace_latest(key, msg),
+            None => self.tx.send(msg).await.is_ok(),
+        }
+    }
+
+    /// Queue `msg` without waiting, for callers outside the runtime (e.g.
+    /// file watcher callbacks). A message that would have to wait is
+    /// dropped; returns whether it was queued.
+    pub fn try_send(&self, msg: AppMessage) -> bool {
+        match msg.coalesce_key() {
+            Some(key) => self.replace_latest(key, msg),
+            None => self.tx.try_send(msg).is_ok(),
+        }
+    }
+
+    fn replace_latest(&self, key: &'static str, msg: AppMessage) -> bool {
+        if self.tx.is_closed() {
+            return false;
+        }
+        self.latest.lock().unwrap().insert(key, msg);
+        self.notify.notify_one();
+        true
+    }
+}
+
+pub struct AppReceiver {
+    rx: mpsc::Receiver<AppMessage>,
+    latest: Latest,
+    notify: Arc<Notify>,
+}
+
+impl AppReceiver {
+    /// The next message; newest-only messages come first so they stay fresh.
+    /// `None` once every sender is gone.
+    pub async fn recv(&mut self) -> Option<AppMessage> {
+        loop {
+            if let Some(msg) = self.take_latest() {
+                return Some(msg);
+            }
+            tokio::select! {
+                msg = self.rx.recv() => return msg,
+                _ = self.notify.notified() => {}
+            }
+        }
+    }
+
+    fn take_latest(&self) -> Option<AppMessage> {
+        let mut latest = self.latest.lock().unwrap();
+        let key = *latest.keys().next()?;
+        latest.remove(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn newest_only_messages_replace_undelivered_ones() {
+        let (tx, mut rx) = app_channel();
+        for _ in 0..3 {
+            assert!(tx.send(AppMessage::QuotesFailed("down".to_string())).await);
+        }
+        assert!(tx.send(AppMessage::QuotesUpdated(Vec::new())).await);
+        assert!(tx.send(AppMessage::SourceStarted("CNBC".to_string())).await);
+
+        assert!(matches!(
+            rx.recv().await,
+            Some(AppMessage::QuotesUpdated(_))
+        ));
+        assert!(matches!(
+            rx.recv().await,
+            Some(AppMessage::SourceStarted(_))
+        ));
+        drop(tx);
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn try_send_drops_when_the_queue_is_full() {
+        let (tx, _rx) = app_channel();
+        for _ in 0..APP_CHANNEL_CAPACITY {
+            assert!(tx.try_send(AppMessage::SourceStarted("CNBC".to_string())));
+        }
+        assert!(!tx.try_send(AppMessage::SourceStarted("CNBC".to_string())));
+        // Newest-only messages never wait for room
+        assert!(tx.try_send(AppMessage::ConfigChanged));
+    }
+}
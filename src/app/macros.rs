@@ -0,0 +1,60 @@
+use crossterm::event::KeyEvent;
+
+/// Longest macro kept, in keys
+const MAX_MACRO_KEYS: usize = 200;
+
+/// An edit to the selected article that `.` repeats on the current selection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepeatableEdit {
+    Delete,
+    /// Archive (`true`) or restore to the inbox (`false`)
+    SetRead(bool),
+    /// Star (`true`) or unstar (`false`)
+    SetStarred(bool),
+    /// Add (`true`) or remove (`false`) a tag
+    Tag(String, bool),
+}
+
+/// Records keystrokes for a macro that is replayed as if typed again.
+/// A new recording replaces the previous macro.
+#[derive(Debug, Default)]
+pub struct MacroRecorder {
+    recording: Option<Vec<KeyEvent>>,
+    recorded: Vec<KeyEvent>,
+}
+
+impl MacroRecorder {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn start(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Finish recording; returns the number of keys recorded
+    pub fn stop(&mut self) -> usize {
+        if let Some(keys) = self.recording.take() {
+            self.recorded = keys;
+        }
+        self.recorded.len()
+    }
+
+    /// Add a key to the recording, if one is running. Returns false once
+    /// the macro is full.
+    pub fn record(&mut self, key: KeyEvent) -> bool {
+        match &mut self.recording {
+            Some(keys) if keys.len() < MAX_MACRO_KEYS => {
+                keys.push(key);
+                true
+            }
+            Some(_) => false,
+            None => true,
+        }
+    }
+
+    /// Keys of the last finished recording
+    pub fn recorded(&self) -> &[KeyEvent] {
+        &self.recorded
+    }
+}
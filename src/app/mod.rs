@@ -1,46 +1,257 @@
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::Position;
+use std::collections::HashMap;
+use std::time::Duration;
+
+mod channel;
+mod macros;
+mod opener;
+mod refresh;
+mod share;
+mod undo;
+
+pub use channel::{app_channel, AppReceiver, AppSender, APP_CHANNEL_CAPACITY};
+pub use macros::{MacroRecorder, RepeatableEdit};
+pub use opener::{compile_openers, Opener, Openers};
+pub use refresh::{RefreshReport, RefreshTracker, SourceReport, SourceState};
+pub use share::{compile_share_targets, ShareAction, ShareTarget, WebhookRequest};
+pub use undo::{UndoOp, UndoStack};
 
 use crate::adaptors::FetchDiagnostic;
+use crate::clock::{self, SharedClock};
 use crate::db::sqlite::NewsDB;
-use crate::models::NewsItem;
+use crate::error::ErrorCategory;
+use crate::export::{report, ReportRequest};
+use crate::i18n;
+use crate::models::{
+    source_id, AlertKeywords, FilterState, FollowedStory, NewsItem, Quote, Source, SourceKind,
+    SourceStats, StoryIndex, TimeRange,
+};
+use crate::profile::{self, Phase};
+use crate::scripting::ScriptEngine;
+use crate::sync::{SyncDirection, SyncOutcome};
+use crate::tr;
+use crate::ui::components::{FilterChip, ListView};
+use crate::ui::keymap::{footer_hints, Command, KeyBindings, KeyContext};
 use crate::ui::{
-    Action, Component, DetailPaneComponent, NewsListComponent, SearchBarComponent,
-    StatusBarComponent, StatusMessage,
+    Action, Component, DetailPaneComponent, FilterChipsComponent, HelpOverlayComponent,
+    NewsListComponent, RenderState, SearchBarComponent, ShareMenuComponent, StatusBarComponent,
+    StatusMessage, TickerTapeComponent,
 };
 
+/// How long a rate-limited source is left alone when it doesn't say
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(15 * 60);
+/// Lines of an article one notch of the mouse wheel scrolls
+const WHEEL_LINES: u16 = 3;
+
+/// The action of the first of `commands` that `component` takes
+fn dispatch(component: &mut dyn Component, commands: &[Command]) -> Option<Action> {
+    commands
+        .iter()
+        .find_map(|command| component.handle_command(*command))
+}
+
 /// Identifies which component currently has focus
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TabComponent {
+    SearchBar,
+    FilterChips,
     NewsList,
+    /// The pinned article's pane, while comparing
+    PinnedPane,
     DetailPane,
     StatusBar,
 }
 
+/// How keyboard input is interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    /// Single-key commands go to the focused pane, then to global shortcuts
+    Normal,
+    /// Text entry: printable keys go to the search bar, never to shortcuts
+    Insert,
+    /// An overlay captures all input until it is dismissed
+    Modal,
+}
+
 /// Messages sent from background tasks to main event loop
 #[derive(Debug)]
 pub enum AppMessage {
-    RefreshComplete {
+    /// A queued source acquired a fetch slot
+    SourceStarted(String),
+    /// A source finished fetching (successfully or not)
+    SourceFetched {
         items: Vec<NewsItem>,
-        diagnostics: Vec<FetchDiagnostic>,
+        diagnostic: FetchDiagnostic,
+    },
+    QuotesUpdated(Vec<Quote>),
+    QuotesFailed(String),
+    ArticleQuotes(Vec<Quote>),
+    /// A file in the script directory changed
+    ScriptsChanged,
+    /// config.toml was written
+    ConfigChanged,
+    /// An article's read/starred state was sent to its remote server; the
+    /// error text if it failed
+    StatePushed {
+        id: String,
+        read: bool,
+        starred: bool,
+        result: Result<(), String>,
+    },
+    /// A share webhook finished; the error text if it failed
+    WebhookSent {
+        target: String,
+        result: Result<(), String>,
+    },
+    /// A bulk open finished; the URLs that failed to open
+    BulkOpened {
+        opened: usize,
+        failed: Vec<String>,
+    },
+    /// `:sync` finished; the error text if it failed
+    Synced(Result<SyncOutcome, String>),
+    /// `:cache clear` finished: the number of articles removed, or the
+    /// error text
+    CacheCleared(Result<usize, String>),
+    /// The full text of an article was fetched; the error text if it failed
+    ContentFetched {
+        id: String,
+        result: Result<String, String>,
     },
-    RefreshFailed(String),
 }
 
-/// Application state machine
-#[derive(Debug, Clone, Copy)]
+impl AppMessage {
+    /// Kind of message superseded by a newer one of the same kind, so an
+    /// undelivered one can be replaced; `None` for messages that must all
+    /// be delivered
+    pub fn coalesce_key(&self) -> Option<&'static str> {
+        match self {
+            AppMessage::QuotesUpdated(_) | AppMessage::QuotesFailed(_) => Some("quotes"),
+            AppMessage::ArticleQuotes(_) => Some("article_quotes"),
+            AppMessage::ScriptsChanged => Some("scripts"),
+            AppMessage::ConfigChanged => Some("config"),
+            AppMessage::SourceStarted(_)
+            | AppMessage::SourceFetched { .. }
+            | AppMessage::WebhookSent { .. }
+            | AppMessage::StatePushed { .. }
+            | AppMessage::BulkOpened { .. }
+            | AppMessage::Synced(_)
+            | AppMessage::CacheCleared(_)
+            | AppMessage::ContentFetched { .. } => None,
+        }
+    }
+}
+
+/// Articles for the main loop to open in the browser, one every `delay`
+#[derive(Debug, Clone)]
+pub struct BulkOpenRequest {
+    /// Each link with the opener for its source
+    pub links: Vec<(Opener, String)>,
+    pub delay: Duration,
+}
+
+/// How long a bulk open waits for its confirming second keypress
+const BULK_OPEN_CONFIRM_WINDOW: Duration = Duration::from_secs(5);
+
+/// Application state machine, summarizing the refresh tracker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppState {
     Idle,
-    Loading,
+    /// A refresh batch is running; counts of sources by state
+    Loading {
+        queued: usize,
+        in_flight: usize,
+        finished: usize,
+    },
 }
 
 /// Main application state
 pub struct App {
+    pub ticker_tape: TickerTapeComponent,
     pub search_bar: SearchBarComponent,
+    pub filter_chips: FilterChipsComponent,
     pub news_list: NewsListComponent,
     pub detail_pane: DetailPaneComponent,
+    pub pinned_pane: DetailPaneComponent,
     pub status_bar: StatusBarComponent,
-    pub app_state: AppState,
+    pub share_menu: ShareMenuComponent,
+    pub help: HelpOverlayComponent,
+    pub refresh: RefreshTracker,
     pub focused_component: TabComponent,
+    /// Quick filters applied on top of the search query
+    filter: FilterState,
+    /// Article the selected one is compared against, side by side; a copy,
+    /// so it stays while the list changes
+    pinned: Option<NewsItem>,
+    undo_stack: UndoStack,
+    /// Edit `.` repeats on the current selection
+    last_edit: Option<RepeatableEdit>,
+    macros: MacroRecorder,
+    /// Macro keys for the main loop to feed back as input
+    pending_replay: Vec<Event>,
+    /// Quotes by symbol, with when they arrived by the app clock
+    quote_cache: HashMap<String, (Quote, DateTime<Utc>)>,
+    /// Latest quotes for the ticker tape, in tape order
+    tape_quotes: Vec<Quote>,
+    quote_cache_ttl: Option<Duration>,
+    pending_quote_request: Option<Vec<String>>,
+    share_targets: Vec<ShareTarget>,
+    pending_webhook: Option<WebhookRequest>,
+    /// Bulk opens of more articles than this ask for confirmation
+    bulk_open_limit: usize,
+    bulk_open_delay: Duration,
+    /// Article count and time of a bulk open waiting for confirmation
+    bulk_open_confirm: Option<(usize, DateTime<Utc>)>,
+    pending_bulk_open: Option<BulkOpenRequest>,
+    pending_sync: Option<SyncDirection>,
+    /// `:cache clear` was entered and the main loop hasn't run it yet
+    pending_cache_clear: bool,
+    /// Id and URL of the article whose full text the main loop should fetch
+    pending_content: Option<(String, String)>,
+    /// Remote servers' articles whose read/starred state the main loop
+    /// should send back to the server
+    pending_state_pushes: Vec<NewsItem>,
+    openers: Openers,
+    /// Commands each key triggers, `[keys]`
+    keys: KeyBindings,
+    followed: Vec<FollowedStory>,
+    alerts: AlertKeywords,
+    /// Alert keywords the running refresh brought in, with how many new
+    /// articles mention each
+    alert_hits: Vec<(String, usize)>,
+    /// Opening an article marks it read
+    mark_read_on_open: bool,
+    /// Showing an article in the detail pane this long marks it read
+    mark_read_after: Option<Duration>,
+    /// Browsing a database another process writes: nothing is changed,
+    /// and refreshing reloads the list instead of fetching
+    read_only: bool,
+    /// Article in the detail pane and since when; no time once it has been
+    /// marked read or its read state was set by hand
+    viewing: Option<(String, Option<DateTime<Utc>>)>,
+    /// Per-source window in which a repeated headline is dropped as a repost
+    title_dedup: HashMap<String, chrono::Duration>,
+    /// Headline similarity at which another source's copy of a stored story
+    /// is collapsed into it; None stores every copy
+    dedup_threshold: Option<f32>,
+    /// Rate-limited sources by name, and when they may be fetched again
+    cooldowns: HashMap<String, DateTime<Utc>>,
+    /// Opened on first copy or paste; on X11 the copied text lives as long as it does
+    clipboard: Option<arboard::Clipboard>,
+    last_clock_minute: i64,
+    /// Source of the current time for ages, dismissal and read timers
+    clock: SharedClock,
+    scripts_changed: bool,
+    config_changed: bool,
+    /// Drawn with the plain layout for screen readers (`ui::linear`)
+    screen_reader: bool,
+    /// The latest focus or selection change in words; only kept for the
+    /// screen reader layout
+    announcement: String,
 }
 
 impl App {
@@ -51,264 +262,2199 @@ impl App {
         let mut news_list = NewsListComponent::new(initial_news);
         news_list.set_focus(true); // NewsList starts with focus
 
+        // The detail pane shows the list selection; quotes for it are
+        // requested once set_article_quotes is called
         let mut detail_pane = DetailPaneComponent::new();
         detail_pane.set_focus(false);
-
-        // Initialize detail pane with first article if available
-        if let Some(first_article) = news_list.selected_item() {
-            detail_pane.set_article(first_article.clone());
-        }
+        let clock = clock::system();
+        // Nothing is marked read until the user selects it
+        let viewing = news_list
+            .selected_item()
+            .map(|item| (item.id.clone(), None));
 
         Self {
+            ticker_tape: TickerTapeComponent::new(),
             search_bar,
+            filter_chips: FilterChipsComponent::new(),
             news_list,
             detail_pane,
+            pinned_pane: DetailPaneComponent::pinned(),
             status_bar: StatusBarComponent::new(),
-            app_state: AppState::Idle,
+            share_menu: ShareMenuComponent::new(),
+            help: HelpOverlayComponent::new(),
+            refresh: RefreshTracker::default(),
             focused_component: TabComponent::NewsList,
+            filter: FilterState::default(),
+            pinned: None,
+            undo_stack: UndoStack::default(),
+            last_edit: None,
+            macros: MacroRecorder::default(),
+            pending_replay: Vec::new(),
+            quote_cache: HashMap::new(),
+            tape_quotes: Vec::new(),
+            quote_cache_ttl: None,
+            pending_quote_request: None,
+            share_targets: Vec::new(),
+            pending_webhook: None,
+            bulk_open_limit: 10,
+            bulk_open_delay: Duration::from_millis(250),
+            bulk_open_confirm: None,
+            pending_bulk_open: None,
+            pending_sync: None,
+            pending_cache_clear: false,
+            pending_content: None,
+            pending_state_pushes: Vec::new(),
+            openers: Openers::default(),
+            keys: KeyBindings::default(),
+            followed: Vec::new(),
+            alerts: AlertKeywords::default(),
+            alert_hits: Vec::new(),
+            mark_read_on_open: true,
+            mark_read_after: None,
+            read_only: false,
+            viewing,
+            title_dedup: HashMap::new(),
+            dedup_threshold: None,
+            cooldowns: HashMap::new(),
+            clipboard: None,
+            last_clock_minute: 0,
+            clock,
+            scripts_changed: false,
+            config_changed: false,
+            screen_reader: false,
+            announcement: String::new(),
+        }
+    }
+
+    /// Enable live quotes for tickers in the selected article, reusing fetched
+    /// quotes for `ttl`. `None` disables them.
+    pub fn set_article_quotes(&mut self, ttl: Option<Duration>) {
+        self.quote_cache_ttl = ttl;
+        self.request_article_quotes();
+    }
+
+    /// Apply user scripts to the news list (None disables scripting)
+    pub fn set_scripts(&mut self, scripts: Option<ScriptEngine>) {
+        self.news_list.set_scripts(scripts);
+        self.show_selected_article();
+    }
+
+    /// Whether config.toml changed since the last call; the main loop owns
+    /// config-driven services, so it performs the reload
+    pub fn take_config_reload(&mut self) -> bool {
+        std::mem::take(&mut self.config_changed)
+    }
+
+    /// Entries of the share menu, in menu order
+    pub fn set_share_targets(&mut self, targets: Vec<ShareTarget>) {
+        self.share_menu.set_entries(
+            targets
+                .iter()
+                .map(|target| (target.key, target.name.clone()))
+                .collect(),
+        );
+        self.share_targets = targets;
+    }
+
+    /// Ask for confirmation before opening more than `limit` articles at
+    /// once, and open them `delay` apart
+    pub fn set_bulk_open(&mut self, limit: usize, delay: Duration) {
+        self.bulk_open_limit = limit;
+        self.bulk_open_delay = delay;
+    }
+
+    /// Stories to watch for new coverage
+    pub fn set_followed_stories(&mut self, stories: Vec<FollowedStory>) {
+        self.followed = stories.clone();
+        self.news_list.set_followed(stories);
+    }
+
+    /// Keywords new articles raise an alert for
+    pub fn set_alerts(&mut self, alerts: AlertKeywords) {
+        self.alerts = alerts.clone();
+        self.news_list.set_alerts(alerts);
+    }
+
+    /// Which keys trigger which commands, and the footer hints naming them
+    pub fn set_key_bindings(&mut self, keys: KeyBindings) {
+        self.keys = keys;
+        self.status_bar
+            .set_hints(footer_hints(self.footer_context(), &self.keys));
+    }
+
+    /// How articles are opened, and whether opening marks them read
+    pub fn set_openers(&mut self, openers: Openers, mark_read: bool) {
+        self.openers = openers;
+        self.mark_read_on_open = mark_read;
+    }
+
+    /// Read the time from `clock` instead of the system clock, here and in
+    /// the list and status bar
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.status_bar.set_clock(clock.clone());
+        self.news_list.set_clock(clock.clone());
+        self.last_clock_minute = clock.now().timestamp() / 60;
+        if let Some((_, Some(since))) = &mut self.viewing {
+            *since = clock.now();
+        }
+        self.clock = clock;
+    }
+
+    /// Mark articles read once the detail pane has shown them for `after`;
+    /// `None` leaves them unread until opened
+    pub fn set_mark_read_after(&mut self, after: Option<Duration>) {
+        self.mark_read_after = after;
+    }
+
+    /// Refuse edits to articles and follows, and leave read state alone
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Draw with the plain layout for screen readers, announcing focus and
+    /// selection changes, and spin the status spinner in ASCII
+    pub fn set_screen_reader(&mut self, enabled: bool) {
+        self.screen_reader = enabled;
+        self.status_bar.set_ascii_spinner(enabled);
+        self.announcement.clear();
+        self.announce_focus();
+    }
+
+    pub fn is_screen_reader(&self) -> bool {
+        self.screen_reader
+    }
+
+    /// Show text in the locale `code` (`[app] locale`), or English if there
+    /// is no catalog for it
+    pub fn set_locale(&mut self, code: &str) -> anyhow::Result<()> {
+        let result = i18n::set_locale(code);
+        self.status_bar
+            .set_hints(footer_hints(self.footer_context(), &self.keys));
+        result
+    }
+
+    /// The latest focus or selection change, e.g. "Article 2 of 40: ..."
+    pub fn announcement(&self) -> &str {
+        &self.announcement
+    }
+
+    /// Describe what has focus now; the screen reader layout shows it
+    /// whenever it changes
+    fn announce_focus(&mut self) {
+        if self.screen_reader {
+            self.announcement = self.describe_focus();
+        }
+    }
+
+    /// What has focus and what is selected in it, in words
+    fn describe_focus(&self) -> String {
+        if self.help.is_visible() {
+            return tr!("focus.help");
+        }
+        if self.share_menu.is_visible() {
+            let entries = self.share_menu.entries();
+            let index = self.share_menu.selected_index();
+            return match entries.get(index) {
+                Some((_, name)) => tr!(
+                    "focus.share",
+                    target = name,
+                    position = index + 1,
+                    count = entries.len()
+                ),
+                None => tr!("focus.share-empty"),
+            };
+        }
+        match self.focused_component {
+            TabComponent::SearchBar if self.search_bar.is_command() => tr!("focus.command"),
+            TabComponent::SearchBar => tr!("focus.search"),
+            TabComponent::FilterChips => {
+                let chips = self.filter_chips.chips();
+                let index = self.filter_chips.selected_index();
+                match chips.get(index) {
+                    Some(chip) => tr!(
+                        "focus.filter",
+                        filter = chip.label(),
+                        position = index + 1,
+                        count = chips.len()
+                    ),
+                    None => tr!("focus.filters-empty"),
+                }
+            }
+            TabComponent::NewsList => {
+                let count = self.news_list.filtered_items().len();
+                match (
+                    self.news_list.selected_position(),
+                    self.news_list.selected_item(),
+                ) {
+                    (Some(position), Some(item)) => tr!(
+                        "focus.article",
+                        position = position + 1,
+                        count = count,
+                        article = self.news_list.describe(item)
+                    ),
+                    _ => tr!("focus.no-articles"),
+                }
+            }
+            TabComponent::PinnedPane => match &self.pinned {
+                Some(item) => tr!("focus.pinned", title = item.title),
+                None => tr!("focus.pinned-empty"),
+            },
+            TabComponent::DetailPane => match self.news_list.selected_item() {
+                Some(item) => tr!("focus.detail", title = item.title),
+                None => tr!("focus.detail-empty"),
+            },
+            TabComponent::StatusBar => match self.status_bar.plain_expanded() {
+                Some((heading, _)) => heading,
+                None => tr!("focus.status"),
+            },
+        }
+    }
+
+    /// Sources whose reposted headlines are collapsed, with their windows
+    pub fn set_title_dedup(&mut self, windows: HashMap<String, chrono::Duration>) {
+        self.title_dedup = windows;
+    }
+
+    /// Collapse copies of stories other sources already carried, at this
+    /// headline similarity; None keeps them all
+    pub fn set_deduplication(&mut self, threshold: Option<f32>) {
+        self.dedup_threshold = threshold;
+    }
+
+    /// Pick up the cool-downs stored with `sources` by an earlier session
+    pub fn set_cooldowns(&mut self, sources: &[Source]) {
+        let now = self.clock.now();
+        self.cooldowns = sources
+            .iter()
+            .filter_map(|source| Some((source.name.clone(), source.cooldown_until?)))
+            .filter(|(_, until)| *until > now)
+            .collect();
+    }
+
+    /// When `source` may be fetched again, if it is cooling down
+    pub fn cooldown_until(&self, source: &str) -> Option<DateTime<Utc>> {
+        self.cooldowns
+            .get(source)
+            .copied()
+            .filter(|until| *until > self.clock.now())
+    }
+
+    /// Articles the main loop should open in the browser, if any
+    pub fn take_bulk_open(&mut self) -> Option<BulkOpenRequest> {
+        self.pending_bulk_open.take()
+    }
+
+    /// Webhook post the main loop should send, if any
+    pub fn take_webhook_request(&mut self) -> Option<WebhookRequest> {
+        self.pending_webhook.take()
+    }
+
+    /// `:sync` the main loop should run, if any
+    pub fn take_sync_request(&mut self) -> Option<SyncDirection> {
+        self.pending_sync.take()
+    }
+
+    /// Whether the main loop should empty the article text cache
+    pub fn take_cache_clear(&mut self) -> bool {
+        std::mem::take(&mut self.pending_cache_clear)
+    }
+
+    /// Id and URL of the article whose full text the main loop should
+    /// fetch, if any
+    pub fn take_content_request(&mut self) -> Option<(String, String)> {
+        self.pending_content.take()
+    }
+
+    /// Articles whose read/starred state the main loop should send to their
+    /// remote server
+    pub fn take_state_pushes(&mut self) -> Vec<NewsItem> {
+        std::mem::take(&mut self.pending_state_pushes)
+    }
+
+    /// Send `item`'s read/starred state to its server, if it came from one
+    fn push_state(&mut self, id: &str) {
+        if let Some(item) = self
+            .news_list
+            .item(id)
+            .filter(|item| item.source_kind == SourceKind::Remote)
+        {
+            self.pending_state_pushes.push(item.clone());
+        }
+    }
+
+    /// Keys of a macro being replayed, to be handled as if typed
+    pub fn take_replay(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.pending_replay)
+    }
+
+    /// Symbols the main loop should fetch quotes for, if any
+    pub fn take_quote_request(&mut self) -> Option<Vec<String>> {
+        self.pending_quote_request.take()
+    }
+
+    /// Show the list selection in the detail pane from the top. The list
+    /// may have moved the selection itself, e.g. when filtering or when the
+    /// article left the view, so this doesn't start the mark-read timer.
+    fn show_selected_article(&mut self) {
+        self.detail_pane.reset_scroll();
+        self.request_article_quotes();
+        let selected = self.news_list.selected_item();
+        if self.viewing.as_ref().map(|(id, _)| id) != selected.map(|item| &item.id) {
+            self.viewing = selected.map(|item| (item.id.clone(), None));
+        }
+    }
+
+    /// Show the article the user moved the selection to, starting its
+    /// mark-read timer if it's unread
+    fn view_selected_article(&mut self) {
+        let previous = self.viewing.as_ref().map(|(id, _)| id.clone());
+        self.show_selected_article();
+        let now = self.clock.now();
+        let unread = self
+            .news_list
+            .selected_item()
+            .is_some_and(|item| !item.read);
+        if let Some((id, since)) = &mut self.viewing {
+            if unread && previous.as_ref() != Some(id) {
+                *since = Some(now);
+            }
+        }
+    }
+
+    /// Whether the selected article is on screen: not under an overlay, or
+    /// in the plain layout, not replaced by another pane's text
+    fn article_shown(&self) -> bool {
+        if self.help.is_visible() || self.share_menu.is_visible() {
+            return false;
+        }
+        !(self.screen_reader
+            && match self.focused_component {
+                TabComponent::PinnedPane => true,
+                TabComponent::StatusBar => self.status_bar.plain_expanded().is_some(),
+                _ => false,
+            })
+    }
+
+    /// What the next frame draws, borrowed from the current state
+    pub fn render_state(&self) -> RenderState<'_> {
+        let quotes = |article: Option<&NewsItem>| {
+            article
+                .map(|article| {
+                    article
+                        .tickers
+                        .iter()
+                        .filter_map(|ticker| self.quote_cache.get(ticker))
+                        .map(|(quote, _)| quote)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let article = self.news_list.selected_item();
+        let pinned = self.pinned.as_ref();
+        RenderState {
+            article,
+            article_quotes: quotes(article),
+            tape_quotes: &self.tape_quotes,
+            pinned,
+            pinned_quotes: quotes(pinned),
+        }
+    }
+
+    /// Queue a fetch for the selected article's missing or stale symbols
+    fn request_article_quotes(&mut self) {
+        let Some(ttl) = self.quote_cache_ttl else {
+            return;
+        };
+        let Some(article) = self.news_list.selected_item() else {
+            return;
+        };
+
+        let now = self.clock.now();
+        let stale: Vec<String> = article
+            .tickers
+            .iter()
+            .filter(|ticker| {
+                self.quote_cache.get(*ticker).is_none_or(|(_, fetched)| {
+                    let age = now.signed_duration_since(*fetched);
+                    age.to_std().unwrap_or(Duration::ZERO) >= ttl
+                })
+            })
+            .cloned()
+            .collect();
+        if !stale.is_empty() {
+            self.pending_quote_request = Some(stale);
+        }
+    }
+
+    fn cache_quotes(&mut self, quotes: &[Quote]) {
+        let now = self.clock.now();
+        for quote in quotes {
+            self.quote_cache
+                .insert(quote.symbol.clone(), (quote.clone(), now));
         }
     }
 
     /// Handle messages from background tasks
     pub fn handle_message(&mut self, msg: AppMessage, db: &NewsDB) {
         match msg {
-            AppMessage::RefreshComplete { items, diagnostics } => {
-                // Collect database insertion errors
-                let mut db_errors = Vec::new();
+            AppMessage::SourceStarted(source) => {
+                self.refresh.start(&source);
+                self.status_bar.update_loading(self.refresh.progress_text());
+            }
+            AppMessage::SourceFetched {
+                items,
+                mut diagnostic,
+            } => {
+                // Store each source's items as soon as they arrive
+                let now = self.clock.now();
+                let mut stats = SourceStats::fetch(diagnostic.success);
+                let window = self.title_dedup.get(&diagnostic.source).copied();
+                let mut reposts = 0;
+                let mut collapsed = 0;
+                let stories = self.story_index(&items, db);
+                // New articles per followed story, by index into `self.followed`
+                let mut coverage: Vec<(usize, usize)> = Vec::new();
+                let insert_span = profile::span(Phase::Insert, &diagnostic.source);
                 for item in &items {
-                    if let Err(e) = db.insert(item) {
-                        db_errors.push(format!("{}", e));
+                    if let Some(window) = window {
+                        match db.is_repost(item, window) {
+                            Ok(true) => {
+                                reposts += 1;
+                                continue;
+                            }
+                            Ok(false) => {}
+                            Err(e) => self.refresh.report.db_errors.push(format!("{}", e)),
+                        }
+                    }
+                    // Another source had the story first; note this one carried it too
+                    if let Some(kept) = stories.as_ref().and_then(|index| index.find(item)) {
+                        match db.record_duplicate(kept, item) {
+                            Ok(true) => {
+                                collapsed += 1;
+                                stats.add_item(item.published, now, true);
+                            }
+                            Ok(false) => {}
+                            Err(e) => self.refresh.report.db_errors.push(format!("{}", e)),
+                        }
+                        continue;
+                    }
+                    // Checked before storing, so the item doesn't count against itself
+                    let duplicate = db.is_duplicate(&item.source, &item.url);
+                    match (db.insert(item), duplicate) {
+                        (Ok(true), Ok(duplicate)) => {
+                            stats.add_item(item.published, now, duplicate);
+                            if let Some(keyword) = self.alerts.matching(item) {
+                                match self.alert_hits.iter_mut().find(|(k, _)| k == keyword) {
+                                    Some((_, count)) => *count += 1,
+                                    None => self.alert_hits.push((keyword.to_string(), 1)),
+                                }
+                            }
+                            if let Some(story) = self.followed.iter().position(|s| s.matches(item))
+                            {
+                                match coverage.iter_mut().find(|(s, _)| *s == story) {
+                                    Some((_, count)) => *count += 1,
+                                    None => coverage.push((story, 1)),
+                                }
+                            }
+                        }
+                        (Ok(_), Ok(_)) => {}
+                        (Err(e), _) | (_, Err(e)) => {
+                            self.refresh.report.db_errors.push(format!("{}", e))
+                        }
                     }
                 }
+                drop(insert_span);
 
-                // Build status message from diagnostics
-                let success_count = diagnostics.iter().filter(|d| d.success).count();
-                let fail_count = diagnostics.iter().filter(|d| !d.success).count();
-
-                // Collect per-source warnings
-                let warnings: Vec<String> = diagnostics
-                    .iter()
-                    .filter(|d| d.success && !d.warnings.is_empty())
-                    .flat_map(|d| d.warnings.iter().map(|w| format!("{}: {}", d.source, w)))
-                    .collect();
-
-                let has_warnings = !warnings.is_empty();
-
-                let status_msg = if fail_count == 0 && db_errors.is_empty() && !has_warnings {
-                    StatusMessage::success(format!(
-                        "Fetched {} items from {} sources",
-                        items.len(),
-                        success_count
-                    ))
-                } else if success_count > 0 {
-                    let mut msg_parts = vec![format!(
-                        "Fetched {} items from {} sources",
-                        items.len(),
-                        success_count
-                    )];
-                    if fail_count > 0 {
-                        msg_parts.push(format!("{} sources failed", fail_count));
-                    }
-                    if !db_errors.is_empty() {
-                        msg_parts.push(format!("{} DB errors", db_errors.len()));
+                match coverage[..] {
+                    [] => {}
+                    [(story, count)] => {
+                        let msg = tr!(
+                            "status.new-coverage",
+                            title = self.followed[story].title,
+                            count = count
+                        );
+                        self.status_bar.set_message(StatusMessage::info(msg));
                     }
-                    if has_warnings {
-                        msg_parts.extend(warnings);
+                    _ => {
+                        let msg = tr!("status.new-coverage-many", count = coverage.len());
+                        self.status_bar.set_message(StatusMessage::info(msg));
                     }
-                    StatusMessage::warning(msg_parts.join("; "))
-                } else {
-                    StatusMessage::error("All sources failed to fetch".to_string())
-                };
+                }
 
-                self.status_bar.set_message(status_msg);
+                if reposts > 0 {
+                    diagnostic.message = format!(
+                        "{}; collapsed {} reposted headline(s)",
+                        diagnostic.message, reposts
+                    );
+                }
+                if collapsed > 0 {
+                    diagnostic.message = format!(
+                        "{}; {} story(ies) already carried by other sources",
+                        diagnostic.message, collapsed
+                    );
+                }
 
-                // Reload from database
-                let news = match db.load_all() {
-                    Ok(news) => news,
+                let id = source_id(&diagnostic.source);
+                let cooldown = diagnostic
+                    .error
+                    .as_ref()
+                    .filter(|e| e.category() == ErrorCategory::RateLimit)
+                    .map(|e| {
+                        let wait = e.retry_after().unwrap_or(DEFAULT_COOLDOWN);
+                        now + chrono::Duration::from_std(wait).unwrap_or(chrono::Duration::MAX)
+                    });
+                if let Some(until) = cooldown {
+                    diagnostic.message = resuming_at(until);
+                    self.cooldowns.insert(diagnostic.source.clone(), until);
+                }
+                let cleared =
+                    diagnostic.success && self.cooldowns.remove(&diagnostic.source).is_some();
+                if cooldown.is_some() || cleared {
+                    if let Err(e) = db.record_cooldown(&id, cooldown) {
+                        self.refresh.report.db_errors.push(format!("{}", e));
+                    }
+                }
+                if let Err(e) = db.record_stats(&id, &stats) {
+                    self.refresh.report.db_errors.push(format!("{}", e));
+                }
+                if let Some(url) = &diagnostic.moved_to {
+                    if let Err(e) = db.record_move(&id, url) {
+                        self.refresh.report.db_errors.push(format!("{}", e));
+                    }
+                }
+                if diagnostic.success {
+                    if let Err(e) = db.record_fetch(&id, now) {
+                        self.refresh.report.db_errors.push(format!("{}", e));
+                    }
+                    self.news_list.set_last_refresh(Some(now));
+                }
+
+                if self.refresh.finish(diagnostic, items.len(), now) {
+                    self.finish_refresh(db);
+                } else {
+                    self.status_bar.update_loading(self.refresh.progress_text());
+                }
+            }
+            AppMessage::QuotesUpdated(quotes) => {
+                self.cache_quotes(&quotes);
+                self.tape_quotes = quotes;
+            }
+            AppMessage::QuotesFailed(err) => {
+                let msg = StatusMessage::warning(tr!("status.quotes-failed", error = err));
+                self.status_bar.set_message(msg);
+            }
+            AppMessage::ArticleQuotes(quotes) => {
+                self.cache_quotes(&quotes);
+            }
+            // Editors emit several events per save; reload once on the next tick
+            AppMessage::ScriptsChanged => self.scripts_changed = true,
+            AppMessage::ConfigChanged => self.config_changed = true,
+            AppMessage::WebhookSent { target, result } => {
+                let msg = match result {
+                    Ok(()) => StatusMessage::success(tr!("status.sent", target = target)),
                     Err(e) => {
-                        let msg =
-                            StatusMessage::error(format!("Failed to load from database: {}", e));
-                        self.status_bar.set_message(msg);
-                        Vec::new()
+                        StatusMessage::error(tr!("status.send-failed", target = target, error = e))
                     }
                 };
-                self.news_list.set_news(news);
+                self.status_bar.set_message(msg);
+            }
+            AppMessage::StatePushed {
+                id,
+                read,
+                starred,
+                result,
+            } => {
+                let result = result.and_then(|()| {
+                    db.state_pushed(&id, read, starred)
+                        .map_err(|e| format!("{:#}", e))
+                });
+                if let Err(e) = result {
+                    let msg = StatusMessage::error(tr!("status.push-state-failed", error = e));
+                    self.status_bar.set_message(msg);
+                }
+            }
+            AppMessage::BulkOpened { opened, failed } => {
+                let msg = match failed.first() {
+                    None => StatusMessage::success(tr!("status.opened", count = opened)),
+                    Some(url) => StatusMessage::error(tr!(
+                        "status.opened-some",
+                        count = opened,
+                        failed = failed.len(),
+                        url = url
+                    )),
+                };
+                self.status_bar.set_message(msg);
+            }
+            AppMessage::Synced(result) => self.finish_sync(result, db),
+            AppMessage::CacheCleared(result) => {
+                let msg = match result {
+                    Ok(count) => StatusMessage::success(tr!("status.cache-cleared", count = count)),
+                    Err(e) => StatusMessage::error(tr!("status.cache-clear-failed", error = e)),
+                };
+                self.status_bar.set_message(msg);
+            }
+            AppMessage::ContentFetched { id, result } => self.finish_content(&id, result, db),
+        }
+    }
 
-                // Update detail pane with first article after refresh
-                if let Some(first_article) = self.news_list.selected_item() {
-                    self.detail_pane.set_article(first_article.clone());
+    /// Store fetched article text and show it in the detail pane
+    fn finish_content(&mut self, id: &str, result: Result<String, String>, db: &NewsDB) {
+        let msg = match result {
+            Err(e) => StatusMessage::error(tr!("status.full-text-failed", error = e)),
+            Ok(text) if text.is_empty() => StatusMessage::warning(tr!("status.full-text-empty")),
+            Ok(text) => match db.set_content(id, &text) {
+                Ok(()) => {
+                    self.reload_news(db);
+                    StatusMessage::success(tr!("status.full-text-loaded"))
                 }
+                Err(e) => StatusMessage::error(format!("{:#}", e)),
+            },
+        };
+        self.status_bar.set_message(msg);
+    }
 
-                self.app_state = AppState::Idle;
+    /// Report a finished `:sync`, merging what a pull brought in
+    fn finish_sync(&mut self, result: Result<SyncOutcome, String>, db: &NewsDB) {
+        let msg = match result {
+            Err(e) => StatusMessage::error(tr!("status.sync-failed", error = e)),
+            Ok(SyncOutcome::Pushed {
+                committed: false, ..
+            }) => StatusMessage::info(tr!("status.sync-unchanged")),
+            Ok(SyncOutcome::Pushed { pushed: false, .. }) => {
+                StatusMessage::success(tr!("status.sync-committed"))
+            }
+            Ok(SyncOutcome::Pushed { .. }) => StatusMessage::success(tr!("status.sync-pushed")),
+            Ok(SyncOutcome::Pulled { states, feeds }) => {
+                let (mut added, mut updated, mut deleted) = (0, 0, 0);
+                for state in &states {
+                    match db.import_state(state) {
+                        Ok(merged) => {
+                            added += merged.added;
+                            updated += merged.updated;
+                            deleted += merged.deleted;
+                        }
+                        Err(e) => {
+                            let error = format!("{:#}", e);
+                            let msg =
+                                StatusMessage::error(tr!("status.sync-failed", error = error));
+                            self.status_bar.set_message(msg);
+                            return;
+                        }
+                    }
+                }
+                self.reload_news(db);
+                StatusMessage::success(tr!(
+                    "status.sync-pulled",
+                    added = added,
+                    updated = updated,
+                    deleted = deleted,
+                    feeds = feeds.added.len()
+                ))
             }
-            AppMessage::RefreshFailed(err) => {
-                let msg = StatusMessage::error(format!("Fetch failed: {}", err));
+        };
+        self.status_bar.set_message(msg);
+    }
+
+    fn reload_scripts(&mut self) {
+        let Some((loaded, errors)) = self.news_list.reload_scripts() else {
+            return;
+        };
+        if errors.is_empty() {
+            let msg = StatusMessage::success(tr!("status.scripts-reloaded", count = loaded));
+            self.status_bar.set_message(msg);
+        }
+        for error in errors {
+            self.status_bar.set_message(StatusMessage::warning(error));
+        }
+        self.show_selected_article();
+    }
+
+    /// Current state of the refresh state machine
+    pub fn app_state(&self) -> AppState {
+        if !self.refresh.is_active() {
+            return AppState::Idle;
+        }
+        AppState::Loading {
+            queued: self.refresh.count(&[SourceState::Queued]),
+            in_flight: self.refresh.count(&[SourceState::InFlight]),
+            finished: self
+                .refresh
+                .count(&[SourceState::Done, SourceState::Failed]),
+        }
+    }
+
+    /// Queue sources for refresh. Returns the ones the caller should start fetching;
+    /// sources already queued or in flight are skipped.
+    pub fn begin_refresh(&mut self, sources: Vec<String>) -> Vec<String> {
+        let (cooling, sources): (Vec<String>, Vec<String>) = sources
+            .into_iter()
+            .partition(|name| self.cooldown_until(name).is_some());
+        let queued = self.refresh.queue(sources);
+        if !queued.is_empty() {
+            self.status_bar.update_loading(self.refresh.progress_text());
+        } else if let [name, ..] = cooling.as_slice() {
+            // Nothing else to fetch, so say why nothing happens
+            let until = self.cooldown_until(name).unwrap_or_default();
+            let mut text = format!("{} {}", name, resuming_at(until));
+            if cooling.len() > 1 {
+                let more = tr!("status.more-cooling", count = cooling.len() - 1);
+                text.push_str(&format!(" ({})", more));
+            }
+            self.status_bar.set_message(StatusMessage::warning(text));
+        }
+        queued
+    }
+
+    /// Stored articles that `items` may repeat, when deduplication is on
+    fn story_index(&mut self, items: &[NewsItem], db: &NewsDB) -> Option<StoryIndex> {
+        let threshold = self.dedup_threshold?;
+        let since = items.iter().map(|item| item.published).min()?;
+        let until = items.iter().map(|item| item.published).max()?;
+        match db.story_index(since, until, threshold) {
+            Ok(index) => Some(index),
+            Err(e) => {
+                self.refresh.report.db_errors.push(format!("{}", e));
+                None
+            }
+        }
+    }
+
+    /// Summarize the completed batch and reload the list from the database
+    fn finish_refresh(&mut self, db: &NewsDB) {
+        let report = self.refresh.report.clone();
+        self.status_bar.set_message(StatusMessage::report(report));
+        if !self.alert_hits.is_empty() {
+            let hits: Vec<String> = std::mem::take(&mut self.alert_hits)
+                .into_iter()
+                .map(|(keyword, count)| format!("{} ({})", keyword, count))
+                .collect();
+            let msg = tr!("status.alert", keywords = hits.join(", "));
+            self.status_bar.set_message(StatusMessage::alert(msg));
+        }
+        self.load_news(db, false);
+    }
+
+    /// Reload the list from the database after another process changed it,
+    /// keeping the selection. Returns how many articles are new.
+    pub fn reload_news(&mut self, db: &NewsDB) -> usize {
+        self.load_news(db, true)
+    }
+
+    /// Replace the list with the stored articles, selecting the first one
+    /// unless `keep_selection`, in which case returns how many are new
+    fn load_news(&mut self, db: &NewsDB, keep_selection: bool) -> usize {
+        let news = match db.load_all() {
+            Ok(news) => news,
+            Err(e) => {
+                let msg = StatusMessage::error(tr!("status.load-failed", error = e));
                 self.status_bar.set_message(msg);
-                self.app_state = AppState::Idle;
+                Vec::new()
             }
+        };
+        let added = if keep_selection {
+            self.news_list.replace_news(news)
+        } else {
+            self.news_list.set_news(news);
+            0
+        };
+        // Pick up the reliability scores the last fetches changed
+        if let Ok(sources) = db.load_sources() {
+            self.news_list.set_sources(&sources);
         }
+
+        // Update detail pane with the selected article after reload
+        self.show_selected_article();
+        added
     }
 
     /// handle keyboard/mouse events. Returns the Action emitted by components
     pub fn handle_event(&mut self, event: &Event) -> Action {
-        if let Event::Key(KeyEvent {
-            code: KeyCode::Char('h'),
-            modifiers: KeyModifiers::CONTROL,
-            ..
-        }) = event
-        {
-            let was_showing = self.status_bar.is_showing_history();
-            self.status_bar.toggle_history();
-            let is_showing = self.status_bar.is_showing_history();
+        let action = self.route_event(event);
+        self.keep_focus_in_ring();
+        // Focus or mode may have changed, so keep the footer hints in sync
+        self.status_bar
+            .set_hints(footer_hints(self.footer_context(), &self.keys));
+        self.announce_focus();
+        action
+    }
 
-            // If we just closed history and StatusBar was focused, move focus to NewsList
-            if was_showing && !is_showing && self.focused_component == TabComponent::StatusBar {
-                self.status_bar.set_focus(false);
-                self.news_list.set_focus(true);
-                self.focused_component = TabComponent::NewsList;
+    /// Key context of whatever currently receives input
+    fn footer_context(&self) -> KeyContext {
+        if self.help.is_visible() {
+            return KeyContext::Help;
+        }
+        if self.share_menu.is_visible() {
+            return KeyContext::ShareMenu;
+        }
+        match self.focused_component {
+            TabComponent::SearchBar => KeyContext::SearchBar,
+            TabComponent::FilterChips => KeyContext::FilterChips,
+            TabComponent::NewsList => KeyContext::NewsList,
+            TabComponent::PinnedPane | TabComponent::DetailPane => KeyContext::DetailPane,
+            TabComponent::StatusBar if self.status_bar.is_showing_history() => {
+                KeyContext::StatusHistory
             }
-
-            return Action::None;
+            TabComponent::StatusBar if self.status_bar.is_showing_errors() => {
+                KeyContext::StatusErrors
+            }
+            TabComponent::StatusBar => KeyContext::StatusReport,
         }
+    }
 
-        // Esc is an overloaded event. This one checks only for dismissing status
-        if let Event::Key(KeyEvent {
-            code: KeyCode::Esc, ..
-        }) = event
-        {
-            if !self.search_bar.is_focused() {
-                let action = Action::DismissStatus;
-                self.status_bar.update(&action);
+    /// Current input mode, derived from focus
+    pub fn input_mode(&self) -> InputMode {
+        if self.help.is_visible() || self.share_menu.is_visible() {
+            return InputMode::Modal;
+        }
+        match self.focused_component {
+            TabComponent::SearchBar => InputMode::Insert,
+            _ => InputMode::Normal,
+        }
+    }
 
+    fn route_event(&mut self, event: &Event) -> Action {
+        // Bracketed paste only has a destination while editing text
+        if let Event::Paste(_) = event {
+            if self.input_mode() != InputMode::Insert {
                 return Action::None;
             }
+            let action = self.search_bar.handle_event(event);
+            self.update_all(&action);
+            return action;
         }
 
-        // Handle '/' to enter search mode
-        if let Event::Key(KeyEvent {
-            code: KeyCode::Char('/'),
-            modifiers,
-            ..
-        }) = event
-        {
-            if modifiers.is_empty() && !self.search_bar.is_focused() {
-                self.search_bar.set_focus(true);
-                return Action::None;
+        if let Event::Mouse(mouse) = event {
+            return self.route_mouse(mouse);
+        }
+
+        let Event::Key(key) = event else {
+            return Action::None;
+        };
+
+        let commands = self.keys.commands(key);
+
+        // Keys typed while recording become the macro, except the keys that control it
+        let controls_macro = self.input_mode() == InputMode::Normal
+            && commands
+                .iter()
+                .any(|c| matches!(c, Command::RecordMacro | Command::PlayMacro));
+        if !controls_macro && !self.macros.record(*key) {
+            let count = self.macros.stop();
+            let msg = tr!("status.macro-full", count = count);
+            self.status_bar.set_message(StatusMessage::warning(msg));
+        }
+
+        // Keys that can't be typed don't collide with text entry, so they work in every mode
+        let typed = matches!(key.code, KeyCode::Char(_))
+            && !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !typed && commands.contains(&Command::Quit) {
+            return Action::Quit;
+        }
+        if !typed && commands.contains(&Command::History) {
+            self.toggle_history();
+            return Action::None;
+        }
+
+        match self.input_mode() {
+            InputMode::Insert => self.route_insert(event, key, &commands),
+            InputMode::Normal => self.route_normal(&commands),
+            // Overlays capture all input until they dismiss themselves
+            InputMode::Modal if self.help.is_visible() => {
+                dispatch(&mut self.help, &commands).unwrap_or(Action::None)
             }
+            InputMode::Modal => match dispatch(&mut self.share_menu, &commands) {
+                Some(action) => action,
+                None => self.share_menu.handle_event(event),
+            },
         }
+    }
 
-        if self.search_bar.is_focused() {
-            let action = self.search_bar.handle_event(event);
+    /// The wheel scrolls whichever pane is under the cursor, focused or not:
+    /// the list moves its selection, the article and the expanded status
+    /// views scroll
+    fn route_mouse(&mut self, mouse: &MouseEvent) -> Action {
+        let position = Position::new(mouse.column, mouse.row);
+        let down = match mouse.kind {
+            MouseEventKind::ScrollDown => true,
+            MouseEventKind::ScrollUp => false,
+            MouseEventKind::Down(MouseButton::Left) => return self.route_click(position),
+            _ => return Action::None,
+        };
+        // The key help scrolls wherever the cursor is
+        if self.help.is_visible() {
+            self.help.scroll(down, WHEEL_LINES as usize);
+            return Action::None;
+        }
+        // Overlays take all input, and the plain layout has no panes
+        if self.input_mode() == InputMode::Modal || self.is_screen_reader() {
+            return Action::None;
+        }
+        if self.news_list.contains(position) {
+            let action = self.news_list.move_selection(down);
+            self.update_all(&action);
+            return action;
+        }
+        if self.pinned.is_some() && self.pinned_pane.contains(position) {
+            self.pinned_pane.scroll(down, WHEEL_LINES);
+        } else if self.detail_pane.contains(position) {
+            self.detail_pane.scroll(down, WHEEL_LINES);
+        } else if self.status_bar.contains(position) && self.status_bar.is_expanded() {
+            self.status_bar.scroll_expanded(down);
+        }
+        Action::None
+    }
+
+    /// A left click focuses the pane under the cursor, if Tab could reach
+    /// it; on the list it also selects the article clicked
+    fn route_click(&mut self, position: Position) -> Action {
+        if self.input_mode() == InputMode::Modal || self.is_screen_reader() {
+            return Action::None;
+        }
+        // Panes no longer drawn keep their last area, so only the ring counts
+        let ring = self.focus_ring();
+        let clicked = [
+            (TabComponent::SearchBar, self.search_bar.contains(position)),
+            (
+                TabComponent::FilterChips,
+                self.filter_chips.contains(position),
+            ),
+            (TabComponent::NewsList, self.news_list.contains(position)),
+            (
+                TabComponent::PinnedPane,
+                self.pinned_pane.contains(position),
+            ),
+            (
+                TabComponent::DetailPane,
+                self.detail_pane.contains(position),
+            ),
+            (TabComponent::StatusBar, self.status_bar.contains(position)),
+        ]
+        .into_iter()
+        .find(|(target, hit)| *hit && ring.contains(target));
+        let Some((target, _)) = clicked else {
+            return Action::None;
+        };
+        self.set_focused(target);
+        if target != TabComponent::NewsList {
+            return Action::None;
+        }
+        let action = self.news_list.click(position);
+        self.update_all(&action);
+        action
+    }
+
+    /// Insert mode: the search bar owns every text key. Only non-text keys
+    /// (Tab, ↑/↓, Enter) reach focus cycling and result navigation.
+    fn route_insert(&mut self, event: &Event, key: &KeyEvent, commands: &[Command]) -> Action {
+        // Terminals without bracketed paste still send these
+        let paste = match key.code {
+            KeyCode::Char('v') => key.modifiers.contains(KeyModifiers::CONTROL),
+            KeyCode::Insert => key.modifiers.contains(KeyModifiers::SHIFT),
+            _ => false,
+        };
+        if paste {
+            return self.paste_clipboard();
+        }
 
-            // If SearchBar handled it, broadcast and return
-            if !matches!(action, Action::None) {
+        let action = self.search_bar.handle_event(event);
+
+        // Esc clears the query and leaves search mode; hand focus back to the list
+        if !self.search_bar.is_focused() {
+            self.set_focused(TabComponent::NewsList);
+        }
+
+        // If SearchBar handled it, broadcast and return
+        if !matches!(action, Action::None) {
+            self.update_all(&action);
+            return action;
+        }
+
+        if commands.contains(&Command::NextPane) {
+            self.cycle_focus(true);
+            return Action::None;
+        }
+        if commands.contains(&Command::PreviousPane) {
+            self.cycle_focus(false);
+            return Action::None;
+        }
+        let navigation: Vec<Command> = commands
+            .iter()
+            .copied()
+            .filter(|c| matches!(c, Command::Up | Command::Down | Command::Open))
+            .collect();
+        let action = dispatch(&mut self.news_list, &navigation).unwrap_or(Action::None);
+        self.update_all(&action);
+        action
+    }
+
+    /// Paste the system clipboard's text into the search bar
+    fn paste_clipboard(&mut self) -> Action {
+        let text = match &mut self.clipboard {
+            Some(clipboard) => clipboard.get_text(),
+            None => arboard::Clipboard::new().and_then(|mut clipboard| {
+                let text = clipboard.get_text();
+                self.clipboard = Some(clipboard);
+                text
+            }),
+        };
+        match text {
+            Ok(text) => {
+                let action = self.search_bar.paste(&text);
                 self.update_all(&action);
-                return action;
+                action
+            }
+            Err(arboard::Error::ContentNotAvailable) => {
+                let msg = StatusMessage::info(tr!("status.clipboard-empty"));
+                self.status_bar.set_message(msg);
+                Action::None
+            }
+            Err(e) => {
+                let msg = StatusMessage::error(tr!("status.clipboard-failed", error = e));
+                self.status_bar.set_message(msg);
+                Action::None
             }
-            // SearchBar returned Action::None, fall through to focused component
         }
+    }
 
-        // tab is unique
-        if let Event::Key(KeyEvent {
-            code: KeyCode::Tab, ..
-        }) = event
-        {
-            self.cycle_focus();
+    /// Normal mode: the focused component sees the key's commands first;
+    /// app-wide shortcuts only apply to those it doesn't take.
+    fn route_normal(&mut self, commands: &[Command]) -> Action {
+        let focused: &mut dyn Component = match self.focused_component {
+            TabComponent::FilterChips => &mut self.filter_chips,
+            TabComponent::NewsList => &mut self.news_list,
+            TabComponent::PinnedPane => &mut self.pinned_pane,
+            TabComponent::DetailPane => &mut self.detail_pane,
+            TabComponent::StatusBar => &mut self.status_bar,
+            TabComponent::SearchBar => return Action::None,
+        };
+        if let Some(action) = dispatch(focused, commands) {
+            self.update_all(&action);
+            return action;
+        }
+
+        for &command in commands {
+            let action = match command {
+                Command::Search => {
+                    self.set_focused(TabComponent::SearchBar);
+                    Action::None
+                }
+                Command::CommandLine => {
+                    self.set_focused(TabComponent::SearchBar);
+                    self.search_bar.start_command();
+                    let action = Action::SearchQueryChanged(String::new());
+                    self.update_all(&action);
+                    action
+                }
+                Command::NextPane => {
+                    self.cycle_focus(true);
+                    Action::None
+                }
+                Command::PreviousPane => {
+                    self.cycle_focus(false);
+                    Action::None
+                }
+                Command::History => {
+                    self.toggle_history();
+                    Action::None
+                }
+                Command::Dismiss => {
+                    self.status_bar.update(&Action::DismissStatus);
+                    Action::None
+                }
+                Command::Refresh => Action::RefreshRequested,
+                Command::RefreshSource => match self.news_list.selected_item() {
+                    Some(item) => Action::RefreshSourceRequested(item.source.clone()),
+                    None => Action::None,
+                },
+                Command::LastHour | Command::Today | Command::ThisWeek | Command::AllTime => {
+                    let range = match command {
+                        Command::LastHour => Some(TimeRange::LastHour),
+                        Command::Today => Some(TimeRange::Today),
+                        Command::ThisWeek => Some(TimeRange::ThisWeek),
+                        _ => None,
+                    };
+                    // Pressing the active filter's key again removes it
+                    self.filter.time_range = range.filter(|r| self.filter.time_range != Some(*r));
+                    self.apply_filter()
+                }
+                Command::FilterSource => {
+                    let Some(source) = self.news_list.selected_item().map(|i| i.source.clone())
+                    else {
+                        return Action::None;
+                    };
+                    self.filter.toggle_source(&source);
+                    self.apply_filter()
+                }
+                Command::FilterTag => {
+                    let tag = self
+                        .news_list
+                        .selected_item()
+                        .and_then(|item| item.tags.first().cloned());
+                    let Some(tag) = tag else {
+                        let msg = StatusMessage::info(tr!("status.no-tags"));
+                        self.status_bar.set_message(msg);
+                        return Action::None;
+                    };
+                    self.filter.toggle_tag(&tag);
+                    self.apply_filter()
+                }
+                Command::Share => {
+                    if let Some(item) = self.news_list.selected_item() {
+                        let title = item.title.clone();
+                        self.share_menu.open(&title);
+                    }
+                    Action::None
+                }
+                Command::Density => {
+                    let density = self.news_list.density().next();
+                    self.news_list.set_density(density);
+                    let msg = tr!("status.density", density = density.as_str());
+                    self.status_bar.set_message(StatusMessage::info(msg));
+                    Action::None
+                }
+                Command::Errors if self.status_bar.error_count() == 0 => {
+                    self.status_bar
+                        .set_message(StatusMessage::info(tr!("status.no-errors")));
+                    Action::None
+                }
+                Command::Errors => {
+                    self.status_bar.toggle_errors();
+                    if self.status_bar.is_showing_errors() {
+                        self.set_focused(TabComponent::StatusBar);
+                    }
+                    Action::None
+                }
+                Command::Grow | Command::Shrink if self.status_bar.is_expanded() => {
+                    self.status_bar.resize_expanded(command == Command::Grow);
+                    Action::None
+                }
+                Command::OpenAll => self.request_open_all(),
+                Command::FullText => {
+                    self.request_full_text();
+                    Action::None
+                }
+                Command::RepeatEdit => self.repeat_last_edit(),
+                Command::RecordMacro if self.macros.is_recording() => {
+                    let count = self.macros.stop();
+                    let msg = tr!("status.macro-recorded", count = count);
+                    self.status_bar.set_message(StatusMessage::info(msg));
+                    Action::None
+                }
+                Command::RecordMacro => {
+                    self.macros.start();
+                    let msg = tr!("status.macro-recording");
+                    self.status_bar.set_message(StatusMessage::info(msg));
+                    Action::None
+                }
+                Command::PlayMacro if self.macros.is_recording() => {
+                    let msg = tr!("status.macro-stop-first");
+                    self.status_bar.set_message(StatusMessage::info(msg));
+                    Action::None
+                }
+                Command::PlayMacro if self.macros.recorded().is_empty() => {
+                    let msg = tr!("status.macro-none");
+                    self.status_bar.set_message(StatusMessage::info(msg));
+                    Action::None
+                }
+                Command::PlayMacro => {
+                    let keys = self.macros.recorded().iter().map(|k| Event::Key(*k));
+                    self.pending_replay.extend(keys);
+                    Action::None
+                }
+                Command::Undo => Action::Undo,
+                // The list pins; elsewhere the key only ends a comparison
+                Command::Pin if self.pinned.is_some() => {
+                    self.unpin();
+                    Action::None
+                }
+                Command::Help => {
+                    self.help.open(&self.keys);
+                    Action::None
+                }
+                Command::Quit => Action::Quit,
+                _ => continue,
+            };
+            return action;
+        }
+        Action::None
+    }
+
+    /// Fetch the selected article's page in the background and keep its
+    /// main text as the article's content
+    fn request_full_text(&mut self) {
+        let Some(item) = self.news_list.selected_item() else {
+            return;
+        };
+        if self.read_only {
+            let msg = StatusMessage::warning(tr!("status.read-only-content"));
+            self.status_bar.set_message(msg);
+            return;
+        }
+        let msg = StatusMessage::info(tr!("status.full-text-fetching", title = item.title));
+        self.pending_content = Some((item.id.clone(), item.url.clone()));
+        self.status_bar.set_message(msg);
+    }
+
+    /// Open every listed article, once confirmed if there are many. A
+    /// second `O` within a few seconds confirms.
+    fn request_open_all(&mut self) -> Action {
+        let count = self.news_list.filtered_items().len();
+        if count == 0 {
+            let msg = StatusMessage::info(tr!("status.nothing-to-open"));
+            self.status_bar.set_message(msg);
             return Action::None;
         }
+        let now = self.clock.now();
+        let confirmed = self.bulk_open_confirm.take().is_some_and(|(n, at)| {
+            let waited = now.signed_duration_since(at);
+            n == count && waited.to_std().unwrap_or(Duration::ZERO) < BULK_OPEN_CONFIRM_WINDOW
+        });
+        if count > self.bulk_open_limit && !confirmed {
+            self.bulk_open_confirm = Some((count, now));
+            let msg = tr!("status.open-all-confirm", count = count);
+            self.status_bar.set_message(StatusMessage::warning(msg));
+            return Action::None;
+        }
+        Action::OpenAllRequested
+    }
 
-        // 'r' and 'q' should be handled by the app globally at this point
-        if let Event::Key(KeyEvent {
-            code: KeyCode::Char('r'),
-            ..
-        }) = event
-        {
-            return Action::RefreshRequested;
+    /// Open `url` with the selected article's opener, marking it read
+    fn open_article(&mut self, url: &str, db: &NewsDB) {
+        let item = self
+            .news_list
+            .selected_item()
+            .filter(|item| item.url == url);
+        let opener = item.map_or(&Opener::Browser, |item| {
+            self.openers.for_source(&item.source)
+        });
+        let unread = item
+            .filter(|item| !item.read && self.mark_read_on_open)
+            .map(|item| item.id.clone());
+        if let Err(e) = opener.open(url) {
+            self.status_bar.set_message(StatusMessage::error(e));
+            return;
         }
+        if let Some(id) = unread {
+            self.set_read(&id, true, db);
+        }
+    }
 
-        if let Event::Key(KeyEvent {
-            code: KeyCode::Char('q'),
-            ..
-        }) = event
-        {
-            return Action::Quit;
+    /// Mark the listed articles read and hand them to the main loop to open
+    fn open_all(&mut self, db: &NewsDB) {
+        let items = self.news_list.filtered_items();
+        let links: Vec<(Opener, String)> = items
+            .iter()
+            .map(|item| {
+                let opener = self.openers.for_source(&item.source).clone();
+                (opener, item.url.clone())
+            })
+            .collect();
+        let unread: Vec<String> = items
+            .iter()
+            .filter(|item| !item.read && self.mark_read_on_open)
+            .map(|item| item.id.clone())
+            .collect();
+        for id in unread {
+            if self.set_read(&id, true, db).is_none() {
+                break;
+            }
         }
+        let msg = tr!("status.opening", count = links.len());
+        self.status_bar.set_message(StatusMessage::info(msg));
+        self.pending_bulk_open = Some(BulkOpenRequest {
+            links,
+            delay: self.bulk_open_delay,
+        });
+    }
 
-        // Route to focused component (NewsList, DetailPane, or StatusBar)
-        let action = match self.focused_component {
-            TabComponent::NewsList => self.news_list.handle_event(event),
-            TabComponent::DetailPane => self.detail_pane.handle_event(event),
-            TabComponent::StatusBar => self.status_bar.handle_event(event),
+    /// The last delete, archive, star or tag, applied to the current selection
+    fn repeat_last_edit(&mut self) -> Action {
+        let (Some(edit), Some(item)) = (self.last_edit.clone(), self.news_list.selected_item())
+        else {
+            let msg = StatusMessage::info(tr!("status.nothing-to-repeat"));
+            self.status_bar.set_message(msg);
+            return Action::None;
         };
+        match edit {
+            RepeatableEdit::Delete => Action::ArticleDeleted(item.id.clone()),
+            // Archiving repeats as archiving, even on an already read article
+            RepeatableEdit::SetRead(read) if item.read != read => {
+                Action::ArchiveToggled(item.id.clone())
+            }
+            RepeatableEdit::SetRead(_) => Action::None,
+            RepeatableEdit::SetStarred(starred) if item.starred != starred => {
+                Action::StarToggled(item.id.clone())
+            }
+            RepeatableEdit::SetStarred(_) => Action::None,
+            RepeatableEdit::Tag(tag, add) if item.tags.contains(&tag) != add => {
+                Action::TagToggled(item.id.clone(), tag)
+            }
+            RepeatableEdit::Tag(..) => Action::None,
+        }
+    }
+
+    /// Broadcast the current filter to the components
+    fn apply_filter(&mut self) -> Action {
+        let action = Action::FilterApplied(self.filter.clone());
         self.update_all(&action);
         action
     }
 
+    /// Drop one filter, leaving the others and the search query in place
+    fn remove_filter(&mut self, chip: &FilterChip) {
+        match chip {
+            FilterChip::Source(name) => self.filter.sources.retain(|s| s != name),
+            FilterChip::Tag(tag) => self.filter.tags.retain(|t| t != tag),
+            FilterChip::Time(_) => self.filter.time_range = None,
+            FilterChip::ReadState(_) => {
+                self.news_list.set_view(ListView::All);
+                self.show_selected_article();
+            }
+        }
+        if !matches!(chip, FilterChip::ReadState(_)) {
+            self.apply_filter();
+        }
+        self.refresh_chips();
+    }
+
+    /// Rebuild the chips from the filter and list view
+    fn refresh_chips(&mut self) {
+        let mut chips: Vec<FilterChip> = Vec::new();
+        if self.news_list.view() != ListView::All {
+            chips.push(FilterChip::ReadState(self.news_list.view()));
+        }
+        chips.extend(self.filter.time_range.map(FilterChip::Time));
+        chips.extend(self.filter.sources.iter().cloned().map(FilterChip::Source));
+        chips.extend(self.filter.tags.iter().cloned().map(FilterChip::Tag));
+        self.filter_chips.set_chips(chips);
+
+        self.keep_focus_in_ring();
+    }
+
+    /// Hand focus back to the list when the focused component has left the
+    /// ring (last filter removed, history closed, report dismissed)
+    fn keep_focus_in_ring(&mut self) {
+        if !self.focus_ring().contains(&self.focused_component) {
+            self.set_focused(TabComponent::NewsList);
+        }
+    }
+
+    fn toggle_history(&mut self) {
+        self.status_bar.toggle_history();
+        self.keep_focus_in_ring();
+    }
+
     fn update_all(&mut self, action: &Action) {
         // Broadcast action to all components including status_bar
-        self.news_list.update(&action);
-        self.detail_pane.update(&action);
-        self.search_bar.update(&action);
-        self.status_bar.update(&action);
+        self.news_list.update(action);
+        self.detail_pane.update(action);
+        self.search_bar.update(action);
+        self.status_bar.update(action);
 
         // Handle selection changes that need to update detail pane
         match action {
-            Action::SelectionChanged(_) | Action::SearchQueryChanged(_) => {
-                if let Some(selected_article) = self.news_list.selected_item() {
-                    self.detail_pane.set_article(selected_article.clone());
-                }
+            Action::SelectionChanged(_) => self.view_selected_article(),
+            Action::SearchQueryChanged(_) | Action::FilterApplied(_) | Action::ViewChanged => {
+                self.show_selected_article();
             }
+            Action::FilterRemoved(chip) => self.remove_filter(chip),
             _ => {}
         }
+        // The list view may have changed (A)
+        self.refresh_chips();
     }
 
-    /// Tab to cycle focus (dynamic 2/3-way cycle based on history visibility)
-    fn cycle_focus(&mut self) {
-        self.focused_component = match self.focused_component {
-            TabComponent::NewsList => {
-                self.news_list.set_focus(false);
-                self.detail_pane.set_focus(true);
-                TabComponent::DetailPane
-            }
-            TabComponent::DetailPane => {
-                self.detail_pane.set_focus(false);
-                // Only include StatusBar in cycle if history is showing
-                if self.status_bar.is_showing_history() {
-                    self.status_bar.set_focus(true);
-                    TabComponent::StatusBar
-                } else {
-                    // Skip StatusBar, go back to NewsList
-                    self.news_list.set_focus(true);
-                    TabComponent::NewsList
-                }
-            }
-            TabComponent::StatusBar => {
-                self.status_bar.set_focus(false);
-                self.news_list.set_focus(true);
-                TabComponent::NewsList
-            }
+    /// Focus ring in Tab order. The filter chips only join while a filter is
+    /// active, the status bar while its history, errors or a refresh report is up.
+    fn focus_ring(&self) -> Vec<TabComponent> {
+        let mut ring = vec![TabComponent::SearchBar];
+        if !self.filter_chips.is_empty() {
+            ring.push(TabComponent::FilterChips);
+        }
+        ring.push(TabComponent::NewsList);
+        if self.pinned.is_some() {
+            ring.push(TabComponent::PinnedPane);
+        }
+        ring.push(TabComponent::DetailPane);
+        if self.status_bar.is_showing_history()
+            || self.status_bar.is_showing_errors()
+            || self.status_bar.has_report()
+        {
+            ring.push(TabComponent::StatusBar);
+        }
+        ring
+    }
+
+    /// Move focus to `target`, ensuring exactly one component is marked focused
+    fn set_focused(&mut self, target: TabComponent) {
+        self.search_bar.set_focus(target == TabComponent::SearchBar);
+        self.filter_chips
+            .set_focus(target == TabComponent::FilterChips);
+        self.news_list.set_focus(target == TabComponent::NewsList);
+        self.pinned_pane
+            .set_focus(target == TabComponent::PinnedPane);
+        self.detail_pane
+            .set_focus(target == TabComponent::DetailPane);
+        self.status_bar.set_focus(target == TabComponent::StatusBar);
+        self.focused_component = target;
+    }
+
+    /// Tab / Shift+Tab to move forward / backward around the focus ring
+    fn cycle_focus(&mut self, forward: bool) {
+        let ring = self.focus_ring();
+        let current = ring
+            .iter()
+            .position(|c| *c == self.focused_component)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % ring.len()
+        } else {
+            (current + ring.len() - 1) % ring.len()
         };
+        self.set_focused(ring[next]);
     }
 
     /// Handle an Action and perform side effects (like opening URLs)
     /// Returns false if app should quit, true otherwise
-    pub fn handle_action(&mut self, action: &Action) -> bool {
+    pub fn handle_action(&mut self, action: &Action, db: &NewsDB) -> bool {
+        let running = self.apply_action(action, db);
+        // Deleting, undoing and filtering move the selection too
+        self.announce_focus();
+        running
+    }
+
+    fn apply_action(&mut self, action: &Action, db: &NewsDB) -> bool {
+        if self.read_only {
+            let refused = match action {
+                Action::ArticleDeleted(_) => Some("status.read-only-delete"),
+                Action::ArchiveToggled(_) => Some("status.read-only-archive"),
+                Action::StarToggled(_) => Some("status.read-only-star"),
+                Action::TagToggled(..) => Some("status.read-only-tag"),
+                Action::FollowToggled(_) => Some("status.read-only-follow"),
+                _ => None,
+            };
+            if let Some(id) = refused {
+                let msg = StatusMessage::warning(tr!(id));
+                self.status_bar.set_message(msg);
+                return true;
+            }
+        }
         match action {
             Action::Quit => return false,
-            Action::ArticleOpened(url) => {
-                if let Err(e) = open::that(url) {
-                    let msg = StatusMessage::error(format!("Failed to open browser: {}", e));
-                    self.status_bar.set_message(msg);
+            Action::ArticleOpened(url) => self.open_article(url, db),
+            Action::OpenAllRequested => self.open_all(db),
+            Action::OpenSelected => {
+                if let Some(url) = self.news_list.selected_item().map(|item| item.url.clone()) {
+                    self.open_article(&url, db);
                 }
             }
+            Action::ArticleDeleted(id) => self.delete_article(id, db),
+            Action::ArchiveToggled(id) => self.toggle_archived(id, db),
+            Action::StarToggled(id) => self.toggle_starred(id, db),
+            Action::TagToggled(id, tag) => self.toggle_tag(id, tag, db),
+            Action::FollowToggled(id) => self.toggle_follow(id, db),
+            Action::PinToggled(id) => self.toggle_pin(id),
+            Action::ShareSelected(index) => self.share(*index),
+            Action::CommandEntered(command) => self.run_command(command, db),
+            Action::Undo => self.undo(db),
             _ => {}
         }
         true
     }
 
-    /// Periodic update for spinner animation and auto-dismiss checks
-    pub fn tick(&mut self) {
+    /// Run a `:` command line
+    fn run_command(&mut self, command: &str, db: &NewsDB) {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let msg = match words[..] {
+            [] => return,
+            ["sync", "pull"] if self.read_only => {
+                StatusMessage::warning(tr!("status.read-only-sync"))
+            }
+            ["sync", direction] if SyncDirection::parse(direction).is_some() => {
+                self.pending_sync = SyncDirection::parse(direction);
+                StatusMessage::info(match direction {
+                    "pull" => tr!("status.sync-pulling"),
+                    _ => tr!("status.sync-pushing"),
+                })
+            }
+            ["sync", ..] => StatusMessage::error(tr!("status.sync-usage")),
+            ["tag", tag] => match self.news_list.selected_item() {
+                Some(item) => {
+                    let action = Action::TagToggled(item.id.clone(), tag.to_string());
+                    self.apply_action(&action, db);
+                    return;
+                }
+                None => StatusMessage::info(tr!("status.nothing-to-tag")),
+            },
+            ["tag", ..] => StatusMessage::error(tr!("status.tag-usage")),
+            ["cache", "clear"] => {
+                self.pending_cache_clear = true;
+                StatusMessage::info(tr!("status.cache-clearing"))
+            }
+            ["cache", ..] => StatusMessage::error(tr!("status.cache-usage")),
+            ["report", ref args @ ..] => match ReportRequest::parse(args) {
+                Ok(request) => self.write_report(&request, db),
+                Err(e) => StatusMessage::error(tr!("status.report-failed", error = e)),
+            },
+            _ => StatusMessage::error(tr!("status.unknown-command", command = command)),
+        };
+        self.status_bar.set_message(msg);
+    }
+
+    /// Write the stored articles matching a `:report` query to its file
+    fn write_report(&self, request: &ReportRequest, db: &NewsDB) -> StatusMessage {
+        let now = self.clock.now();
+        let since = now - chrono::Duration::days(request.days);
+        let written = db.search(&request.query, since).and_then(|items| {
+            let text = report(&request.query, &items, since, now, request.format);
+            std::fs::write(&request.path, text)
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", request.path, e))?;
+            Ok(items.len())
+        });
+        match written {
+            Ok(count) => StatusMessage::success(tr!(
+                "status.report-written",
+                count = count,
+                path = request.path
+            )),
+            Err(e) => StatusMessage::error(tr!("status.report-failed", error = format!("{:#}", e))),
+        }
+    }
+
+    fn delete_article(&mut self, id: &str, db: &NewsDB) {
+        if let Err(e) = db.delete(id, self.clock.now()) {
+            let msg = StatusMessage::error(tr!("status.delete-failed", error = e));
+            self.status_bar.set_message(msg);
+            return;
+        }
+        if let Some((item, index)) = self.news_list.remove(id) {
+            let msg = StatusMessage::info(tr!("status.deleted", title = item.title));
+            self.status_bar.set_message(msg);
+            self.undo_stack.push(UndoOp::Delete {
+                item: Box::new(item),
+                index,
+            });
+            self.last_edit = Some(RepeatableEdit::Delete);
+            self.show_selected_article();
+        }
+    }
+
+    /// Follow the story of article `id`, or unfollow the story it belongs to
+    fn toggle_follow(&mut self, id: &str, db: &NewsDB) {
+        let Some(item) = self.news_list.selected_item().filter(|item| item.id == id) else {
+            return;
+        };
+        let msg = match self.news_list.followed_story(item).cloned() {
+            Some(story) => match db.unfollow_story(story.id) {
+                Ok(()) => {
+                    self.followed.retain(|s| s.id != story.id);
+                    StatusMessage::info(tr!("status.unfollowed", title = story.title))
+                }
+                Err(e) => StatusMessage::error(tr!("status.unfollow-failed", error = e)),
+            },
+            None => {
+                let mut story = FollowedStory::from_item(item);
+                match db.follow_story(&story) {
+                    Ok(row) => {
+                        story.id = row;
+                        let msg = tr!("status.following", title = story.title);
+                        self.followed.push(story);
+                        StatusMessage::success(msg)
+                    }
+                    Err(e) => StatusMessage::error(tr!("status.follow-failed", error = e)),
+                }
+            }
+        };
+        self.status_bar.set_message(msg);
+        self.news_list.set_followed(self.followed.clone());
+        self.show_selected_article();
+    }
+
+    /// Pin article `id` to compare the articles selected after it against,
+    /// or unpin it if it's the one pinned
+    fn toggle_pin(&mut self, id: &str) {
+        if self.pinned.as_ref().is_some_and(|item| item.id == id) {
+            self.unpin();
+            return;
+        }
+        let Some(item) = self.news_list.selected_item().filter(|item| item.id == id) else {
+            return;
+        };
+        let msg = tr!("status.pinned", title = item.title);
+        self.pinned = Some(item.clone());
+        self.pinned_pane.reset_scroll();
+        self.status_bar.set_message(StatusMessage::info(msg));
+    }
+
+    /// End the comparison
+    fn unpin(&mut self) {
+        if let Some(item) = self.pinned.take() {
+            let msg = tr!("status.unpinned", title = item.title);
+            self.status_bar.set_message(StatusMessage::info(msg));
+            self.keep_focus_in_ring();
+        }
+    }
+
+    /// Share the selected article with the target at `index`
+    fn share(&mut self, index: usize) {
+        let (Some(target), Some(item)) = (
+            self.share_targets.get(index),
+            self.news_list.selected_item(),
+        ) else {
+            return;
+        };
+        let text = target.render(item);
+        let result = match &target.action {
+            ShareAction::Copy => {
+                let copied = match &mut self.clipboard {
+                    Some(clipboard) => clipboard.set_text(text.clone()),
+                    None => arboard::Clipboard::new().and_then(|mut clipboard| {
+                        let copied = clipboard.set_text(text.clone());
+                        self.clipboard = Some(clipboard);
+                        copied
+                    }),
+                };
+                copied
+                    .map(|_| tr!("status.copied", text = text))
+                    .map_err(|e| tr!("status.clipboard-failed", error = e))
+            }
+            ShareAction::Open => open::that(&text)
+                .map(|_| tr!("status.opened-link", url = text))
+                .map_err(|e| tr!("status.open-failed", error = e)),
+            ShareAction::Command => target
+                .command(item)
+                .map_err(|e| e.to_string())
+                .and_then(|args| spawn_detached(&args))
+                .map(|_| tr!("status.command-started", target = target.name)),
+            ShareAction::Webhook { url } => {
+                self.pending_webhook = Some(WebhookRequest {
+                    target: target.name.clone(),
+                    url: url.clone(),
+                    text,
+                });
+                Ok(tr!("status.sending", target = target.name))
+            }
+        };
+        let msg = match result {
+            Ok(text) => StatusMessage::success(text),
+            Err(e) => StatusMessage::error(e),
+        };
+        self.status_bar.set_message(msg);
+    }
+
+    /// Store an article's read state and update the list. Returns the
+    /// previous state, or None if the update failed.
+    fn set_read(&mut self, id: &str, read: bool, db: &NewsDB) -> Option<bool> {
+        if self.read_only {
+            return None;
+        }
+        if let Err(e) = db.set_read(id, read, self.clock.now()) {
+            let msg = StatusMessage::error(tr!("status.read-state-failed", error = e));
+            self.status_bar.set_message(msg);
+            return None;
+        }
+        let was_read = self.news_list.set_read(id, read);
+        self.push_state(id);
+        // Once its state has been set, viewing the article doesn't change it
+        // again, e.g. an article restored to the inbox stays unread
+        self.viewing = Some((id.to_string(), None));
+        self.show_selected_article();
+        was_read
+    }
+
+    /// Mark the article in the detail pane read once it has been shown for
+    /// `mark_read_after`. Returns whether it was marked.
+    fn mark_viewed_read(&mut self, db: &NewsDB) -> bool {
+        let Some(after) = self.mark_read_after else {
+            return false;
+        };
+        if !self.article_shown() {
+            return false;
+        }
+        let Some((id, Some(since))) = &self.viewing else {
+            return false;
+        };
+        let elapsed = self.clock.now().signed_duration_since(*since);
+        if elapsed.to_std().unwrap_or(Duration::ZERO) < after {
+            return false;
+        }
+        let id = id.clone();
+        self.set_read(&id, true, db).is_some()
+    }
+
+    /// Archive (mark read) an unread article, or restore a read one to the inbox
+    fn toggle_archived(&mut self, id: &str, db: &NewsDB) {
+        let Some(item) = self.news_list.selected_item().filter(|i| i.id == id) else {
+            return;
+        };
+        let title = item.title.clone();
+        let read = item.read;
+        if self.set_read(id, !read, db).is_none() {
+            return;
+        }
+        let message = if read {
+            "status.restored"
+        } else {
+            "status.archived"
+        };
+        let msg = StatusMessage::info(tr!(message, title = title));
+        self.status_bar.set_message(msg);
+        self.undo_stack.push(UndoOp::Archive {
+            id: id.to_string(),
+            title,
+            read,
+        });
+        self.last_edit = Some(RepeatableEdit::SetRead(!read));
+    }
+
+    /// Star an article, or unstar a starred one
+    fn toggle_starred(&mut self, id: &str, db: &NewsDB) {
+        let Some(item) = self.news_list.selected_item().filter(|i| i.id == id) else {
+            return;
+        };
+        let title = item.title.clone();
+        let starred = !item.starred;
+        if let Err(e) = self.set_starred(id, starred, db) {
+            let msg = StatusMessage::error(tr!("status.star-failed", error = e));
+            self.status_bar.set_message(msg);
+            return;
+        }
+        let message = if starred {
+            "status.starred"
+        } else {
+            "status.unstarred"
+        };
+        let msg = StatusMessage::info(tr!(message, title = title.clone()));
+        self.status_bar.set_message(msg);
+        self.undo_stack.push(UndoOp::Star {
+            id: id.to_string(),
+            title,
+            starred: !starred,
+        });
+        self.last_edit = Some(RepeatableEdit::SetStarred(starred));
+    }
+
+    /// Add `tag` to an article, or remove it if the article has it
+    fn toggle_tag(&mut self, id: &str, tag: &str, db: &NewsDB) {
+        let Some(item) = self.news_list.selected_item().filter(|i| i.id == id) else {
+            return;
+        };
+        let title = item.title.clone();
+        let before = item.tags.clone();
+        let mut tags = before.clone();
+        let add = !tags.iter().any(|t| t == tag);
+        if add {
+            tags.push(tag.to_string());
+        } else {
+            tags.retain(|t| t != tag);
+        }
+        if let Err(e) = self.set_tags(id, tags, db) {
+            let msg = StatusMessage::error(tr!("status.tag-failed", error = e));
+            self.status_bar.set_message(msg);
+            return;
+        }
+        let message = if add {
+            "status.tagged"
+        } else {
+            "status.untagged"
+        };
+        let msg = StatusMessage::info(tr!(message, tag = tag, title = title.clone()));
+        self.status_bar.set_message(msg);
+        self.undo_stack.push(UndoOp::Tag {
+            id: id.to_string(),
+            title,
+            tags: before,
+        });
+        self.last_edit = Some(RepeatableEdit::Tag(tag.to_string(), add));
+    }
+
+    /// Store an article's starred state, update the list and send the
+    /// state to the article's server
+    fn set_starred(&mut self, id: &str, starred: bool, db: &NewsDB) -> anyhow::Result<()> {
+        db.set_starred(id, starred, self.clock.now())?;
+        self.news_list.set_starred(id, starred);
+        self.push_state(id);
+        Ok(())
+    }
+
+    /// Store an article's tags and update the list
+    fn set_tags(&mut self, id: &str, tags: Vec<String>, db: &NewsDB) -> anyhow::Result<()> {
+        db.set_tags(id, &tags, self.clock.now())?;
+        self.news_list.set_tags(id, tags);
+        self.show_selected_article();
+        Ok(())
+    }
+
+    /// Revert the most recent edit
+    fn undo(&mut self, db: &NewsDB) {
+        let Some(op) = self.undo_stack.pop() else {
+            self.status_bar
+                .set_message(StatusMessage::info(tr!("status.nothing-to-undo")));
+            return;
+        };
+
+        let description = op.describe_undo();
+        let result = match op {
+            UndoOp::Delete { item, index } => db.undelete(&item).map(|_| {
+                self.news_list.restore(*item, index);
+            }),
+            UndoOp::Archive { id, read, .. } => {
+                db.set_read(&id, read, self.clock.now()).map(|_| {
+                    self.news_list.set_read(&id, read);
+                    self.push_state(&id);
+                })
+            }
+            UndoOp::Star { id, starred, .. } => self.set_starred(&id, starred, db),
+            UndoOp::Tag { id, tags, .. } => self.set_tags(&id, tags, db),
+        };
+        let msg = match result {
+            Ok(()) => {
+                self.show_selected_article();
+                StatusMessage::success(description)
+            }
+            Err(e) => StatusMessage::error(tr!("status.undo-failed", error = e)),
+        };
+        self.status_bar.set_message(msg);
+    }
+
+    /// Once-a-second housekeeping: auto-dismiss, clock updates, script reloads
+    /// and marking viewed articles read.
+    /// Returns true if anything visible changed.
+    pub fn tick(&mut self, db: &NewsDB) -> bool {
+        // The market clock shows minutes, so only a new minute needs a redraw
+        let minute = self.clock.now().timestamp() / 60;
+        let clock_changed = minute != self.last_clock_minute;
+        self.last_clock_minute = minute;
+
+        // Row ages ("5m ago") move on with the clock
+        if clock_changed {
+            self.news_list.advance_now();
+        }
+
+        // Time filters are relative to now, so articles age out of them
+        if clock_changed && self.filter.time_range.is_some() {
+            self.news_list
+                .update(&Action::FilterApplied(self.filter.clone()));
+        }
+
+        let scripts_changed = std::mem::take(&mut self.scripts_changed);
+        if scripts_changed {
+            self.reload_scripts();
+        }
+        let script_errors = self.news_list.take_script_errors();
+        let has_script_errors = !script_errors.is_empty();
+        for error in script_errors {
+            self.status_bar.set_message(StatusMessage::warning(error));
+        }
+
+        let status_changed = self.status_bar.check_auto_dismiss();
+        if status_changed {
+            self.keep_focus_in_ring();
+        }
+
+        let viewed = self.mark_viewed_read(db);
+
+        status_changed || clock_changed || scripts_changed || has_script_errors || viewed
+    }
+
+    /// Whether a refresh is in flight (drives the spinner timer)
+    pub fn is_loading(&self) -> bool {
+        self.refresh.is_active()
+    }
+
+    /// Advance the loading spinner by one frame
+    pub fn tick_spinner(&mut self) {
         self.status_bar.tick_spinner();
-        self.status_bar.check_auto_dismiss();
+    }
+}
+
+/// Diagnostic for a rate-limited source, e.g. "rate-limited, resuming at 14:32"
+fn resuming_at(until: DateTime<Utc>) -> String {
+    tr!(
+        "status.rate-limited",
+        time = until.with_timezone(&Local).format("%H:%M")
+    )
+}
+
+/// Run a share or open command without waiting for it; its output is discarded
+fn spawn_detached(args: &[String]) -> Result<(), String> {
+    let (program, args) = args.split_first().ok_or("Empty command")?;
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    // Reap the process when it exits
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{articles, fixed_now, manual_clock};
+
+    fn press(app: &mut App, db: &NewsDB, code: KeyCode) {
+        let action = app.handle_event(&Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+        app.handle_action(&action, db);
+    }
+
+    fn read_count(app: &App) -> usize {
+        (0..4)
+            .filter_map(|i| app.news_list.item(&format!("u{}", i)))
+            .filter(|item| item.read)
+            .count()
+    }
+
+    #[test]
+    fn only_the_article_moved_to_is_marked_read_when_hiding_read() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let clock = manual_clock();
+        let mut app = App::new(articles(4));
+        app.set_clock(clock.shared());
+        app.set_mark_read_after(Some(Duration::from_secs(3)));
+        press(&mut app, &db, KeyCode::Char('H'));
+
+        // Left alone, the first article isn't marked read
+        clock.advance(chrono::Duration::seconds(10));
+        app.tick(&db);
+        assert_eq!(read_count(&app), 0);
+
+        // Selected, it is; hidden, its successor doesn't start a timer
+        press(&mut app, &db, KeyCode::Down);
+        for _ in 0..5 {
+            clock.advance(chrono::Duration::seconds(3));
+            app.tick(&db);
+        }
+        assert_eq!(read_count(&app), 1);
+        assert!(app.news_list.item("u1").unwrap().read);
+    }
+
+    #[test]
+    fn quit_keys_work_while_typing_unless_they_type() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut app = App::new(articles(1));
+        press(&mut app, &db, KeyCode::Char('/'));
+        let q = Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert!(!matches!(app.handle_event(&q), Action::Quit));
+        let ctrl_c = Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(matches!(app.handle_event(&ctrl_c), Action::Quit));
+    }
+
+    #[test]
+    fn stars_and_tags_repeat_on_the_next_article() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut items = articles(3);
+        items[2].source_kind = SourceKind::Remote;
+        for item in &items {
+            db.insert(item).unwrap();
+        }
+        let mut app = App::new(items);
+        press(&mut app, &db, KeyCode::Char('*'));
+        press(&mut app, &db, KeyCode::Down);
+        press(&mut app, &db, KeyCode::Char('.'));
+        assert!(app.news_list.item("u0").unwrap().starred);
+        assert!(app.news_list.item("u1").unwrap().starred);
+        assert!(app.take_state_pushes().is_empty());
+
+        app.handle_action(&Action::CommandEntered("tag macro".to_string()), &db);
+        press(&mut app, &db, KeyCode::Down);
+        press(&mut app, &db, KeyCode::Char('.'));
+        let u2 = app.news_list.item("u2").unwrap();
+        assert_eq!(u2.tags, ["macro"]);
+        assert!(!u2.starred);
+        // Tagging again, by hand, removes the tag
+        app.handle_action(&Action::CommandEntered("tag macro".to_string()), &db);
+        assert!(app.news_list.item("u2").unwrap().tags.is_empty());
+
+        let stored = db.load_all().unwrap();
+        let stored = |id: &str| stored.iter().find(|item| item.id == id).unwrap().clone();
+        assert!(stored("u1").starred);
+        assert_eq!(stored("u1").tags, ["macro"]);
+        assert!(stored("u2").tags.is_empty());
+
+        // Starring a remote server's article sends it there
+        press(&mut app, &db, KeyCode::Char('*'));
+        let pushed = app.take_state_pushes();
+        assert_eq!(pushed.len(), 1);
+        assert!(pushed[0].id == "u2" && pushed[0].starred);
+    }
+
+    #[test]
+    fn colon_opens_a_command_line() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut app = App::new(articles(1));
+        press(&mut app, &db, KeyCode::Char(':'));
+        for c in "tag x".chars() {
+            press(&mut app, &db, KeyCode::Char(c));
+        }
+        // A command being typed doesn't filter the list
+        assert!(app.news_list.selected_item().is_some());
+        let action = app.handle_event(&Event::Key(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+        )));
+        assert!(matches!(&action, Action::CommandEntered(c) if c == "tag x"));
+        assert_eq!(app.focused_component, TabComponent::NewsList);
+    }
+
+    #[test]
+    fn reports_and_cache_clears_run_from_the_prompt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("story.md");
+        let db = NewsDB::new(":memory:").unwrap();
+        let items = articles(3);
+        for item in &items {
+            db.insert(item).unwrap();
+        }
+        let mut app = App::new(items);
+        app.set_clock(manual_clock().shared());
+
+        let command = format!("report story --days 1 --out {}", path.display());
+        app.handle_action(&Action::CommandEntered(command), &db);
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("# Research report: story"));
+        assert!(text.contains("Story 2"));
+
+        assert!(!app.take_cache_clear());
+        app.handle_action(&Action::CommandEntered("cache".to_string()), &db);
+        assert!(!app.take_cache_clear());
+        app.handle_action(&Action::CommandEntered("cache clear".to_string()), &db);
+        assert!(app.take_cache_clear());
+        assert!(!app.take_cache_clear());
+    }
+
+    #[test]
+    fn undoing_an_archive_restores_the_article() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let items = articles(2);
+        for item in &items {
+            db.insert(item).unwrap();
+        }
+        let mut app = App::new(items);
+        let stored_read = |db: &NewsDB| {
+            db.load_all()
+                .unwrap()
+                .iter()
+                .find(|item| item.id == "u0")
+                .unwrap()
+                .read
+        };
+        press(&mut app, &db, KeyCode::Char('e'));
+        assert!(stored_read(&db));
+
+        press(&mut app, &db, KeyCode::Char('u'));
+        assert!(!stored_read(&db));
+    }
+
+    #[test]
+    fn stars_and_tags_are_undone_newest_first() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let items = articles(1);
+        db.insert(&items[0]).unwrap();
+        let mut app = App::new(items);
+        press(&mut app, &db, KeyCode::Char('*'));
+        app.handle_action(&Action::CommandEntered("tag macro".to_string()), &db);
+        app.handle_action(&Action::CommandEntered("tag rates".to_string()), &db);
+
+        press(&mut app, &db, KeyCode::Char('u'));
+        let u0 = app.news_list.item("u0").unwrap();
+        assert_eq!(u0.tags, ["macro"]);
+        assert!(u0.starred);
+        press(&mut app, &db, KeyCode::Char('u'));
+        press(&mut app, &db, KeyCode::Char('u'));
+        let u0 = app.news_list.item("u0").unwrap();
+        assert!(u0.tags.is_empty());
+        assert!(!u0.starred);
+
+        let stored = db.load_all().unwrap();
+        assert!(stored[0].tags.is_empty() && !stored[0].starred);
+    }
+
+    #[test]
+    fn edits_are_stamped_with_the_app_clock() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let items = articles(2);
+        for item in &items {
+            db.insert(item).unwrap();
+        }
+        let clock = manual_clock();
+        let mut app = App::new(items);
+        app.set_clock(clock.shared());
+        press(&mut app, &db, KeyCode::Char('e'));
+        press(&mut app, &db, KeyCode::Char('*'));
+        app.handle_action(&Action::CommandEntered("tag macro".to_string()), &db);
+        let later = fixed_now() + chrono::Duration::minutes(5);
+        clock.set(later);
+        press(&mut app, &db, KeyCode::Down);
+        press(&mut app, &db, KeyCode::Char('d'));
+
+        let state = db.export_state(later).unwrap();
+        let u0 = state.items.iter().find(|s| s.item.id == "u0").unwrap();
+        assert_eq!(u0.read_at, Some(fixed_now()));
+        assert_eq!(u0.starred_at, Some(fixed_now()));
+        assert_eq!(u0.tags_at, Some(fixed_now()));
+        assert_eq!(state.deleted.len(), 1);
+        assert_eq!(state.deleted[0].deleted_at, later);
+    }
+
+    #[test]
+    fn bulk_open_confirmation_expires_by_the_app_clock() {
+        let clock = manual_clock();
+        let mut app = App::new(articles(3));
+        app.set_clock(clock.shared());
+        app.set_bulk_open(2, Duration::ZERO);
+        let open_all = Event::Key(KeyEvent::new(KeyCode::Char('O'), KeyModifiers::NONE));
+        assert!(matches!(app.handle_event(&open_all), Action::None));
+        clock.advance(chrono::Duration::seconds(4));
+        assert!(matches!(
+            app.handle_event(&open_all),
+            Action::OpenAllRequested
+        ));
+
+        // Confirming too late asks again
+        assert!(matches!(app.handle_event(&open_all), Action::None));
+        clock.advance(chrono::Duration::seconds(6));
+        assert!(matches!(app.handle_event(&open_all), Action::None));
+        assert!(matches!(
+            app.handle_event(&open_all),
+            Action::OpenAllRequested
+        ));
+    }
+
+    #[test]
+    fn article_quotes_are_refetched_once_stale_by_the_app_clock() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let clock = manual_clock();
+        let mut items = articles(1);
+        items[0].tickers = vec!["AAPL".to_string()];
+        let mut app = App::new(items);
+        app.set_clock(clock.shared());
+        app.set_article_quotes(Some(Duration::from_secs(60)));
+        assert_eq!(app.take_quote_request(), Some(vec!["AAPL".to_string()]));
+
+        let quote = Quote {
+            symbol: "AAPL".to_string(),
+            price: 190.0,
+            change: 1.5,
+            change_percent: 0.8,
+            as_of: fixed_now(),
+        };
+        app.handle_message(AppMessage::ArticleQuotes(vec![quote]), &db);
+        clock.advance(chrono::Duration::seconds(30));
+        app.request_article_quotes();
+        assert_eq!(app.take_quote_request(), None);
+
+        clock.advance(chrono::Duration::seconds(30));
+        app.request_article_quotes();
+        assert_eq!(app.take_quote_request(), Some(vec!["AAPL".to_string()]));
     }
 }
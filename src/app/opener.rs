@@ -0,0 +1,92 @@
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashMap};
+
+use super::share::split_args;
+use super::spawn_detached;
+use crate::config::{OpenConfig, SourceConfig};
+
+/// How an article's link is opened
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Opener {
+    /// The system's default browser
+    #[default]
+    Browser,
+    /// A program and its arguments. `{url}` in an argument is replaced by
+    /// the link, which is appended when no argument contains it.
+    Command(Vec<String>),
+}
+
+impl Opener {
+    /// `"default"` (or empty) for the system browser, otherwise a command
+    /// line such as `firefox -P work {url}`
+    pub fn parse(command: &str) -> Result<Self> {
+        let command = command.trim();
+        if command.is_empty() || command == "default" {
+            return Ok(Opener::Browser);
+        }
+        let args = split_args(command)?;
+        if args.first().is_none_or(|program| program.is_empty()) {
+            return Err(anyhow!("Open command '{}' has no program", command));
+        }
+        Ok(Opener::Command(args))
+    }
+
+    pub fn open(&self, url: &str) -> Result<(), String> {
+        match self {
+            Opener::Browser => {
+                open::that(url).map_err(|e| format!("Failed to open browser: {}", e))
+            }
+            Opener::Command(args) => {
+                let mut filled: Vec<String> =
+                    args.iter().map(|arg| arg.replace("{url}", url)).collect();
+                if !args.iter().any(|arg| arg.contains("{url}")) {
+                    filled.push(url.to_string());
+                }
+                spawn_detached(&filled)
+            }
+        }
+    }
+}
+
+/// The opener for each source, falling back to `[open] command`
+#[derive(Debug, Clone, Default)]
+pub struct Openers {
+    default: Opener,
+    by_source: HashMap<String, Opener>,
+}
+
+impl Openers {
+    pub fn for_source(&self, source: &str) -> &Opener {
+        self.by_source.get(source).unwrap_or(&self.default)
+    }
+}
+
+/// Openers from `[open]` and `[sources.<name>] open_command`, collecting
+/// errors for commands that don't parse; those fall back to the default
+pub fn compile_openers(
+    open: &OpenConfig,
+    sources: &BTreeMap<String, SourceConfig>,
+) -> (Openers, Vec<String>) {
+    let mut errors = Vec::new();
+    let default = match open.command.as_deref().map(Opener::parse) {
+        Some(Ok(opener)) => opener,
+        Some(Err(e)) => {
+            errors.push(format!("[open] command: {:#}", e));
+            Opener::Browser
+        }
+        None => Opener::Browser,
+    };
+    let mut by_source = HashMap::new();
+    for (name, source) in sources {
+        let Some(command) = &source.open_command else {
+            continue;
+        };
+        match Opener::parse(command) {
+            Ok(opener) => {
+                by_source.insert(name.clone(), opener);
+            }
+            Err(e) => errors.push(format!("[sources.\"{}\"] open_command: {:#}", name, e)),
+        }
+    }
+    (Openers { default, by_source }, errors)
+}
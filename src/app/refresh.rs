@@ -0,0 +1,261 @@
+use chrono::{DateTime, Utc};
+
+use crate::adaptors::FetchDiagnostic;
+use crate::error::{ErrorCategory, NewsHubError};
+use crate::tr;
+use crate::ui::MessageLevel;
+
+/// Refresh progress of a single source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceState {
+    /// Waiting for a free fetch slot
+    Queued,
+    InFlight,
+    Done,
+    Failed,
+}
+
+impl SourceState {
+    fn is_active(self) -> bool {
+        matches!(self, SourceState::Queued | SourceState::InFlight)
+    }
+}
+
+/// Tracks one refresh batch: which sources are queued, in flight, or finished,
+/// plus the results needed for the end-of-batch summary. Sources may join a
+/// running batch (e.g. a single-source refresh during a full refresh).
+#[derive(Debug, Default)]
+pub struct RefreshTracker {
+    sources: Vec<(String, SourceState)>,
+    pub(super) report: RefreshReport,
+}
+
+/// Outcome of a finished refresh batch, source by source. The status bar
+/// shows [`summary`](Self::summary) and expands into the breakdown.
+#[derive(Debug, Clone, Default)]
+pub struct RefreshReport {
+    /// In the order the sources finished
+    pub sources: Vec<SourceReport>,
+    /// Failures storing fetched items
+    pub db_errors: Vec<String>,
+    pub finished_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceReport {
+    pub source: String,
+    pub success: bool,
+    pub item_count: usize,
+    /// Why the fetch failed, or the adaptor's success note
+    pub message: String,
+    pub warnings: Vec<String>,
+    pub error: Option<NewsHubError>,
+}
+
+impl RefreshReport {
+    pub fn item_count(&self) -> usize {
+        self.sources.iter().map(|s| s.item_count).sum()
+    }
+
+    pub fn success_count(&self) -> usize {
+        self.sources.iter().filter(|s| s.success).count()
+    }
+
+    pub fn failure_count(&self) -> usize {
+        self.sources.len() - self.success_count()
+    }
+
+    /// Sources that failed with an error of `category`
+    pub fn failures(&self, category: ErrorCategory) -> impl Iterator<Item = &SourceReport> {
+        self.sources
+            .iter()
+            .filter(move |s| s.error.as_ref().is_some_and(|e| e.category() == category))
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.sources.iter().map(|s| s.warnings.len()).sum()
+    }
+
+    /// Error if nothing could be fetched, warning if anything went wrong
+    pub fn level(&self) -> MessageLevel {
+        if self.success_count() == 0 {
+            MessageLevel::Error
+        } else if self.failure_count() > 0 || self.warning_count() > 0 || !self.db_errors.is_empty()
+        {
+            MessageLevel::Warning
+        } else {
+            MessageLevel::Success
+        }
+    }
+
+    /// Whether the breakdown says more than the summary
+    pub fn has_details(&self) -> bool {
+        self.level() != MessageLevel::Success
+    }
+
+    /// One line for the status bar, e.g. "Fetched 120 items from 11 sources
+    /// · 1 failed · 3 warnings"
+    pub fn summary(&self) -> String {
+        let failed: Vec<&SourceReport> = self.sources.iter().filter(|s| !s.success).collect();
+        if self.success_count() == 0 {
+            return match failed.as_slice() {
+                [only] => tr!(
+                    "status.fetch-failed",
+                    source = only.source,
+                    message = only.message
+                ),
+                _ => tr!("status.all-failed"),
+            };
+        }
+
+        let mut text = tr!(
+            "status.fetched",
+            items = self.item_count(),
+            sources = self.success_count()
+        );
+        let counts = [
+            (failed.len(), "status.failed", "status.failed"),
+            (self.warning_count(), "status.warning", "status.warnings"),
+            (self.db_errors.len(), "status.db-error", "status.db-errors"),
+        ];
+        for (count, one, many) in counts {
+            if count > 0 {
+                let id = if count == 1 { one } else { many };
+                text.push_str(&format!(" · {}", tr!(id, count = count)));
+                if id == "status.failed" {
+                    text.push_str(&self.failure_breakdown());
+                }
+            }
+        }
+        text
+    }
+
+    /// Failures by category, e.g. " (auth)" or " (2 network, 1 auth)"
+    fn failure_breakdown(&self) -> String {
+        let counts: Vec<(ErrorCategory, usize)> = ErrorCategory::ALL
+            .into_iter()
+            .map(|category| (category, self.failures(category).count()))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        match counts.as_slice() {
+            [] => String::new(),
+            [(category, _)] => format!(" ({})", category.label()),
+            _ => {
+                let parts: Vec<String> = counts
+                    .iter()
+                    .map(|(category, count)| format!("{} {}", count, category.label()))
+                    .collect();
+                format!(" ({})", parts.join(", "))
+            }
+        }
+    }
+}
+
+impl RefreshTracker {
+    /// True while any source is queued or in flight
+    pub fn is_active(&self) -> bool {
+        self.sources.iter().any(|(_, state)| state.is_active())
+    }
+
+    pub fn state_of(&self, source: &str) -> Option<SourceState> {
+        self.sources
+            .iter()
+            .find(|(name, _)| name == source)
+            .map(|(_, state)| *state)
+    }
+
+    /// Queue sources for fetching, skipping any already queued or in flight.
+    /// Starting from idle begins a fresh batch. Returns the newly queued names.
+    pub fn queue(&mut self, names: Vec<String>) -> Vec<String> {
+        if !self.is_active() {
+            *self = Self::default();
+        }
+
+        let mut queued = Vec::new();
+        for name in names {
+            match self.sources.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, state)) if state.is_active() => continue,
+                Some((_, state)) => *state = SourceState::Queued,
+                None => self.sources.push((name.clone(), SourceState::Queued)),
+            }
+            queued.push(name);
+        }
+        queued
+    }
+
+    fn set_state(&mut self, source: &str, new_state: SourceState) {
+        if let Some((_, state)) = self.sources.iter_mut().find(|(n, _)| n == source) {
+            *state = new_state;
+        }
+    }
+
+    pub fn start(&mut self, source: &str) {
+        self.set_state(source, SourceState::InFlight);
+    }
+
+    /// Record a fetch that finished at `now`. Returns true if this completed
+    /// the batch.
+    pub fn finish(
+        &mut self,
+        diagnostic: FetchDiagnostic,
+        item_count: usize,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let state = if diagnostic.success {
+            SourceState::Done
+        } else {
+            SourceState::Failed
+        };
+        self.set_state(&diagnostic.source, state);
+        self.report.sources.push(SourceReport {
+            source: diagnostic.source,
+            success: diagnostic.success,
+            item_count,
+            message: diagnostic.message,
+            warnings: diagnostic.warnings,
+            error: diagnostic.error,
+        });
+        let done = !self.is_active();
+        if done {
+            self.report.finished_at = now;
+        }
+        done
+    }
+
+    /// Number of sources in any of the `wanted` states
+    pub fn count(&self, wanted: &[SourceState]) -> usize {
+        self.sources
+            .iter()
+            .filter(|(_, state)| wanted.contains(state))
+            .count()
+    }
+
+    /// e.g. "Fetching news... 5/12 done · in flight: CNBC, FT · 3 queued"
+    pub fn progress_text(&self) -> String {
+        let finished = self.count(&[SourceState::Done, SourceState::Failed]);
+        let queued = self.count(&[SourceState::Queued]);
+        let in_flight: Vec<&str> = self
+            .sources
+            .iter()
+            .filter(|(_, state)| *state == SourceState::InFlight)
+            .map(|(name, _)| name.as_str())
+            .collect();
+
+        let mut text = tr!(
+            "status.fetching",
+            done = finished,
+            total = self.sources.len()
+        );
+        if !in_flight.is_empty() {
+            let sources = in_flight.join(", ");
+            text.push_str(&format!(
+                " · {}",
+                tr!("status.in-flight", sources = sources)
+            ));
+        }
+        if queued > 0 {
+            text.push_str(&format!(" · {}", tr!("status.queued", count = queued)));
+        }
+        text
+    }
+}
@@ -0,0 +1,384 @@
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::{ShareActionKind, ShareTargetConfig};
+use crate::models::NewsItem;
+use crate::ui::keymap::{Command, KeyBindings};
+
+/// Commands the share menu takes before its entries' keys
+const MENU_COMMANDS: &[Command] = &[
+    Command::Up,
+    Command::Down,
+    Command::ScrollUp,
+    Command::ScrollDown,
+    Command::Select,
+    Command::Dismiss,
+    Command::Quit,
+];
+
+/// What a share target does with an article
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareAction {
+    Copy,
+    Open,
+    Command,
+    Webhook { url: String },
+}
+
+/// An entry in the share menu
+#[derive(Debug, Clone)]
+pub struct ShareTarget {
+    pub name: String,
+    pub key: Option<char>,
+    pub action: ShareAction,
+    template: String,
+}
+
+/// A webhook post for the main loop to send
+#[derive(Debug, Clone)]
+pub struct WebhookRequest {
+    pub target: String,
+    pub url: String,
+    pub text: String,
+}
+
+impl ShareTarget {
+    fn new(name: &str, key: char, action: ShareAction, template: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            key: Some(key),
+            action,
+            template: template.to_string(),
+        }
+    }
+
+    /// Targets available without any config
+    pub fn builtin() -> Vec<ShareTarget> {
+        vec![
+            ShareTarget::new("Copy URL", 'c', ShareAction::Copy, "{url}"),
+            ShareTarget::new(
+                "Copy Markdown link",
+                'm',
+                ShareAction::Copy,
+                "[{title}]({url})",
+            ),
+            ShareTarget::new("Open in browser", 'o', ShareAction::Open, "{url}"),
+            ShareTarget::new(
+                "Open archived copy",
+                'a',
+                ShareAction::Open,
+                "https://web.archive.org/web/{url}",
+            ),
+        ]
+    }
+
+    pub fn from_config(cfg: &ShareTargetConfig, bindings: &KeyBindings) -> Result<Self> {
+        if cfg.name.trim().is_empty() {
+            return Err(anyhow!("Share target has an empty name"));
+        }
+        if let Some((key, command)) = cfg
+            .key
+            .and_then(|key| Some((key, menu_command(key, bindings)?)))
+        {
+            return Err(anyhow!(
+                "Share target '{}': key '{}' is bound to {} in the menu",
+                cfg.name,
+                key,
+                command.name()
+            ));
+        }
+        let action = match cfg.action {
+            ShareActionKind::Copy => ShareAction::Copy,
+            ShareActionKind::Open => ShareAction::Open,
+            ShareActionKind::Command => ShareAction::Command,
+            ShareActionKind::Webhook => {
+                let url = cfg
+                    .url
+                    .clone()
+                    .ok_or_else(|| anyhow!("Share target '{}': webhook needs a url", cfg.name))?;
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(anyhow!(
+                        "Share target '{}': url must start with http:// or https://",
+                        cfg.name
+                    ));
+                }
+                ShareAction::Webhook { url }
+            }
+        };
+        let template = match (&cfg.template, &action) {
+            (Some(template), _) => template.clone(),
+            (None, ShareAction::Webhook { .. }) => "{title} {url}".to_string(),
+            (None, ShareAction::Command) => {
+                return Err(anyhow!(
+                    "Share target '{}': command needs a template",
+                    cfg.name
+                ))
+            }
+            (None, _) => "{url}".to_string(),
+        };
+        if action == ShareAction::Command && split_args(&template)?.is_empty() {
+            return Err(anyhow!("Share target '{}': empty command", cfg.name));
+        }
+
+        Ok(Self {
+            name: cfg.name.clone(),
+            key: cfg.key,
+            action,
+            template,
+        })
+    }
+
+    /// The template filled in for `item`
+    pub fn render(&self, item: &NewsItem) -> String {
+        fill(&self.template, item)
+    }
+
+    /// Program and arguments for a command target. The template is split
+    /// before placeholders are filled, so article text never becomes extra
+    /// arguments or shell syntax.
+    pub fn command(&self, item: &NewsItem) -> Result<Vec<String>> {
+        Ok(split_args(&self.template)?
+            .iter()
+            .map(|arg| fill(arg, item))
+            .collect())
+    }
+}
+
+/// Built-in targets followed by configured ones, collecting errors for invalid
+/// ones. Built-in targets whose key `bindings` gives to the menu lose the key.
+pub fn compile_share_targets(
+    configs: &[ShareTargetConfig],
+    bindings: &KeyBindings,
+) -> (Vec<ShareTarget>, Vec<String>) {
+    let mut targets = ShareTarget::builtin();
+    for target in &mut targets {
+        target.key = target.key.filter(|k| menu_command(*k, bindings).is_none());
+    }
+    let mut errors = Vec::new();
+    for cfg in configs {
+        match ShareTarget::from_config(cfg, bindings) {
+            Ok(target) => targets.push(target),
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+    (targets, errors)
+}
+
+/// The menu command `key` is bound to, if any
+fn menu_command(key: char, bindings: &KeyBindings) -> Option<Command> {
+    bindings
+        .commands(&KeyEvent::new(KeyCode::Char(key), KeyModifiers::NONE))
+        .into_iter()
+        .find(|command| MENU_COMMANDS.contains(command))
+}
+
+/// Fill `{placeholder}`s in one pass, so article text is never re-expanded
+fn fill(template: &str, item: &NewsItem) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let value = tail.find('}').and_then(|end| {
+            let value = match &tail[1..end] {
+                "title" => item.title.clone(),
+                "url" => item.url.clone(),
+                "source" => item.source.clone(),
+                "summary" => item.summary.clone(),
+                "title_encoded" => url_encode(&item.title),
+                "url_encoded" => url_encode(&item.url),
+                _ => return None,
+            };
+            Some((value, end + 1))
+        });
+        match value {
+            Some((value, len)) => {
+                out.push_str(&value);
+                rest = &tail[len..];
+            }
+            // Not a placeholder; keep the brace
+            None => {
+                out.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Split a command line on whitespace, honouring single and double quotes.
+/// Backslashes are kept as written, so Windows paths need no escaping; a
+/// quote goes inside the other kind of quotes.
+pub(super) fn split_args(line: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err(anyhow!("Unterminated quote in command '{}'", line));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::keymap::compile_bindings;
+    use std::collections::BTreeMap;
+
+    fn args(line: &str) -> Vec<String> {
+        split_args(line).unwrap()
+    }
+
+    fn item(title: &str) -> NewsItem {
+        NewsItem {
+            title: title.to_string(),
+            url: "https://example.com/a?b=1&c=2".to_string(),
+            source: "CNBC".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn command_target(template: &str) -> ShareTargetConfig {
+        ShareTargetConfig {
+            name: "Notes".to_string(),
+            key: Some('n'),
+            action: ShareActionKind::Command,
+            template: Some(template.to_string()),
+            url: None,
+        }
+    }
+
+    #[test]
+    fn splits_on_whitespace_outside_quotes() {
+        assert_eq!(args("notes  add\t-x"), ["notes", "add", "-x"]);
+        assert_eq!(args(r#"notes "a b" 'c d'"#), ["notes", "a b", "c d"]);
+        // Quotes join onto the text around them and hold the other kind
+        assert_eq!(args(r#"--tag="x y"z"#), ["--tag=x yz"]);
+        assert_eq!(args(r#"say "it's" 'a "b"'"#), ["say", "it's", r#"a "b""#]);
+        assert!(args("   ").is_empty());
+    }
+
+    #[test]
+    fn keeps_empty_quoted_arguments() {
+        assert_eq!(args(r#"notes "" ''"#), ["notes", "", ""]);
+    }
+
+    #[test]
+    fn keeps_backslashes_as_written() {
+        assert_eq!(
+            args(r#"C:\Tools\notes.exe a\ b "c\""#),
+            [r"C:\Tools\notes.exe", r"a\", "b", r"c\"]
+        );
+    }
+
+    #[test]
+    fn rejects_unterminated_quotes() {
+        assert!(split_args(r#"notes "add"#).is_err());
+        assert!(split_args("notes 'add").is_err());
+        assert!(split_args(r#"notes "it's"#).is_err());
+    }
+
+    #[test]
+    fn fills_placeholders_once() {
+        let item = item("{url} & {title}");
+        assert_eq!(
+            fill("[{title}]({url})", &item),
+            "[{url} & {title}](https://example.com/a?b=1&c=2)"
+        );
+        assert_eq!(
+            fill("{url_encoded}", &item),
+            "https%3A%2F%2Fexample.com%2Fa%3Fb%3D1%26c%3D2"
+        );
+        // Anything else in braces stays as written
+        assert_eq!(fill("{nope} {source} {", &item), "{nope} CNBC {");
+    }
+
+    #[test]
+    fn article_text_stays_one_argument() {
+        let target = ShareTarget::from_config(
+            &command_target("notes add {title} '{source}: {url}'"),
+            &KeyBindings::default(),
+        )
+        .unwrap();
+        let title = "Rates rise; rm -rf ~ $(reboot) `id` | tee \"x\" 'y' > z";
+        assert_eq!(
+            target.command(&item(title)).unwrap(),
+            ["notes", "add", title, "CNBC: https://example.com/a?b=1&c=2"]
+        );
+        assert_eq!(target.command(&item("")).unwrap()[2], "");
+    }
+
+    #[test]
+    fn rejects_empty_and_unterminated_commands() {
+        let bindings = KeyBindings::default();
+        assert!(ShareTarget::from_config(&command_target("  "), &bindings).is_err());
+        assert!(ShareTarget::from_config(&command_target("notes '{title}"), &bindings).is_err());
+    }
+
+    #[test]
+    fn keys_the_menu_is_bound_to_are_refused() {
+        let error = ShareTarget::from_config(
+            &ShareTargetConfig {
+                key: Some('j'),
+                ..command_target("notes")
+            },
+            &KeyBindings::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Share target 'Notes': key 'j' is bound to scroll_down in the menu"
+        );
+
+        // Once quit moves off q, q is free; a built-in on the new key loses it
+        let mut keys = BTreeMap::new();
+        keys.insert("quit".to_string(), vec!["c".to_string()]);
+        let (bindings, _) = compile_bindings(&keys);
+        let q = ShareTargetConfig {
+            key: Some('q'),
+            ..command_target("notes")
+        };
+        let (targets, errors) = compile_share_targets(&[q], &bindings);
+        assert!(errors.is_empty());
+        assert_eq!(targets[0].key, None);
+        assert_eq!(targets.last().unwrap().key, Some('q'));
+    }
+}
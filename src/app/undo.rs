@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+
+use crate::models::NewsItem;
+use crate::tr;
+
+/// Maximum number of operations kept for undo
+const UNDO_CAPACITY: usize = 50;
+
+/// An edit with enough state to revert it: a delete, archive or restore,
+/// star or tag. Articles marked read by opening or viewing them can't be
+/// undone; restore them with the archive key instead.
+#[derive(Debug, Clone)]
+pub enum UndoOp {
+    /// Article removed from the database; `index` is its position in the list
+    Delete { item: Box<NewsItem>, index: usize },
+    /// Article archived or restored; `read` is its state before
+    Archive {
+        id: String,
+        title: String,
+        read: bool,
+    },
+    /// Article starred or unstarred; `starred` is its state before
+    Star {
+        id: String,
+        title: String,
+        starred: bool,
+    },
+    /// A tag added or removed; `tags` are the article's tags before
+    Tag {
+        id: String,
+        title: String,
+        tags: Vec<String>,
+    },
+}
+
+impl UndoOp {
+    /// Status text shown after the operation is reverted
+    pub fn describe_undo(&self) -> String {
+        match self {
+            UndoOp::Delete { item, .. } => tr!("status.undid-delete", title = item.title),
+            UndoOp::Archive {
+                title, read: true, ..
+            } => {
+                tr!("status.undid-restore", title = title)
+            }
+            UndoOp::Archive { title, .. } => tr!("status.undid-archive", title = title),
+            UndoOp::Star {
+                title,
+                starred: true,
+                ..
+            } => tr!("status.undid-unstar", title = title),
+            UndoOp::Star { title, .. } => tr!("status.undid-star", title = title),
+            UndoOp::Tag { title, .. } => tr!("status.undid-tag", title = title),
+        }
+    }
+}
+
+/// Bounded stack of reversible operations, newest last
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    ops: VecDeque<UndoOp>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, op: UndoOp) {
+        self.ops.push_back(op);
+        if self.ops.len() > UNDO_CAPACITY {
+            self.ops.pop_front();
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<UndoOp> {
+        self.ops.pop_back()
+    }
+}
@@ -0,0 +1,827 @@
+use std::fmt;
+use std::path::Path;
+use toml::de::{DeTable, DeValue};
+use toml::Spanned;
+
+use super::{RemoteKind, TomlConfig, MIN_TAPE_INTERVAL_SECS};
+use crate::adaptors::{
+    build_quote_adaptor, find_bundle, is_builtin_feed, Rewrite, BUNDLES, MIN_REFRESH_INTERVAL,
+};
+#[cfg(feature = "tui")]
+use crate::app::{Opener, ShareTarget};
+use crate::i18n;
+use crate::market::Exchange;
+#[cfg(feature = "tui")]
+use crate::ui::columns::Column;
+#[cfg(feature = "tui")]
+use crate::ui::highlight::HighlightRule;
+#[cfg(feature = "tui")]
+use crate::ui::keymap::compile_bindings;
+
+/// Known keys for each `[section]`
+const SECTION_KEYS: &[(&str, &[&str])] = &[
+    (
+        "app",
+        &[
+            "deduplication_enabled",
+            "similarity_threshold",
+            "inbox_zero",
+            "time_format",
+            "fetch_on_start",
+            "stale_after_mins",
+            "very_stale_after_mins",
+            "reliability_weighting",
+            "bulk_open_confirm_above",
+            "bulk_open_delay_ms",
+            "bundles",
+            "follow_database",
+            "screen_reader",
+            "locale",
+            "mouse",
+        ],
+    ),
+    ("market", &["exchanges", "custom"]),
+    ("quotes", &["provider", "article_quotes", "cache_ttl_secs"]),
+    (
+        "ticker_tape",
+        &["enabled", "symbols", "refresh_interval_secs"],
+    ),
+    ("scripts", &["enabled", "dir"]),
+    ("list", &["columns", "density"]),
+    (
+        "status",
+        &[
+            "history_size",
+            "history_height",
+            "info_secs",
+            "success_secs",
+            "warning_secs",
+            "error_secs",
+            "persist_history",
+        ],
+    ),
+    ("cache", &["dir", "max_mb", "ttl_days"]),
+    (
+        "crawl",
+        &[
+            "respect_robots",
+            "max_concurrent",
+            "max_per_host",
+            "min_delay_ms",
+            "max_delay_ms",
+            "exempt_hosts",
+        ],
+    ),
+    ("open", &["command", "mark_read", "mark_read_after_secs"]),
+    ("watchlist", &["symbols"]),
+    ("fred", &["series", "days_ahead", "days_back"]),
+    ("transcripts", &["max_age_days"]),
+    (
+        "reddit",
+        &["subreddits", "limit", "user_agent", "redirect_uri"],
+    ),
+    ("bridge", &["rsshub", "nitter", "routes"]),
+    (
+        "refresh",
+        &["adaptive", "min_interval_secs", "max_interval_secs"],
+    ),
+    ("sync", &["dir", "machine"]),
+    ("alerts", &["keywords"]),
+];
+const HIGHLIGHT_KEYS: &[&str] = &["keyword", "color", "bold", "whole_word"];
+const REMOTE_KEYS: &[&str] = &["kind", "name", "url", "username", "password_env", "limit"];
+const FEED_KEYS: &[&str] = &[
+    "kind",
+    "name",
+    "author",
+    "publication",
+    "url",
+    "account",
+    "route",
+];
+const WATCH_KEYS: &[&str] = &["url", "name", "min_words"];
+const JSON_KEYS: &[&str] = &[
+    "name",
+    "url",
+    "query",
+    "items",
+    "fields",
+    "auth_header",
+    "auth_env",
+];
+const JSON_FIELD_KEYS: &[&str] = &["title", "url", "date", "summary", "id", "author"];
+const SHARE_KEYS: &[&str] = &["name", "key", "action", "template", "url"];
+const COLUMN_KEYS: &[&str] = &["name", "width"];
+const SOURCE_KEYS: &[&str] = &[
+    "enabled",
+    "url",
+    "timeout_secs",
+    "interval_secs",
+    "adaptive",
+    "tags",
+    "item_tags",
+    "group",
+    "priority",
+    "color",
+    "open_command",
+    "max_items",
+    "max_age_hours",
+    "title_dedup_mins",
+    "strip_prefixes",
+    "title_rewrites",
+    "url_rewrites",
+];
+const REWRITE_KEYS: &[&str] = &["pattern", "replace"];
+const EXCHANGE_KEYS: &[&str] = &[
+    "code",
+    "timezone",
+    "open",
+    "close",
+    "pre_open",
+    "post_close",
+];
+/// Sources registered from credentials rather than the built-in feed list
+const API_SOURCES: &[&str] = &[
+    "Benzinga",
+    "Yahoo Finance",
+    "FRED",
+    "Earnings Calls",
+    "Reddit",
+];
+/// Sections documented in config.toml.example for adaptors that don't read them yet
+const RESERVED_SECTIONS: &[&str] = &["adapters"];
+
+/// A problem found in config.toml
+#[derive(Debug, Clone)]
+pub struct ConfigIssue {
+    /// 1-based line the problem refers to
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Strictly validate config.toml contents: syntax, unknown keys, value types,
+/// and values the app would reject or silently adjust. Issues are in file order.
+pub fn check_config(contents: &str) -> Vec<ConfigIssue> {
+    let mut checker = Checker {
+        contents,
+        issues: Vec::new(),
+    };
+
+    let (doc, errors) = DeTable::parse_recoverable(contents);
+    if !errors.is_empty() {
+        for e in errors {
+            let line = e.span().map_or(1, |span| checker.line_at(span.start));
+            checker.push(line, e.message().to_string());
+        }
+        // Keys and values can't be trusted past a syntax error
+        return checker.finish();
+    }
+    let doc = doc.into_inner();
+    // Types come from deserializing the real config; only the first mismatch is reported
+    let config = toml::from_str::<TomlConfig>(contents);
+    // `[[feed]]` names are derived, so they come from the parsed config
+    let feed_names: Vec<String> = config.as_ref().map_or(Vec::new(), |config| {
+        config
+            .feed
+            .iter()
+            .filter_map(|feed| feed.resolve(&config.bridge).ok().map(|(_, name)| name))
+            .collect()
+    });
+    let watch_names: Vec<String> = config.as_ref().map_or(Vec::new(), |config| {
+        config.watch.iter().map(|page| page.name()).collect()
+    });
+    checker.check_keys(&doc, &feed_names, &watch_names);
+
+    match config {
+        Ok(config) => checker.check_values(&doc, &config),
+        Err(e) => {
+            let line = e.span().map_or(1, |span| checker.line_at(span.start));
+            checker.push(line, e.message().trim().to_string());
+        }
+    }
+    checker.finish()
+}
+
+struct Checker<'a> {
+    contents: &'a str,
+    issues: Vec<ConfigIssue>,
+}
+
+impl Checker<'_> {
+    fn line_at(&self, offset: usize) -> usize {
+        let offset = offset.min(self.contents.len());
+        self.contents[..offset].matches('\n').count() + 1
+    }
+
+    fn line_of<T>(&self, spanned: &Spanned<T>) -> usize {
+        self.line_at(spanned.span().start)
+    }
+
+    fn push(&mut self, line: usize, message: String) {
+        self.issues.push(ConfigIssue { line, message });
+    }
+
+    fn finish(mut self) -> Vec<ConfigIssue> {
+        self.issues.sort_by_key(|issue| issue.line);
+        self.issues
+    }
+
+    fn check_keys(&mut self, doc: &DeTable, feed_names: &[String], watch_names: &[String]) {
+        // `[[remote]]` servers, `[[feed]]`, `[[watch]]` and `[[json]]` entries are sources too
+        let mut source_names: Vec<String> = doc
+            .get("remote")
+            .map(array_tables)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| {
+                let table = entry.get_ref().as_table()?;
+                let name = table.get("name").or_else(|| table.get("kind"))?;
+                let name = name.get_ref().as_str()?;
+                let kind = RemoteKind::ALL.into_iter().find(|k| k.as_str() == name);
+                Some(kind.map_or(name, |k| k.display_name()).to_string())
+            })
+            .collect();
+        source_names.extend(feed_names.iter().cloned());
+        source_names.extend(watch_names.iter().cloned());
+        source_names.extend(
+            doc.get("json")
+                .map(array_tables)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|entry| {
+                    let name = entry.get_ref().as_table()?.get("name")?;
+                    Some(name.get_ref().as_str()?.to_string())
+                }),
+        );
+
+        for (key, value) in doc {
+            let name = key.get_ref().as_ref();
+            if RESERVED_SECTIONS.contains(&name) {
+                continue;
+            }
+            if name == "highlight" {
+                self.check_table_array(value, "highlight", HIGHLIGHT_KEYS);
+                continue;
+            }
+            if name == "sources" {
+                self.check_sources(value, &source_names);
+                continue;
+            }
+            if name == "remote" {
+                self.check_table_array(value, "remote", REMOTE_KEYS);
+                continue;
+            }
+            if name == "feed" {
+                self.check_table_array(value, "feed", FEED_KEYS);
+                continue;
+            }
+            if name == "watch" {
+                self.check_table_array(value, "watch", WATCH_KEYS);
+                continue;
+            }
+            if name == "json" {
+                self.check_table_array(value, "json", JSON_KEYS);
+                for entry in array_tables(value) {
+                    let fields = entry.get_ref().as_table().and_then(|t| t.get("fields"));
+                    if let Some(table) = fields.and_then(|f| f.get_ref().as_table()) {
+                        self.check_table(table, "json.fields", JSON_FIELD_KEYS);
+                    }
+                }
+                continue;
+            }
+            if name == "share" {
+                self.check_table_array(value, "share", SHARE_KEYS);
+                continue;
+            }
+            // Command names belong to the UI, which checks them with the keys
+            if name == "keys" {
+                continue;
+            }
+            match SECTION_KEYS.iter().find(|(section, _)| *section == name) {
+                Some((_, keys)) => {
+                    if let Some(table) = value.get_ref().as_table() {
+                        self.check_table(table, name, keys);
+                        if name == "market" {
+                            if let Some(custom) = table.get("custom") {
+                                self.check_table_array(custom, "market.custom", EXCHANGE_KEYS);
+                            }
+                        }
+                        if name == "list" {
+                            if let Some(columns) = table.get("columns") {
+                                self.check_table_array(columns, "list.columns", COLUMN_KEYS);
+                            }
+                        }
+                    }
+                }
+                None => self.push(self.line_of(key), format!("unknown section `{}`", name)),
+            }
+        }
+    }
+
+    fn check_table(&mut self, table: &DeTable, path: &str, known: &[&str]) {
+        for key in table.keys() {
+            let name = key.get_ref().as_ref();
+            if !known.contains(&name) {
+                let message = match suggest(name, known) {
+                    Some(hint) => {
+                        format!("unknown key `{}.{}` (did you mean `{}`?)", path, name, hint)
+                    }
+                    None => format!("unknown key `{}.{}`", path, name),
+                };
+                self.push(self.line_of(key), message);
+            }
+        }
+    }
+
+    /// `[sources.<name>]`: names must match a known source, keys must be known
+    fn check_sources(&mut self, value: &Spanned<DeValue>, source_names: &[String]) {
+        let Some(sources) = value.get_ref().as_table() else {
+            return;
+        };
+        for (key, value) in sources {
+            let name = key.get_ref().as_ref();
+            let is_known = is_builtin_feed(name)
+                || API_SOURCES.contains(&name)
+                || source_names.iter().any(|source| source == name);
+            if !is_known {
+                self.push(self.line_of(key), format!("unknown source `{}`", name));
+            }
+            if let Some(table) = value.get_ref().as_table() {
+                self.check_table(table, &format!("sources.{}", name), SOURCE_KEYS);
+                for key in ["title_rewrites", "url_rewrites"] {
+                    if let Some(rewrites) = table.get(key) {
+                        let path = format!("sources.{}.{}", name, key);
+                        self.check_table_array(rewrites, &path, REWRITE_KEYS);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_table_array(&mut self, value: &Spanned<DeValue>, path: &str, known: &[&str]) {
+        for entry in array_tables(value) {
+            if let Some(table) = entry.get_ref().as_table() {
+                self.check_table(table, path, known);
+            }
+        }
+    }
+
+    /// Semantic checks, reported at the line of the offending value
+    fn check_values(&mut self, doc: &DeTable, config: &TomlConfig) {
+        let line_of_key = |checker: &Self, section: &str, key: &str| {
+            let section = doc.get(section);
+            section
+                .and_then(|s| s.get_ref().as_table())
+                .and_then(|t| t.get(key))
+                .or(section)
+                .map_or(1, |v| checker.line_of(v))
+        };
+
+        if !(0.0..=1.0).contains(&config.app.similarity_threshold) {
+            let line = line_of_key(self, "app", "similarity_threshold");
+            self.push(
+                line,
+                "`app.similarity_threshold` must be between 0 and 1".to_string(),
+            );
+        }
+        if !i18n::is_available(&config.app.locale) {
+            let line = line_of_key(self, "app", "locale");
+            let known: Vec<&str> = i18n::LOCALES.iter().map(|(code, _, _)| *code).collect();
+            self.push(
+                line,
+                format!(
+                    "unknown locale '{}' (available: {})",
+                    config.app.locale,
+                    known.join(", ")
+                ),
+            );
+        }
+
+        if let Err(e) = build_quote_adaptor(&config.quotes.provider) {
+            let line = line_of_key(self, "quotes", "provider");
+            self.push(line, format!("{:#}", e));
+        }
+        if config.quotes.cache_ttl_secs == 0 {
+            let line = line_of_key(self, "quotes", "cache_ttl_secs");
+            self.push(
+                line,
+                "`quotes.cache_ttl_secs` must be at least 1".to_string(),
+            );
+        }
+
+        if config.app.very_stale_after_mins < config.app.stale_after_mins {
+            let line = line_of_key(self, "app", "very_stale_after_mins");
+            self.push(
+                line,
+                "`app.very_stale_after_mins` is below `app.stale_after_mins` and is raised to it"
+                    .to_string(),
+            );
+        }
+
+        for name in &config.app.bundles {
+            if find_bundle(name).is_none() {
+                let known: Vec<&str> = BUNDLES.iter().map(|b| b.name).collect();
+                let line = line_of_key(self, "app", "bundles");
+                self.push(
+                    line,
+                    format!(
+                        "unknown bundle `{}` in `app.bundles` (available: {})",
+                        name,
+                        known.join(", ")
+                    ),
+                );
+            }
+        }
+
+        for symbol in &config.watchlist.symbols {
+            if symbol.trim().is_empty() || symbol.contains(char::is_whitespace) {
+                let line = line_of_key(self, "watchlist", "symbols");
+                self.push(
+                    line,
+                    format!("invalid symbol `{}` in `watchlist.symbols`", symbol),
+                );
+            }
+        }
+
+        for (key, value) in [
+            ("max_mb", config.cache.max_mb),
+            ("ttl_days", config.cache.ttl_days),
+        ] {
+            if value == 0 {
+                let line = line_of_key(self, "cache", key);
+                self.push(line, format!("`cache.{}` must be at least 1", key));
+            }
+        }
+
+        for (key, value) in [
+            ("max_concurrent", config.crawl.max_concurrent),
+            ("max_per_host", config.crawl.max_per_host),
+        ] {
+            if value == 0 {
+                let line = line_of_key(self, "crawl", key);
+                self.push(line, format!("`crawl.{}` must be at least 1", key));
+            }
+        }
+        if config.crawl.max_delay_ms < config.crawl.min_delay_ms {
+            let line = line_of_key(self, "crawl", "max_delay_ms");
+            self.push(
+                line,
+                "`crawl.max_delay_ms` is below `crawl.min_delay_ms` and is raised to it"
+                    .to_string(),
+            );
+        }
+
+        if config.refresh.max_interval_secs < config.refresh.min_interval_secs {
+            let line = line_of_key(self, "refresh", "max_interval_secs");
+            self.push(
+                line,
+                "`refresh.max_interval_secs` is below `refresh.min_interval_secs` and is raised to it"
+                    .to_string(),
+            );
+        }
+
+        if config.status.history_size == 0 {
+            let line = line_of_key(self, "status", "history_size");
+            self.push(line, "`status.history_size` must be at least 1".to_string());
+        }
+        if config.status.history_height < 3 {
+            let line = line_of_key(self, "status", "history_height");
+            self.push(
+                line,
+                "`status.history_height` must be at least 3".to_string(),
+            );
+        }
+
+        if config.ticker_tape.refresh_interval_secs < MIN_TAPE_INTERVAL_SECS {
+            let line = line_of_key(self, "ticker_tape", "refresh_interval_secs");
+            self.push(
+                line,
+                format!(
+                    "`ticker_tape.refresh_interval_secs` below {} is raised to {}",
+                    MIN_TAPE_INTERVAL_SECS, MIN_TAPE_INTERVAL_SECS
+                ),
+            );
+        }
+        if config.ticker_tape.enabled && config.ticker_tape.symbols.is_empty() {
+            let line = line_of_key(self, "ticker_tape", "symbols");
+            self.push(
+                line,
+                "ticker tape is enabled but has no symbols".to_string(),
+            );
+        }
+
+        let market = doc.get("market").and_then(|m| m.get_ref().as_table());
+        let custom_entries: Vec<_> = market
+            .and_then(|m| m.get("custom"))
+            .map(array_tables)
+            .unwrap_or_default();
+        for (cfg, entry) in config.market.custom.iter().zip(&custom_entries) {
+            if let Err(e) = Exchange::from_config(cfg) {
+                self.push(self.line_of(entry), format!("{:#}", e));
+            }
+        }
+        let exchange_values: Vec<&Spanned<DeValue>> = market
+            .and_then(|m| m.get("exchanges"))
+            .and_then(|v| v.get_ref().as_array())
+            .map(|a| a.iter().collect())
+            .unwrap_or_default();
+        for (code, value) in config.market.exchanges.iter().zip(exchange_values) {
+            let is_custom = config
+                .market
+                .custom
+                .iter()
+                .any(|c| c.code.eq_ignore_ascii_case(code));
+            if !is_custom && Exchange::builtin(code).is_none() {
+                self.push(self.line_of(value), format!("unknown exchange '{}'", code));
+            }
+        }
+
+        // Colors are parsed by the UI, so highlight rules are only checked with it
+        #[cfg(feature = "tui")]
+        {
+            let highlight_entries: Vec<_> =
+                doc.get("highlight").map(array_tables).unwrap_or_default();
+            for (cfg, entry) in config.highlight.iter().zip(&highlight_entries) {
+                if let Err(e) = HighlightRule::from_config(cfg) {
+                    self.push(self.line_of(entry), e.to_string());
+                }
+            }
+        }
+
+        let sources = doc.get("sources").and_then(|s| s.get_ref().as_table());
+        for (name, source) in &config.sources {
+            let table = sources
+                .and_then(|s| s.get(name.as_str()))
+                .and_then(|s| s.get_ref().as_table());
+            let line_of = |checker: &Self, key: &str| {
+                table
+                    .and_then(|t| t.get(key))
+                    .map_or(1, |v| checker.line_of(v))
+            };
+            if let Some(url) = &source.url {
+                if !is_builtin_feed(name) {
+                    let line = line_of(self, "url");
+                    self.push(
+                        line,
+                        format!("`sources.{}.url` only applies to built-in RSS feeds", name),
+                    );
+                } else if !url.starts_with("http://") && !url.starts_with("https://") {
+                    let line = line_of(self, "url");
+                    self.push(
+                        line,
+                        format!("`sources.{}.url` must start with http:// or https://", name),
+                    );
+                }
+            }
+            #[cfg(feature = "tui")]
+            if let Some(Err(e)) = source.open_command.as_deref().map(Opener::parse) {
+                let line = line_of(self, "open_command");
+                self.push(line, format!("`sources.{}.open_command`: {:#}", name, e));
+            }
+            if source.timeout_secs == Some(0) {
+                let line = line_of(self, "timeout_secs");
+                self.push(
+                    line,
+                    format!("`sources.{}.timeout_secs` must be at least 1", name),
+                );
+            }
+            for (key, value) in [
+                ("max_items", source.max_items.map(|n| n as u64)),
+                ("max_age_hours", source.max_age_hours),
+                ("title_dedup_mins", source.title_dedup_mins),
+            ] {
+                if value == Some(0) {
+                    let line = line_of(self, key);
+                    self.push(
+                        line,
+                        format!("`sources.{}.{}` must be at least 1", name, key),
+                    );
+                }
+            }
+            if let Some(secs) = source.interval_secs {
+                if secs < MIN_REFRESH_INTERVAL.as_secs() {
+                    let line = line_of(self, "interval_secs");
+                    self.push(
+                        line,
+                        format!(
+                            "`sources.{}.interval_secs` below {} is raised to {}",
+                            name,
+                            MIN_REFRESH_INTERVAL.as_secs(),
+                            MIN_REFRESH_INTERVAL.as_secs()
+                        ),
+                    );
+                }
+            }
+            for (key, rewrites) in [
+                ("title_rewrites", &source.title_rewrites),
+                ("url_rewrites", &source.url_rewrites),
+            ] {
+                let entries: Vec<_> = table
+                    .and_then(|t| t.get(key))
+                    .map(array_tables)
+                    .unwrap_or_default();
+                for (cfg, entry) in rewrites.iter().zip(&entries) {
+                    if let Err(e) = Rewrite::from_config(cfg) {
+                        self.push(
+                            self.line_of(*entry),
+                            format!("`sources.{}.{}`: {:#}", name, key, e),
+                        );
+                    }
+                }
+            }
+            #[cfg(feature = "tui")]
+            if let Some(color) = &source.color {
+                if color.parse::<ratatui::style::Color>().is_err() {
+                    let line = line_of(self, "color");
+                    self.push(
+                        line,
+                        format!("`sources.{}.color`: unknown color '{}'", name, color),
+                    );
+                }
+            }
+        }
+
+        let remote_entries: Vec<_> = doc.get("remote").map(array_tables).unwrap_or_default();
+        for (remote, entry) in config.remote.iter().zip(&remote_entries) {
+            let line = self.line_of(entry);
+            if !remote.url.starts_with("http://") && !remote.url.starts_with("https://") {
+                self.push(
+                    line,
+                    format!(
+                        "`remote` {}: url must start with http:// or https://",
+                        remote.name()
+                    ),
+                );
+            }
+            if std::env::var(remote.password_env()).map_or(true, |p| p.is_empty()) {
+                self.push(
+                    line,
+                    format!(
+                        "`remote` {}: {} is not set; the server is skipped",
+                        remote.name(),
+                        remote.password_env()
+                    ),
+                );
+            }
+        }
+
+        for (name, path) in &config.bridge.routes {
+            if !path.starts_with('/') {
+                let line = line_of_key(self, "bridge", "routes");
+                self.push(
+                    line,
+                    format!("`bridge.routes.{}` must be a path starting with /", name),
+                );
+            }
+        }
+
+        let feed_entries: Vec<_> = doc.get("feed").map(array_tables).unwrap_or_default();
+        for (feed, entry) in config.feed.iter().zip(&feed_entries) {
+            if let Err(e) = feed.resolve(&config.bridge) {
+                self.push(self.line_of(entry), format!("`feed`: {:#}", e));
+            }
+        }
+
+        let watch_entries: Vec<_> = doc.get("watch").map(array_tables).unwrap_or_default();
+        for (page, entry) in config.watch.iter().zip(&watch_entries) {
+            if !page.url.starts_with("http://") && !page.url.starts_with("https://") {
+                self.push(
+                    self.line_of(entry),
+                    format!(
+                        "`watch` {}: url must start with http:// or https://",
+                        page.name()
+                    ),
+                );
+            }
+            if page.min_words == 0 {
+                self.push(
+                    self.line_of(entry),
+                    format!("`watch` {}: min_words must be at least 1", page.name()),
+                );
+            }
+        }
+
+        let json_entries: Vec<_> = doc.get("json").map(array_tables).unwrap_or_default();
+        for (source, entry) in config.json.iter().zip(&json_entries) {
+            let line = self.line_of(entry);
+            if !source.url.starts_with("http://") && !source.url.starts_with("https://") {
+                self.push(
+                    line,
+                    format!(
+                        "`json` {}: url must start with http:// or https://",
+                        source.name
+                    ),
+                );
+            }
+            if let Some(var) = &source.auth_env {
+                if std::env::var(var).map_or(true, |v| v.is_empty()) {
+                    self.push(
+                        line,
+                        format!(
+                            "`json` {}: {} is not set; the source is skipped",
+                            source.name, var
+                        ),
+                    );
+                }
+            }
+        }
+
+        // Share targets and list columns are built by the UI, so they are only checked with it
+        #[cfg(feature = "tui")]
+        {
+            if let Some(Err(e)) = config.open.command.as_deref().map(Opener::parse) {
+                let line = line_of_key(self, "open", "command");
+                self.push(line, format!("`open.command`: {:#}", e));
+            }
+
+            // Entries compile in name order, so a new error belongs to the one just added
+            let mut keys = std::collections::BTreeMap::new();
+            let mut reported = 0;
+            for (name, chords) in &config.keys {
+                keys.insert(name.clone(), chords.clone());
+                let (_, errors) = compile_bindings(&keys);
+                if let Some(error) = errors.get(reported) {
+                    let line = line_of_key(self, "keys", name);
+                    self.push(line, error.clone());
+                    reported = errors.len();
+                }
+            }
+
+            let (bindings, _) = compile_bindings(&config.keys);
+            let share_entries: Vec<_> = doc.get("share").map(array_tables).unwrap_or_default();
+            for (cfg, entry) in config.share.iter().zip(&share_entries) {
+                if let Err(e) = ShareTarget::from_config(cfg, &bindings) {
+                    self.push(self.line_of(entry), e.to_string());
+                }
+            }
+
+            let column_entries: Vec<_> = doc
+                .get("list")
+                .and_then(|l| l.get_ref().as_table())
+                .and_then(|l| l.get("columns"))
+                .map(array_tables)
+                .unwrap_or_default();
+            for (i, (cfg, entry)) in config.list.columns.iter().zip(&column_entries).enumerate() {
+                let repeated = config.list.columns[..i].iter().any(|c| c.name == cfg.name);
+                if repeated {
+                    self.push(
+                        self.line_of(entry),
+                        format!(
+                            "List column '{}' is listed more than once",
+                            cfg.name.as_str()
+                        ),
+                    );
+                } else if let Err(e) = Column::from_config(cfg) {
+                    self.push(self.line_of(entry), e.to_string());
+                }
+            }
+        }
+
+        let dir = Path::new(&config.scripts.dir);
+        if config.scripts.enabled && dir.exists() && !dir.is_dir() {
+            let line = line_of_key(self, "scripts", "dir");
+            self.push(
+                line,
+                format!("`scripts.dir` '{}' is not a directory", config.scripts.dir),
+            );
+        }
+    }
+}
+
+/// Tables of an array of tables (`[[name]]`), in file order
+fn array_tables<'a, 'i>(value: &'a Spanned<DeValue<'i>>) -> Vec<&'a Spanned<DeValue<'i>>> {
+    value
+        .get_ref()
+        .as_array()
+        .map(|a| a.iter().filter(|v| v.get_ref().is_table()).collect())
+        .unwrap_or_default()
+}
+
+/// Closest known key for a likely typo (one edit, or a case/separator slip)
+fn suggest<'k>(name: &str, known: &[&'k str]) -> Option<&'k str> {
+    let normalized = name.to_ascii_lowercase().replace('-', "_");
+    known
+        .iter()
+        .copied()
+        .find(|k| *k == normalized || edit_distance(k, &normalized) <= 1)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr.push((prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1));
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
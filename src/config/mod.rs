@@ -0,0 +1,1204 @@
+use anyhow::{anyhow, Context, Result};
+use dotenvy::dotenv;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::adaptors::{DEFAULT_BUNDLES, MIN_REFRESH_INTERVAL};
+
+mod check;
+mod opml;
+
+pub use check::{check_config, ConfigIssue};
+pub use opml::{export_opml, parse_opml, plan_import, OpmlFeed, OpmlImport};
+
+pub const CONFIG_PATH: &str = "config.toml";
+
+/// Add `text` to the end of config.toml, creating it if needed
+pub fn append_to_config(text: &str) -> Result<()> {
+    use std::io::Write;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(CONFIG_PATH)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .with_context(|| format!("Failed to write {}", CONFIG_PATH))
+}
+
+#[derive(Debug, Default)]
+pub struct Config {
+    pub app: AppConfig,
+    pub market: MarketConfig,
+    pub quotes: QuotesConfig,
+    pub ticker_tape: TickerTapeConfig,
+    pub highlight: Vec<HighlightRuleConfig>,
+    pub scripts: ScriptsConfig,
+    /// Per-source overrides keyed by source name, e.g. `[sources.CNBC]`
+    pub sources: BTreeMap<String, SourceConfig>,
+    /// Aggregator servers to use as sources, `[[remote]]`
+    pub remote: Vec<RemoteConfig>,
+    /// Publisher feeds built from an author or publication name, `[[feed]]`
+    pub feed: Vec<FeedConfig>,
+    pub bridge: BridgeConfig,
+    /// Pages without a feed watched for changes, `[[watch]]`
+    pub watch: Vec<WatchConfig>,
+    /// JSON or GraphQL APIs mapped to articles by field paths, `[[json]]`
+    pub json: Vec<JsonSourceConfig>,
+    /// Extra share menu entries, `[[share]]`
+    pub share: Vec<ShareTargetConfig>,
+    pub list: ListConfig,
+    pub status: StatusConfig,
+    pub cache: CacheConfig,
+    pub crawl: CrawlConfig,
+    pub open: OpenConfig,
+    pub watchlist: WatchlistConfig,
+    pub fred: FredConfig,
+    pub transcripts: TranscriptsConfig,
+    pub reddit: RedditConfig,
+    pub refresh: RefreshConfig,
+    pub sync: SyncConfig,
+    pub alerts: AlertsConfig,
+    /// Keys for each command by its name, `[keys]`; unlisted commands keep
+    /// their default keys
+    pub keys: BTreeMap<String, Vec<String>>,
+    // pub adapters: AdapterSettings,
+    // pub scoring: ScoringConfig,
+    pub credentials: ApiKeys,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TomlConfig {
+    pub app: AppConfig,
+    pub market: MarketConfig,
+    pub quotes: QuotesConfig,
+    pub ticker_tape: TickerTapeConfig,
+    pub highlight: Vec<HighlightRuleConfig>,
+    pub scripts: ScriptsConfig,
+    /// Per-source overrides keyed by source name, e.g. `[sources.CNBC]`
+    pub sources: BTreeMap<String, SourceConfig>,
+    /// Aggregator servers to use as sources, `[[remote]]`
+    pub remote: Vec<RemoteConfig>,
+    /// Publisher feeds built from an author or publication name, `[[feed]]`
+    pub feed: Vec<FeedConfig>,
+    pub bridge: BridgeConfig,
+    /// Pages without a feed watched for changes, `[[watch]]`
+    pub watch: Vec<WatchConfig>,
+    /// JSON or GraphQL APIs mapped to articles by field paths, `[[json]]`
+    pub json: Vec<JsonSourceConfig>,
+    /// Extra share menu entries, `[[share]]`
+    pub share: Vec<ShareTargetConfig>,
+    pub list: ListConfig,
+    pub status: StatusConfig,
+    pub cache: CacheConfig,
+    pub crawl: CrawlConfig,
+    pub open: OpenConfig,
+    pub watchlist: WatchlistConfig,
+    pub fred: FredConfig,
+    pub transcripts: TranscriptsConfig,
+    pub reddit: RedditConfig,
+    pub refresh: RefreshConfig,
+    pub sync: SyncConfig,
+    pub alerts: AlertsConfig,
+    /// Keys for each command by its name, `[keys]`; unlisted commands keep
+    /// their default keys
+    pub keys: BTreeMap<String, Vec<String>>,
+    // pub adapters: AdapterSettings,
+    // pub scoring: ScoringConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub deduplication_enabled: bool,
+    pub similarity_threshold: f32,
+    /// Hide read articles from the main list; they stay in the archive view
+    pub inbox_zero: bool,
+    /// How the news list shows when an article was published
+    pub time_format: TimeFormat,
+    /// Refresh every source at startup, showing the cached articles meanwhile
+    pub fetch_on_start: bool,
+    /// Minutes after which the newest article and last refresh show as stale (yellow)
+    pub stale_after_mins: u64,
+    /// Minutes after which they show as very stale (red)
+    pub very_stale_after_mins: u64,
+    /// Scale script scores by each source's reliability, ranking unreliable feeds lower
+    pub reliability_weighting: bool,
+    /// Opening more listed articles than this at once (O) asks for confirmation
+    pub bulk_open_confirm_above: usize,
+    /// Pause between browser tabs when opening several articles
+    pub bulk_open_delay_ms: u64,
+    /// Built-in feed bundles to fetch; see `news-hub bundles`
+    pub bundles: Vec<String>,
+    /// Reload the list when another news-hub process stores articles in the
+    /// same database
+    pub follow_database: bool,
+    /// Plain line-by-line layout for terminal screen readers: no borders or
+    /// glyphs, and focus and selection changes announced as text
+    pub screen_reader: bool,
+    /// Language of the interface, e.g. "en" or "de"
+    pub locale: String,
+    /// Capture the mouse so the wheel scrolls and clicks focus the pane
+    /// under the cursor
+    pub mouse: bool,
+}
+
+/// Publish time column of the news list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeFormat {
+    /// Age of the article, e.g. "2h ago"
+    #[default]
+    Relative,
+    /// Local time today, date before that
+    Absolute,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            deduplication_enabled: true,
+            similarity_threshold: 0.85,
+            inbox_zero: false,
+            time_format: TimeFormat::Relative,
+            fetch_on_start: false,
+            stale_after_mins: 60,
+            very_stale_after_mins: 240,
+            reliability_weighting: false,
+            bulk_open_confirm_above: 10,
+            bulk_open_delay_ms: 250,
+            bundles: DEFAULT_BUNDLES.iter().map(|b| b.to_string()).collect(),
+            follow_database: true,
+            screen_reader: false,
+            locale: "en".to_string(),
+            mouse: true,
+        }
+    }
+}
+
+/// Exchanges shown in the status bar market clock
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct MarketConfig {
+    /// Exchange codes, either built-in (NYSE, LSE, ...) or defined under `[[market.custom]]`
+    pub exchanges: Vec<String>,
+    pub custom: Vec<ExchangeConfig>,
+}
+
+impl Default for MarketConfig {
+    fn default() -> Self {
+        Self {
+            exchanges: vec!["NYSE".to_string()],
+            custom: Vec::new(),
+        }
+    }
+}
+
+/// User-defined exchange session. Times are "HH:MM" in the exchange's timezone.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExchangeConfig {
+    pub code: String,
+    pub timezone: String,
+    pub open: String,
+    pub close: String,
+    pub pre_open: Option<String>,
+    pub post_close: Option<String>,
+}
+
+/// Quote provider shared by the ticker tape and the detail pane
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct QuotesConfig {
+    pub provider: String,
+    /// Show live quotes for tickers mentioned in the selected article
+    pub article_quotes: bool,
+    /// How long a fetched quote is reused before refetching
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for QuotesConfig {
+    fn default() -> Self {
+        Self {
+            provider: "yahoo".to_string(),
+            article_quotes: true,
+            cache_ttl_secs: 60,
+        }
+    }
+}
+
+/// Lowest ticker tape refresh interval; smaller values are raised to this
+pub const MIN_TAPE_INTERVAL_SECS: u64 = 5;
+
+/// Optional scrolling quote line across the top of the UI
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct TickerTapeConfig {
+    pub enabled: bool,
+    pub symbols: Vec<String>,
+    pub refresh_interval_secs: u64,
+}
+
+impl Default for TickerTapeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            symbols: [
+                "ES=F", "NQ=F", "YM=F", "EURUSD=X", "USDJPY=X", "BTC-USD", "ETH-USD",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            refresh_interval_secs: 60,
+        }
+    }
+}
+
+/// `[[highlight]]` entry: headlines containing `keyword` (case-insensitive) are styled.
+/// `color` accepts ratatui color names ("red", "lightgreen") or hex ("#ff8800").
+#[derive(Debug, Clone, Deserialize)]
+pub struct HighlightRuleConfig {
+    pub keyword: String,
+    pub color: Option<String>,
+    #[serde(default)]
+    pub bold: bool,
+    /// Match only where the keyword isn't part of a longer word
+    #[serde(default = "default_whole_word")]
+    pub whole_word: bool,
+}
+
+fn default_whole_word() -> bool {
+    true
+}
+
+/// Lua scripts (`*.lua` in `dir`) defining filter/score/format_row hooks
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ScriptsConfig {
+    pub enabled: bool,
+    pub dir: String,
+}
+
+impl Default for ScriptsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: "scripts".to_string(),
+        }
+    }
+}
+
+/// `[sources.<name>]` overrides for a registered source; unset fields keep defaults
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SourceConfig {
+    pub enabled: Option<bool>,
+    /// Feed URL replacing a built-in feed's, e.g. after it moved
+    pub url: Option<String>,
+    pub timeout_secs: Option<u64>,
+    /// Refresh this source automatically every N seconds
+    pub interval_secs: Option<u64>,
+    /// Learn the refresh interval from how often the source publishes,
+    /// overriding `[refresh] adaptive`
+    pub adaptive: Option<bool>,
+    pub tags: Vec<String>,
+    /// Tags added to every article from the source, e.g. "crypto"
+    pub item_tags: Vec<String>,
+    pub group: Option<String>,
+    /// Higher sorts first when sources are listed
+    pub priority: Option<i32>,
+    /// Colour for the source name in the news list
+    pub color: Option<String>,
+    /// Command opening this source's articles instead of `[open] command`
+    pub open_command: Option<String>,
+    /// Keep at most this many of the newest items per fetch
+    pub max_items: Option<usize>,
+    /// Skip items published longer ago than this
+    pub max_age_hours: Option<u64>,
+    /// Collapse items repeating a headline the source published within this
+    /// many minutes, for feeds that repost under a new GUID
+    pub title_dedup_mins: Option<u64>,
+    /// Boilerplate removed from the start of headlines, e.g. "BREAKING:"
+    pub strip_prefixes: Vec<String>,
+    /// Regex rewrites of headlines, applied in order
+    pub title_rewrites: Vec<RewriteConfig>,
+    /// Regex rewrites of article links, e.g. to drop AMP paths
+    pub url_rewrites: Vec<RewriteConfig>,
+}
+
+/// Regex `pattern` replaced by `replace`, which may use `$1` or `${name}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct RewriteConfig {
+    pub pattern: String,
+    #[serde(default)]
+    pub replace: String,
+}
+
+/// Protocol spoken by a `[[remote]]` server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteKind {
+    /// FreshRSS, via its Google Reader API
+    FreshRss,
+    /// Miniflux, via its Google Reader API
+    Miniflux,
+    /// Nextcloud News app
+    Nextcloud,
+    /// Tiny Tiny RSS
+    TtRss,
+}
+
+impl RemoteKind {
+    pub const ALL: [RemoteKind; 4] = [
+        RemoteKind::FreshRss,
+        RemoteKind::Miniflux,
+        RemoteKind::Nextcloud,
+        RemoteKind::TtRss,
+    ];
+
+    /// Value of `kind` in config
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RemoteKind::FreshRss => "freshrss",
+            RemoteKind::Miniflux => "miniflux",
+            RemoteKind::Nextcloud => "nextcloud",
+            RemoteKind::TtRss => "ttrss",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            RemoteKind::FreshRss => "FreshRSS",
+            RemoteKind::Miniflux => "Miniflux",
+            RemoteKind::Nextcloud => "Nextcloud News",
+            RemoteKind::TtRss => "Tiny Tiny RSS",
+        }
+    }
+}
+
+/// A self-hosted aggregator whose items and read/starred state news-hub syncs
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteConfig {
+    pub kind: RemoteKind,
+    /// Source name; defaults to the server kind (e.g. "FreshRSS")
+    pub name: Option<String>,
+    /// Google Reader API root for FreshRSS/Miniflux (e.g.
+    /// `https://rss.example.com/api/greader.php`), otherwise the server root
+    pub url: String,
+    pub username: String,
+    /// Environment variable holding the password; defaults to
+    /// `<NAME>_PASSWORD`, e.g. `FRESHRSS_PASSWORD`
+    pub password_env: Option<String>,
+    /// Items pulled per refresh
+    #[serde(default = "default_remote_limit")]
+    pub limit: usize,
+}
+
+fn default_remote_limit() -> usize {
+    200
+}
+
+impl RemoteConfig {
+    pub fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.kind.display_name().to_string())
+    }
+
+    pub fn password_env(&self) -> String {
+        self.password_env.clone().unwrap_or_else(|| {
+            let name: String = self
+                .name()
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() {
+                        c.to_ascii_uppercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            format!("{}_PASSWORD", name)
+        })
+    }
+}
+
+/// RSSHub route templates available without config; `{account}` is
+/// replaced by the feed's account
+pub const RSSHUB_ROUTES: &[(&str, &str)] = &[
+    ("x", "/twitter/user/{account}"),
+    ("instagram", "/instagram/user/{account}"),
+    ("telegram", "/telegram/channel/{account}"),
+    ("youtube", "/youtube/user/@{account}"),
+    ("reddit", "/reddit/subreddit/{account}"),
+];
+
+/// Self-hosted bridges that make feeds for sites without one
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct BridgeConfig {
+    /// RSSHub instance root, e.g. `https://rsshub.example.com`
+    pub rsshub: Option<String>,
+    /// Nitter instance root, for X accounts
+    pub nitter: Option<String>,
+    /// Extra or replacement RSSHub route templates by name
+    pub routes: BTreeMap<String, String>,
+}
+
+impl BridgeConfig {
+    /// Path template of a named RSSHub route, configured ones first
+    pub fn route(&self, name: &str) -> Option<&str> {
+        self.routes.get(name).map(String::as_str).or_else(|| {
+            RSSHUB_ROUTES
+                .iter()
+                .find(|(route, _)| *route == name)
+                .map(|(_, path)| *path)
+        })
+    }
+}
+
+/// Publisher or bridge whose feed URL a `[[feed]]` entry builds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedKind {
+    /// Any RSS or Atom feed, by its URL
+    Rss,
+    SeekingAlpha,
+    Substack,
+    RssHub,
+    Nitter,
+    Lobsters,
+}
+
+/// A feed given by URL (`kind = "rss"`), or built from a publisher or
+/// bridge, e.g. `kind = "substack"` with `publication = "noahpinion"`, or
+/// `kind = "nitter"` with `account`
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedConfig {
+    pub kind: FeedKind,
+    /// Source name; defaults to one built from the other fields
+    pub name: Option<String>,
+    /// Seeking Alpha author slug; without it, Market Currents is fetched
+    pub author: Option<String>,
+    /// Substack publication, the `<name>` of `<name>.substack.com`
+    pub publication: Option<String>,
+    /// The feed itself for `rss`; the site root of a Substack on its own
+    /// domain, or of a Lobsters-style site
+    pub url: Option<String>,
+    /// Account an RSSHub route or Nitter follows
+    pub account: Option<String>,
+    /// RSSHub route: a template name from `[bridge.routes]` or the built-in
+    /// ones, or a path starting with `/`
+    pub route: Option<String>,
+}
+
+impl FeedConfig {
+    /// Feed URL and source name; bridge feeds need the instance in `bridge`
+    pub fn resolve(&self, bridge: &BridgeConfig) -> Result<(String, String)> {
+        let (url, name) = match self.kind {
+            FeedKind::Rss => {
+                let url = self
+                    .url
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("rss needs a url"))?;
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(anyhow!("url must start with http:// or https://"));
+                }
+                let host = url.split("://").nth(1).unwrap_or(url);
+                let host = host.split('/').next().unwrap_or(host);
+                (url.to_string(), host.to_string())
+            }
+            FeedKind::SeekingAlpha => match &self.author {
+                Some(author) => (
+                    format!("https://seekingalpha.com/author/{}.xml", author),
+                    format!("Seeking Alpha: {}", author),
+                ),
+                None => (
+                    "https://seekingalpha.com/market_currents.xml".to_string(),
+                    "Seeking Alpha Market Currents".to_string(),
+                ),
+            },
+            FeedKind::Substack => match (&self.publication, &self.url) {
+                (Some(publication), _) => (
+                    format!("https://{}.substack.com/feed", publication),
+                    publication.clone(),
+                ),
+                (None, Some(url)) => {
+                    if !url.starts_with("http://") && !url.starts_with("https://") {
+                        return Err(anyhow!("url must start with http:// or https://"));
+                    }
+                    let root = url.trim_end_matches('/').trim_end_matches("/feed");
+                    let host = root.split("://").nth(1).unwrap_or(root);
+                    (format!("{}/feed", root), host.to_string())
+                }
+                (None, None) => return Err(anyhow!("substack needs a publication or url")),
+            },
+            FeedKind::RssHub => {
+                let base = bridge_root(bridge.rsshub.as_deref(), "rsshub")?;
+                let route = self
+                    .route
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("rsshub needs a route"))?;
+                let (path, name) = if route.starts_with('/') {
+                    (route, format!("RSSHub {}", route))
+                } else {
+                    let path = bridge
+                        .route(route)
+                        .ok_or_else(|| anyhow!("unknown rsshub route `{}`", route))?;
+                    let account = self.account.as_deref().unwrap_or(route);
+                    (path, format!("{} ({})", account, route))
+                };
+                let path = match (&self.account, path.contains("{account}")) {
+                    (Some(account), _) => path.replace("{account}", account),
+                    (None, false) => path.to_string(),
+                    (None, true) => {
+                        return Err(anyhow!("rsshub route `{}` needs an account", route))
+                    }
+                };
+                (format!("{}{}", base, path), name)
+            }
+            FeedKind::Lobsters => match &self.url {
+                Some(url) => {
+                    if !url.starts_with("http://") && !url.starts_with("https://") {
+                        return Err(anyhow!("url must start with http:// or https://"));
+                    }
+                    let root = url.trim_end_matches('/');
+                    let host = root.split("://").nth(1).unwrap_or(root);
+                    (format!("{}/hottest.json", root), host.to_string())
+                }
+                None => (
+                    "https://lobste.rs/hottest.json".to_string(),
+                    "Lobsters".to_string(),
+                ),
+            },
+            FeedKind::Nitter => {
+                let base = bridge_root(bridge.nitter.as_deref(), "nitter")?;
+                let account = self
+                    .account
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("nitter needs an account"))?
+                    .trim_start_matches('@');
+                (format!("{}/{}/rss", base, account), format!("@{}", account))
+            }
+        };
+        Ok((url, self.name.clone().unwrap_or(name)))
+    }
+}
+
+/// Instance root of a bridge without the trailing slash
+fn bridge_root<'a>(root: Option<&'a str>, key: &str) -> Result<&'a str> {
+    let root = root.ok_or_else(|| anyhow!("{} needs `bridge.{}` set", key, key))?;
+    if !root.starts_with("http://") && !root.starts_with("https://") {
+        return Err(anyhow!(
+            "`bridge.{}` must start with http:// or https://",
+            key
+        ));
+    }
+    Ok(root.trim_end_matches('/'))
+}
+
+/// A page without a feed whose text changes become articles
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchConfig {
+    pub url: String,
+    /// Source name; defaults to the page's host
+    pub name: Option<String>,
+    /// Changes smaller than this many added or removed words are ignored
+    #[serde(default = "default_watch_min_words")]
+    pub min_words: usize,
+}
+
+fn default_watch_min_words() -> usize {
+    10
+}
+
+impl WatchConfig {
+    pub fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            let host = self.url.split("://").nth(1).unwrap_or(&self.url);
+            host.split('/').next().unwrap_or(host).to_string()
+        })
+    }
+}
+
+/// A JSON API read as a news source. Paths are dot-separated keys with
+/// optional `[n]` indexes, e.g. `data.articles` or `links[0].href`; a
+/// leading `$.` is allowed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonSourceConfig {
+    pub name: String,
+    pub url: String,
+    /// GraphQL query, POSTed as `{"query": ...}` instead of a GET
+    pub query: Option<String>,
+    /// Path to the array of items; empty when the response is the array
+    #[serde(default)]
+    pub items: String,
+    pub fields: JsonFieldsConfig,
+    /// Header carrying the credential
+    #[serde(default = "default_json_auth_header")]
+    pub auth_header: String,
+    /// Environment variable holding the header's whole value, e.g. `Bearer
+    /// <token>`; the source is skipped while it is unset
+    pub auth_env: Option<String>,
+}
+
+fn default_json_auth_header() -> String {
+    "Authorization".to_string()
+}
+
+/// Paths of an item's fields within each element of a `[[json]]` array
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonFieldsConfig {
+    pub title: String,
+    pub url: String,
+    /// A date string in any layout RSS feeds are read in (RFC 3339, RFC
+    /// 2822, `YYYY-MM-DD HH:MM:SS`, ...) or Unix seconds/milliseconds
+    pub date: String,
+    pub summary: Option<String>,
+    /// Stable id; defaults to the URL
+    pub id: Option<String>,
+    pub author: Option<String>,
+}
+
+/// What a share menu entry does with its rendered template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareActionKind {
+    /// Copy the text to the clipboard
+    Copy,
+    /// Open the text as a URL in the browser
+    Open,
+    /// Run the text as a command (no shell)
+    Command,
+    /// POST `{"text": ...}` to `url`
+    Webhook,
+}
+
+/// A share menu entry. Templates use `{title}`, `{url}`, `{source}`,
+/// `{summary}`, and URL-encoded `{title_encoded}`/`{url_encoded}`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShareTargetConfig {
+    pub name: String,
+    /// Key that picks the entry directly in the menu
+    pub key: Option<char>,
+    pub action: ShareActionKind,
+    /// Defaults to `{url}`, or `{title} {url}` for webhooks
+    pub template: Option<String>,
+    /// Webhook endpoint
+    pub url: Option<String>,
+}
+
+/// News list layout
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ListConfig {
+    /// Columns in display order
+    pub columns: Vec<ColumnConfig>,
+    pub density: ListDensity,
+}
+
+/// How much of each article the news list shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListDensity {
+    /// One line per article
+    #[default]
+    Compact,
+    /// Headline plus the start of the summary
+    Comfortable,
+    /// The whole headline, wrapped over as many lines as it needs
+    Wrapped,
+}
+
+impl ListDensity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ListDensity::Compact => "compact",
+            ListDensity::Comfortable => "comfortable",
+            ListDensity::Wrapped => "wrapped",
+        }
+    }
+
+    /// The density after this one, cycling back to compact
+    pub fn next(self) -> Self {
+        match self {
+            ListDensity::Compact => ListDensity::Comfortable,
+            ListDensity::Comfortable => ListDensity::Wrapped,
+            ListDensity::Wrapped => ListDensity::Compact,
+        }
+    }
+}
+
+impl Default for ListConfig {
+    fn default() -> Self {
+        Self {
+            columns: [ColumnKind::Time, ColumnKind::Title, ColumnKind::Source]
+                .into_iter()
+                .map(|name| ColumnConfig { name, width: None })
+                .collect(),
+            density: ListDensity::Compact,
+        }
+    }
+}
+
+/// Status bar messages and their history
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct StatusConfig {
+    /// Messages kept in the history
+    pub history_size: usize,
+    /// Rows of the expanded history, report and errors views, borders
+    /// included, until + and - resize them
+    pub history_height: u16,
+    /// Seconds each level of message stays up; 0 keeps it until dismissed
+    pub info_secs: u64,
+    pub success_secs: u64,
+    pub warning_secs: u64,
+    /// Errors also stay behind the error badge until cleared
+    pub error_secs: u64,
+    /// Keep the history in the database across sessions
+    pub persist_history: bool,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            history_size: 50,
+            history_height: 15,
+            info_secs: 5,
+            success_secs: 3,
+            warning_secs: 5,
+            error_secs: 10,
+            persist_history: false,
+        }
+    }
+}
+
+/// Politeness rules for fetching article pages in bulk (`backfill --content`)
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct CrawlConfig {
+    /// Skip pages a site's robots.txt disallows
+    pub respect_robots: bool,
+    /// Pages fetched at the same time across all sites
+    pub max_concurrent: usize,
+    /// Pages fetched at the same time from one site
+    pub max_per_host: usize,
+    /// Pause before each request to a site, picked at random between these
+    pub min_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// Sites (and their subdomains) fetched without robots.txt or delays
+    pub exempt_hosts: Vec<String>,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        Self {
+            respect_robots: true,
+            max_concurrent: 4,
+            max_per_host: 1,
+            min_delay_ms: 1000,
+            max_delay_ms: 3000,
+            exempt_hosts: Vec::new(),
+        }
+    }
+}
+
+/// How Enter and `o` open articles
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct OpenConfig {
+    /// Command line run with the article link, e.g. `firefox -P work {url}`;
+    /// unset or "default" uses the system browser
+    pub command: Option<String>,
+    /// Mark an article read when it is opened
+    pub mark_read: bool,
+    /// Mark an article read once the detail pane has shown it this long;
+    /// 0 turns it off
+    pub mark_read_after_secs: u64,
+}
+
+impl Default for OpenConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            mark_read: true,
+            mark_read_after_secs: 3,
+        }
+    }
+}
+
+/// Symbols followed closely; Yahoo Finance fetches a headline feed for each
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct WatchlistConfig {
+    pub symbols: Vec<String>,
+}
+
+/// FRED economic data releases, fetched when `FRED_API_KEY` is set
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct FredConfig {
+    /// Series ids whose releases are listed, with their latest values once out
+    pub series: Vec<String>,
+    /// List releases scheduled up to this many days ahead
+    pub days_ahead: u64,
+    /// and those released up to this many days ago
+    pub days_back: u64,
+}
+
+impl Default for FredConfig {
+    fn default() -> Self {
+        Self {
+            series: ["CPIAUCSL", "PAYEMS", "UNRATE"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            days_ahead: 14,
+            days_back: 7,
+        }
+    }
+}
+
+/// Earnings-call transcripts of watchlist symbols, fetched when
+/// `FMP_API_KEY` is set
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct TranscriptsConfig {
+    /// Skip calls held longer ago than this
+    pub max_age_days: u64,
+}
+
+impl Default for TranscriptsConfig {
+    fn default() -> Self {
+        Self { max_age_days: 30 }
+    }
+}
+
+/// Refresh intervals learned from each source's publishing cadence
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RefreshConfig {
+    /// Refresh every source about twice per typical gap between its
+    /// articles; `[sources.<name>] adaptive` overrides it per source
+    pub adaptive: bool,
+    /// Bounds of a learned interval
+    pub min_interval_secs: u64,
+    pub max_interval_secs: u64,
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            adaptive: false,
+            min_interval_secs: 120,
+            max_interval_secs: 3600,
+        }
+    }
+}
+
+impl RefreshConfig {
+    /// Interval for a source publishing every `cadence`: half of it, so a
+    /// new article waits at most half a gap, within the configured bounds.
+    /// Without a cadence (too few articles yet) the longest interval is used.
+    pub fn interval_for(&self, cadence: Option<Duration>) -> Duration {
+        let min = Duration::from_secs(self.min_interval_secs).max(MIN_REFRESH_INTERVAL);
+        let max = Duration::from_secs(self.max_interval_secs).max(min);
+        cadence.map_or(max, |c| (c / 2).clamp(min, max))
+    }
+}
+
+/// Keywords that raise an alert when a refresh brings in an article
+/// mentioning one
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    /// Words, phrases or tickers (`NVDA` or `$NVDA`), matched as whole words
+    pub keywords: Vec<String>,
+}
+
+/// A git repository `:sync pull` and `:sync push` exchange subscriptions
+/// and article state through
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Working copy of the repository, relative to the working directory;
+    /// sync is off while unset
+    pub dir: Option<String>,
+    /// Name of this machine's files in the repository; the host name by default
+    pub machine: Option<String>,
+}
+
+impl SyncConfig {
+    /// `machine`, or the host name, made safe for a file name
+    pub fn machine(&self) -> String {
+        let host = || {
+            env::var("HOSTNAME")
+                .ok()
+                .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+                .or_else(|| {
+                    let output = std::process::Command::new("hostname").output().ok()?;
+                    String::from_utf8(output.stdout).ok()
+                })
+        };
+        let name = self.machine.clone().or_else(host).unwrap_or_default();
+        let name: String = name
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        if name.is_empty() {
+            "news-hub".to_string()
+        } else {
+            name
+        }
+    }
+}
+
+/// Hot posts of subreddits, fetched when `REDDIT_CLIENT_ID` and
+/// `REDDIT_CLIENT_SECRET` are set
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct RedditConfig {
+    /// Subreddit names, with or without the `r/`
+    pub subreddits: Vec<String>,
+    /// Posts fetched per subreddit
+    pub limit: u32,
+    /// Reddit asks for a unique, descriptive user agent
+    pub user_agent: String,
+    /// Redirect URI registered with the Reddit app, for `news-hub auth reddit`
+    pub redirect_uri: String,
+}
+
+impl Default for RedditConfig {
+    fn default() -> Self {
+        Self {
+            subreddits: vec!["stocks".to_string(), "wallstreetbets".to_string()],
+            limit: 25,
+            user_agent: format!("news-hub/{}", env!("CARGO_PKG_VERSION")),
+            redirect_uri: "http://localhost:65010/authorize_callback".to_string(),
+        }
+    }
+}
+
+/// On-disk cache of extracted article text
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Directory holding the cache, relative to the working directory
+    pub dir: String,
+    /// Size cap; the least recently read articles are evicted beyond it
+    pub max_mb: u64,
+    /// Articles are extracted again after this many days
+    pub ttl_days: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            dir: "data/cache".to_string(),
+            max_mb: 100,
+            ttl_days: 30,
+        }
+    }
+}
+
+/// What a news list column shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnKind {
+    Time,
+    Source,
+    Tickers,
+    Score,
+    Comments,
+    Title,
+}
+
+impl ColumnKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColumnKind::Time => "time",
+            ColumnKind::Source => "source",
+            ColumnKind::Tickers => "tickers",
+            ColumnKind::Score => "score",
+            ColumnKind::Comments => "comments",
+            ColumnKind::Title => "title",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ColumnConfig {
+    pub name: ColumnKind,
+    /// Width in cells. Defaults per column; the title fills the remaining space.
+    pub width: Option<u16>,
+}
+
+#[derive(Debug, Default)]
+pub struct ApiKeys {
+    pub benzinga_key: Option<String>,
+    pub marketaux_key: Option<String>,
+    pub fred_key: Option<String>,
+    /// Financial Modeling Prep, for earnings-call transcripts
+    pub fmp_key: Option<String>,
+    pub reddit_client_id: Option<String>,
+    pub reddit_client_secret: Option<String>,
+    /// `[[remote]]` passwords keyed by remote name
+    pub remote_passwords: BTreeMap<String, String>,
+    /// `[[json]]` auth header values keyed by source name
+    pub json_auth: BTreeMap<String, String>,
+}
+
+impl ApiKeys {
+    /// Whether any key, password or auth header is set
+    pub fn any(&self) -> bool {
+        [
+            &self.benzinga_key,
+            &self.marketaux_key,
+            &self.fred_key,
+            &self.fmp_key,
+            &self.reddit_client_id,
+            &self.reddit_client_secret,
+        ]
+        .iter()
+        .any(|key| key.is_some())
+            || !self.remote_passwords.is_empty()
+            || !self.json_auth.is_empty()
+    }
+}
+
+impl Config {
+    /// A startup warning when no source can fetch anything: bundles and
+    /// feeds need no key, so only a config without them needs credentials
+    pub fn credentials_warning(&self) -> Option<String> {
+        let keyless = !self.app.bundles.is_empty()
+            || !self.feed.is_empty()
+            || !self.watch.is_empty()
+            || !self.json.is_empty()
+            || !self.remote.is_empty();
+        (!keyless && !self.credentials.any()).then(|| {
+            "No API credentials found; set them in the environment (see .env.example)".to_string()
+        })
+    }
+
+    /// Load config.toml (defaults if absent) and credentials from the environment
+    pub fn load() -> Result<Self> {
+        // Load .env file (silently fail if not present)
+        dotenv().ok();
+
+        // Load TOML config. The app works out of the box, so a missing file means defaults
+        let toml_config: TomlConfig = if Path::new(CONFIG_PATH).exists() {
+            let config_contents =
+                std::fs::read_to_string(CONFIG_PATH).context("Failed to read config.toml")?;
+            toml::from_str(&config_contents).context("Failed to parse config.toml")?
+        } else {
+            TomlConfig::default()
+        };
+
+        // Load credentials from environment
+        let credentials = ApiKeys {
+            benzinga_key: env::var("BENZINGA_KEY").ok().filter(|k| !k.is_empty()),
+            marketaux_key: env::var("MARKETAUX_API_KEY").ok(),
+            fred_key: env::var("FRED_API_KEY").ok().filter(|k| !k.is_empty()),
+            fmp_key: env::var("FMP_API_KEY").ok().filter(|k| !k.is_empty()),
+            reddit_client_id: env::var("REDDIT_CLIENT_ID").ok().filter(|k| !k.is_empty()),
+            reddit_client_secret: env::var("REDDIT_CLIENT_SECRET")
+                .ok()
+                .filter(|k| !k.is_empty()),
+            remote_passwords: toml_config
+                .remote
+                .iter()
+                .filter_map(|remote| {
+                    let password = env::var(remote.password_env()).ok()?;
+                    Some((remote.name(), password)).filter(|(_, p)| !p.is_empty())
+                })
+                .collect(),
+            json_auth: toml_config
+                .json
+                .iter()
+                .filter_map(|source| {
+                    let value = env::var(source.auth_env.as_ref()?).ok()?;
+                    Some((source.name.clone(), value)).filter(|(_, v)| !v.is_empty())
+                })
+                .collect(),
+        };
+
+        Ok(Self {
+            app: toml_config.app,
+            market: toml_config.market,
+            quotes: toml_config.quotes,
+            ticker_tape: toml_config.ticker_tape,
+            highlight: toml_config.highlight,
+            scripts: toml_config.scripts,
+            sources: toml_config.sources,
+            remote: toml_config.remote,
+            feed: toml_config.feed,
+            bridge: toml_config.bridge,
+            watch: toml_config.watch,
+            json: toml_config.json,
+            share: toml_config.share,
+            list: toml_config.list,
+            status: toml_config.status,
+            cache: toml_config.cache,
+            crawl: toml_config.crawl,
+            open: toml_config.open,
+            watchlist: toml_config.watchlist,
+            fred: toml_config.fred,
+            transcripts: toml_config.transcripts,
+            reddit: toml_config.reddit,
+            refresh: toml_config.refresh,
+            sync: toml_config.sync,
+            alerts: toml_config.alerts,
+            keys: toml_config.keys,
+            // adapters: toml_config.adapters,
+            // scoring: toml_config.scoring,
+            credentials,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn without_sources() -> Config {
+        let mut config = Config::default();
+        config.app.bundles.clear();
+        config
+    }
+
+    #[test]
+    fn any_credential_counts() {
+        assert!(Config::default().credentials_warning().is_none());
+        assert!(without_sources().credentials_warning().is_some());
+
+        let keys: [fn(&mut ApiKeys); 8] = [
+            |keys| keys.benzinga_key = Some("k".to_string()),
+            |keys| keys.marketaux_key = Some("k".to_string()),
+            |keys| keys.fred_key = Some("k".to_string()),
+            |keys| keys.fmp_key = Some("k".to_string()),
+            |keys| keys.reddit_client_id = Some("k".to_string()),
+            |keys| keys.reddit_client_secret = Some("k".to_string()),
+            |keys| {
+                keys.remote_passwords
+                    .insert("home".to_string(), "k".to_string());
+            },
+            |keys| {
+                keys.json_auth.insert("api".to_string(), "k".to_string());
+            },
+        ];
+        for set in keys {
+            let mut config = without_sources();
+            set(&mut config.credentials);
+            assert!(config.credentials_warning().is_none());
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! OPML subscription lists, as other feed readers export them. Importing
+//! turns each feed into a `[[feed]]` entry of `kind = "rss"` appended to
+//! config.toml, so the file stays the one place sources are configured;
+//! exporting lists every configured feed for backup or another reader.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use quick_xml::encoding::Decoder;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::Config;
+use crate::adaptors::AdaptorRegistry;
+use crate::models::SourceKind;
+
+/// A feed listed in an OPML file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpmlFeed {
+    /// The outline's title or text; may be empty
+    pub title: String,
+    /// `xmlUrl` of the outline
+    pub url: String,
+    /// Title of the folder outline it sits in, if any
+    pub category: Option<String>,
+}
+
+/// Attribute of an outline, unescaped; empty values count as missing
+fn attr(element: &BytesStart, name: &str, decoder: Decoder) -> Option<String> {
+    let value = element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()?
+        .decode_and_unescape_value(decoder)
+        .ok()?
+        .trim()
+        .to_string();
+    Some(value).filter(|v| !v.is_empty())
+}
+
+/// Feeds of an OPML document, in document order. Outlines without an
+/// `xmlUrl` are folders; feeds nested in several take the innermost one's
+/// title as their category.
+pub fn parse_opml(xml: &[u8]) -> Result<Vec<OpmlFeed>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut feeds = Vec::new();
+    let mut saw_opml = false;
+    // Open outlines: the folder title each one set, if it is a folder
+    let mut open: Vec<Option<String>> = Vec::new();
+    loop {
+        let decoder = reader.decoder();
+        let event = reader
+            .read_event_into(&mut buf)
+            .with_context(|| format!("Invalid OPML at byte {}", reader.buffer_position()))?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let name = e.local_name();
+                if name.as_ref() == b"opml" {
+                    saw_opml = true;
+                }
+                if name.as_ref() != b"outline" {
+                    buf.clear();
+                    continue;
+                }
+                let title = attr(e, "title", decoder).or_else(|| attr(e, "text", decoder));
+                let is_feed = match attr(e, "xmlUrl", decoder) {
+                    Some(url) => {
+                        feeds.push(OpmlFeed {
+                            title: title.clone().unwrap_or_default(),
+                            url,
+                            category: open.iter().rev().find_map(|c| c.clone()),
+                        });
+                        true
+                    }
+                    None => false,
+                };
+                if matches!(event, Event::Start(_)) {
+                    open.push(if is_feed { None } else { title });
+                }
+            }
+            Event::End(ref e) if e.local_name().as_ref() == b"outline" => {
+                open.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    if !saw_opml {
+        return Err(anyhow!("Not an OPML document"));
+    }
+    Ok(feeds)
+}
+
+/// What importing a list of feeds would add to config.toml
+#[derive(Debug, Default)]
+pub struct OpmlImport {
+    /// Source name and URL of each new feed
+    pub added: Vec<(String, String)>,
+    /// Feeds left out, and why
+    pub skipped: Vec<(String, String)>,
+    /// TOML to append to config.toml; empty when nothing is added
+    pub toml: String,
+}
+
+/// Quoted TOML string
+fn quoted(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+/// Plan adding `feeds` next to the sources `config` already has. Feeds
+/// whose URL is configured are skipped; a name already taken gets the feed's
+/// host added. Folder titles become the new sources' groups, unless the
+/// source already has a `[sources]` table.
+pub fn plan_import(feeds: &[OpmlFeed], config: &Config) -> OpmlImport {
+    let registry = AdaptorRegistry::from_config(config);
+    let mut names: Vec<String> = registry.iter().map(|s| s.name().to_string()).collect();
+    let mut urls: Vec<String> = registry
+        .iter()
+        .filter_map(|s| s.adaptor().url().map(normalize_url))
+        .collect();
+    let mut import = OpmlImport::default();
+
+    for feed in feeds {
+        let label = if feed.title.is_empty() {
+            feed.url.clone()
+        } else {
+            feed.title.clone()
+        };
+        if !feed.url.starts_with("http://") && !feed.url.starts_with("https://") {
+            import
+                .skipped
+                .push((label, "not an http(s) address".to_string()));
+            continue;
+        }
+        let url = normalize_url(&feed.url);
+        if urls.contains(&url) {
+            import
+                .skipped
+                .push((label, "already configured".to_string()));
+            continue;
+        }
+
+        let host = feed.url.split("://").nth(1).unwrap_or(&feed.url);
+        let host = host.split('/').next().unwrap_or(host);
+        let mut name = if feed.title.is_empty() {
+            host.to_string()
+        } else {
+            feed.title.clone()
+        };
+        if names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            name = format!("{} ({})", name, host);
+        }
+        if names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            import
+                .skipped
+                .push((label, format!("a source named \"{}\" exists", name)));
+            continue;
+        }
+
+        import.toml.push_str(&format!(
+            "\n[[feed]]\nkind = \"rss\"\nname = {}\nurl = {}\n",
+            quoted(&name),
+            quoted(&feed.url)
+        ));
+        if let Some(group) = feed
+            .category
+            .as_ref()
+            .filter(|_| !config.sources.contains_key(&name))
+        {
+            import.toml.push_str(&format!(
+                "\n[sources.{}]\ngroup = {}\n",
+                quoted(&name),
+                quoted(group)
+            ));
+        }
+        names.push(name.clone());
+        urls.push(url);
+        import.added.push((name, feed.url.clone()));
+    }
+
+    if !import.toml.is_empty() {
+        import.toml.insert_str(0, "\n# Imported from OPML\n");
+    }
+    import
+}
+
+/// URL compared for duplicates: scheme and trailing slash don't matter
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split("://").nth(1).unwrap_or(url);
+    url.trim_end_matches('/').to_lowercase()
+}
+
+/// A group's feeds, as source name and feed URL
+type Folder<'a> = (Option<&'a str>, Vec<(&'a str, &'a str)>);
+
+/// Enabled feed sources of `config` as an OPML document, grouped into
+/// folders by their `group`. Watched pages and API sources have no feed to
+/// list, so they are left out.
+pub fn export_opml(config: &Config, now: DateTime<Utc>) -> String {
+    let registry = AdaptorRegistry::from_config(config);
+    let mut folders: Vec<Folder> = Vec::new();
+    for source in registry.iter().filter(|s| s.is_enabled()) {
+        let Some(url) = source.adaptor().url() else {
+            continue;
+        };
+        let is_watched = config.watch.iter().any(|w| w.name() == source.name());
+        if source.adaptor().kind() != SourceKind::Rss || is_watched {
+            continue;
+        }
+        let group = source.options.group.as_deref();
+        match folders.iter_mut().find(|(g, _)| *g == group) {
+            Some((_, feeds)) => feeds.push((source.name(), url)),
+            None => folders.push((group, vec![(source.name(), url)])),
+        }
+    }
+    // Ungrouped feeds after the folders
+    folders.sort_by_key(|(group, _)| group.is_none());
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<opml version=\"2.0\">\n  <head>\n");
+    out.push_str("    <title>news-hub feeds</title>\n");
+    out.push_str(&format!(
+        "    <dateCreated>{}</dateCreated>\n",
+        now.to_rfc2822()
+    ));
+    out.push_str("  </head>\n  <body>\n");
+    for (group, feeds) in &folders {
+        let indent = if group.is_some() { "      " } else { "    " };
+        if let Some(group) = group {
+            let group = escape(*group);
+            out.push_str(&format!(
+                "    <outline text=\"{}\" title=\"{}\">\n",
+                group, group
+            ));
+        }
+        for (name, url) in feeds {
+            let name = escape(*name);
+            out.push_str(&format!(
+                "{}<outline type=\"rss\" text=\"{}\" title=\"{}\" xmlUrl=\"{}\"/>\n",
+                indent,
+                name,
+                name,
+                escape(*url)
+            ));
+        }
+        if group.is_some() {
+            out.push_str("    </outline>\n");
+        }
+    }
+    out.push_str("  </body>\n</opml>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head><title>Subscriptions</title></head>
+  <body>
+    <outline text="Markets" title="Markets">
+      <outline type="rss" text="CNBC Top News" xmlUrl="https://www.cnbc.com/id/100003114/device/rss/rss.html"/>
+      <outline type="rss" text="Calculated Risk" title="Calculated Risk"
+               xmlUrl="https://www.calculatedriskblog.com/feeds/posts/default" htmlUrl="https://www.calculatedriskblog.com/"/>
+      <outline text="Central banks">
+        <outline type="rss" text="BoE &amp; MPC" xmlUrl="https://www.bankofengland.co.uk/rss/news"/>
+      </outline>
+    </outline>
+    <outline type="rss" xmlUrl="https://example.com/atom.xml"/>
+    <outline type="rss" text="Newsletter" xmlUrl="mailto:someone@example.com"/>
+  </body>
+</opml>"#;
+
+    #[test]
+    fn feeds_keep_their_folder() {
+        let feeds = parse_opml(OPML.as_bytes()).unwrap();
+        assert_eq!(feeds.len(), 5);
+        assert_eq!(feeds[0].category.as_deref(), Some("Markets"));
+        assert_eq!(feeds[2].title, "BoE & MPC");
+        assert_eq!(feeds[2].category.as_deref(), Some("Central banks"));
+        assert_eq!(feeds[3].title, "");
+        assert_eq!(feeds[3].category, None);
+
+        assert!(parse_opml(b"<rss><channel/></rss>").is_err());
+        assert!(parse_opml(b"<opml><body><outline").is_err());
+    }
+
+    #[test]
+    fn import_skips_configured_feeds_and_renames_clashes() {
+        let existing: crate::config::TomlConfig = toml::from_str(
+            r#"
+            [[feed]]
+            kind = "rss"
+            url = "https://example.org/feed"
+            name = "Calculated Risk"
+
+            [sources."BoE & MPC"]
+            tags = ["macro"]
+            "#,
+        )
+        .unwrap();
+        let config = Config {
+            feed: existing.feed,
+            sources: existing.sources,
+            ..Config::default()
+        };
+        let feeds = parse_opml(OPML.as_bytes()).unwrap();
+        let import = plan_import(&feeds, &config);
+
+        // CNBC Top News is a built-in feed
+        let added: Vec<&str> = import.added.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(
+            added,
+            [
+                "Calculated Risk (www.calculatedriskblog.com)",
+                "BoE & MPC",
+                "example.com"
+            ]
+        );
+        assert_eq!(import.skipped.len(), 2);
+        assert_eq!(import.skipped[0].1, "already configured");
+
+        // The appended text parses back into the same feeds
+        let appended: crate::config::TomlConfig = toml::from_str(&import.toml).unwrap();
+        assert_eq!(appended.feed.len(), 3);
+        assert_eq!(
+            appended.feed[1].url.as_deref(),
+            Some("https://www.bankofengland.co.uk/rss/news")
+        );
+        assert_eq!(
+            appended.sources["Calculated Risk (www.calculatedriskblog.com)"]
+                .group
+                .as_deref(),
+            Some("Markets")
+        );
+        assert!(!appended.sources.contains_key("BoE & MPC"));
+    }
+
+    #[test]
+    fn exported_feeds_import_as_already_configured() {
+        let toml: crate::config::TomlConfig = toml::from_str(
+            r#"
+            [app]
+            bundles = ["us-markets"]
+
+            [[feed]]
+            kind = "rss"
+            url = "https://example.org/feed?a=1&b=2"
+            name = "Calculated <Risk>"
+
+            [[watch]]
+            url = "https://example.com/ir"
+            name = "IR page"
+
+            [sources.Barrons]
+            enabled = false
+
+            [sources."Calculated <Risk>"]
+            group = "Blogs & more"
+            "#,
+        )
+        .unwrap();
+        let config = Config {
+            app: toml.app,
+            feed: toml.feed,
+            watch: toml.watch,
+            sources: toml.sources,
+            ..Config::default()
+        };
+        let opml = export_opml(&config, Utc::now());
+        assert!(opml.contains("<outline text=\"Blogs &amp; more\""));
+
+        let feeds = parse_opml(opml.as_bytes()).unwrap();
+        let names: Vec<&str> = feeds.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(names[0], "Calculated <Risk>");
+        assert_eq!(feeds[0].url, "https://example.org/feed?a=1&b=2");
+        assert_eq!(feeds[0].category.as_deref(), Some("Blogs & more"));
+        assert!(names.contains(&"CNBC"));
+        assert!(!names.contains(&"Barrons"));
+        assert!(!names.contains(&"IR page"));
+
+        let import = plan_import(&feeds, &config);
+        assert!(import.added.is_empty());
+        assert_eq!(import.skipped.len(), feeds.len());
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+/// Schema migrations, applied in order. The database's `user_version` records
+/// how many have run, so each entry runs exactly once. Never edit a released
+/// migration; append a new one instead.
+const MIGRATIONS: &[&str] = &[
+    // 1: initial schema (IF NOT EXISTS so databases created before versioning adopt it)
+    "CREATE TABLE IF NOT EXISTS news (
+        id TEXT PRIMARY KEY,
+        source TEXT NOT NULL,
+        title TEXT NOT NULL,
+        url TEXT NOT NULL,
+        summary TEXT NOT NULL,
+        published TEXT,
+        updated_at TEXT NOT NULL,
+        UNIQUE(source, url)
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_news_published
+        ON news(published DESC);
+
+    CREATE INDEX IF NOT EXISTS idx_news_source
+        ON news(source);",
+    // 2: rich metadata and user state. tags/tickers are JSON arrays; NULL
+    // tickers mark rows stored before extraction existed.
+    "ALTER TABLE news ADD COLUMN author TEXT;
+    ALTER TABLE news ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';
+    ALTER TABLE news ADD COLUMN tickers TEXT;
+    ALTER TABLE news ADD COLUMN image_url TEXT;
+    ALTER TABLE news ADD COLUMN content TEXT;
+    ALTER TABLE news ADD COLUMN language TEXT;
+    ALTER TABLE news ADD COLUMN score REAL NOT NULL DEFAULT 0;
+    ALTER TABLE news ADD COLUMN source_kind TEXT NOT NULL DEFAULT 'rss';
+    ALTER TABLE news ADD COLUMN is_read INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE news ADD COLUMN starred INTEGER NOT NULL DEFAULT 0;
+    UPDATE news SET source_kind = 'api' WHERE source NOT LIKE 'RSS\\_%' ESCAPE '\\';",
+    // 3: sources as rows; news items reference them by id. Existing items are
+    // backfilled with ids derived from their source name, minus the RSS_ prefix.
+    "CREATE TABLE sources (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        kind TEXT NOT NULL DEFAULT 'rss',
+        url TEXT,
+        source_group TEXT,
+        enabled INTEGER NOT NULL DEFAULT 1,
+        priority INTEGER NOT NULL DEFAULT 0,
+        color TEXT,
+        last_fetch TEXT
+    );
+    ALTER TABLE news ADD COLUMN source_id TEXT REFERENCES sources(id);
+    UPDATE news SET source_id =
+        lower(replace(substr(source, CASE WHEN source LIKE 'RSS\\_%' ESCAPE '\\' THEN 5 ELSE 1 END), ' ', '-'));
+    INSERT OR IGNORE INTO sources (id, name, kind)
+        SELECT source_id,
+               substr(source, CASE WHEN source LIKE 'RSS\\_%' ESCAPE '\\' THEN 5 ELSE 1 END),
+               source_kind
+        FROM news GROUP BY source_id;
+    CREATE INDEX idx_news_source_id ON news(source_id);",
+    // 4: drop the legacy RSS_ prefix from stored source names. A prefixed row
+    // whose unprefixed twin already exists would violate UNIQUE(source, url).
+    "DELETE FROM news
+        WHERE source LIKE 'RSS\\_%' ESCAPE '\\'
+          AND EXISTS (SELECT 1 FROM news AS twin
+                      WHERE twin.source = trim(substr(news.source, 5))
+                        AND twin.url = news.url);
+    UPDATE news SET source = trim(substr(source, 5))
+        WHERE source LIKE 'RSS\\_%' ESCAPE '\\';",
+    // 5: resume position of interrupted backfill runs, keyed by task
+    "CREATE TABLE backfill_progress (
+        task TEXT PRIMARY KEY,
+        last_id TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );",
+    // 6: date of the scheduled event an item announces (YYYY-MM-DD)
+    "ALTER TABLE news ADD COLUMN event_date TEXT;
+    CREATE INDEX idx_news_event_date ON news(event_date) WHERE event_date IS NOT NULL;",
+    // 7: headline as it was before the source last edited it
+    "ALTER TABLE news ADD COLUMN previous_title TEXT;
+    ALTER TABLE news ADD COLUMN previous_summary TEXT;
+    ALTER TABLE news ADD COLUMN revised_at TEXT;",
+    // 8: status messages kept across sessions
+    "CREATE TABLE status_log (
+        id INTEGER PRIMARY KEY,
+        level TEXT NOT NULL,
+        text TEXT NOT NULL,
+        count INTEGER NOT NULL DEFAULT 1,
+        logged_at TEXT NOT NULL
+    );",
+    // 9: fetch history behind each source's reliability score
+    "ALTER TABLE sources ADD COLUMN fetch_count INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE sources ADD COLUMN success_count INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE sources ADD COLUMN new_items INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE sources ADD COLUMN duplicate_items INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE sources ADD COLUMN lag_secs REAL NOT NULL DEFAULT 0;",
+    // 10: where a source's feed permanently moved
+    "ALTER TABLE sources ADD COLUMN moved_to TEXT;",
+    // 11: stories followed for new coverage; keywords and tickers are JSON arrays
+    "CREATE TABLE followed_stories (
+        id INTEGER PRIMARY KEY,
+        item_id TEXT NOT NULL UNIQUE,
+        title TEXT NOT NULL,
+        keywords TEXT NOT NULL,
+        tickers TEXT NOT NULL,
+        followed_at TEXT NOT NULL
+    );",
+    // 12: comment counts from aggregators
+    "ALTER TABLE news ADD COLUMN comments INTEGER NOT NULL DEFAULT 0;",
+    // 13: when a rate-limited source may be fetched again
+    "ALTER TABLE sources ADD COLUMN cooldown_until TEXT;",
+    // 14: other sources' copies of a stored story, collapsed by deduplication
+    "CREATE TABLE duplicates (
+        item_id TEXT NOT NULL REFERENCES news(id) ON DELETE CASCADE,
+        source TEXT NOT NULL,
+        url TEXT NOT NULL,
+        title TEXT NOT NULL,
+        published TEXT NOT NULL,
+        PRIMARY KEY (source, url)
+    );
+    CREATE INDEX idx_duplicates_item_id ON duplicates(item_id);",
+    // 15: when user state was last set, and deleted articles, for syncing
+    // state between machines (last writer wins)
+    "ALTER TABLE news ADD COLUMN read_changed_at TEXT;
+    ALTER TABLE news ADD COLUMN starred_changed_at TEXT;
+    ALTER TABLE news ADD COLUMN tags_changed_at TEXT;
+    CREATE TABLE tombstones (
+        source TEXT NOT NULL,
+        url TEXT NOT NULL,
+        item_id TEXT NOT NULL,
+        deleted_at TEXT NOT NULL,
+        PRIMARY KEY (source, url)
+    );",
+    // 16: tickers as rows, so articles can be looked up by ticker. Rows
+    // stored before v2 have none until `backfill --tickers`.
+    "CREATE TABLE news_tickers (
+        item_id TEXT NOT NULL REFERENCES news(id) ON DELETE CASCADE,
+        ticker TEXT NOT NULL,
+        PRIMARY KEY (item_id, ticker)
+    );
+    CREATE INDEX idx_news_tickers_ticker ON news_tickers(ticker);
+    INSERT OR IGNORE INTO news_tickers (item_id, ticker)
+        SELECT news.id, json_each.value FROM news, json_each(news.tickers)
+        WHERE news.tickers IS NOT NULL;",
+    // 17: read/starred state set here and not yet sent to the item's remote
+    // server; until it is, fetching the item doesn't overwrite it
+    "ALTER TABLE news ADD COLUMN state_pending INTEGER NOT NULL DEFAULT 0;",
+];
+
+/// Migrations applied so far; errors if the database is from a newer build
+fn schema_version(conn: &Connection) -> Result<usize> {
+    let current: usize = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .context("Failed to read schema version")?;
+    if current > MIGRATIONS.len() {
+        anyhow::bail!(
+            "Database schema version {} is newer than this build supports ({})",
+            current,
+            MIGRATIONS.len()
+        );
+    }
+    Ok(current)
+}
+
+/// Check, without changing anything, that the schema is the one this build
+/// expects. Used for databases opened read-only, which can't be migrated.
+pub fn check_schema(conn: &Connection) -> Result<()> {
+    let current = schema_version(conn)?;
+    if current < MIGRATIONS.len() {
+        anyhow::bail!(
+            "Database schema version {} is older than this build's ({}); \
+             open it once without --read-only to upgrade it",
+            current,
+            MIGRATIONS.len()
+        );
+    }
+    Ok(())
+}
+
+/// Bring the schema up to date, each migration in its own transaction
+pub fn migrate(conn: &mut Connection) -> Result<()> {
+    let current = schema_version(conn)?;
+
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(current) {
+        let version = index + 1;
+        let tx = conn.transaction()?;
+        tx.execute_batch(sql)
+            .with_context(|| format!("Failed to apply database migration {}", version))?;
+        // PRAGMA doesn't take bound parameters
+        tx.execute_batch(&format!("PRAGMA user_version = {}", version))?;
+        tx.commit()?;
+    }
+    Ok(())
+}
@@ -1 +1,3 @@
+mod migrations;
 pub mod sqlite;
+pub mod state;
@@ -1,15 +1,44 @@
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
 use std::fs;
 use std::path::Path;
 
-use crate::models::NewsItem;
-use rusqlite::{params, Connection};
+use super::migrations::{check_schema, migrate};
+use super::state::{ItemState, StateFile, StateImport, Tombstone, STATE_VERSION};
+use crate::models::{
+    dedup::SAME_STORY_HOURS,
+    extract_tickers,
+    id::{disambiguate, display_id},
+    normalize_source_name, source_id, ticker_filter, FollowedStory, NewsItem, Revision, Source,
+    SourceKind, SourceStats, StoryIndex,
+};
+use rusqlite::OptionalExtension;
+use rusqlite::{params, Connection, DatabaseName, OpenFlags};
+use tracing::warn;
+
+/// Columns read by [`news_from_row`], in order
+const NEWS_COLUMNS: &str = "id, source, title, url, summary, published, updated_at,
+    author, tags, tickers, image_url, content, language, score,
+    source_kind, is_read, starred, source_id, event_date,
+    previous_title, previous_summary, revised_at, comments,
+    (SELECT json_group_array(DISTINCT source) FROM duplicates WHERE item_id = news.id)";
+
+/// How long a read-only reader waits for a writer's lock
+const READ_ONLY_BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
 pub struct NewsDB {
     conn: Connection,
 }
 
+/// A status message as kept in the status log
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusLogEntry {
+    pub level: String,
+    pub text: String,
+    pub count: usize,
+    pub logged_at: DateTime<Utc>,
+}
+
 impl NewsDB {
     pub fn new(path: &str) -> Result<Self> {
         // Create parent directory if it doesn't exist
@@ -17,82 +46,1421 @@ impl NewsDB {
             fs::create_dir_all(parent).context("Failed to create database directory")?;
         }
 
-        let conn =
+        let mut conn =
             Connection::open(path).context(format!("Failed to open database at {}", path))?;
 
-        conn.execute_batch(
-            "CREATE TABLE IF NOT EXISTS news (
-                id TEXT PRIMARY KEY,
-                source TEXT NOT NULL,
-                title TEXT NOT NULL,
-                url TEXT NOT NULL,
-                summary TEXT NOT NULL,
-                published TEXT,
-                updated_at TEXT NOT NULL,
-                UNIQUE(source, url)
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_news_published
-                ON news(published DESC);
-
-            CREATE INDEX IF NOT EXISTS idx_news_source
-                ON news(source);",
+        migrate(&mut conn)?;
+        conn.execute_batch("PRAGMA foreign_keys = ON")
+            .context("Failed to enable foreign keys")?;
+
+        Ok(Self { conn })
+    }
+
+    /// Open an existing database without ever writing to it, e.g. one a
+    /// daemon keeps up to date. Writes fail, and the schema must already be
+    /// current since it can't be migrated.
+    pub fn open_read_only(path: &str) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
         )
-        .context("Failed to create news table and indexes")?;
+        .context(format!("Failed to open database at {} read-only", path))?;
+        // Wait out the writer's transactions rather than failing reads
+        conn.busy_timeout(READ_ONLY_BUSY_TIMEOUT)
+            .context("Failed to set busy timeout")?;
+        check_schema(&conn)?;
 
         Ok(Self { conn })
     }
 
-    // currently implemented as an upsert
-    pub fn insert(&self, item: &NewsItem) -> Result<()> {
+    /// Changes whenever another connection commits to the database; this
+    /// connection's own writes leave it as it is
+    pub fn data_version(&self) -> Result<i64> {
+        self.conn
+            .query_row("PRAGMA data_version", [], |row| row.get(0))
+            .context("Failed to read database data version")
+    }
+
+    /// Whether the database was opened with [`NewsDB::open_read_only`]
+    pub fn is_read_only(&self) -> bool {
+        self.conn.is_readonly(DatabaseName::Main).unwrap_or(false)
+    }
+
+    // currently implemented as an upsert. Read/starred state belongs to the
+    // user, so re-fetching an item never resets it, except for items from a
+    // remote server, which owns that state once changes made here have been
+    // sent to it (see [`NewsDB::state_pushed`]). An item whose URL is
+    // already stored for its source updates that row in place, even if its id
+    // changed (e.g. a feed rewrote its GUIDs). An id already stored for a
+    // different URL is a collision; the item is stored under a disambiguated id.
+    // When an update changes the title or summary, the old ones are kept as
+    // the item's previous revision. Deleted items are not stored again,
+    // tags synced from another machine win over the source's, and article
+    // text fetched from the page is kept when the source sends none.
+    //
+    // Returns whether the item was new.
+    pub fn insert(&self, item: &NewsItem) -> Result<bool> {
+        let source = normalize_source_name(&item.source);
+        if self.deleted_at(source, &item.url)?.is_some() {
+            return Ok(false);
+        }
+        let id = if let Some(id) = self.id_for_url(source, &item.url)? {
+            id
+        } else {
+            match self.stored_url(&item.id)? {
+                Some(url) if url != item.url => {
+                    let id = disambiguate(&item.id, &item.url);
+                    warn!(
+                        "ID collision on {}: {} vs {}; storing as {}",
+                        display_id(&item.id),
+                        url,
+                        item.url,
+                        display_id(&id)
+                    );
+                    id
+                }
+                _ => item.id.clone(),
+            }
+        };
+        let is_new = self.stored_url(&id)?.is_none();
+        // Items from a source that was never synced still need its row
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO sources (id, name, kind) VALUES (?1, ?2, ?3)",
+                params![item.source_id, source, item.source_kind.as_str()],
+            )
+            .context("Failed to insert source")?;
         self.conn
             .execute(
                 "INSERT INTO news
-                (id, source, title, url, summary, published, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                (id, source, title, url, summary, published, updated_at,
+                 author, tags, tickers, image_url, content, language, score,
+                 source_kind, is_read, starred, source_id, event_date, comments)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                    ?19, ?20)
             ON CONFLICT(id) DO UPDATE SET
+                previous_title = CASE WHEN title != excluded.title
+                    OR summary != excluded.summary THEN title ELSE previous_title END,
+                previous_summary = CASE WHEN title != excluded.title
+                    OR summary != excluded.summary THEN summary ELSE previous_summary END,
+                revised_at = CASE WHEN title != excluded.title
+                    OR summary != excluded.summary THEN excluded.updated_at ELSE revised_at END,
                 title = excluded.title,
                 summary = excluded.summary,
                 url = excluded.url,
-                updated_at = excluded.updated_at",
+                updated_at = excluded.updated_at,
+                author = excluded.author,
+                tags = CASE WHEN tags_changed_at IS NULL THEN excluded.tags ELSE tags END,
+                tickers = excluded.tickers,
+                image_url = excluded.image_url,
+                content = COALESCE(excluded.content, content),
+                language = excluded.language,
+                score = excluded.score,
+                comments = excluded.comments,
+                source_kind = excluded.source_kind,
+                event_date = excluded.event_date,
+                is_read = CASE WHEN excluded.source_kind = 'remote' AND state_pending = 0
+                    THEN excluded.is_read ELSE is_read END,
+                starred = CASE WHEN excluded.source_kind = 'remote' AND state_pending = 0
+                    THEN excluded.starred ELSE starred END",
                 params![
-                    item.id,
-                    item.source,
+                    id,
+                    source,
                     item.title,
                     item.url,
                     item.summary,
                     item.published.to_rfc3339(),
                     item.updated_at.to_rfc3339(),
+                    item.author,
+                    serde_json::to_string(&item.tags)?,
+                    serde_json::to_string(&item.tickers)?,
+                    item.image_url,
+                    item.content,
+                    item.language,
+                    item.score,
+                    item.source_kind.as_str(),
+                    item.read,
+                    item.starred,
+                    item.source_id,
+                    item.event_date,
+                    item.comments,
                 ],
             )
             .context("Failed to upsert news item")?;
+        self.store_tickers(&id, &item.tickers)?;
+        Ok(is_new)
+    }
+
+    /// Replace the item's rows in `news_tickers` with `tickers`
+    fn store_tickers(&self, id: &str, tickers: &[String]) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM news_tickers WHERE item_id = ?1", params![id])
+            .context("Failed to clear tickers")?;
+        let mut stmt = self.conn.prepare_cached(
+            "INSERT OR IGNORE INTO news_tickers (item_id, ticker) VALUES (?1, ?2)",
+        )?;
+        for ticker in tickers {
+            stmt.execute(params![id, ticker])
+                .context("Failed to store ticker")?;
+        }
+        Ok(())
+    }
+
+    /// Whether another source already stored an article at `url`
+    pub fn is_duplicate(&self, source: &str, url: &str) -> Result<bool> {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM news WHERE url = ?1 AND source != ?2 LIMIT 1",
+                params![url, normalize_source_name(source)],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|found| found.is_some())
+            .context("Failed to look up news item")
+    }
+
+    /// Whether `item`'s source already has a different article with the same
+    /// headline published within `window` of it, i.e. the item is a repost
+    /// under a new GUID or URL
+    pub fn is_repost(&self, item: &NewsItem, window: chrono::Duration) -> Result<bool> {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM news
+                WHERE source = ?1 AND url != ?2 AND lower(trim(title)) = lower(trim(?3))
+                    AND published BETWEEN ?4 AND ?5
+                LIMIT 1",
+                params![
+                    normalize_source_name(&item.source),
+                    item.url,
+                    item.title,
+                    (item.published - window).to_rfc3339(),
+                    (item.published + window).to_rfc3339(),
+                ],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|found| found.is_some())
+            .context("Failed to look up news item")
+    }
+
+    fn stored_url(&self, id: &str) -> Result<Option<String>> {
+        self.conn
+            .query_row("SELECT url FROM news WHERE id = ?1", params![id], |row| {
+                row.get(0)
+            })
+            .optional()
+            .context("Failed to look up news item")
+    }
+
+    fn id_for_url(&self, source: &str, url: &str) -> Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT id FROM news WHERE source = ?1 AND url = ?2",
+                params![source, url],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to look up news item")
+    }
+
+    /// Stored articles published from `since` to `until`, widened by how far
+    /// apart copies of a story may be, to find which ones new articles repeat
+    pub fn story_index(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        threshold: f32,
+    ) -> Result<StoryIndex> {
+        let window = chrono::Duration::hours(SAME_STORY_HOURS);
+        let mut stmt = self.conn.prepare(
+            "SELECT id, source, title, url, published FROM news
+             WHERE published BETWEEN ?1 AND ?2",
+        )?;
+        let mut rows = stmt.query(params![
+            (since - window).to_rfc3339(),
+            (until + window).to_rfc3339()
+        ])?;
+        let mut index = StoryIndex::new(threshold);
+        while let Some(row) = rows.next()? {
+            let (id, source, title, url): (String, String, String, String) =
+                (row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?);
+            let published: String = row.get(4)?;
+            let Ok(published) = published.parse() else {
+                continue;
+            };
+            index.add(&id, &source, &title, &url, published);
+        }
+        Ok(index)
+    }
+
+    /// Record `item` as another source's copy of stored article `kept`
+    /// instead of storing it. Returns whether it wasn't recorded before.
+    pub fn record_duplicate(&self, kept: &str, item: &NewsItem) -> Result<bool> {
+        let inserted = self
+            .conn
+            .execute(
+                "INSERT OR IGNORE INTO duplicates (item_id, source, url, title, published)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    kept,
+                    normalize_source_name(&item.source),
+                    item.url,
+                    item.title,
+                    item.published.to_rfc3339()
+                ],
+            )
+            .context("Failed to record duplicate")?;
+        Ok(inserted > 0)
+    }
+
+    /// The most recent stored item with this URL, from any source
+    pub fn find_by_url(&self, url: &str) -> Result<Option<NewsItem>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM news WHERE url = ?1 ORDER BY published DESC LIMIT 1",
+                    NEWS_COLUMNS
+                ),
+                params![url],
+                news_from_row,
+            )
+            .optional()
+            .context("Failed to look up news item by URL")
+    }
+
+    /// Delete an item at `at`, leaving a tombstone so it isn't fetched
+    /// again and the deletion syncs to other machines
+    pub fn delete(&self, id: &str, at: DateTime<Utc>) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO tombstones (source, url, item_id, deleted_at)
+                 SELECT source, url, id, ?2 FROM news WHERE id = ?1",
+                params![id, at.to_rfc3339()],
+            )
+            .context("Failed to record deletion")?;
+        self.conn
+            .execute("DELETE FROM news WHERE id = ?1", params![id])
+            .context("Failed to delete news item")?;
         Ok(())
     }
 
+    /// Store a deleted item again, e.g. on undo
+    pub fn undelete(&self, item: &NewsItem) -> Result<()> {
+        self.conn
+            .execute(
+                "DELETE FROM tombstones WHERE source = ?1 AND url = ?2",
+                params![normalize_source_name(&item.source), item.url],
+            )
+            .context("Failed to forget deletion")?;
+        self.insert(item)?;
+        Ok(())
+    }
+
+    /// When the item at `url` was deleted from `source`, if it was
+    fn deleted_at(&self, source: &str, url: &str) -> Result<Option<DateTime<Utc>>> {
+        let at: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT deleted_at FROM tombstones WHERE source = ?1 AND url = ?2",
+                params![source, url],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to look up deletion")?;
+        Ok(at.and_then(|at| at.parse().ok()))
+    }
+
+    /// Store article text fetched from the item's page
+    pub fn set_content(&self, id: &str, content: &str) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE news SET content = ?2 WHERE id = ?1",
+                params![id, content],
+            )
+            .context("Failed to store article text")?;
+        Ok(())
+    }
+
+    /// Set an item's read state, as changed at `at`. A remote server's item
+    /// keeps it until the change has been sent there, see
+    /// [`NewsDB::state_pushed`].
+    pub fn set_read(&self, id: &str, read: bool, at: DateTime<Utc>) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE news SET is_read = ?2, read_changed_at = ?3,
+                    state_pending = CASE WHEN source_kind = 'remote' THEN 1 ELSE 0 END
+                 WHERE id = ?1",
+                params![id, read, at.to_rfc3339()],
+            )
+            .context("Failed to update read state")?;
+        Ok(())
+    }
+
+    /// Star or unstar an item. Like read state, a remote server's item keeps
+    /// it until the change has been sent there.
+    pub fn set_starred(&self, id: &str, starred: bool, at: DateTime<Utc>) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE news SET starred = ?2, starred_changed_at = ?3,
+                    state_pending = CASE WHEN source_kind = 'remote' THEN 1 ELSE 0 END
+                 WHERE id = ?1",
+                params![id, starred, at.to_rfc3339()],
+            )
+            .context("Failed to update starred state")?;
+        Ok(())
+    }
+
+    /// Replace an item's tags; fetching it again keeps these
+    pub fn set_tags(&self, id: &str, tags: &[String], at: DateTime<Utc>) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE news SET tags = ?2, tags_changed_at = ?3 WHERE id = ?1",
+                params![id, serde_json::to_string(tags)?, at.to_rfc3339()],
+            )
+            .context("Failed to update tags")?;
+        Ok(())
+    }
+
+    /// Record that the item's remote server now has the read/starred state
+    /// that was sent, so fetching the item takes the server's state again.
+    /// A change made while it was being sent stays pending.
+    pub fn state_pushed(&self, id: &str, read: bool, starred: bool) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE news SET state_pending = 0
+                 WHERE id = ?1 AND is_read = ?2 AND starred = ?3",
+                params![id, read, starred],
+            )
+            .context("Failed to record sent state")?;
+        Ok(())
+    }
+
+    /// Remote servers' articles whose read/starred state was set here and
+    /// hasn't been sent to the server yet
+    pub fn pending_state(&self) -> Result<Vec<NewsItem>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM news WHERE state_pending = 1 ORDER BY id",
+            NEWS_COLUMNS
+        ))?;
+        let items = stmt
+            .query_map([], news_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to load unsent read state")?;
+        Ok(items)
+    }
+
+    /// Articles whose read, starred or tags state was set here or synced
+    /// from elsewhere, and deleted articles, for another machine to merge.
+    /// Remote servers' articles are left out; the server syncs those.
+    pub fn export_state(&self, now: DateTime<Utc>) -> Result<StateFile> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {}, read_changed_at, starred_changed_at, tags_changed_at FROM news
+             WHERE source_kind != 'remote' AND (read_changed_at IS NOT NULL
+                OR starred_changed_at IS NOT NULL OR tags_changed_at IS NOT NULL)
+             ORDER BY id",
+            NEWS_COLUMNS
+        ))?;
+        let items = stmt
+            .query_map([], |row| {
+                Ok(ItemState {
+                    item: news_from_row(row)?,
+                    read_at: parse_time(row.get(24)?),
+                    starred_at: parse_time(row.get(25)?),
+                    tags_at: parse_time(row.get(26)?),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read article state")?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT item_id, source, url, deleted_at FROM tombstones ORDER BY source, url",
+        )?;
+        let deleted = stmt
+            .query_map([], |row| {
+                let deleted_at: String = row.get(3)?;
+                Ok(Tombstone {
+                    id: row.get(0)?,
+                    source: row.get(1)?,
+                    url: row.get(2)?,
+                    deleted_at: deleted_at.parse().unwrap_or(now),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read deletions")?;
+
+        Ok(StateFile {
+            version: STATE_VERSION,
+            exported_at: now,
+            items,
+            deleted,
+        })
+    }
+
+    /// Merge state exported by another machine. Each value is taken if it
+    /// was set later than the one stored here; a deletion wins over state
+    /// set before it, and state set after a deletion brings the article back.
+    pub fn import_state(&self, state: &StateFile) -> Result<StateImport> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut summary = StateImport::default();
+
+        for remote in &state.items {
+            let item = &remote.item;
+            let source = normalize_source_name(&item.source);
+            if let Some(deleted_at) = self.deleted_at(source, &item.url)? {
+                if remote.changed_at().is_none_or(|at| at <= deleted_at) {
+                    continue;
+                }
+                self.conn.execute(
+                    "DELETE FROM tombstones WHERE source = ?1 AND url = ?2",
+                    params![source, item.url],
+                )?;
+            }
+
+            let local = self.local_state(source, &item.url)?;
+            let id = match &local {
+                Some(local) => local.id.clone(),
+                None => {
+                    if self.insert(item)? {
+                        summary.added += 1;
+                    }
+                    match self.id_for_url(source, &item.url)? {
+                        Some(id) => id,
+                        None => continue,
+                    }
+                }
+            };
+            let newer = |remote: Option<DateTime<Utc>>, local: Option<DateTime<Utc>>| {
+                remote.is_some_and(|remote| local.is_none_or(|local| remote > local))
+            };
+            let mut changed = false;
+            if newer(remote.read_at, local.as_ref().and_then(|l| l.read_at)) {
+                self.conn.execute(
+                    "UPDATE news SET is_read = ?2, read_changed_at = ?3 WHERE id = ?1",
+                    params![id, item.read, remote.read_at.map(|at| at.to_rfc3339())],
+                )?;
+                changed = true;
+            }
+            if newer(remote.starred_at, local.as_ref().and_then(|l| l.starred_at)) {
+                self.conn.execute(
+                    "UPDATE news SET starred = ?2, starred_changed_at = ?3 WHERE id = ?1",
+                    params![
+                        id,
+                        item.starred,
+                        remote.starred_at.map(|at| at.to_rfc3339())
+                    ],
+                )?;
+                changed = true;
+            }
+            if newer(remote.tags_at, local.as_ref().and_then(|l| l.tags_at)) {
+                self.conn.execute(
+                    "UPDATE news SET tags = ?2, tags_changed_at = ?3 WHERE id = ?1",
+                    params![
+                        id,
+                        serde_json::to_string(&item.tags)?,
+                        remote.tags_at.map(|at| at.to_rfc3339())
+                    ],
+                )?;
+                changed = true;
+            }
+            if changed && local.is_some() {
+                summary.updated += 1;
+            }
+        }
+
+        for tombstone in &state.deleted {
+            let source = normalize_source_name(&tombstone.source);
+            if let Some(local) = self.local_state(source, &tombstone.url)? {
+                if local
+                    .changed_at()
+                    .is_some_and(|at| at >= tombstone.deleted_at)
+                {
+                    // Set here after it was deleted there; it stays
+                    continue;
+                }
+                self.delete(&local.id, tombstone.deleted_at)?;
+                summary.deleted += 1;
+            } else if self
+                .deleted_at(source, &tombstone.url)?
+                .is_none_or(|at| at < tombstone.deleted_at)
+            {
+                self.conn.execute(
+                    "INSERT OR REPLACE INTO tombstones (source, url, item_id, deleted_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        source,
+                        tombstone.url,
+                        tombstone.id,
+                        tombstone.deleted_at.to_rfc3339()
+                    ],
+                )?;
+            }
+        }
+
+        tx.commit().context("Failed to save synced state")?;
+        Ok(summary)
+    }
+
+    /// Id and state timestamps of the stored item at `url` from `source`
+    fn local_state(&self, source: &str, url: &str) -> Result<Option<LocalState>> {
+        self.conn
+            .query_row(
+                "SELECT id, read_changed_at, starred_changed_at, tags_changed_at
+                 FROM news WHERE source = ?1 AND url = ?2",
+                params![source, url],
+                |row| {
+                    Ok(LocalState {
+                        id: row.get(0)?,
+                        read_at: parse_time(row.get(1)?),
+                        starred_at: parse_time(row.get(2)?),
+                        tags_at: parse_time(row.get(3)?),
+                    })
+                },
+            )
+            .optional()
+            .context("Failed to look up article state")
+    }
+
     pub fn load_all(&self) -> Result<Vec<NewsItem>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM news ORDER BY published DESC LIMIT 500",
+            NEWS_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([], news_from_row)?;
+
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Items published since `since` whose title, summary, content or
+    /// tickers contain every word of `query` (case-insensitive), newest first.
+    /// A `$` word such as `$AAPL` only matches items tagged with that ticker.
+    pub fn search(&self, query: &str, since: DateTime<Utc>) -> Result<Vec<NewsItem>> {
+        let mut sql = format!("SELECT {} FROM news WHERE published >= ?1", NEWS_COLUMNS);
+        let mut values = vec![since.to_rfc3339()];
+        for word in query.split_whitespace() {
+            if let Some(ticker) = ticker_filter(word) {
+                values.push(ticker);
+                let n = values.len();
+                sql.push_str(&format!(
+                    " AND id IN (SELECT item_id FROM news_tickers WHERE ticker = ?{n})"
+                ));
+                continue;
+            }
+            values.push(format!(
+                "%{}%",
+                word.replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_")
+            ));
+            let n = values.len();
+            sql.push_str(&format!(
+                " AND (title LIKE ?{n} ESCAPE '\\' OR summary LIKE ?{n} ESCAPE '\\'
+                    OR content LIKE ?{n} ESCAPE '\\' OR tickers LIKE ?{n} ESCAPE '\\')"
+            ));
+        }
+        sql.push_str(" ORDER BY published DESC");
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(&values), news_from_row)?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Publish times of the source's newest `limit` items, newest first
+    pub fn publish_times(&self, source_id: &str, limit: usize) -> Result<Vec<DateTime<Utc>>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, source, title, url, summary, published, updated_at FROM news
-             ORDER BY published DESC LIMIT 500",
+            "SELECT published FROM news WHERE source_id = ?1 AND published IS NOT NULL
+             ORDER BY published DESC LIMIT ?2",
         )?;
+        let rows = stmt.query_map(params![source_id, limit], |row| row.get::<_, String>(0))?;
+        Ok(rows.filter_map(|r| r.ok()?.parse().ok()).collect())
+    }
 
-        let rows = stmt.query_map([], |row| {
-            let published_str: String = row.get(5)?;
-            let published = published_str.parse().unwrap_or_else(|_| Utc::now());
+    /// Items announcing events on or after `from`, soonest first
+    pub fn load_events(&self, from: NaiveDate) -> Result<Vec<NewsItem>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM news WHERE event_date >= ?1 ORDER BY event_date, published",
+            NEWS_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![from], news_from_row)?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Number of stored items with an id after `after` (all items for None)
+    pub fn count_news_after(&self, after: Option<&str>) -> Result<usize> {
+        self.conn
+            .query_row(
+                "SELECT COUNT(*) FROM news WHERE id > ?1",
+                params![after.unwrap_or("")],
+                |row| row.get(0),
+            )
+            .context("Failed to count news items")
+    }
+
+    /// Up to `limit` items with ids after `after`, in id order. Used to walk
+    /// the whole table in batches.
+    pub fn load_news_after(&self, after: Option<&str>, limit: usize) -> Result<Vec<NewsItem>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM news WHERE id > ?1 ORDER BY id LIMIT ?2",
+            NEWS_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![after.unwrap_or(""), limit], news_from_row)?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Last item id processed by an interrupted backfill `task`
+    pub fn backfill_cursor(&self, task: &str) -> Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT last_id FROM backfill_progress WHERE task = ?1",
+                params![task],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read backfill progress")
+    }
+
+    /// Save the position of backfill `task`; None clears it
+    pub fn save_backfill_cursor(&self, task: &str, last_id: Option<&str>) -> Result<()> {
+        match last_id {
+            Some(id) => self.conn.execute(
+                "INSERT INTO backfill_progress (task, last_id, updated_at)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(task) DO UPDATE SET
+                    last_id = excluded.last_id,
+                    updated_at = excluded.updated_at",
+                params![task, id, Utc::now().to_rfc3339()],
+            ),
+            None => self.conn.execute(
+                "DELETE FROM backfill_progress WHERE task = ?1",
+                params![task],
+            ),
+        }
+        .context("Failed to save backfill progress")?;
+        Ok(())
+    }
+
+    /// Store the registered sources, keeping each one's `last_fetch`. A
+    /// recorded move is forgotten once the source's URL changes.
+    pub fn sync_sources(&self, sources: &[Source]) -> Result<()> {
+        for source in sources {
+            self.conn
+                .execute(
+                    "INSERT INTO sources
+                    (id, name, kind, url, source_group, enabled, priority, color)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                ON CONFLICT(id) DO UPDATE SET
+                    moved_to = CASE WHEN url IS excluded.url THEN moved_to ELSE NULL END,
+                    name = excluded.name,
+                    kind = excluded.kind,
+                    url = excluded.url,
+                    source_group = excluded.source_group,
+                    enabled = excluded.enabled,
+                    priority = excluded.priority,
+                    color = excluded.color",
+                    params![
+                        source.id,
+                        source.name,
+                        source.kind.as_str(),
+                        source.url,
+                        source.group,
+                        source.enabled,
+                        source.priority,
+                        source.color,
+                    ],
+                )
+                .context("Failed to upsert source")?;
+        }
+        Ok(())
+    }
 
-            let updated_at_str: String = row.get(6)?;
-            let updated_at = updated_at_str.parse().unwrap_or_else(|_| published);
+    /// All known sources, highest priority first
+    pub fn load_sources(&self) -> Result<Vec<Source>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, kind, url, source_group, enabled, priority, color, last_fetch,
+                    fetch_count, success_count, new_items, duplicate_items, lag_secs, moved_to,
+                    cooldown_until
+             FROM sources
+             ORDER BY priority DESC, name",
+        )?;
 
-            Ok(NewsItem {
+        let rows = stmt.query_map([], |row| {
+            let kind: String = row.get(2)?;
+            let last_fetch: Option<String> = row.get(8)?;
+            let cooldown_until: Option<String> = row.get(15)?;
+            Ok(Source {
                 id: row.get(0)?,
-                source: row.get(1)?,
-                title: row.get(2)?,
+                name: row.get(1)?,
+                kind: SourceKind::parse(&kind),
                 url: row.get(3)?,
-                summary: row.get(4)?,
-                published,
-                updated_at,
+                group: row.get(4)?,
+                enabled: row.get(5)?,
+                priority: row.get(6)?,
+                color: row.get(7)?,
+                last_fetch: last_fetch.and_then(|s| s.parse().ok()),
+                stats: SourceStats {
+                    fetches: row.get::<_, i64>(9)? as u64,
+                    successes: row.get::<_, i64>(10)? as u64,
+                    new_items: row.get::<_, i64>(11)? as u64,
+                    duplicates: row.get::<_, i64>(12)? as u64,
+                    lag_secs: row.get(13)?,
+                },
+                moved_to: row.get(14)?,
+                cooldown_until: cooldown_until.and_then(|s| s.parse().ok()),
             })
         })?;
 
         Ok(rows.filter_map(|r| r.ok()).collect())
     }
+
+    /// Record a successful fetch of source `id`
+    pub fn record_fetch(&self, id: &str, at: DateTime<Utc>) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE sources SET last_fetch = ?2 WHERE id = ?1",
+                params![id, at.to_rfc3339()],
+            )
+            .context("Failed to record source fetch")?;
+        Ok(())
+    }
+
+    /// Record that source `id`'s feed permanently moved to `url`
+    pub fn record_move(&self, id: &str, url: &str) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE sources SET moved_to = ?2 WHERE id = ?1",
+                params![id, url],
+            )
+            .context("Failed to record source move")?;
+        Ok(())
+    }
+
+    /// Record that source `id` is rate-limited until `until`, or clear it
+    pub fn record_cooldown(&self, id: &str, until: Option<DateTime<Utc>>) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE sources SET cooldown_until = ?2 WHERE id = ?1",
+                params![id, until.map(|at| at.to_rfc3339())],
+            )
+            .context("Failed to record source cool-down")?;
+        Ok(())
+    }
+
+    /// Add one refresh's stats to source `id`'s totals
+    pub fn record_stats(&self, id: &str, stats: &SourceStats) -> Result<()> {
+        self.conn
+            .execute(
+                "UPDATE sources SET
+                    fetch_count = fetch_count + ?2,
+                    success_count = success_count + ?3,
+                    new_items = new_items + ?4,
+                    duplicate_items = duplicate_items + ?5,
+                    lag_secs = lag_secs + ?6
+                WHERE id = ?1",
+                params![
+                    id,
+                    stats.fetches as i64,
+                    stats.successes as i64,
+                    stats.new_items as i64,
+                    stats.duplicates as i64,
+                    stats.lag_secs
+                ],
+            )
+            .context("Failed to record source stats")?;
+        Ok(())
+    }
+
+    pub fn append_status_log(&self, entry: &StatusLogEntry) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO status_log (level, text, count, logged_at) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    entry.level,
+                    entry.text,
+                    entry.count as i64,
+                    entry.logged_at.to_rfc3339()
+                ],
+            )
+            .context("Failed to write status log")?;
+        Ok(())
+    }
+
+    /// The newest `limit` entries, oldest first
+    pub fn load_status_log(&self, limit: usize) -> Result<Vec<StatusLogEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT level, text, count, logged_at FROM
+                (SELECT * FROM status_log ORDER BY id DESC LIMIT ?1)
+             ORDER BY id",
+        )?;
+        let entries = stmt
+            .query_map(params![limit as i64], |row| {
+                let logged_at: String = row.get(3)?;
+                Ok(StatusLogEntry {
+                    level: row.get(0)?,
+                    text: row.get(1)?,
+                    count: row.get::<_, i64>(2)?.max(1) as usize,
+                    logged_at: logged_at.parse().unwrap_or_else(|_| Utc::now()),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to load status log")?;
+        Ok(entries)
+    }
+
+    /// Store a followed story, returning its row id
+    pub fn follow_story(&self, story: &FollowedStory) -> Result<i64> {
+        self.conn
+            .execute(
+                "INSERT INTO followed_stories (item_id, title, keywords, tickers, followed_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    story.item_id,
+                    story.title,
+                    serde_json::to_string(&story.keywords)?,
+                    serde_json::to_string(&story.tickers)?,
+                    story.followed_at.to_rfc3339(),
+                ],
+            )
+            .context("Failed to follow story")?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    pub fn unfollow_story(&self, id: i64) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM followed_stories WHERE id = ?1", params![id])
+            .context("Failed to unfollow story")?;
+        Ok(())
+    }
+
+    /// Followed stories, oldest first
+    pub fn load_followed_stories(&self) -> Result<Vec<FollowedStory>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, item_id, title, keywords, tickers, followed_at
+            FROM followed_stories ORDER BY id",
+        )?;
+        let stories = stmt
+            .query_map([], |row| {
+                let keywords: String = row.get(3)?;
+                let tickers: String = row.get(4)?;
+                let followed_at: String = row.get(5)?;
+                Ok(FollowedStory {
+                    id: row.get(0)?,
+                    item_id: row.get(1)?,
+                    title: row.get(2)?,
+                    keywords: serde_json::from_str(&keywords).unwrap_or_default(),
+                    tickers: serde_json::from_str(&tickers).unwrap_or_default(),
+                    followed_at: followed_at.parse().unwrap_or_else(|_| Utc::now()),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to load followed stories")?;
+        Ok(stories)
+    }
+
+    /// Drop all but the newest `keep` entries
+    pub fn prune_status_log(&self, keep: usize) -> Result<()> {
+        self.conn
+            .execute(
+                "DELETE FROM status_log WHERE id NOT IN
+                    (SELECT id FROM status_log ORDER BY id DESC LIMIT ?1)",
+                params![keep as i64],
+            )
+            .context("Failed to prune status log")?;
+        Ok(())
+    }
+}
+
+/// State timestamps of a stored item, see [`NewsDB::import_state`]
+struct LocalState {
+    id: String,
+    read_at: Option<DateTime<Utc>>,
+    starred_at: Option<DateTime<Utc>>,
+    tags_at: Option<DateTime<Utc>>,
+}
+
+impl LocalState {
+    fn changed_at(&self) -> Option<DateTime<Utc>> {
+        [self.read_at, self.starred_at, self.tags_at]
+            .into_iter()
+            .flatten()
+            .max()
+    }
+}
+
+fn parse_time(value: Option<String>) -> Option<DateTime<Utc>> {
+    value?.parse().ok()
+}
+
+fn news_from_row(row: &rusqlite::Row) -> rusqlite::Result<NewsItem> {
+    let published_str: String = row.get(5)?;
+    let published = published_str.parse().unwrap_or_else(|_| Utc::now());
+
+    let updated_at_str: String = row.get(6)?;
+    let updated_at = updated_at_str.parse().unwrap_or(published);
+
+    let title: String = row.get(2)?;
+    let summary: String = row.get(4)?;
+    let tags: String = row.get(8)?;
+    // Rows stored before v2 have no tickers column value; derive them
+    let tickers = match row.get::<_, Option<String>>(9)? {
+        Some(json) => serde_json::from_str(&json).unwrap_or_default(),
+        None => extract_tickers(&[&title, &summary]),
+    };
+    let source_kind: String = row.get(14)?;
+    let source: String = row.get(1)?;
+    let source_id = row
+        .get::<_, Option<String>>(17)?
+        .unwrap_or_else(|| source_id(&source));
+    let previous = match (row.get(19)?, row.get(20)?) {
+        (Some(title), Some(summary)) => {
+            let revised_at: Option<String> = row.get(21)?;
+            Some(Revision {
+                title,
+                summary,
+                revised_at: revised_at
+                    .and_then(|at| at.parse().ok())
+                    .unwrap_or(updated_at),
+            })
+        }
+        _ => None,
+    };
+
+    Ok(NewsItem {
+        id: row.get(0)?,
+        source,
+        source_id,
+        title,
+        url: row.get(3)?,
+        summary,
+        published,
+        updated_at,
+        author: row.get(7)?,
+        tags: serde_json::from_str(&tags).unwrap_or_default(),
+        tickers,
+        image_url: row.get(10)?,
+        content: row.get(11)?,
+        language: row.get(12)?,
+        score: row.get(13)?,
+        comments: row.get(22)?,
+        also_reported_by: row
+            .get::<_, Option<String>>(23)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        source_kind: SourceKind::parse(&source_kind),
+        read: row.get(15)?,
+        starred: row.get(16)?,
+        event_date: row.get(18)?,
+        previous,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixed_now;
+    use chrono::TimeZone;
+
+    fn item(id: &str, url: &str) -> NewsItem {
+        NewsItem {
+            id: id.to_string(),
+            source: "CNBC".to_string(),
+            source_id: source_id("CNBC"),
+            title: url.to_string(),
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn source_stats_accumulate() {
+        let db = NewsDB::new(":memory:").unwrap();
+        assert!(db
+            .insert(&item("cnbc-guid-1", "https://example.com/a"))
+            .unwrap());
+        assert!(!db
+            .insert(&item("cnbc-guid-1", "https://example.com/a"))
+            .unwrap());
+        assert!(db.is_duplicate("Reuters", "https://example.com/a").unwrap());
+        assert!(!db.is_duplicate("CNBC", "https://example.com/a").unwrap());
+
+        let mut stats = SourceStats::fetch(true);
+        stats.add_item(Utc::now(), Utc::now(), true);
+        db.record_stats("cnbc", &stats).unwrap();
+        db.record_stats("cnbc", &SourceStats::fetch(false)).unwrap();
+
+        let sources = db.load_sources().unwrap();
+        let stored = sources[0].stats;
+        assert_eq!((stored.fetches, stored.successes), (2, 1));
+        assert_eq!((stored.new_items, stored.duplicates), (1, 1));
+        assert_eq!(stored.reliability(), Some(0.5 * 0.5));
+    }
+
+    #[test]
+    fn read_only_database_reads_but_never_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("news.db");
+        let path = path.to_str().unwrap();
+        assert!(NewsDB::open_read_only(path).is_err());
+
+        let writer = NewsDB::new(path).unwrap();
+        assert!(!writer.is_read_only());
+        writer
+            .insert(&item("cnbc-guid-1", "https://example.com/a"))
+            .unwrap();
+
+        let reader = NewsDB::open_read_only(path).unwrap();
+        assert!(reader.is_read_only());
+        assert_eq!(reader.load_all().unwrap().len(), 1);
+        assert!(reader.set_read("cnbc-guid-1", true, fixed_now()).is_err());
+        assert!(reader
+            .insert(&item("cnbc-guid-2", "https://example.com/b"))
+            .is_err());
+
+        // The writer's later inserts show up on the next load, and the
+        // reader can tell they happened
+        let version = reader.data_version().unwrap();
+        let writer_version = writer.data_version().unwrap();
+        writer
+            .insert(&item("cnbc-guid-3", "https://example.com/c"))
+            .unwrap();
+        assert_ne!(reader.data_version().unwrap(), version);
+        assert_eq!(writer.data_version().unwrap(), writer_version);
+        assert_eq!(reader.load_all().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn collapsed_copies_are_listed_on_the_kept_item() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut kept = item("cnbc-guid-1", "https://cnbc.com/boeing");
+        kept.title = "Boeing halts 737 MAX deliveries after door plug blowout".to_string();
+        db.insert(&kept).unwrap();
+
+        let mut copy = kept.clone();
+        copy.source = "Reuters".to_string();
+        copy.url = "https://reuters.com/boeing".to_string();
+        let index = db
+            .story_index(copy.published, copy.published, 0.85)
+            .unwrap();
+        assert_eq!(index.find(&copy), Some("cnbc-guid-1"));
+        assert!(index.find(&kept).is_none());
+
+        assert!(db.record_duplicate("cnbc-guid-1", &copy).unwrap());
+        assert!(!db.record_duplicate("cnbc-guid-1", &copy).unwrap());
+        assert_eq!(db.load_all().unwrap()[0].also_reported_by, ["Reuters"]);
+
+        // Deleting the story forgets its copies
+        db.delete("cnbc-guid-1", fixed_now()).unwrap();
+        db.undelete(&kept).unwrap();
+        assert!(db.load_all().unwrap()[0].also_reported_by.is_empty());
+    }
+
+    #[test]
+    fn state_syncs_both_ways_and_newest_wins() {
+        let desktop = NewsDB::new(":memory:").unwrap();
+        let laptop = NewsDB::new(":memory:").unwrap();
+        for id in ["a", "b", "c"] {
+            desktop
+                .insert(&item(id, &format!("https://example.com/{}", id)))
+                .unwrap();
+        }
+        desktop.set_read("a", true, fixed_now()).unwrap();
+        desktop.delete("b", fixed_now()).unwrap();
+        laptop.insert(&item("b", "https://example.com/b")).unwrap();
+        laptop.insert(&item("c", "https://example.com/c")).unwrap();
+
+        let state = desktop.export_state(Utc::now()).unwrap();
+        assert_eq!(state.items.len(), 1);
+        assert_eq!(state.deleted.len(), 1);
+        let merged = laptop.import_state(&state).unwrap();
+        assert_eq!(
+            merged,
+            StateImport {
+                added: 1,
+                updated: 0,
+                deleted: 1
+            }
+        );
+        let items = laptop.load_all().unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().any(|i| i.id == "a" && i.read));
+        // The deletion sticks when a feed serves the article again
+        assert!(!laptop.insert(&item("b", "https://example.com/b")).unwrap());
+
+        // Marked unread on the laptop after it was read on the desktop
+        laptop
+            .set_read("a", false, fixed_now() + chrono::Duration::hours(1))
+            .unwrap();
+        let merged = desktop
+            .import_state(&laptop.export_state(Utc::now()).unwrap())
+            .unwrap();
+        assert_eq!(merged.updated, 1);
+        assert!(!desktop.load_all().unwrap().iter().any(|i| i.read));
+        // The desktop's older state doesn't undo it
+        let merged = laptop.import_state(&state).unwrap();
+        assert_eq!(merged, StateImport::default());
+        assert!(!laptop.load_all().unwrap().iter().any(|i| i.read));
+    }
+
+    #[test]
+    fn remote_state_set_here_survives_refetch_until_pushed() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut remote = item("freshrss-1", "https://example.com/1");
+        remote.source_kind = SourceKind::Remote;
+        db.insert(&remote).unwrap();
+
+        // Marked read here; the server still says unread until it's told
+        db.set_read("freshrss-1", true, fixed_now()).unwrap();
+        db.insert(&remote).unwrap();
+        assert!(db.load_all().unwrap()[0].read);
+
+        // Once sent, the server's state wins again
+        db.state_pushed("freshrss-1", true, false).unwrap();
+        db.insert(&remote).unwrap();
+        assert!(!db.load_all().unwrap()[0].read);
+    }
+
+    #[test]
+    fn remote_state_syncs_both_ways() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut remote = item("nextcloud-7", "https://example.com/7");
+        remote.source_kind = SourceKind::Remote;
+        db.insert(&remote).unwrap();
+        assert!(db.pending_state().unwrap().is_empty());
+
+        // Starred on the server: taken as it is
+        remote.starred = true;
+        db.insert(&remote).unwrap();
+        assert!(db.load_all().unwrap()[0].starred);
+
+        // Read here: waits to be sent, with the server's star kept
+        db.set_read("nextcloud-7", true, fixed_now()).unwrap();
+        let pending = db.pending_state().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert!(pending[0].read && pending[0].starred);
+
+        // Unread again before the push finished: the sent state is stale
+        db.set_read("nextcloud-7", false, fixed_now()).unwrap();
+        db.state_pushed("nextcloud-7", true, true).unwrap();
+        assert_eq!(db.pending_state().unwrap().len(), 1);
+        db.state_pushed("nextcloud-7", false, true).unwrap();
+        assert!(db.pending_state().unwrap().is_empty());
+
+        // Local items never wait on a server
+        db.insert(&item("cnbc-1", "https://example.com/c")).unwrap();
+        db.set_read("cnbc-1", true, fixed_now()).unwrap();
+        assert!(db.pending_state().unwrap().is_empty());
+    }
+
+    #[test]
+    fn cooldown_survives_source_sync() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let source = Source::new("Benzinga", SourceKind::Api);
+        db.sync_sources(std::slice::from_ref(&source)).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 3, 14, 14, 32, 0).unwrap();
+        db.record_cooldown("benzinga", Some(until)).unwrap();
+
+        // Re-syncing on config reload keeps it
+        db.sync_sources(&[source]).unwrap();
+        assert_eq!(db.load_sources().unwrap()[0].cooldown_until, Some(until));
+
+        db.record_cooldown("benzinga", None).unwrap();
+        assert_eq!(db.load_sources().unwrap()[0].cooldown_until, None);
+    }
+
+    #[test]
+    fn reposted_headline_is_detected_within_window() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut first = item("cnbc-guid-1", "https://example.com/a");
+        first.title = "Stocks rally".to_string();
+        db.insert(&first).unwrap();
+
+        let mut repost = item("cnbc-guid-2", "https://example.com/a?v=2");
+        repost.title = " stocks rally".to_string();
+        repost.published = first.published + chrono::Duration::minutes(10);
+        let window = chrono::Duration::minutes(30);
+        assert!(db.is_repost(&repost, window).unwrap());
+        // The stored copy itself isn't a repost
+        assert!(!db.is_repost(&first, window).unwrap());
+
+        repost.published = first.published + chrono::Duration::hours(2);
+        assert!(!db.is_repost(&repost, window).unwrap());
+    }
+
+    #[test]
+    fn search_matches_every_word() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut first = item("cnbc-guid-1", "https://example.com/a");
+        first.title = "Nvidia beats on data center revenue".to_string();
+        let mut second = item("cnbc-guid-2", "https://example.com/b");
+        second.title = "Nvidia_100% rally".to_string();
+        db.insert(&first).unwrap();
+        db.insert(&second).unwrap();
+
+        let since = first.published - chrono::Duration::days(1);
+        let ids = |query: &str| -> Vec<String> {
+            db.search(query, since)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.id)
+                .collect()
+        };
+        assert_eq!(ids("nvidia REVENUE"), ["cnbc-guid-1"]);
+        assert_eq!(ids("_100%"), ["cnbc-guid-2"]);
+        assert!(ids("nvidia guidance").is_empty());
+    }
+
+    #[test]
+    fn search_by_ticker_uses_stored_tickers() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut chips = item("cnbc-guid-1", "https://example.com/a");
+        chips.tickers = vec!["NVDA".to_string(), "AMD".to_string()];
+        let mut mention = item("cnbc-guid-2", "https://example.com/b");
+        mention.title = "Why NVDA bulls are wrong".to_string();
+        db.insert(&chips).unwrap();
+        db.insert(&mention).unwrap();
+
+        let since = chips.published - chrono::Duration::days(1);
+        let ids = |query: &str| -> Vec<String> {
+            db.search(query, since)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.id)
+                .collect()
+        };
+        // The title mentions NVDA, but the item isn't tagged with it
+        assert_eq!(ids("$nvda"), ["cnbc-guid-1"]);
+        assert_eq!(ids("$AMD example"), ["cnbc-guid-1"]);
+
+        // Re-fetching replaces the item's tickers; deleting drops them
+        chips.tickers = vec!["AMD".to_string()];
+        db.insert(&chips).unwrap();
+        assert!(ids("$NVDA").is_empty());
+        db.delete("cnbc-guid-1", fixed_now()).unwrap();
+        assert!(ids("$AMD").is_empty());
+    }
+
+    #[test]
+    fn fetched_content_survives_refetch() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let item = item("cnbc-guid-1", "https://example.com/a");
+        db.insert(&item).unwrap();
+        db.set_content("cnbc-guid-1", "The full article.").unwrap();
+        // The feed still has no content for it
+        db.insert(&item).unwrap();
+        let stored = db.find_by_url("https://example.com/a").unwrap().unwrap();
+        assert_eq!(stored.content.as_deref(), Some("The full article."));
+    }
+
+    #[test]
+    fn status_log_keeps_newest_entries() {
+        let db = NewsDB::new(":memory:").unwrap();
+        for i in 0..5 {
+            let entry = StatusLogEntry {
+                level: "warning".to_string(),
+                text: format!("message {}", i),
+                count: 1,
+                logged_at: Utc::now(),
+            };
+            db.append_status_log(&entry).unwrap();
+        }
+        db.prune_status_log(3).unwrap();
+
+        let texts: Vec<String> = db
+            .load_status_log(2)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.text)
+            .collect();
+        assert_eq!(texts, vec!["message 3", "message 4"]);
+        assert_eq!(db.load_status_log(10).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn id_collision_is_disambiguated() {
+        let db = NewsDB::new(":memory:").unwrap();
+        db.insert(&item("cnbc-guid-1", "https://example.com/a"))
+            .unwrap();
+        db.insert(&item("cnbc-guid-1", "https://example.com/b"))
+            .unwrap();
+        // Re-fetching either item updates its own row
+        db.insert(&item("cnbc-guid-1", "https://example.com/b"))
+            .unwrap();
+        db.insert(&item("cnbc-guid-1", "https://example.com/a"))
+            .unwrap();
+
+        let mut ids: Vec<(String, String)> = db
+            .load_all()
+            .unwrap()
+            .into_iter()
+            .map(|i| (i.id, i.url))
+            .collect();
+        ids.sort();
+        assert_eq!(
+            ids,
+            vec![
+                (
+                    "cnbc-guid-1".to_string(),
+                    "https://example.com/a".to_string()
+                ),
+                (
+                    disambiguate("cnbc-guid-1", "https://example.com/b"),
+                    "https://example.com/b".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_id_updates_row_with_same_url() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut first = item("cnbc-guid-1", "https://example.com/a");
+        first.starred = true;
+        db.insert(&first).unwrap();
+        let mut renamed = item("cnbc-guid-2", "https://example.com/a");
+        renamed.title = "Updated".to_string();
+        db.insert(&renamed).unwrap();
+
+        let all = db.load_all().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, "cnbc-guid-1");
+        assert_eq!(all[0].title, "Updated");
+        assert!(all[0].starred);
+
+        let found = db.find_by_url("https://example.com/a").unwrap().unwrap();
+        assert_eq!(found.id, "cnbc-guid-1");
+        assert!(db.find_by_url("https://example.com/b").unwrap().is_none());
+    }
+
+    #[test]
+    fn short_hash_ids_stored_earlier_keep_their_rows() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let url = "https://www.cnbc.com/2024/05/01/stocks.html";
+        db.insert(&item("cnbc-hash-981a26ee371aa247", url)).unwrap();
+        let full = crate::models::id::feed_item_id("CNBC", None, false, url);
+        assert!(!db.insert(&item(&full, url)).unwrap());
+
+        let all = db.load_all().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, "cnbc-hash-981a26ee371aa247");
+    }
+
+    #[test]
+    fn edited_headline_keeps_previous_revision() {
+        let db = NewsDB::new(":memory:").unwrap();
+        let mut original = item("cnbc-guid-1", "https://example.com/a");
+        original.title = "Stocks rise".to_string();
+        db.insert(&original).unwrap();
+        // An unchanged re-fetch is not a revision
+        db.insert(&original).unwrap();
+        assert!(db.load_all().unwrap()[0].previous.is_none());
+
+        let mut edited = original.clone();
+        edited.title = "Stocks fall".to_string();
+        db.insert(&edited).unwrap();
+        db.insert(&edited).unwrap();
+
+        let stored = &db.load_all().unwrap()[0];
+        assert_eq!(stored.title, "Stocks fall");
+        let previous = stored.previous.as_ref().unwrap();
+        assert_eq!(previous.title, "Stocks rise");
+        assert_eq!(previous.summary, "");
+    }
 }
@@ -0,0 +1,95 @@
+//! Per-article user state exchanged between machines through a synced
+//! folder. Each read, starred and tags value carries when it was last set,
+//! deletions are kept as tombstones, and merging keeps the newest of each
+//! (last writer wins), so two machines that take turns running
+//! `news-hub state sync FILE` converge.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::models::NewsItem;
+
+/// Version of the file format written by [`StateFile::write`]
+pub const STATE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateFile {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub items: Vec<ItemState>,
+    pub deleted: Vec<Tombstone>,
+}
+
+/// An article whose state was set on some machine, with the article itself
+/// so a machine that hasn't fetched it yet can store it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemState {
+    pub item: NewsItem,
+    pub read_at: Option<DateTime<Utc>>,
+    pub starred_at: Option<DateTime<Utc>>,
+    pub tags_at: Option<DateTime<Utc>>,
+}
+
+impl ItemState {
+    /// When any of the state was last set
+    pub fn changed_at(&self) -> Option<DateTime<Utc>> {
+        [self.read_at, self.starred_at, self.tags_at]
+            .into_iter()
+            .flatten()
+            .max()
+    }
+}
+
+/// A deleted article, kept so the deletion reaches other machines and so
+/// re-fetching doesn't bring it back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub id: String,
+    pub source: String,
+    pub url: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// What merging a state file changed
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct StateImport {
+    /// Articles stored because another machine had set their state
+    pub added: usize,
+    /// Articles whose state was older than the file's
+    pub updated: usize,
+    /// Articles deleted on another machine
+    pub deleted: usize,
+}
+
+impl StateFile {
+    /// Read a state file; a missing one is empty
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        let state: StateFile = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        if state.version > STATE_VERSION {
+            return Err(anyhow!(
+                "{} was written by a newer news-hub (state version {})",
+                path.display(),
+                state.version
+            ));
+        }
+        Ok(Some(state))
+    }
+
+    /// Write through a temporary file so a sync client never picks up half
+    /// a file
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+use super::{enrich, ContentCache, Crawler, Enrichment};
+use crate::config::CrawlConfig;
+use crate::db::sqlite::NewsDB;
+
+/// Articles loaded and saved per step; progress is saved after each batch
+const BATCH_SIZE: usize = 50;
+
+/// Counts for a backfill run
+#[derive(Debug, Clone, Default)]
+pub struct BackfillProgress {
+    /// Stored articles
+    pub total: usize,
+    /// Articles done, including those done by an interrupted earlier run
+    pub processed: usize,
+    /// Articles an earlier run had already processed
+    pub resumed_from: usize,
+    pub updated: usize,
+    /// One message per article that failed, e.g. an unreachable page
+    pub errors: Vec<String>,
+}
+
+/// Run `steps` over every stored article in id order. The position is saved
+/// after each batch, so an interrupted run resumes where it stopped unless
+/// `restart` is set. Article text goes through `cache`, if given, and pages
+/// are fetched following the `crawl` politeness rules, up to
+/// `crawl.max_concurrent` at once. `on_progress` is called after each article.
+pub async fn backfill(
+    db: &NewsDB,
+    steps: &[Enrichment],
+    restart: bool,
+    cache: Option<Arc<ContentCache>>,
+    crawl: &CrawlConfig,
+    mut on_progress: impl FnMut(&BackfillProgress),
+) -> Result<BackfillProgress> {
+    let mut steps = steps.to_vec();
+    steps.sort();
+    steps.dedup();
+    let task: Vec<&str> = steps.iter().map(|s| s.as_str()).collect();
+    let task = task.join(",");
+
+    let mut cursor = if restart {
+        None
+    } else {
+        db.backfill_cursor(&task)?
+    };
+    let mut progress = BackfillProgress {
+        total: db.count_news_after(None)?,
+        ..Default::default()
+    };
+    progress.resumed_from = progress.total - db.count_news_after(cursor.as_deref())?;
+    progress.processed = progress.resumed_from;
+
+    let crawler = Arc::new(Crawler::new(reqwest::Client::new(), crawl));
+    let max_concurrent = crawl.max_concurrent.max(1);
+    loop {
+        let batch = db.load_news_after(cursor.as_deref(), BATCH_SIZE)?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        cursor = Some(last.id.clone());
+
+        // The whole batch is done before its position is saved
+        let mut pending = batch.into_iter();
+        let mut tasks = JoinSet::new();
+        loop {
+            while tasks.len() < max_concurrent {
+                let Some(mut item) = pending.next() else {
+                    break;
+                };
+                let (steps, crawler, cache) = (steps.clone(), crawler.clone(), cache.clone());
+                tasks.spawn(async move {
+                    let result = enrich(&mut item, &steps, &crawler, cache.as_deref()).await;
+                    (item, result)
+                });
+            }
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            let (item, result) = joined?;
+            match result {
+                Ok(true) => {
+                    db.insert(&item)?;
+                    progress.updated += 1;
+                }
+                Ok(false) => {}
+                Err(e) => progress.errors.push(format!("{}: {:#}", item.url, e)),
+            }
+            progress.processed += 1;
+            on_progress(&progress);
+        }
+        db.save_backfill_cursor(&task, cursor.as_deref())?;
+    }
+
+    // Finished; the next run starts from the beginning
+    db.save_backfill_cursor(&task, None)?;
+    Ok(progress)
+}
@@ -0,0 +1,262 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::clock::{self, SharedClock};
+use crate::config::CacheConfig;
+use crate::models::id::hash_hex;
+
+/// Extension of cached article files
+const ENTRY_EXT: &str = "txt";
+
+/// Size and use of the content cache
+#[derive(Debug, Clone, Default)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub bytes: u64,
+    /// Entries past the TTL, removed by the next write or prune
+    pub expired: usize,
+    /// Lookups answered from the cache since it was opened
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// Extracted article text on disk, keyed by URL, so re-extracting an article
+/// doesn't fetch its page again. Entries expire `ttl` after they were
+/// written; above `max_bytes` the least recently read are evicted first.
+///
+/// Each file holds the write time in Unix seconds on its first line and the
+/// text after it. The file's modification time tracks the last read.
+pub struct ContentCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    ttl: Duration,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    clock: SharedClock,
+}
+
+impl ContentCache {
+    pub fn new(dir: impl Into<PathBuf>, max_bytes: u64, ttl: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes,
+            ttl,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            clock: clock::system(),
+        }
+    }
+
+    pub fn from_config(cfg: &CacheConfig) -> Self {
+        Self::new(
+            &cfg.dir,
+            cfg.max_mb.max(1) * 1024 * 1024,
+            Duration::from_secs(cfg.ttl_days.max(1) * 24 * 3600),
+        )
+    }
+
+    /// Date writes and reads, and judge expiry, by `clock`
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", hash_hex(url), ENTRY_EXT))
+    }
+
+    /// Cached text for `url`, unless missing or expired
+    pub fn get(&self, url: &str) -> Option<String> {
+        let path = self.path(url);
+        let text = fs::read_to_string(&path).ok().and_then(|contents| {
+            let (written, text) = contents.split_once('\n')?;
+            if self.is_expired(written.parse().ok()?) {
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+            Some(text.to_string())
+        });
+        match &text {
+            Some(_) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                // Mark as recently used for eviction
+                if let Ok(file) = fs::File::options().append(true).open(&path) {
+                    let _ = file.set_modified(self.clock.now().into());
+                }
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        text
+    }
+
+    /// Store `text` for `url`, then evict entries beyond the size cap
+    pub fn put(&self, url: &str, text: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.path(url);
+        fs::write(&path, format!("{}\n{}", self.unix_now(), text))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        self.prune()?;
+        Ok(())
+    }
+
+    /// Remove expired entries, then the least recently read until the cache
+    /// fits in `max_bytes`. Returns the number removed.
+    pub fn prune(&self) -> Result<usize> {
+        let mut entries = Vec::new();
+        let mut removed = 0;
+        for entry in self.entries()? {
+            if entry.expired {
+                fs::remove_file(&entry.path)?;
+                removed += 1;
+            } else {
+                entries.push(entry);
+            }
+        }
+
+        let mut total: u64 = entries.iter().map(|e| e.bytes).sum();
+        entries.sort_by_key(|e| e.used);
+        for entry in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            fs::remove_file(&entry.path)?;
+            total -= entry.bytes;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Remove every entry. Returns the number removed.
+    pub fn clear(&self) -> Result<usize> {
+        let entries = self.entries()?;
+        for entry in &entries {
+            fs::remove_file(&entry.path)?;
+        }
+        Ok(entries.len())
+    }
+
+    pub fn stats(&self) -> Result<CacheStats> {
+        let entries = self.entries()?;
+        Ok(CacheStats {
+            entries: entries.len(),
+            bytes: entries.iter().map(|e| e.bytes).sum(),
+            expired: entries.iter().filter(|e| e.expired).count(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        })
+    }
+
+    fn is_expired(&self, written: u64) -> bool {
+        self.unix_now().saturating_sub(written) > self.ttl.as_secs()
+    }
+
+    fn unix_now(&self) -> u64 {
+        self.clock.now().timestamp().try_into().unwrap_or(0)
+    }
+
+    /// Cache files, or none when the directory doesn't exist yet
+    fn entries(&self) -> Result<Vec<Entry>> {
+        let dir = match fs::read_dir(&self.dir) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", self.dir.display()))
+            }
+        };
+        let mut entries = Vec::new();
+        for file in dir.flatten() {
+            let path = file.path();
+            if path.extension().is_none_or(|ext| ext != ENTRY_EXT) {
+                continue;
+            }
+            let Ok(metadata) = file.metadata() else {
+                continue;
+            };
+            let written = fs::File::open(&path).ok().and_then(|f| {
+                let mut line = String::new();
+                BufReader::new(f).read_line(&mut line).ok()?;
+                line.trim_end().parse().ok()
+            });
+            entries.push(Entry {
+                expired: written.is_none_or(|w| self.is_expired(w)),
+                bytes: metadata.len(),
+                used: metadata.modified().unwrap_or(UNIX_EPOCH),
+                path,
+            });
+        }
+        Ok(entries)
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    bytes: u64,
+    /// Last read, or when written if never read
+    used: SystemTime,
+    expired: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::manual_clock;
+
+    #[test]
+    fn least_recently_read_is_evicted_first() {
+        let dir = std::env::temp_dir().join(format!("news-hub-cache-{}", std::process::id()));
+        let cache = ContentCache::new(&dir, 70, Duration::from_secs(3600));
+        cache.clear().unwrap();
+
+        cache.put("https://example.com/a", &"a".repeat(20)).unwrap();
+        cache.put("https://example.com/b", &"b".repeat(20)).unwrap();
+        // b was read long ago, a just now
+        let past = SystemTime::now() - Duration::from_secs(60);
+        let b = fs::File::options()
+            .append(true)
+            .open(cache.path("https://example.com/b"))
+            .unwrap();
+        b.set_modified(past).unwrap();
+        assert!(cache.get("https://example.com/a").is_some());
+
+        cache.put("https://example.com/c", &"c".repeat(20)).unwrap();
+        assert!(cache.get("https://example.com/b").is_none());
+        assert_eq!(cache.get("https://example.com/a").unwrap(), "a".repeat(20));
+        assert_eq!(cache.stats().unwrap().entries, 2);
+
+        assert_eq!(cache.clear().unwrap(), 2);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn entries_expire_after_the_ttl() {
+        let dir = std::env::temp_dir().join(format!("news-hub-cache-ttl-{}", std::process::id()));
+        let clock = manual_clock();
+        let mut cache = ContentCache::new(&dir, 1024, Duration::from_secs(24 * 3600));
+        cache.set_clock(clock.shared());
+        cache.clear().unwrap();
+
+        cache.put("https://example.com/a", "a").unwrap();
+        clock.advance(chrono::Duration::hours(12));
+        cache.put("https://example.com/b", "b").unwrap();
+        clock.advance(chrono::Duration::hours(13));
+        let stats = cache.stats().unwrap();
+        assert_eq!((stats.entries, stats.expired), (2, 1));
+
+        assert_eq!(cache.prune().unwrap(), 1);
+        assert!(cache.get("https://example.com/a").is_none());
+        assert_eq!(cache.get("https://example.com/b").unwrap(), "b");
+
+        cache.clear().unwrap();
+        let _ = fs::remove_dir(&dir);
+    }
+}
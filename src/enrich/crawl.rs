@@ -0,0 +1,307 @@
+use anyhow::{anyhow, Context, Result};
+use reqwest::{Client, Url};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{OnceCell, Semaphore};
+use tokio::time::Instant;
+
+use crate::config::CrawlConfig;
+
+/// Timeout for fetching an article page or robots.txt
+const PAGE_TIMEOUT: Duration = Duration::from_secs(20);
+/// Name matched against robots.txt `User-agent` lines
+const ROBOTS_AGENT: &str = "news-hub";
+/// Longest `Crawl-delay` honoured, so one site can't stall a backfill
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(30);
+
+/// Fetches article pages politely: per-site robots.txt rules, a limit on
+/// requests in flight to each site and a random pause between them. Exempt
+/// sites are fetched directly.
+pub struct Crawler {
+    client: Client,
+    respect_robots: bool,
+    max_per_host: usize,
+    min_delay: Duration,
+    max_delay: Duration,
+    exempt_hosts: Vec<String>,
+    hosts: Mutex<HashMap<String, Arc<HostState>>>,
+}
+
+/// What the crawler knows about one site
+struct HostState {
+    slots: Semaphore,
+    robots: OnceCell<Robots>,
+    /// When the last request to the site started
+    last_request: tokio::sync::Mutex<Option<Instant>>,
+}
+
+impl Crawler {
+    pub fn new(client: Client, cfg: &CrawlConfig) -> Self {
+        let min_delay = Duration::from_millis(cfg.min_delay_ms);
+        Self {
+            client,
+            respect_robots: cfg.respect_robots,
+            max_per_host: cfg.max_per_host.max(1),
+            min_delay,
+            max_delay: Duration::from_millis(cfg.max_delay_ms).max(min_delay),
+            exempt_hosts: cfg
+                .exempt_hosts
+                .iter()
+                .map(|h| h.trim().trim_start_matches('.').to_ascii_lowercase())
+                .filter(|h| !h.is_empty())
+                .collect(),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn is_exempt(&self, host: &str) -> bool {
+        self.exempt_hosts
+            .iter()
+            .any(|h| host == h || host.ends_with(&format!(".{}", h)))
+    }
+
+    fn host_state(&self, host: &str) -> Arc<HostState> {
+        let mut hosts = self.hosts.lock().unwrap();
+        hosts
+            .entry(host.to_string())
+            .or_insert_with(|| {
+                Arc::new(HostState {
+                    slots: Semaphore::new(self.max_per_host.min(Semaphore::MAX_PERMITS)),
+                    robots: OnceCell::new(),
+                    last_request: tokio::sync::Mutex::new(None),
+                })
+            })
+            .clone()
+    }
+
+    /// Body of the page at `url`. Fails without a request when robots.txt
+    /// disallows it.
+    pub async fn get(&self, url: &str) -> Result<String> {
+        let parsed = Url::parse(url).context("Invalid article URL")?;
+        let host = parsed.host_str().unwrap_or_default().to_ascii_lowercase();
+        if host.is_empty() || self.is_exempt(&host) {
+            return self.fetch(url).await;
+        }
+
+        let state = self.host_state(&host);
+        let mut crawl_delay = None;
+        if self.respect_robots {
+            let robots = state
+                .robots
+                .get_or_init(|| self.fetch_robots(&parsed))
+                .await;
+            let target = match parsed.query() {
+                Some(query) => format!("{}?{}", parsed.path(), query),
+                None => parsed.path().to_string(),
+            };
+            if !robots.allows(&target) {
+                return Err(anyhow!("Disallowed by {}'s robots.txt", host));
+            }
+            crawl_delay = robots.crawl_delay;
+        }
+
+        let _slot = state.slots.acquire().await?;
+        {
+            let mut last_request = state.last_request.lock().await;
+            if let Some(last) = *last_request {
+                let delay = self.random_delay().max(crawl_delay.unwrap_or_default());
+                tokio::time::sleep_until(last + delay).await;
+            }
+            *last_request = Some(Instant::now());
+        }
+        self.fetch(url).await
+    }
+
+    async fn fetch(&self, url: &str) -> Result<String> {
+        self.client
+            .get(url)
+            .timeout(PAGE_TIMEOUT)
+            .send()
+            .await
+            .context("Failed to fetch article")?
+            .error_for_status()?
+            .text()
+            .await
+            .context("Failed to read article")
+    }
+
+    /// The site's rules for us; a missing or unreachable robots.txt allows
+    /// everything
+    async fn fetch_robots(&self, url: &Url) -> Robots {
+        let mut robots_url = url.clone();
+        robots_url.set_path("/robots.txt");
+        robots_url.set_query(None);
+        robots_url.set_fragment(None);
+        let response = self
+            .client
+            .get(robots_url)
+            .timeout(PAGE_TIMEOUT)
+            .send()
+            .await
+            .ok()
+            .filter(|r| r.status().is_success());
+        match response {
+            Some(response) => Robots::parse(&response.text().await.unwrap_or_default()),
+            None => Robots::default(),
+        }
+    }
+
+    /// A pause between `min_delay` and `max_delay`
+    fn random_delay(&self) -> Duration {
+        let span = (self.max_delay - self.min_delay).as_millis() as u64;
+        if span == 0 {
+            return self.min_delay;
+        }
+        // Clock nanoseconds are random enough to keep requests off a fixed beat
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos() as u64;
+        self.min_delay + Duration::from_millis(nanos % (span + 1))
+    }
+}
+
+/// The robots.txt group that applies to us: ours if the file names us,
+/// otherwise the `*` group
+#[derive(Debug, Default)]
+struct Robots {
+    /// Path patterns, `true` for `Allow`
+    rules: Vec<(String, bool)>,
+    crawl_delay: Option<Duration>,
+}
+
+impl Robots {
+    fn parse(text: &str) -> Self {
+        let mut ours = None;
+        let mut any = None;
+        // User agents of the group being read, and whether its rules have started
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        let mut group = Robots::default();
+        let mut finish = |agents: &[String], group: Robots| {
+            if agents.iter().any(|a| a == ROBOTS_AGENT) {
+                ours.get_or_insert(group);
+            } else if agents.iter().any(|a| a == "*") {
+                any.get_or_insert(group);
+            }
+        };
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let (key, value) = (key.trim().to_ascii_lowercase(), value.trim());
+            match key.as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        finish(&agents, std::mem::take(&mut group));
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    agents.push(value.to_ascii_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // An empty Disallow allows everything
+                    if !value.is_empty() {
+                        group.rules.push((value.to_string(), key == "allow"));
+                    }
+                }
+                "crawl-delay" => {
+                    in_rules = true;
+                    group.crawl_delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                        .map(|secs| Duration::from_secs_f64(secs).min(MAX_CRAWL_DELAY));
+                }
+                _ => {}
+            }
+        }
+        finish(&agents, group);
+        ours.or(any).unwrap_or_default()
+    }
+
+    /// The longest matching rule decides; `Allow` wins a tie
+    fn allows(&self, path: &str) -> bool {
+        let path = if path.is_empty() { "/" } else { path };
+        self.rules
+            .iter()
+            .filter(|(pattern, _)| matches_pattern(pattern, path))
+            .max_by_key(|(pattern, allow)| (pattern.len(), *allow))
+            .is_none_or(|(_, allow)| *allow)
+    }
+}
+
+/// robots.txt path match: a prefix, where `*` matches any run of characters
+/// and a trailing `$` anchors the end
+fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        let is_last = i + 1 == parts.len();
+        if anchored && is_last {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = "\
+User-agent: Googlebot
+Disallow: /
+
+User-agent: *
+Disallow: /private/
+Disallow: /*.pdf$
+Allow: /private/press/
+Crawl-delay: 5
+";
+
+    #[test]
+    fn star_group_applies_to_us() {
+        let robots = Robots::parse(ROBOTS);
+        assert!(robots.allows("/markets/story.html"));
+        assert!(!robots.allows("/private/memo.html"));
+        assert!(robots.allows("/private/press/release.html"));
+        assert_eq!(robots.crawl_delay, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn wildcards_and_end_anchors() {
+        let robots = Robots::parse(ROBOTS);
+        assert!(!robots.allows("/reports/q3.pdf"));
+        assert!(robots.allows("/reports/q3.pdf.html"));
+    }
+
+    #[test]
+    fn our_group_wins_over_star() {
+        let robots =
+            Robots::parse("User-agent: *\nDisallow: /\n\nUser-agent: news-hub\nAllow: /\n");
+        assert!(robots.allows("/anything"));
+    }
+
+    #[test]
+    fn empty_or_missing_rules_allow_everything() {
+        assert!(Robots::parse("User-agent: *\nDisallow:\n").allows("/a"));
+        assert!(Robots::default().allows("/a"));
+    }
+}
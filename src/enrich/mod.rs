@@ -0,0 +1,132 @@
+//! Enrichment steps that add derived data to stored articles.
+
+use anyhow::Result;
+
+use crate::models::{extract_tickers, scheduled_date, NewsItem};
+
+mod backfill;
+mod cache;
+mod crawl;
+mod readability;
+
+pub use backfill::{backfill, BackfillProgress};
+pub use cache::{CacheStats, ContentCache};
+pub use crawl::Crawler;
+pub use readability::readable_text;
+
+/// Paragraphs shorter than this are usually bylines, captions or buttons
+const MIN_PARAGRAPH_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Enrichment {
+    /// Cashtag tickers from the title, summary and content
+    Tickers,
+    /// Article text from the linked page, for items without content
+    Content,
+    /// Date of the scheduled event the item announces
+    Events,
+}
+
+impl Enrichment {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Enrichment::Tickers => "tickers",
+            Enrichment::Content => "content",
+            Enrichment::Events => "events",
+        }
+    }
+}
+
+/// Main text of the article at `url`, taken from `cache` when it has the
+/// page, otherwise fetched through `crawler` and stored there. Empty when
+/// the page has no recognizable article text.
+pub async fn article_text(
+    url: &str,
+    crawler: &Crawler,
+    cache: Option<&ContentCache>,
+) -> Result<String> {
+    if let Some(text) = cache.and_then(|c| c.get(url)) {
+        return Ok(text);
+    }
+    let text = readable_text(&crawler.get(url).await?);
+    if let Some(cache) = cache.filter(|_| !text.is_empty()) {
+        cache.put(url, &text)?;
+    }
+    Ok(text)
+}
+
+/// Run `steps` over `item`. Article text is taken from `cache` when it has
+/// the page, otherwise fetched through `crawler` and stored there. Returns
+/// whether the item changed.
+pub async fn enrich(
+    item: &mut NewsItem,
+    steps: &[Enrichment],
+    crawler: &Crawler,
+    cache: Option<&ContentCache>,
+) -> Result<bool> {
+    let mut changed = false;
+    // Content first, so ticker extraction sees the fetched text
+    if steps.contains(&Enrichment::Content) && item.content.is_none() {
+        let text = article_text(&item.url, crawler, cache).await?;
+        if !text.is_empty() {
+            item.content = Some(text);
+            changed = true;
+        }
+    }
+    if steps.contains(&Enrichment::Tickers) {
+        let content = item.content.as_deref().unwrap_or("");
+        let mut tickers = item.tickers.clone();
+        for ticker in extract_tickers(&[&item.title, &item.summary, content]) {
+            if !tickers.contains(&ticker) {
+                tickers.push(ticker);
+            }
+        }
+        if tickers != item.tickers {
+            item.tickers = tickers;
+            changed = true;
+        }
+    }
+    if steps.contains(&Enrichment::Events) && item.event_date.is_none() {
+        item.event_date = scheduled_date(&[&item.title, &item.summary], item.published);
+        changed |= item.event_date.is_some();
+    }
+    Ok(changed)
+}
+
+/// Plain text of an HTML fragment on one line, e.g. a feed description
+pub fn html_to_text(html: &str) -> String {
+    collapse_whitespace(&decode_entities(&strip_tags(html)))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&rsquo;", "'")
+        .replace("&lsquo;", "'")
+        .replace("&rdquo;", "\"")
+        .replace("&ldquo;", "\"")
+        .replace("&mdash;", "—")
+        .replace("&amp;", "&")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
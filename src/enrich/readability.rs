@@ -0,0 +1,266 @@
+//! Main article text of a page, in the spirit of Mozilla's Readability: each
+//! paragraph scores its container (and half that for the container's
+//! parent) by length and commas, containers named like comments, sidebars
+//! or promos are marked down, and link-heavy ones lose their share of link
+//! text. The best scoring container's paragraphs are the article.
+
+use super::{collapse_whitespace, decode_entities, MIN_PARAGRAPH_CHARS};
+
+/// Elements whose contents are never article text
+const SKIPPED: &[&str] = &[
+    "script",
+    "style",
+    "noscript",
+    "template",
+    "svg",
+    "nav",
+    "header",
+    "footer",
+    "aside",
+    "form",
+    "button",
+    "figcaption",
+];
+/// Elements that can hold the article
+const CONTAINERS: &[&str] = &["article", "main", "section", "div", "td", "body"];
+/// Elements whose text counts as a paragraph
+const PARAGRAPHS: &[&str] = &["p", "pre", "blockquote"];
+/// Class or id words that mark article content, and page furniture
+const POSITIVE: &[&str] = &[
+    "article", "body", "content", "entry", "main", "page", "post", "story", "text",
+];
+const NEGATIVE: &[&str] = &[
+    "comment",
+    "footer",
+    "sidebar",
+    "related",
+    "promo",
+    "share",
+    "social",
+    "nav",
+    "newsletter",
+    "subscribe",
+    "advert",
+    "sponsor",
+    "popup",
+    "cookie",
+    "menu",
+    "widget",
+];
+/// Paragraphs more than this share link text are lists of links
+const MAX_LINK_DENSITY: f32 = 0.5;
+
+/// A container seen on the page
+struct Container {
+    parent: Option<usize>,
+    weight: f32,
+    score: f32,
+    chars: usize,
+    link_chars: usize,
+}
+
+/// A paragraph long enough to count, and the containers it sits in
+struct Paragraph {
+    text: String,
+    ancestors: Vec<usize>,
+}
+
+/// The article's paragraphs separated by blank lines; empty when the page
+/// has no paragraph long enough to be article text
+pub fn readable_text(html: &str) -> String {
+    let mut containers: Vec<Container> = Vec::new();
+    let mut paragraphs: Vec<Paragraph> = Vec::new();
+    // Open containers, innermost last, by tag name and index
+    let mut open: Vec<(String, usize)> = Vec::new();
+    // The paragraph being read: its text, and how much of it is link text
+    let mut current: Option<(String, usize)> = None;
+    let mut link_depth = 0usize;
+
+    let lower = html.to_ascii_lowercase();
+    let mut pos = 0;
+    while pos < html.len() {
+        let Some(start) = lower[pos..].find('<').map(|i| pos + i) else {
+            push_text(&mut current, &html[pos..], link_depth);
+            break;
+        };
+        push_text(&mut current, &html[pos..start], link_depth);
+        if lower[start..].starts_with("<!--") {
+            pos = lower[start..]
+                .find("-->")
+                .map_or(html.len(), |i| start + i + 3);
+            continue;
+        }
+        let Some(end) = lower[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        pos = end;
+        let tag = &lower[start + 1..end - 1];
+        let closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect();
+
+        if !closing && SKIPPED.contains(&name.as_str()) && !tag.ends_with('/') {
+            // Jump past the matching close tag
+            let close = format!("</{}", name);
+            pos = lower[pos..].find(&close).map_or(html.len(), |i| pos + i);
+            pos = lower[pos..].find('>').map_or(html.len(), |i| pos + i + 1);
+            continue;
+        }
+        if name == "a" {
+            link_depth = if closing {
+                link_depth.saturating_sub(1)
+            } else {
+                link_depth + 1
+            };
+            continue;
+        }
+        if name == "br" {
+            push_text(&mut current, " ", link_depth);
+            continue;
+        }
+        let is_paragraph = PARAGRAPHS.contains(&name.as_str());
+        let is_container = CONTAINERS.contains(&name.as_str());
+        if !is_paragraph && !is_container {
+            continue;
+        }
+        // Any block boundary ends the paragraph being read
+        if let Some((text, link_chars)) = current.take() {
+            finish_paragraph(text, link_chars, &open, &mut containers, &mut paragraphs);
+        }
+        if is_paragraph && !closing {
+            current = Some((String::new(), 0));
+        } else if is_container && closing {
+            if let Some(at) = open.iter().rposition(|(open_name, _)| *open_name == name) {
+                open.truncate(at);
+            }
+        } else if is_container && !tag.ends_with('/') {
+            containers.push(Container {
+                parent: open.last().map(|(_, index)| *index),
+                weight: class_weight(tag),
+                score: 0.0,
+                chars: 0,
+                link_chars: 0,
+            });
+            open.push((name, containers.len() - 1));
+        }
+    }
+    if let Some((text, link_chars)) = current.take() {
+        finish_paragraph(text, link_chars, &open, &mut containers, &mut paragraphs);
+    }
+
+    let best = containers
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.score > 0.0)
+        .map(|(i, c)| {
+            let link_density = c.link_chars as f32 / c.chars.max(1) as f32;
+            (i, (c.score + c.weight) * (1.0 - link_density))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i);
+    paragraphs
+        .into_iter()
+        .filter(|p| match best {
+            Some(best) => p.ancestors.contains(&best),
+            None => p.ancestors.is_empty(),
+        })
+        .map(|p| p.text)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn push_text(current: &mut Option<(String, usize)>, raw: &str, link_depth: usize) {
+    if let Some((text, link_chars)) = current {
+        text.push_str(raw);
+        if link_depth > 0 {
+            *link_chars += raw.len();
+        }
+    }
+}
+
+/// Keep a finished paragraph if it is long enough, crediting its score to
+/// its container and half to that container's parent
+fn finish_paragraph(
+    raw: String,
+    link_chars: usize,
+    open: &[(String, usize)],
+    containers: &mut [Container],
+    paragraphs: &mut Vec<Paragraph>,
+) {
+    let text = collapse_whitespace(&decode_entities(&raw));
+    let chars = text.chars().count();
+    if chars < MIN_PARAGRAPH_CHARS || link_chars as f32 > raw.len() as f32 * MAX_LINK_DENSITY {
+        return;
+    }
+    let score = 1.0 + text.matches(',').count() as f32 + (chars as f32 / 100.0).min(3.0);
+    if let Some(&(_, index)) = open.last() {
+        let container = &mut containers[index];
+        container.score += score;
+        container.chars += raw.len();
+        container.link_chars += link_chars;
+        if let Some(parent) = container.parent {
+            containers[parent].score += score / 2.0;
+        }
+    }
+    paragraphs.push(Paragraph {
+        text,
+        ancestors: open.iter().map(|(_, index)| *index).collect(),
+    });
+}
+
+/// Score adjustment for a container from the words in its class and id
+fn class_weight(tag: &str) -> f32 {
+    let names: String = ["class=", "id="]
+        .iter()
+        .filter_map(|attr| {
+            let value = &tag[tag.find(attr)? + attr.len()..];
+            let value = value.trim_start_matches(['"', '\'']);
+            Some(value.split(['"', '\'']).next().unwrap_or(value).to_string())
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut weight = 0.0;
+    if NEGATIVE.iter().any(|word| names.contains(word)) {
+        weight -= 25.0;
+    }
+    if POSITIVE.iter().any(|word| names.contains(word)) {
+        weight += 25.0;
+    }
+    weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn article_container_wins_over_page_furniture() {
+        let html = r#"<html><body>
+            <nav><p>Home, Markets, Tech, Opinion, Video, Podcasts and more sections</p></nav>
+            <div class="sidebar">
+              <p>Sign up for our newsletter, the best way to start your trading day.</p>
+            </div>
+            <div id="story-body" class="article-content">
+              <p>Shares of the chipmaker rose 4% on Tuesday, after it raised its outlook
+                 for the year &amp; announced a buyback.</p>
+              <p>Analysts said demand from data centers, which doubled last year, shows
+                 no sign of slowing.<br>More details followed.</p>
+              <div class="related"><p><a href="/a">Read more: why chip stocks keep climbing</a></p></div>
+              <p>Short.</p>
+            </div>
+            <script>var p = "<p>not text, at all, not even close to text</p>";</script>
+            <div class="comments"><p>First! This is the best article I have read all week.</p></div>
+        </body></html>"#;
+        assert_eq!(
+            readable_text(html),
+            "Shares of the chipmaker rose 4% on Tuesday, after it raised its outlook \
+             for the year & announced a buyback.\n\n\
+             Analysts said demand from data centers, which doubled last year, shows \
+             no sign of slowing. More details followed."
+        );
+        assert_eq!(readable_text("<p>Too short</p>"), "");
+    }
+}
@@ -0,0 +1,312 @@
+//! Errors as the user sees them. Adaptors and storage return `anyhow` errors;
+//! where one is shown, [`NewsHubError::classify`] sorts it into a category
+//! with a short message ("invalid API key", "HTTP 503 (server error)"), so
+//! every source fails the same way and reports can group failures by
+//! [`ErrorCategory`]. Adaptors that know what went wrong return a
+//! `NewsHubError` directly and it is kept as is.
+
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// Kind of failure, for grouping and filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    Network,
+    Parse,
+    Auth,
+    RateLimit,
+    Storage,
+}
+
+impl ErrorCategory {
+    pub const ALL: [ErrorCategory; 5] = [
+        ErrorCategory::Network,
+        ErrorCategory::Parse,
+        ErrorCategory::Auth,
+        ErrorCategory::RateLimit,
+        ErrorCategory::Storage,
+    ];
+
+    /// Name used in config and reports
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorCategory::Network => "network",
+            ErrorCategory::Parse => "parse",
+            ErrorCategory::Auth => "auth",
+            ErrorCategory::RateLimit => "rate-limit",
+            ErrorCategory::Storage => "storage",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.label().eq_ignore_ascii_case(label.trim()))
+    }
+
+    /// What the user can do about it
+    pub fn hint(self) -> &'static str {
+        match self {
+            ErrorCategory::Network => "check the connection or the source's URL",
+            ErrorCategory::Parse => "the source may have changed its format",
+            ErrorCategory::Auth => "check the credentials in config.toml",
+            ErrorCategory::RateLimit => "wait before refreshing again",
+            ErrorCategory::Storage => "check the database file and free disk space",
+        }
+    }
+}
+
+/// A failure worth showing, by category. The message says what happened
+/// without the source name, which the UI puts in front of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NewsHubError {
+    /// The source couldn't be reached or answered with a server error
+    Network(String),
+    /// The response arrived but isn't what the adaptor expects
+    Parse(String),
+    /// Credentials are missing or were rejected
+    Auth(String),
+    /// The source asked us to slow down, for `retry_after` if it said
+    RateLimit {
+        message: String,
+        retry_after: Option<Duration>,
+    },
+    /// Reading or writing the database or cache failed
+    Storage(String),
+}
+
+impl NewsHubError {
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            NewsHubError::Network(_) => ErrorCategory::Network,
+            NewsHubError::Parse(_) => ErrorCategory::Parse,
+            NewsHubError::Auth(_) => ErrorCategory::Auth,
+            NewsHubError::RateLimit { .. } => ErrorCategory::RateLimit,
+            NewsHubError::Storage(_) => ErrorCategory::Storage,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            NewsHubError::Network(m)
+            | NewsHubError::Parse(m)
+            | NewsHubError::Auth(m)
+            | NewsHubError::RateLimit { message: m, .. }
+            | NewsHubError::Storage(m) => m,
+        }
+    }
+
+    /// How long a rate-limited source asked to be left alone
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            NewsHubError::RateLimit { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Make this a rate limit lasting `retry_after`, if the response had a
+    /// `Retry-After` header; a 503 with one is a cool-down request too
+    pub fn with_retry_after(self, retry_after: Option<Duration>) -> Self {
+        match (self, retry_after) {
+            (NewsHubError::RateLimit { message, .. }, retry_after) => NewsHubError::RateLimit {
+                message,
+                retry_after,
+            },
+            (error, Some(retry_after)) => NewsHubError::RateLimit {
+                message: error.message().to_string(),
+                retry_after: Some(retry_after),
+            },
+            (error, None) => error,
+        }
+    }
+
+    /// An unsuccessful response, with an optional excerpt of its body
+    pub fn from_response(response: &reqwest::Response, body: Option<&str>) -> Self {
+        Self::from_status(response.status().as_u16(), body)
+            .with_retry_after(retry_after(response.headers(), Utc::now()))
+    }
+
+    /// An unsuccessful HTTP response, with an optional excerpt of its body
+    pub fn from_status(status: u16, body: Option<&str>) -> Self {
+        let (hint, category) = match status {
+            401 | 403 => ("blocked or needs a login", ErrorCategory::Auth),
+            404 | 410 => ("moved or removed", ErrorCategory::Network),
+            429 => ("rate limited", ErrorCategory::RateLimit),
+            500..=599 => ("server error", ErrorCategory::Network),
+            _ => ("unexpected response", ErrorCategory::Network),
+        };
+        let mut message = format!("HTTP {} ({})", status, hint);
+        if let Some(body) = body.filter(|b| !b.is_empty()) {
+            message.push_str(": ");
+            message.push_str(body);
+        }
+        Self::new(category, message)
+    }
+
+    pub fn new(category: ErrorCategory, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match category {
+            ErrorCategory::Network => NewsHubError::Network(message),
+            ErrorCategory::Parse => NewsHubError::Parse(message),
+            ErrorCategory::Auth => NewsHubError::Auth(message),
+            ErrorCategory::RateLimit => NewsHubError::RateLimit {
+                message,
+                retry_after: None,
+            },
+            ErrorCategory::Storage => NewsHubError::Storage(message),
+        }
+    }
+
+    /// Sort an error into a category by what caused it. A `NewsHubError`
+    /// anywhere in the chain wins; otherwise HTTP, SQLite and I/O errors
+    /// are recognized, and anything else counts as a response the adaptor
+    /// couldn't use.
+    pub fn classify(error: &anyhow::Error) -> Self {
+        if let Some(e) = error.chain().find_map(|e| e.downcast_ref::<NewsHubError>()) {
+            return e.clone();
+        }
+        for cause in error.chain() {
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                return Self::from_reqwest(e);
+            }
+            if cause.is::<rusqlite::Error>() || cause.is::<std::io::Error>() {
+                return NewsHubError::Storage(format!("{:#}", error));
+            }
+        }
+        NewsHubError::Parse(format!("{:#}", error))
+    }
+
+    /// Leaves the URL out: query strings often carry API keys
+    fn from_reqwest(error: &reqwest::Error) -> Self {
+        if let Some(status) = error.status() {
+            return Self::from_status(status.as_u16(), None);
+        }
+        if error.is_timeout() {
+            NewsHubError::Network("timed out".to_string())
+        } else if error.is_connect() {
+            NewsHubError::Network("couldn't connect".to_string())
+        } else if error.is_decode() {
+            NewsHubError::Parse("unexpected response format".to_string())
+        } else if error.is_redirect() {
+            NewsHubError::Network("too many redirects".to_string())
+        } else if error.is_body() {
+            NewsHubError::Network("connection dropped while reading the response".to_string())
+        } else {
+            NewsHubError::Network("request failed".to_string())
+        }
+    }
+}
+
+/// The wait a `Retry-After` header asks for, given in seconds or as an
+/// HTTP date
+pub fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        at.signed_duration_since(now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+impl fmt::Display for NewsHubError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for NewsHubError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn statuses_map_to_categories() {
+        let auth = NewsHubError::from_status(401, None);
+        assert_eq!(auth.category(), ErrorCategory::Auth);
+        assert_eq!(auth.to_string(), "HTTP 401 (blocked or needs a login)");
+        let limited = NewsHubError::from_status(429, Some("slow down"));
+        assert_eq!(limited.category(), ErrorCategory::RateLimit);
+        assert_eq!(limited.to_string(), "HTTP 429 (rate limited): slow down");
+        assert_eq!(
+            NewsHubError::from_status(503, None).category(),
+            ErrorCategory::Network
+        );
+    }
+
+    #[test]
+    fn retry_after_takes_seconds_or_a_date() {
+        let now = DateTime::parse_from_rfc3339("2024-03-14T14:30:00Z")
+            .unwrap()
+            .to_utc();
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers, now), None);
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(120)));
+        headers.insert(
+            RETRY_AFTER,
+            "Thu, 14 Mar 2024 14:32:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(120)));
+        // A date already past means now
+        headers.insert(
+            RETRY_AFTER,
+            "Thu, 14 Mar 2024 14:00:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers, now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn retry_after_makes_any_status_a_rate_limit() {
+        let unavailable = NewsHubError::from_status(503, None);
+        assert_eq!(unavailable.clone().with_retry_after(None), unavailable);
+        let cooling = unavailable.with_retry_after(Some(Duration::from_secs(60)));
+        assert_eq!(cooling.category(), ErrorCategory::RateLimit);
+        assert_eq!(cooling.retry_after(), Some(Duration::from_secs(60)));
+        assert_eq!(cooling.to_string(), "HTTP 503 (server error)");
+    }
+
+    #[test]
+    fn typed_error_survives_context() {
+        let error = Err::<(), _>(NewsHubError::Auth("invalid API key".to_string()))
+            .context("Failed to fetch Benzinga")
+            .unwrap_err();
+        assert_eq!(
+            NewsHubError::classify(&error),
+            NewsHubError::Auth("invalid API key".to_string())
+        );
+    }
+
+    #[test]
+    fn causes_are_recognized() {
+        let sqlite = anyhow::Error::new(rusqlite::Error::InvalidQuery).context("Failed to insert");
+        assert_eq!(
+            NewsHubError::classify(&sqlite).category(),
+            ErrorCategory::Storage
+        );
+        let other = anyhow!("No array at `data` in the response");
+        assert_eq!(
+            NewsHubError::classify(&other),
+            NewsHubError::Parse("No array at `data` in the response".to_string())
+        );
+    }
+
+    #[test]
+    fn labels_round_trip() {
+        for category in ErrorCategory::ALL {
+            assert_eq!(ErrorCategory::from_label(category.label()), Some(category));
+        }
+        assert_eq!(
+            ErrorCategory::from_label(" Rate-Limit "),
+            Some(ErrorCategory::RateLimit)
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::models::NewsItem;
+
+/// Longest content line allowed by RFC 5545, in octets
+const MAX_LINE_OCTETS: usize = 75;
+
+/// An iCalendar document with one all-day event per item that has an
+/// `event_date`. Event UIDs are item ids, so re-importing updates events
+/// instead of duplicating them.
+pub fn calendar(items: &[NewsItem], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//news-hub//Market events//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "X-WR-CALNAME:news-hub market events");
+
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    for item in items {
+        let Some(date) = item.event_date else {
+            continue;
+        };
+        let mut description = format!("{}\n{}", item.source, item.url);
+        if !item.tickers.is_empty() {
+            description.push_str(&format!("\nTickers: {}", item.tickers.join(", ")));
+        }
+
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}@news-hub", item.id));
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        push_line(
+            &mut out,
+            &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+        );
+        push_line(
+            &mut out,
+            &format!(
+                "DTEND;VALUE=DATE:{}",
+                (date + Duration::days(1)).format("%Y%m%d")
+            ),
+        );
+        push_line(&mut out, &format!("SUMMARY:{}", escape(&item.title)));
+        push_line(&mut out, &format!("DESCRIPTION:{}", escape(&description)));
+        push_line(&mut out, &format!("URL:{}", item.url));
+        if !item.tickers.is_empty() {
+            let tickers: Vec<String> = item.tickers.iter().map(|t| escape(t)).collect();
+            push_line(&mut out, &format!("CATEGORIES:{}", tickers.join(",")));
+        }
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Escape a TEXT value
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Append a content line, folded at 75 octets without splitting characters
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space counts toward the continuation line
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    /// Content lines with folding undone
+    fn unfold(text: &str) -> Vec<String> {
+        text.replace("\r\n ", "")
+            .split("\r\n")
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(
+            escape("Q3: revenue, guidance; outlook\nback\\slash\r\nend"),
+            r"Q3: revenue\, guidance\; outlook\nback\\slash\nend"
+        );
+    }
+
+    #[test]
+    fn folds_lines_at_75_octets() {
+        let mut out = String::new();
+        push_line(&mut out, &"a".repeat(75));
+        assert_eq!(out, format!("{}\r\n", "a".repeat(75)));
+
+        let line = format!("SUMMARY:{}", "é".repeat(60));
+        let mut out = String::new();
+        push_line(&mut out, &line);
+        let physical: Vec<&str> = out.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(physical.len() > 1);
+        assert!(physical.iter().all(|l| l.len() <= 75));
+        assert!(physical[1..].iter().all(|l| l.starts_with(' ')));
+        assert_eq!(unfold(&out), [line, String::new()]);
+    }
+
+    #[test]
+    fn writes_an_event_per_dated_item() {
+        let now = Utc.with_ymd_and_hms(2024, 4, 20, 12, 0, 0).unwrap();
+        let dated = NewsItem {
+            id: "a1".to_string(),
+            title: "Apple to report earnings, Q2; call at 5pm".to_string(),
+            source: "CNBC".to_string(),
+            url: "https://example.com/a".to_string(),
+            tickers: vec!["AAPL".to_string()],
+            event_date: NaiveDate::from_ymd_opt(2024, 5, 2),
+            ..Default::default()
+        };
+        let undated = NewsItem {
+            id: "b2".to_string(),
+            event_date: None,
+            ..dated.clone()
+        };
+
+        let lines = unfold(&calendar(&[dated, undated], now));
+        assert_eq!(lines.iter().filter(|l| *l == "BEGIN:VEVENT").count(), 1);
+        for expected in [
+            "UID:a1@news-hub",
+            "DTSTAMP:20240420T120000Z",
+            "DTSTART;VALUE=DATE:20240502",
+            "DTEND;VALUE=DATE:20240503",
+            r"SUMMARY:Apple to report earnings\, Q2\; call at 5pm",
+            r"DESCRIPTION:CNBC\nhttps://example.com/a\nTickers: AAPL",
+            "CATEGORIES:AAPL",
+        ] {
+            assert!(lines.iter().any(|l| l == expected), "missing {}", expected);
+        }
+    }
+}
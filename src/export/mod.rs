@@ -0,0 +1,7 @@
+//! Writing stored articles to formats other tools understand.
+
+pub mod ics;
+pub mod report;
+
+pub use ics::calendar;
+pub use report::{report, ReportFormat, ReportRequest};
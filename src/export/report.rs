@@ -0,0 +1,233 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use std::collections::BTreeMap;
+
+use crate::models::{source_id, NewsItem};
+
+/// Longest summary kept per article, in characters
+const SUMMARY_CHARS: usize = 300;
+/// Days of articles a report covers unless asked otherwise
+const REPORT_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// A report asked for as `QUERY... [--days N] [--html] [--out FILE]`, by
+/// `export report` or `:report`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportRequest {
+    pub query: String,
+    pub days: i64,
+    pub format: ReportFormat,
+    /// The file given, or one named after the query
+    pub path: String,
+}
+
+impl ReportRequest {
+    pub fn parse(args: &[&str]) -> Result<Self> {
+        let mut words = Vec::new();
+        let mut days = REPORT_DAYS;
+        let mut format = ReportFormat::Markdown;
+        let mut path = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match *arg {
+                "--html" => format = ReportFormat::Html,
+                "--days" => {
+                    days = args
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| anyhow!("--days needs a positive number"))?
+                }
+                "--out" => {
+                    let file = args.next().ok_or_else(|| anyhow!("--out needs a file"))?;
+                    path = Some(file.to_string());
+                }
+                word => words.push(word),
+            }
+        }
+        if words.is_empty() {
+            bail!("give a query, e.g. `report nvidia earnings`");
+        }
+        let path = path.unwrap_or_else(|| {
+            let slug: Vec<String> = words.iter().map(|w| source_id(w)).collect();
+            format!("report-{}.{}", slug.join("-"), format.extension())
+        });
+        Ok(Self {
+            query: words.join(" "),
+            days,
+            format,
+            path,
+        })
+    }
+}
+
+/// A research packet for `query`: the matching `items`, grouped by day
+/// (newest first) and source, each with its link and a short summary
+pub fn report(
+    query: &str,
+    items: &[NewsItem],
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+    format: ReportFormat,
+) -> String {
+    let mut days: BTreeMap<NaiveDate, BTreeMap<&str, Vec<&NewsItem>>> = BTreeMap::new();
+    for item in items {
+        let day = item.published.with_timezone(&Local).date_naive();
+        days.entry(day)
+            .or_default()
+            .entry(item.source.as_str())
+            .or_default()
+            .push(item);
+    }
+
+    let title = format!("Research report: {}", query);
+    let intro = format!(
+        "{} articles from {} to {}, generated {}",
+        items.len(),
+        since.with_timezone(&Local).format("%Y-%m-%d"),
+        now.with_timezone(&Local).format("%Y-%m-%d"),
+        now.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    );
+    let mut out = String::new();
+    match format {
+        ReportFormat::Markdown => {
+            out.push_str(&format!("# {}\n\n{}.\n", title, intro));
+        }
+        ReportFormat::Html => {
+            out.push_str(&format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n<p>{}.</p>\n",
+                escape_html(&title),
+                escape_html(&title),
+                escape_html(&intro)
+            ));
+        }
+    }
+
+    for (day, sources) in days.iter().rev() {
+        let heading = day.format("%Y-%m-%d (%A)").to_string();
+        match format {
+            ReportFormat::Markdown => out.push_str(&format!("\n## {}\n", heading)),
+            ReportFormat::Html => out.push_str(&format!("<h2>{}</h2>\n", heading)),
+        }
+        for (source, articles) in sources {
+            match format {
+                ReportFormat::Markdown => out.push_str(&format!("\n### {}\n\n", source)),
+                ReportFormat::Html => {
+                    out.push_str(&format!("<h3>{}</h3>\n<ul>\n", escape_html(source)))
+                }
+            }
+            for item in articles {
+                let time = item.published.with_timezone(&Local).format("%H:%M");
+                let summary = short_summary(&item.summary);
+                match format {
+                    ReportFormat::Markdown => {
+                        out.push_str(&format!(
+                            "- [{}]({}) — {}\n",
+                            escape_markdown(&item.title),
+                            item.url.replace('(', "%28").replace(')', "%29"),
+                            time
+                        ));
+                        if !summary.is_empty() {
+                            out.push_str(&format!("  > {}\n", escape_markdown(&summary)));
+                        }
+                    }
+                    ReportFormat::Html => {
+                        out.push_str(&format!(
+                            "<li><a href=\"{}\">{}</a> — {}",
+                            escape_html(&item.url),
+                            escape_html(&item.title),
+                            time
+                        ));
+                        if !summary.is_empty() {
+                            out.push_str(&format!(
+                                "<blockquote>{}</blockquote>",
+                                escape_html(&summary)
+                            ));
+                        }
+                        out.push_str("</li>\n");
+                    }
+                }
+            }
+            if format == ReportFormat::Html {
+                out.push_str("</ul>\n");
+            }
+        }
+    }
+
+    if format == ReportFormat::Html {
+        out.push_str("</body>\n</html>\n");
+    }
+    out
+}
+
+/// The summary on one line, cut at a word boundary
+fn short_summary(summary: &str) -> String {
+    let text = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= SUMMARY_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(SUMMARY_CHARS).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    format!("{}…", cut)
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | '*' | '_' | '`' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_report_requests() {
+        assert_eq!(
+            ReportRequest::parse(&["Nvidia", "earnings"]).unwrap(),
+            ReportRequest {
+                query: "Nvidia earnings".to_string(),
+                days: 7,
+                format: ReportFormat::Markdown,
+                path: "report-nvidia-earnings.md".to_string(),
+            }
+        );
+        let request =
+            ReportRequest::parse(&["--html", "fed", "--days", "30", "--out", "fed.html"]).unwrap();
+        assert_eq!(request.query, "fed");
+        assert_eq!(request.days, 30);
+        assert_eq!(request.format, ReportFormat::Html);
+        assert_eq!(request.path, "fed.html");
+
+        assert!(ReportRequest::parse(&[]).is_err());
+        assert!(ReportRequest::parse(&["--html"]).is_err());
+        assert!(ReportRequest::parse(&["fed", "--days", "0"]).is_err());
+        assert!(ReportRequest::parse(&["fed", "--out"]).is_err());
+    }
+}
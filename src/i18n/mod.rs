@@ -0,0 +1,239 @@
+//! Localized user-facing text: key help, pane titles and status message
+//! templates, looked up by id in one message catalog per locale. Catalogs
+//! are plain text files of `id = text` lines built into the binary; `{name}`
+//! placeholders are filled in by [`tr!`](crate::tr) and `\n` starts a new
+//! line. `[app] locale` picks the catalog, and a message it lacks falls back
+//! to English.
+//!
+//! To add a language, copy `en.txt`, translate the text after each `=` and
+//! list the file in [`LOCALES`].
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::fmt::{Display, Write as _};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// Locale code, name and catalog of each language, English first
+pub const LOCALES: &[(&str, &str, &str)] = &[
+    ("en", "English", include_str!("en.txt")),
+    ("de", "Deutsch", include_str!("de.txt")),
+];
+
+/// Index into [`LOCALES`] of the locale in use
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static CATALOGS: OnceLock<Vec<Catalog>> = OnceLock::new();
+
+type Catalog = HashMap<&'static str, String>;
+
+fn catalogs() -> &'static [Catalog] {
+    CATALOGS.get_or_init(|| LOCALES.iter().map(|(_, _, source)| parse(source)).collect())
+}
+
+/// A catalog's messages by id; blank lines and `#` comments are skipped
+fn parse(source: &'static str) -> Catalog {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (id, text) = line.split_once('=')?;
+            Some((id.trim(), text.trim().replace("\\n", "\n")))
+        })
+        .collect()
+}
+
+/// Position in [`LOCALES`] of a locale code such as "de", "de-AT" or
+/// "de_DE.UTF-8"
+fn find(code: &str) -> Option<usize> {
+    let language = code.split(['-', '_', '.']).next().unwrap_or(code);
+    LOCALES
+        .iter()
+        .position(|(known, _, _)| known.eq_ignore_ascii_case(language))
+}
+
+/// Whether `code` names a locale with a catalog
+pub fn is_available(code: &str) -> bool {
+    find(code).is_some()
+}
+
+/// Show text in the locale `code` from now on
+pub fn set_locale(code: &str) -> Result<()> {
+    let index = find(code);
+    CURRENT.store(index.unwrap_or(0), Ordering::Relaxed);
+    if index.is_none() {
+        let known: Vec<&str> = LOCALES.iter().map(|(known, _, _)| *known).collect();
+        return Err(anyhow!(
+            "unknown locale '{}' (available: {}); using English",
+            code,
+            known.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Code of the locale in use
+pub fn locale() -> &'static str {
+    LOCALES[CURRENT.load(Ordering::Relaxed)].0
+}
+
+/// The message `id` in the locale in use, or `id` itself if no catalog has it
+pub fn t(id: &str) -> &str {
+    lookup(CURRENT.load(Ordering::Relaxed), id)
+}
+
+fn lookup(locale: usize, id: &str) -> &str {
+    let catalogs = catalogs();
+    catalogs[locale]
+        .get(id)
+        .or_else(|| catalogs[0].get(id))
+        .map_or(id, String::as_str)
+}
+
+/// The message `id` with each `{name}` replaced by its value in `args`;
+/// [`tr!`](crate::tr) is the shorthand
+pub fn format(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    fill(t(id), args)
+}
+
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let (_, value) = args.iter().find(|(name, _)| *name == &after[..close])?;
+            Some((close, value))
+        });
+        match value {
+            Some((close, value)) => {
+                let _ = write!(out, "{}", value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// A localized message as a `String`, with its placeholders filled in:
+/// `tr!("status.sent", target = name)`
+#[macro_export]
+macro_rules! tr {
+    ($id:expr) => {
+        $crate::i18n::t($id).to_string()
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format(
+            $id,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `{name}` placeholders in a message, sorted
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn translations_match_the_english_catalog() {
+        let catalogs = catalogs();
+        let english = &catalogs[0];
+        for ((code, _, _), catalog) in LOCALES.iter().zip(catalogs).skip(1) {
+            for (id, text) in catalog {
+                let Some(original) = english.get(id) else {
+                    panic!("{}: `{}` is not an English message", code, id);
+                };
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(original),
+                    "{}: placeholders of `{}`",
+                    code,
+                    id
+                );
+            }
+            let missing: Vec<&&str> = english
+                .keys()
+                .filter(|id| !catalog.contains_key(*id))
+                .collect();
+            assert!(missing.is_empty(), "{} lacks {:?}", code, missing);
+        }
+    }
+
+    #[test]
+    fn every_message_used_in_the_source_exists() {
+        let english = &catalogs()[0];
+        let sections: Vec<&str> = english
+            .keys()
+            .filter_map(|id| id.split_once('.').map(|(section, _)| section))
+            .collect();
+        let mut dirs = vec![std::path::PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src"
+        ))];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if path.extension().is_none_or(|ext| ext != "rs") {
+                    continue;
+                }
+                let source = std::fs::read_to_string(&path).unwrap();
+                // Every string literal shaped like a message id
+                for literal in source.split('"').skip(1).step_by(2) {
+                    let Some((section, name)) = literal.split_once('.') else {
+                        continue;
+                    };
+                    let id_like = !name.is_empty()
+                        && literal
+                            .chars()
+                            .all(|c| c.is_ascii_lowercase() || c == '-' || c == '.');
+                    if id_like && sections.contains(&section) {
+                        assert!(
+                            english.contains_key(literal),
+                            "{}: no message `{}`",
+                            path.display(),
+                            literal
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn placeholders_are_filled_by_name() {
+        let name = "Reuters";
+        assert_eq!(
+            fill(
+                "{count} new from {source}, {x}",
+                &[("source", &name), ("count", &3)]
+            ),
+            "3 new from Reuters, {x}"
+        );
+        assert_eq!(fill("{ unclosed", &[]), "{ unclosed");
+        assert_eq!(lookup(1, "title.detail"), "Artikeldetails");
+        assert_eq!(lookup(0, "no.such-message"), "no.such-message");
+        assert_eq!(find("de_DE.UTF-8"), Some(1));
+        assert_eq!(find("fr"), None);
+    }
+}
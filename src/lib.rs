@@ -1,6 +1,61 @@
+//! News aggregation engine behind the news-hub terminal app.
+//!
+//! The fetch pipeline ([`AdaptorRegistry`], [`fetch_all`]), the normalized
+//! [`NewsItem`] model and SQLite storage ([`NewsDB`]) have no terminal
+//! dependencies. The TUI modules (`app`, `ui`, `scripting`, `watch`) are built
+//! with the default `tui` feature; depend on the crate with
+//! `default-features = false` to use it as a library only.
+//!
+//! ```no_run
+//! use news_hub::{fetch_all, AdaptorRegistry, Config, NewsDB};
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let registry = AdaptorRegistry::from_config(&Config::default());
+//! let mut result = fetch_all(&registry).await;
+//! result.collapse_duplicates(0.85);
+//!
+//! let db = NewsDB::new("news.db")?;
+//! for item in &result.items {
+//!     db.insert(item)?;
+//! }
+//! for duplicate in &result.duplicates {
+//!     db.record_duplicate(&duplicate.kept, &duplicate.item)?;
+//! }
+//! for diagnostic in result.diagnostics.iter().filter(|d| !d.success) {
+//!     eprintln!("{}: {}", diagnostic.source, diagnostic.message);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
 pub mod adaptors;
 pub mod models;
+#[cfg(feature = "tui")]
 pub mod app;
+pub mod clock;
 pub mod config;
 pub mod db;
+pub mod enrich;
+pub mod error;
+pub mod export;
+pub mod i18n;
+pub mod market;
+pub mod profile;
+#[cfg(feature = "tui")]
+pub mod scripting;
+pub mod sync;
+#[cfg(test)]
+mod test_support;
+#[cfg(feature = "tui")]
 pub mod ui;
+#[cfg(feature = "tui")]
+pub mod watch;
+
+pub use adaptors::{
+    fetch_all, fetch_one, AdaptorRegistry, FetchDiagnostic, FetchResult, NewsAdaptor,
+    SourceOptions,
+};
+pub use config::Config;
+pub use db::sqlite::NewsDB;
+pub use error::{ErrorCategory, NewsHubError};
+pub use models::{FilterState, NewsItem, Quote, Source};
@@ -1,18 +1,1140 @@
 use crossterm::{event, execute, terminal};
 use dotenvy::dotenv;
+use notify::RecommendedWatcher;
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::collections::HashMap;
 use std::io;
+use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use tokio::time::Duration;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant, MissedTickBehavior};
 
-use news_hub::adaptors::{build_adaptors, fetch_all};
-use news_hub::app::{App, AppMessage, AppState};
-use news_hub::db::sqlite::NewsDB;
-use news_hub::ui::{draw_ui, Action, StatusMessage};
+use news_hub::adaptors::{
+    build_quote_adaptor, code_from_redirect, reddit_tokens, AdaptorRegistry, FetchDiagnostic,
+    QuoteAdaptor, RegisteredAdaptor, BUNDLES, MAX_CONCURRENT_FETCHES,
+};
+use news_hub::app::{
+    app_channel, compile_openers, compile_share_targets, App, AppMessage, AppSender,
+};
+use news_hub::config::{
+    append_to_config, check_config, export_opml, parse_opml, plan_import, Config, RefreshConfig,
+    CONFIG_PATH, MIN_TAPE_INTERVAL_SECS,
+};
+use news_hub::db::sqlite::{NewsDB, StatusLogEntry};
+use news_hub::db::state::StateFile;
+use news_hub::enrich::{article_text, backfill, ContentCache, Crawler, Enrichment};
+use news_hub::export::{calendar, report, ReportRequest};
+use news_hub::market::configured_exchanges;
+use news_hub::models::{publishing_cadence, source_id, AlertKeywords, NewsItem, SourceKind};
+use news_hub::profile::{self, Phase};
+use news_hub::scripting::ScriptEngine;
+use news_hub::sync::{SyncDirection, SyncOutcome, SyncRepo};
+use news_hub::tr;
+use news_hub::ui::columns::compile_columns;
+use news_hub::ui::highlight::compile_rules;
+use news_hub::ui::keymap::compile_bindings;
+use news_hub::ui::{draw_ui, Action, MessageLevel, StatusMessage};
+use news_hub::watch::watch_dir;
+
+const DB_PATH: &str = "data/news.db";
+const USAGE: &str = "Usage: news-hub [--profile] [--read-only] [--screen-reader]
+       news-hub (config check
+                 | backfill [--tickers] [--content] [--events] [--restart]
+                 | export ics [FILE]
+                 | export report QUERY... [--days N] [--html] [--out FILE]
+                 | sources
+                 | bundles
+                 | cache stats|clear
+                 | auth reddit [--forget]
+                 | opml import FILE
+                 | opml export [FILE]
+                 | state export|import|sync FILE
+                 | sync pull|push)";
+/// Flags the TUI takes, in any order
+const TUI_FLAGS: &[&str] = &["--profile", "--read-only", "--screen-reader"];
+/// Default `export ics` output
+const ICS_PATH: &str = "events.ics";
+/// Default `opml export` output
+const OPML_PATH: &str = "feeds.opml";
+
+/// Spinner frame rate while a refresh is in flight
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+/// Newest articles a source's publishing cadence is learned from
+const CADENCE_SAMPLE: usize = 50;
+/// Ticker tape scroll speed (one column per tick)
+const TAPE_SCROLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Fetch the named sources concurrently, bounded by `slots`. Each task reports
+/// when it starts and finishes so the app can track per-source state. A
+/// fetch that can't run or panics still reports finishing, as a failure, so
+/// the refresh never waits on it forever.
+fn spawn_refresh(
+    sources: Vec<String>,
+    registry: &Arc<AdaptorRegistry>,
+    slots: &Arc<Semaphore>,
+    tx: &AppSender,
+) {
+    for source in sources {
+        let registry = Arc::clone(registry);
+        let slots = Arc::clone(slots);
+        let tx = tx.clone();
+
+        tokio::spawn(async move {
+            let fetch = {
+                let source = source.clone();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let adaptor = registry.get(&source)?;
+                    let _permit = slots.acquire().await.ok()?;
+                    tx.send(AppMessage::SourceStarted(source)).await;
+                    Some(adaptor.fetch().await)
+                })
+            };
+            let (items, diagnostic) = match fetch.await {
+                Ok(Some(fetched)) => fetched,
+                Ok(None) => (Vec::new(), fetch_failed(source, "not fetched".to_string())),
+                Err(e) => (
+                    Vec::new(),
+                    fetch_failed(source, format!("fetch failed: {}", e)),
+                ),
+            };
+            tx.send(AppMessage::SourceFetched { items, diagnostic })
+                .await;
+        });
+    }
+}
+
+/// Diagnostic for a fetch that ended without the adaptor reporting one
+fn fetch_failed(source: String, message: String) -> FetchDiagnostic {
+    FetchDiagnostic {
+        source,
+        success: false,
+        message,
+        warnings: Vec::new(),
+        moved_to: None,
+        error: None,
+    }
+}
+
+/// Send an article's read/starred state to the remote server it came from.
+/// Each remote source ignores articles that aren't its own.
+fn spawn_state_push(item: NewsItem, registry: &Arc<AdaptorRegistry>, tx: &AppSender) {
+    let registry = Arc::clone(registry);
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let mut result = Ok(());
+        for source in registry
+            .iter()
+            .filter(|s| s.adaptor().kind() == SourceKind::Remote)
+        {
+            if let Err(e) = source.adaptor().push_state(&item).await {
+                result = Err(format!("{}: {:#}", source.name(), e));
+            }
+        }
+        tx.send(AppMessage::StatePushed {
+            id: item.id,
+            read: item.read,
+            starred: item.starred,
+            result,
+        })
+        .await;
+    });
+}
+
+/// Sources whose scheduled interval has elapsed, rescheduling each one
+/// interval out. A newly scheduled source first comes due one interval from now.
+fn due_sources(
+    intervals: &HashMap<String, Duration>,
+    next_due: &mut HashMap<String, Instant>,
+    now: Instant,
+) -> Vec<String> {
+    let mut due = Vec::new();
+    for (name, interval) in intervals {
+        let next = next_due.entry(name.clone()).or_insert(now + *interval);
+        if now >= *next {
+            due.push(name.clone());
+            *next = now + *interval;
+        }
+    }
+    due
+}
+
+/// Scheduled interval of each source: the configured one, or for adaptive
+/// sources one learned from their stored articles
+fn refresh_intervals(
+    registry: &AdaptorRegistry,
+    db: &NewsDB,
+    cfg: &RefreshConfig,
+) -> HashMap<String, Duration> {
+    let mut intervals: HashMap<String, Duration> = registry
+        .scheduled()
+        .map(|(name, interval)| (name.to_string(), interval))
+        .collect();
+    for source in registry.adaptive() {
+        intervals.insert(source.name().to_string(), learned_interval(source, db, cfg));
+    }
+    intervals
+}
+
+/// Interval matching how often the source publishes; its configured
+/// interval, or the longest one, until it has enough stored articles
+fn learned_interval(source: &RegisteredAdaptor, db: &NewsDB, cfg: &RefreshConfig) -> Duration {
+    let published = db
+        .publish_times(&source_id(source.name()), CADENCE_SAMPLE)
+        .unwrap_or_default();
+    match publishing_cadence(&published) {
+        Some(cadence) => cfg.interval_for(Some(cadence)),
+        None => source
+            .options
+            .interval
+            .unwrap_or_else(|| cfg.interval_for(None)),
+    }
+}
+
+/// Re-learn an adaptive source's interval after it fetched new articles.
+/// A source that picked up pace comes due by its new interval rather than
+/// waiting out the old one.
+fn relearn_interval(
+    services: &mut ConfiguredServices,
+    db: &NewsDB,
+    name: &str,
+    next_due: &mut HashMap<String, Instant>,
+    now: Instant,
+) {
+    let Some(source) = services.sources.adaptive().find(|s| s.name() == name) else {
+        return;
+    };
+    let interval = learned_interval(source, db, &services.refresh);
+    if services.intervals.insert(name.to_string(), interval) != Some(interval) {
+        tracing::debug!("{} now refreshes every {}s", name, interval.as_secs());
+        if let Some(next) = next_due.get_mut(name) {
+            *next = (*next).min(now + interval);
+        }
+    }
+}
+
+/// Background services built from config. Replacing the value (on config
+/// reload) stops the old ticker tape task and file watcher.
+#[derive(Default)]
+struct ConfiguredServices {
+    sources: Arc<AdaptorRegistry>,
+    /// Scheduled refresh interval by source name
+    intervals: HashMap<String, Duration>,
+    refresh: RefreshConfig,
+    /// Reload the list when another process writes the database
+    follow_database: bool,
+    /// Repository `:sync` goes through, if configured
+    sync: Option<SyncRepo>,
+    /// Fetches article pages for their full text (b), and caches the text
+    content: Option<(Arc<Crawler>, Arc<ContentCache>)>,
+    quote_adaptor: Option<Arc<dyn QuoteAdaptor>>,
+    ticker_tape_task: Option<JoinHandle<()>>,
+    _script_watcher: Option<RecommendedWatcher>,
+}
+
+impl Drop for ConfiguredServices {
+    fn drop(&mut self) {
+        if let Some(task) = &self.ticker_tape_task {
+            task.abort();
+        }
+    }
+}
+
+/// Apply config settings to the app and start the services they enable.
+/// Used at startup and on every reload; returns problems as warnings.
+fn apply_config(
+    app: &mut App,
+    config: &Config,
+    db: &NewsDB,
+    tx: &AppSender,
+) -> (ConfiguredServices, Vec<String>) {
+    let mut warnings = Vec::new();
+    if let Err(e) = app.set_locale(&config.app.locale) {
+        warnings.push(format!("{:#}", e));
+    }
+    let mut services = ConfiguredServices::default();
+    services.sources = Arc::new(AdaptorRegistry::from_config(config));
+    services.refresh = config.refresh.clone();
+    services.follow_database = config.app.follow_database;
+    services.sync = SyncRepo::from_config(&config.sync);
+    services.content = Some((
+        Arc::new(Crawler::new(reqwest::Client::new(), &config.crawl)),
+        Arc::new(ContentCache::from_config(&config.cache)),
+    ));
+    services.intervals = refresh_intervals(&services.sources, db, &config.refresh);
+
+    // Keep the sources table in step with the registry; the stored rows add
+    // each source's fetch history
+    let mut sources = services.sources.sources();
+    if db.is_read_only() {
+        // The writing process keeps the table in step
+    } else if let Err(e) = db.sync_sources(&sources) {
+        warnings.push(format!("{:#}", e));
+    }
+    match db.load_sources() {
+        Ok(stored) => sources = stored,
+        Err(e) => warnings.push(format!("{:#}", e)),
+    }
+    app.news_list.set_sources(&sources);
+    app.set_cooldowns(&sources);
+    app.set_deduplication(
+        config
+            .app
+            .deduplication_enabled
+            .then_some(config.app.similarity_threshold),
+    );
+    app.set_alerts(AlertKeywords::new(&config.alerts.keywords));
+    app.set_title_dedup(
+        services
+            .sources
+            .iter()
+            .filter_map(|source| {
+                let window = source.options.title_dedup?;
+                let window = chrono::Duration::from_std(window).ok()?;
+                Some((source.name().to_string(), window))
+            })
+            .collect(),
+    );
+    app.news_list
+        .set_reliability_weighting(config.app.reliability_weighting);
+    app.news_list.set_inbox_zero(config.app.inbox_zero);
+    app.news_list.set_time_format(config.app.time_format);
+    app.set_bulk_open(
+        config.app.bulk_open_confirm_above,
+        Duration::from_millis(config.app.bulk_open_delay_ms),
+    );
+    app.news_list.set_staleness(
+        chrono::Duration::minutes(config.app.stale_after_mins as i64),
+        chrono::Duration::minutes(config.app.very_stale_after_mins as i64),
+    );
+    let (columns, column_errors) = compile_columns(&config.list.columns);
+    app.news_list.set_columns(columns);
+    app.news_list.set_density(config.list.density);
+    warnings.extend(column_errors);
+
+    let exchanges = configured_exchanges(&config.market).unwrap_or_else(|e| {
+        warnings.push(format!("{:#}", e));
+        Vec::new()
+    });
+    app.status_bar.set_exchanges(exchanges);
+    app.status_bar.set_history_size(config.status.history_size);
+    app.status_bar
+        .set_expanded_height(config.status.history_height);
+    let status = &config.status;
+    for (level, secs) in [
+        (MessageLevel::Info, status.info_secs),
+        (MessageLevel::Success, status.success_secs),
+        (MessageLevel::Warning, status.warning_secs),
+        (MessageLevel::Error, status.error_secs),
+    ] {
+        let after = (secs > 0).then(|| Duration::from_secs(secs));
+        app.status_bar.set_dismiss_after(level, after);
+    }
+    app.status_bar.set_persist(config.status.persist_history);
+
+    let (highlight_rules, highlight_errors) = compile_rules(&config.highlight);
+    app.news_list.set_highlight_rules(highlight_rules);
+    warnings.extend(highlight_errors);
+
+    let (openers, opener_errors) = compile_openers(&config.open, &config.sources);
+    app.set_openers(openers, config.open.mark_read);
+    app.set_mark_read_after(
+        Some(Duration::from_secs(config.open.mark_read_after_secs))
+            .filter(|after| !after.is_zero()),
+    );
+    warnings.extend(opener_errors);
+
+    let (bindings, binding_errors) = compile_bindings(&config.keys);
+    warnings.extend(binding_errors);
+
+    let (share_targets, share_errors) = compile_share_targets(&config.share, &bindings);
+    app.set_share_targets(share_targets);
+    warnings.extend(share_errors);
+    app.set_key_bindings(bindings);
+
+    // Quote provider shared by the ticker tape and detail pane
+    services.quote_adaptor = match build_quote_adaptor(&config.quotes.provider) {
+        Ok(adaptor) => Some(Arc::from(adaptor)),
+        Err(e) => {
+            warnings.push(format!("{:#}", e));
+            None
+        }
+    };
+    let article_quotes = services.quote_adaptor.is_some() && config.quotes.article_quotes;
+    app.set_article_quotes(
+        article_quotes.then(|| Duration::from_secs(config.quotes.cache_ttl_secs)),
+    );
+
+    // Ticker tape refreshes on its own interval, independent of news refreshes
+    let tape_adaptor = services
+        .quote_adaptor
+        .clone()
+        .filter(|_| config.ticker_tape.enabled);
+    app.ticker_tape.set_enabled(tape_adaptor.is_some());
+    if let Some(quote_adaptor) = tape_adaptor {
+        let tx = tx.clone();
+        let symbols = config.ticker_tape.symbols.clone();
+        let period = Duration::from_secs(
+            config
+                .ticker_tape
+                .refresh_interval_secs
+                .max(MIN_TAPE_INTERVAL_SECS),
+        );
+
+        services.ticker_tape_task = Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                let msg = match quote_adaptor.fetch_quotes(&symbols).await {
+                    Ok(quotes) => AppMessage::QuotesUpdated(quotes),
+                    Err(e) => AppMessage::QuotesFailed(format!("{:#}", e)),
+                };
+                if !tx.send(msg).await {
+                    break;
+                }
+            }
+        }));
+    }
+
+    // User scripts, reloaded when a file in the script directory changes
+    if config.scripts.enabled {
+        let mut scripts = ScriptEngine::new(&config.scripts.dir);
+        warnings.extend(scripts.reload());
+        if scripts.dir().is_dir() {
+            let tx = tx.clone();
+            let on_change = move || {
+                tx.try_send(AppMessage::ScriptsChanged);
+            };
+            let is_script = |path: &Path| path.extension().is_some_and(|ext| ext == "lua");
+            match watch_dir(scripts.dir(), is_script, on_change) {
+                Ok(watcher) => services._script_watcher = Some(watcher),
+                Err(e) => warnings.push(format!("{:#}", e)),
+            }
+        }
+        app.set_scripts(Some(scripts));
+    } else {
+        app.set_scripts(None);
+    }
+
+    (services, warnings)
+}
+
+/// Re-read config.toml and apply it. A config that fails to load leaves the
+/// running settings untouched.
+fn reload_config(app: &mut App, services: &mut ConfiguredServices, db: &NewsDB, tx: &AppSender) {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            let msg = StatusMessage::error(tr!(
+                "status.config-reload-failed",
+                error = format!("{:#}", e)
+            ));
+            app.status_bar.set_message(msg);
+            return;
+        }
+    };
+
+    // Stop the old services before starting their replacements
+    *services = ConfiguredServices::default();
+    let (new_services, mut warnings) = apply_config(app, &config, db, tx);
+    *services = new_services;
+    warnings.extend(config_check_summary());
+
+    if warnings.is_empty() {
+        let msg = StatusMessage::success(tr!("status.config-reloaded", path = CONFIG_PATH));
+        app.status_bar.set_message(msg);
+    }
+    for warning in warnings {
+        app.status_bar.set_message(StatusMessage::warning(warning));
+    }
+}
+
+/// Load the status history kept by earlier sessions
+fn restore_status_log(app: &mut App, db: &NewsDB) -> anyhow::Result<()> {
+    let messages = db
+        .load_status_log(app.status_bar.history_size())?
+        .into_iter()
+        .filter_map(|entry| {
+            let level = MessageLevel::parse(&entry.level)?;
+            Some(StatusMessage::restored(
+                level,
+                entry.text,
+                entry.count,
+                entry.logged_at,
+            ))
+        })
+        .collect();
+    app.status_bar.restore_history(messages);
+    Ok(())
+}
+
+/// Write new history entries to the status log, keeping as many as the history holds
+fn save_status_log(app: &mut App, db: &NewsDB) -> anyhow::Result<()> {
+    let messages = app.status_bar.take_unsaved_history();
+    if messages.is_empty() || db.is_read_only() {
+        return Ok(());
+    }
+    for message in messages {
+        db.append_status_log(&StatusLogEntry {
+            level: message.level.as_str().to_string(),
+            text: message.text,
+            count: message.count,
+            logged_at: message.timestamp,
+        })?;
+    }
+    db.prune_status_log(app.status_bar.history_size())
+}
+
+/// One-line summary of `config check` problems, if there are any
+fn config_check_summary() -> Option<String> {
+    let contents = std::fs::read_to_string(CONFIG_PATH).ok()?;
+    let issues = check_config(&contents);
+    let first = issues.first()?;
+    Some(format!(
+        "{}: {} problem(s), first at {} (run `news-hub config check`)",
+        CONFIG_PATH,
+        issues.len(),
+        first
+    ))
+}
+
+/// `news-hub config check`: print every problem in config.toml.
+/// Returns the process exit code (1 if any problems were found).
+fn run_config_check() -> i32 {
+    // Remote server passwords come from the environment
+    dotenv().ok();
+    let contents = match std::fs::read_to_string(CONFIG_PATH) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            println!("{} not found; built-in defaults are used", CONFIG_PATH);
+            return 0;
+        }
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", CONFIG_PATH, e);
+            return 1;
+        }
+    };
+
+    let issues = check_config(&contents);
+    for issue in &issues {
+        println!("{}:{}: {}", CONFIG_PATH, issue.line, issue.message);
+    }
+    if issues.is_empty() {
+        println!("{}: OK", CONFIG_PATH);
+        0
+    } else {
+        println!("{} problem(s) found", issues.len());
+        1
+    }
+}
+
+/// `news-hub backfill`: re-run enrichment over stored articles.
+/// Returns the process exit code.
+async fn run_backfill(flags: &[&str]) -> i32 {
+    let mut steps = Vec::new();
+    let mut restart = false;
+    for flag in flags {
+        match *flag {
+            "--tickers" => steps.push(Enrichment::Tickers),
+            "--content" => steps.push(Enrichment::Content),
+            "--events" => steps.push(Enrichment::Events),
+            "--restart" => restart = true,
+            "--sentiment" => {
+                eprintln!(
+                    "backfill: --sentiment is not available; no sentiment enrichment exists yet"
+                );
+                return 2;
+            }
+            _ => {
+                eprintln!("{}", USAGE);
+                return 2;
+            }
+        }
+    }
+    if steps.is_empty() {
+        eprintln!("backfill: choose at least one of --tickers, --content, --events");
+        return 2;
+    }
+
+    let db = match NewsDB::new(DB_PATH) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let cache = Arc::new(ContentCache::from_config(&config.cache));
+
+    let mut reported_errors = 0;
+    let result = backfill(
+        &db,
+        &steps,
+        restart,
+        Some(cache.clone()),
+        &config.crawl,
+        |progress| {
+            for error in &progress.errors[reported_errors..] {
+                eprintln!("\r\x1b[Kwarning: {}", error);
+            }
+            reported_errors = progress.errors.len();
+            eprint!(
+                "\rBackfilling: {}/{} articles, {} updated",
+                progress.processed, progress.total, progress.updated
+            );
+        },
+    )
+    .await;
+    eprintln!();
+
+    match result {
+        Ok(progress) => {
+            if progress.resumed_from > 0 {
+                println!(
+                    "Resumed after {} articles done by an earlier run",
+                    progress.resumed_from
+                );
+            }
+            println!(
+                "Backfilled {} articles: {} updated, {} failed",
+                progress.processed - progress.resumed_from,
+                progress.updated,
+                progress.errors.len()
+            );
+            if let Some(stats) = cache.stats().ok().filter(|s| s.hits > 0) {
+                println!("{} article pages read from the cache", stats.hits);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Backfill stopped: {:#}", e);
+            eprintln!("Run the same command again to resume");
+            1
+        }
+    }
+}
+
+/// `news-hub export ics`: write upcoming events announced by stored articles
+/// to an iCalendar file. Returns the process exit code.
+fn run_export_ics(path: &str) -> i32 {
+    let db = match NewsDB::new(DB_PATH) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let now = chrono::Utc::now();
+    let events = match db.load_events(now.date_naive()) {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    if let Err(e) = std::fs::write(path, calendar(&events, now)) {
+        eprintln!("Failed to write {}: {}", path, e);
+        return 1;
+    }
+    println!("Wrote {} upcoming events to {}", events.len(), path);
+    if events.is_empty() {
+        println!("Run `news-hub backfill --events` to find events in older articles");
+    }
+    0
+}
+
+/// `news-hub export report`: write the stored articles matching a query as
+/// a Markdown or HTML report. Returns the process exit code.
+fn run_export_report(args: &[&str]) -> i32 {
+    let request = match ReportRequest::parse(args) {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("export report: {}", e);
+            return 2;
+        }
+    };
+    let db = match NewsDB::new(DB_PATH) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let now = chrono::Utc::now();
+    let since = now - chrono::Duration::days(request.days);
+    let items = match db.search(&request.query, since) {
+        Ok(items) => items,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let text = report(&request.query, &items, since, now, request.format);
+    if let Err(e) = std::fs::write(&request.path, text) {
+        eprintln!("Failed to write {}: {}", request.path, e);
+        return 1;
+    }
+    println!(
+        "Wrote {} articles matching \"{}\" from the last {} days to {}",
+        items.len(),
+        request.query,
+        request.days,
+        request.path
+    );
+    0
+}
+
+/// `news-hub cache stats|clear`: print the content cache's size, or empty it
+fn run_cache(clear: bool) -> i32 {
+    let cache = match Config::load() {
+        Ok(config) => ContentCache::from_config(&config.cache),
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let result = if clear {
+        cache
+            .clear()
+            .map(|removed| println!("Removed {} cached articles", removed))
+    } else {
+        cache.stats().map(|stats| {
+            println!(
+                "{}: {} articles, {:.1} MB, {} expired",
+                cache.dir().display(),
+                stats.entries,
+                stats.bytes as f64 / (1024.0 * 1024.0),
+                stats.expired
+            )
+        })
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            1
+        }
+    }
+}
+
+/// `news-hub auth SOURCE`: authorize news-hub with a source's OAuth login
+/// and keep the refresh token, or forget it with `--forget`. Returns the
+/// process exit code.
+async fn run_auth(source: &str, forget: bool) -> i32 {
+    // Client ids and secrets come from the environment
+    dotenv().ok();
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let tokens = match source {
+        "reddit" => match (
+            config.credentials.reddit_client_id,
+            config.credentials.reddit_client_secret,
+        ) {
+            (Some(id), Some(secret)) => reddit_tokens(id, secret, &config.reddit),
+            _ => {
+                eprintln!("auth: set REDDIT_CLIENT_ID and REDDIT_CLIENT_SECRET first");
+                return 1;
+            }
+        },
+        _ => {
+            eprintln!("auth: unknown source {:?}; known sources: reddit", source);
+            return 2;
+        }
+    };
+
+    if forget {
+        return match tokens.store().remove(source) {
+            Ok(true) => {
+                println!("Forgot the {} authorization", source);
+                0
+            }
+            Ok(false) => {
+                println!("{} was not authorized", source);
+                0
+            }
+            Err(e) => {
+                eprintln!("{:#}", e);
+                1
+            }
+        };
+    }
+
+    let state = uuid::Uuid::new_v4().simple().to_string();
+    println!(
+        "Open this address, allow access, then paste the address your browser \
+         was sent to (the page itself may fail to load):\n\n{}\n",
+        tokens.authorize_url(&state)
+    );
+    let mut input = String::new();
+    if let Err(e) = io::stdin().read_line(&mut input) {
+        eprintln!("Failed to read the address: {}", e);
+        return 1;
+    }
+    let result = match code_from_redirect(&input, &state) {
+        Ok(code) => tokens.authorize(&code).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => {
+            println!(
+                "Authorized {}; the refresh token is kept in {}",
+                source,
+                tokens.store().path().display()
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("Authorization failed: {:#}", e);
+            1
+        }
+    }
+}
+
+/// `news-hub bundles`: list the built-in feed bundles, marking the ones
+/// `[app] bundles` selects
+fn run_bundles() -> i32 {
+    let selected = match Config::load() {
+        Ok(config) => config.app.bundles,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    for bundle in BUNDLES {
+        let mark = if selected.iter().any(|name| name == bundle.name) {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "{} {} (v{}): {}",
+            mark, bundle.name, bundle.version, bundle.description
+        );
+        for (url, name) in bundle.feeds {
+            println!("      {:<24} {}", name, url);
+        }
+    }
+    println!(
+        "\n* selected; set `bundles` under [app] in {} to change",
+        CONFIG_PATH
+    );
+    0
+}
+
+/// `news-hub opml export`: write the configured feeds to an OPML file.
+/// Returns the process exit code.
+fn run_opml_export(path: &str) -> i32 {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let opml = export_opml(&config, chrono::Utc::now());
+    if let Err(e) = std::fs::write(path, &opml) {
+        eprintln!("Failed to write {}: {}", path, e);
+        return 1;
+    }
+    let feeds = opml.matches("xmlUrl=").count();
+    println!("Wrote {} feeds to {}", feeds, path);
+    0
+}
+
+/// `news-hub opml import FILE`: add the feeds of another reader's OPML
+/// export to config.toml as `[[feed]]` entries and register them as
+/// sources. Returns the process exit code.
+fn run_opml_import(path: &str) -> i32 {
+    let feeds = match std::fs::read(path) {
+        Ok(xml) => parse_opml(&xml),
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path, e);
+            return 1;
+        }
+    };
+    let feeds = match feeds {
+        Ok(feeds) => feeds,
+        Err(e) => {
+            eprintln!("{}: {:#}", path, e);
+            return 1;
+        }
+    };
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+
+    let import = plan_import(&feeds, &config);
+    for (name, reason) in &import.skipped {
+        println!("Skipped {}: {}", name, reason);
+    }
+    if import.added.is_empty() {
+        println!("No new feeds in {}", path);
+        return 0;
+    }
+
+    if let Err(e) = append_to_config(&import.toml) {
+        eprintln!("{:#}", e);
+        return 1;
+    }
+    for (name, url) in &import.added {
+        println!("Added {} ({})", name, url);
+    }
+    println!(
+        "Added {} feeds to {}, skipped {}",
+        import.added.len(),
+        CONFIG_PATH,
+        import.skipped.len()
+    );
+
+    // Register the new sources now so they are listed before the first refresh
+    let registered = Config::load().and_then(|config| {
+        let db = NewsDB::new(DB_PATH)?;
+        db.sync_sources(&AdaptorRegistry::from_config(&config).sources())
+    });
+    if let Err(e) = registered {
+        eprintln!("{:#}", e);
+        return 1;
+    }
+    0
+}
+
+/// `news-hub sources`: list known sources with their fetch history and
+/// reliability score. Returns the process exit code.
+fn run_sources() -> i32 {
+    let db = match NewsDB::new(DB_PATH) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    let mut sources = match db.load_sources() {
+        Ok(sources) => sources,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return 1;
+        }
+    };
+    // Least reliable first; never-fetched sources last
+    sources.sort_by(|a, b| {
+        let key = |s: &news_hub::models::Source| s.stats.reliability().unwrap_or(f64::INFINITY);
+        key(a).total_cmp(&key(b))
+    });
+
+    println!(
+        "{:<28} {:>8} {:>8} {:>8} {:>6} {:>11}",
+        "SOURCE", "FETCHES", "SUCCESS", "DELAY", "DUPES", "RELIABILITY"
+    );
+    for source in &sources {
+        let stats = &source.stats;
+        let reliability = match stats.reliability() {
+            Some(score) => format!("{:.0}%", score * 100.0),
+            None => "-".to_string(),
+        };
+        println!(
+            "{:<28} {:>8} {:>7.0}% {:>7.1}h {:>5.0}% {:>11}",
+            source.name,
+            stats.fetches,
+            stats.success_rate() * 100.0,
+            stats.average_lag_secs() / 3600.0,
+            stats.duplicate_ratio() * 100.0,
+            reliability
+        );
+    }
+
+    for source in sources.iter().filter(|s| s.moved_to.is_some()) {
+        println!(
+            "\n{} has moved to {}\nSet `url` under [sources.\"{}\"] in {} to follow it",
+            source.name,
+            source.moved_to.as_deref().unwrap_or_default(),
+            source.name,
+            CONFIG_PATH
+        );
+    }
+    0
+}
+
+/// `news-hub state export|import|sync FILE`: exchange read, starred and
+/// tags state and deletions with another machine through FILE, typically
+/// in a synced folder. `sync` merges FILE, if there is one, then rewrites it
+/// with the merged state, so machines that each run it converge. Returns
+/// the process exit code.
+fn run_state(command: &str, path: &str) -> i32 {
+    let path = std::path::Path::new(path);
+    let result = NewsDB::new(DB_PATH).and_then(|db| {
+        if command != "export" {
+            match StateFile::read(path)? {
+                Some(state) => {
+                    let merged = db.import_state(&state)?;
+                    println!(
+                        "Merged {}: {} articles added, {} updated, {} deleted",
+                        path.display(),
+                        merged.added,
+                        merged.updated,
+                        merged.deleted
+                    );
+                }
+                None if command == "import" => {
+                    return Err(anyhow::anyhow!("{} does not exist", path.display()));
+                }
+                None => {}
+            }
+        }
+        if command != "import" {
+            let state = db.export_state(chrono::Utc::now())?;
+            state.write(path)?;
+            println!(
+                "Wrote {} articles and {} deletions to {}",
+                state.items.len(),
+                state.deleted.len(),
+                path.display()
+            );
+        }
+        Ok(())
+    });
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            1
+        }
+    }
+}
+
+/// Push `state`, this machine's exported article state, with the configured
+/// feeds; without it, pull the other machines' and add their feeds to
+/// config.toml. Runs git, so it blocks.
+fn sync_with(repo: &SyncRepo, state: Option<StateFile>) -> anyhow::Result<SyncOutcome> {
+    let config = Config::load()?;
+    let Some(state) = state else {
+        let outcome = repo.pull(&config)?;
+        if let SyncOutcome::Pulled { feeds, .. } = &outcome {
+            if !feeds.toml.is_empty() {
+                append_to_config(&feeds.toml)?;
+            }
+        }
+        return Ok(outcome);
+    };
+    repo.push(&state, &export_opml(&config, chrono::Utc::now()))
+}
+
+/// `news-hub sync pull|push`: `:sync` from the command line. Returns the
+/// process exit code.
+fn run_sync(direction: SyncDirection) -> i32 {
+    let result = Config::load().and_then(|config| {
+        let Some(repo) = SyncRepo::from_config(&config.sync) else {
+            return Err(anyhow::anyhow!(
+                "Set `dir` under [sync] in {} to sync",
+                CONFIG_PATH
+            ));
+        };
+        let db = NewsDB::new(DB_PATH)?;
+        let state = match direction {
+            SyncDirection::Push => Some(db.export_state(chrono::Utc::now())?),
+            SyncDirection::Pull => None,
+        };
+        match sync_with(&repo, state)? {
+            SyncOutcome::Pushed { committed, pushed } => {
+                let what = if committed {
+                    "Committed this machine's state"
+                } else {
+                    "Nothing changed since the last push"
+                };
+                let remote = if pushed {
+                    "pushed"
+                } else {
+                    "no remote to push to"
+                };
+                println!("{} in {} ({})", what, repo.dir().display(), remote);
+            }
+            SyncOutcome::Pulled { states, feeds } => {
+                for state in &states {
+                    let merged = db.import_state(state)?;
+                    println!(
+                        "Merged state from {}: {} articles added, {} updated, {} deleted",
+                        state.exported_at.format("%Y-%m-%d %H:%M UTC"),
+                        merged.added,
+                        merged.updated,
+                        merged.deleted
+                    );
+                }
+                for (name, url) in &feeds.added {
+                    println!("Added {} ({})", name, url);
+                }
+                // Register the new feeds now, as `opml import` does
+                if !feeds.added.is_empty() {
+                    let config = Config::load()?;
+                    db.sync_sources(&AdaptorRegistry::from_config(&config).sources())?;
+                }
+            }
+        }
+        Ok(())
+    });
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            1
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        // --profile: run as usual, then print where startup and refreshes
+        // spent their time. --read-only: browse without writing the database.
+        // --screen-reader: the plain layout, as with `[app] screen_reader`.
+        ref flags if flags.iter().all(|f| TUI_FLAGS.contains(f)) => {}
+        ["config", "check"] => std::process::exit(run_config_check()),
+        ["backfill", ref flags @ ..] => std::process::exit(run_backfill(flags).await),
+        ["export", "ics"] => std::process::exit(run_export_ics(ICS_PATH)),
+        ["export", "ics", path] => std::process::exit(run_export_ics(path)),
+        ["export", "report", ref args @ ..] => std::process::exit(run_export_report(args)),
+        ["sources"] => std::process::exit(run_sources()),
+        ["bundles"] => std::process::exit(run_bundles()),
+        ["cache", "stats"] => std::process::exit(run_cache(false)),
+        ["cache", "clear"] => std::process::exit(run_cache(true)),
+        ["auth", source] => std::process::exit(run_auth(source, false).await),
+        ["auth", source, "--forget"] => std::process::exit(run_auth(source, true).await),
+        ["opml", "import", path] => std::process::exit(run_opml_import(path)),
+        ["opml", "export"] => std::process::exit(run_opml_export(OPML_PATH)),
+        ["opml", "export", path] => std::process::exit(run_opml_export(path)),
+        ["state", command @ ("export" | "import" | "sync"), path] => {
+            std::process::exit(run_state(command, path))
+        }
+        ["sync", "pull"] => std::process::exit(run_sync(SyncDirection::Pull)),
+        ["sync", "push"] => std::process::exit(run_sync(SyncDirection::Push)),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+
+    if args.iter().any(|a| a == "--profile") {
+        profile::enable();
+    }
+    let read_only = args.iter().any(|a| a == "--read-only");
+    let screen_reader = args.iter().any(|a| a == "--screen-reader");
+
     dotenv().ok();
 
     // Initialize file-based logging
@@ -27,21 +1149,48 @@ async fn main() -> io::Result<()> {
         .with_ansi(false)
         .init();
 
-    let benzinga_key = std::env::var("BENZINGA_KEY").ok();
-
-    let db = NewsDB::new("data/news.db").expect("Failed to initialize database");
-
-    // Build adaptors dynamically based on available API keys
-    let adaptors = Arc::new(build_adaptors(benzinga_key.clone()));
+    // Config problems are reported in the status bar rather than aborting startup
+    let mut startup_warnings = Vec::new();
+    let span = profile::span(Phase::Startup, "config load");
+    let config = match Config::load() {
+        Ok(config) => {
+            startup_warnings.extend(config_check_summary());
+            startup_warnings.extend(config.credentials_warning());
+            config
+        }
+        Err(e) => {
+            startup_warnings.push(format!("{:#}; using defaults", e));
+            Config::default()
+        }
+    };
+    drop(span);
+    let span = profile::span(Phase::Startup, "database open");
+    let db = if read_only {
+        match NewsDB::open_read_only(DB_PATH) {
+            Ok(db) => db,
+            Err(e) => {
+                eprintln!("{:#}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        NewsDB::new(DB_PATH).expect("Failed to initialize database")
+    };
+    drop(span);
 
     // TUI setup
     terminal::enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(&mut stdout, terminal::EnterAlternateScreen)?;
+    execute!(
+        &mut stdout,
+        terminal::EnterAlternateScreen,
+        event::EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Initialize app with database-loaded news
+    let span = profile::span(Phase::Startup, "article load");
     let initial_news = match db.load_all() {
         Ok(news) => news,
         Err(e) => {
@@ -49,83 +1198,391 @@ async fn main() -> io::Result<()> {
             Vec::new()
         }
     };
+    drop(span);
 
     // Check if empty before moving
     let is_empty = initial_news.is_empty();
     let mut app = App::new(initial_news);
+    app.set_read_only(read_only);
+
+    // Channel for background task communication
+    let (tx, mut rx) = app_channel();
+
+    let span = profile::span(Phase::Startup, "config apply");
+    let (mut services, config_warnings) = apply_config(&mut app, &config, &db, &tx);
+    drop(span);
+    app.set_screen_reader(screen_reader || config.app.screen_reader);
+    // The wheel scrolls and clicks focus the pane under the cursor; the plain
+    // layout has no panes
+    if config.app.mouse && !app.is_screen_reader() {
+        execute!(terminal.backend_mut(), event::EnableMouseCapture)?;
+    }
+    match db.load_sources() {
+        Ok(sources) => {
+            let last_refresh = sources.iter().filter_map(|s| s.last_fetch).max();
+            app.news_list.set_last_refresh(last_refresh);
+        }
+        Err(e) => startup_warnings.push(format!("{:#}", e)),
+    }
+    match db.load_followed_stories() {
+        Ok(stories) => app.set_followed_stories(stories),
+        Err(e) => startup_warnings.push(format!("{:#}", e)),
+    }
+    startup_warnings.extend(config_warnings);
+    if config.status.persist_history {
+        if let Err(e) = restore_status_log(&mut app, &db) {
+            startup_warnings.push(format!("{:#}", e));
+        }
+    }
 
     // Show initial status if database had errors
-    if is_empty {
-        let msg = StatusMessage::warning("Database is empty. Press 'r' to fetch news.".to_string());
+    if read_only {
+        let msg = StatusMessage::info(tr!("status.read-only"));
         app.status_bar.set_message(msg);
+    } else if is_empty && !config.app.fetch_on_start {
+        let msg = StatusMessage::warning(tr!("status.database-empty")).sticky();
+        app.status_bar.set_message(msg);
+    }
+    for warning in startup_warnings {
+        app.status_bar.set_message(StatusMessage::warning(warning));
     }
 
-    // Channel for background task communication
-    let (tx, mut rx) = mpsc::unbounded_channel();
+    // Settings are reapplied live when config.toml is saved
+    let config_path = Path::new(CONFIG_PATH);
+    let config_dir = match config_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let config_name = config_path.file_name().map(|n| n.to_os_string());
+    let config_tx = tx.clone();
+    let _config_watcher = match watch_dir(
+        config_dir,
+        move |path| path.file_name() == config_name.as_deref(),
+        move || {
+            config_tx.try_send(AppMessage::ConfigChanged);
+        },
+    ) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            app.status_bar
+                .set_message(StatusMessage::warning(format!("{:#}", e)));
+            None
+        }
+    };
 
-    loop {
-        // Draw UI with current state
-        draw_ui(
-            &mut terminal,
-            &app.search_bar,
-            &app.news_list,
-            &app.detail_pane,
-            &app.status_bar,
-            app.app_state,
-        )?;
+    // Terminal input is read on a dedicated thread so the loop below can
+    // sleep until input, a background message, or an active timer wakes it
+    let (input_tx, mut input_rx) = mpsc::unbounded_channel();
+    // Replayed macros go through the same queue as typed keys
+    let replay_tx = input_tx.clone();
+    std::thread::spawn(move || {
+        while let Ok(event) = event::read() {
+            if input_tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Timers: spinner frames only while loading, tape scrolling only while
+    // visible, and a slow housekeeping tick for auto-dismiss and the clock
+    let mut spinner_timer = tokio::time::interval(SPINNER_INTERVAL);
+    let mut tape_timer = tokio::time::interval(TAPE_SCROLL_INTERVAL);
+    let mut housekeeping_timer = tokio::time::interval(Duration::from_secs(1));
+    for timer in [&mut spinner_timer, &mut tape_timer, &mut housekeeping_timer] {
+        timer.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    }
+
+    // Bounds how many sources are fetched at once; the rest wait as "queued"
+    let fetch_slots = Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES));
+
+    // Warm-up refresh; the cached list is drawn first and reloaded when it completes
+    if config.app.fetch_on_start && !read_only {
+        let to_fetch = app.begin_refresh(services.sources.enabled_names());
+        spawn_refresh(to_fetch, &services.sources, &fetch_slots, &tx);
+    }
 
-        // Check for background task messages (non-blocking)
-        if let Ok(msg) = rx.try_recv() {
-            app.handle_message(msg, &db);
+    // Read state a remote server didn't get last time, e.g. while offline
+    if !read_only {
+        match db.pending_state() {
+            Ok(items) => {
+                for item in items {
+                    spawn_state_push(item, &services.sources, &tx);
+                }
+            }
+            Err(e) => app
+                .status_bar
+                .set_message(StatusMessage::error(format!("{:#}", e))),
         }
+    }
+
+    // When each scheduled source next refreshes
+    let mut next_scheduled: HashMap<String, Instant> = HashMap::new();
+    // Last seen version of the database, which moves when another process commits
+    let mut data_version = db.data_version().ok();
 
-        // Update spinner and check auto-dismiss
-        app.tick();
+    let mut needs_redraw = true;
+    let mut first_render = true;
 
-        // Poll for keyboard input
-        if event::poll(Duration::from_millis(200))? {
-            let event = event::read()?;
+    loop {
+        // Draw UI only when state changed since the last frame
+        if needs_redraw {
+            let started = Instant::now();
+            draw_ui(&mut terminal, &app)?;
+            if std::mem::take(&mut first_render) {
+                profile::record(Phase::Startup, "first render", started.elapsed());
+            }
+            needs_redraw = false;
+        }
 
-            // Handle events through component system
-            let action = app.handle_event(&event);
+        tokio::select! {
+            Some(event) = input_rx.recv() => {
+                // Only the wheel and left clicks do anything with the mouse
+                if let event::Event::Mouse(mouse) = &event {
+                    if !matches!(
+                        mouse.kind,
+                        event::MouseEventKind::ScrollUp
+                            | event::MouseEventKind::ScrollDown
+                            | event::MouseEventKind::Down(event::MouseButton::Left)
+                    ) {
+                        continue;
+                    }
+                }
+                needs_redraw = true;
 
-            // Handle refresh action in background
-            if matches!(action, Action::RefreshRequested) {
-                if matches!(app.app_state, AppState::Idle) {
-                    app.app_state = AppState::Loading;
+                // Redraw from scratch at the new size; layout adapts on the next draw
+                if let event::Event::Resize(_, _) = event {
+                    terminal.autoresize()?;
+                    terminal.clear()?;
+                    continue;
+                }
 
-                    // Show loading message
-                    let loading_msg = StatusMessage::loading("Fetching news...".to_string());
-                    app.status_bar.set_message(loading_msg);
+                // Handle events through component system
+                let action = app.handle_event(&event);
 
+                // Read-only, the writing process fetches; refreshing picks up what it stored
+                if read_only
+                    && matches!(action, Action::RefreshRequested | Action::RefreshSourceRequested(_))
+                {
+                    app.reload_news(&db);
+                    let msg = StatusMessage::success(tr!("status.database-reloaded"));
+                    app.status_bar.set_message(msg);
+                }
+
+                // Handle refresh actions in background; each source streams its own progress
+                let requested: Vec<String> = match &action {
+                    _ if read_only => Vec::new(),
+                    Action::RefreshRequested => services.sources.enabled_names(),
+                    Action::RefreshSourceRequested(source) => services
+                        .sources
+                        .get(source)
+                        .filter(|s| s.is_enabled())
+                        .map(|s| vec![s.name().to_string()])
+                        .unwrap_or_default(),
+                    _ => Vec::new(),
+                };
+                if matches!(action, Action::RefreshSourceRequested(_)) && requested.is_empty() {
+                    app.status_bar
+                        .set_message(StatusMessage::warning(tr!("status.no-adaptor")));
+                }
+                let to_fetch = app.begin_refresh(requested);
+                spawn_refresh(to_fetch, &services.sources, &fetch_slots, &tx);
+
+                // Handle other actions (like quit, open URL)
+                if !app.handle_action(&action, &db) {
+                    break;
+                }
+                for event in app.take_replay() {
+                    let _ = replay_tx.send(event);
+                }
+            }
+            Some(msg) = rx.recv() => {
+                let fetched = match &msg {
+                    AppMessage::SourceFetched { diagnostic, .. } if diagnostic.success => {
+                        Some(diagnostic.source.clone())
+                    }
+                    _ => None,
+                };
+                app.handle_message(msg, &db);
+                if let Some(source) = fetched {
+                    let now = Instant::now();
+                    relearn_interval(&mut services, &db, &source, &mut next_scheduled, now);
+                }
+                needs_redraw = true;
+            }
+            _ = spinner_timer.tick(), if app.is_loading() => {
+                app.tick_spinner();
+                needs_redraw = true;
+            }
+            _ = tape_timer.tick(), if app.ticker_tape.is_visible() => {
+                app.ticker_tape.tick();
+                needs_redraw = true;
+            }
+            _ = housekeeping_timer.tick() => {
+                needs_redraw |= app.tick(&db);
+                if let Err(e) = save_status_log(&mut app, &db) {
+                    app.status_bar.set_message(StatusMessage::warning(format!("{:#}", e)));
+                }
+                if app.take_config_reload() {
+                    reload_config(&mut app, &mut services, &db, &tx);
+                    needs_redraw = true;
+                }
+
+                // Another process stored articles; show them without waiting for 'r'
+                if services.follow_database {
+                    let version = db.data_version().ok();
+                    if version.is_some() && std::mem::replace(&mut data_version, version) != version {
+                        let added = app.reload_news(&db);
+                        if added > 0 {
+                            let msg = StatusMessage::info(tr!(
+                                "status.database-changed",
+                                count = added
+                            ));
+                            app.status_bar.set_message(msg);
+                        }
+                        needs_redraw = true;
+                    }
+                }
+
+                // Sources with a configured or learned interval refresh on their own;
+                // read-only, the writing process fetches them
+                let due = due_sources(&services.intervals, &mut next_scheduled, Instant::now());
+                if !due.is_empty() && !read_only {
+                    let to_fetch = app.begin_refresh(due);
+                    spawn_refresh(to_fetch, &services.sources, &fetch_slots, &tx);
+                    needs_redraw = true;
+                }
+            }
+        }
+
+        // Opened one at a time so the browser isn't flooded
+        if let Some(request) = app.take_bulk_open() {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let mut failed = Vec::new();
+                for (i, (opener, url)) in request.links.iter().enumerate() {
+                    if i > 0 {
+                        tokio::time::sleep(request.delay).await;
+                    }
+                    if opener.open(url).is_err() {
+                        failed.push(url.clone());
+                    }
+                }
+                let opened = request.links.len() - failed.len();
+                tx.send(AppMessage::BulkOpened { opened, failed }).await;
+            });
+        }
+
+        if app.take_cache_clear() {
+            match services.content.clone() {
+                None => app
+                    .status_bar
+                    .set_message(StatusMessage::error(tr!("status.cache-off"))),
+                Some((_, cache)) => {
                     let tx = tx.clone();
-                    let adaptors = Arc::clone(&adaptors);
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || cache.clear())
+                            .await
+                            .map_err(|e| e.to_string())
+                            .and_then(|result| result.map_err(|e| format!("{:#}", e)));
+                        tx.send(AppMessage::CacheCleared(result)).await;
+                    });
+                }
+            }
+        }
+
+        if let Some(request) = app.take_webhook_request() {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let result = reqwest::Client::new()
+                    .post(&request.url)
+                    .json(&serde_json::json!({ "text": request.text }))
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+                tx.send(AppMessage::WebhookSent {
+                    target: request.target,
+                    result,
+                })
+                .await;
+            });
+        }
 
+        // git may wait on the network, so it runs off the loop
+        if let Some(direction) = app.take_sync_request() {
+            let state = match direction {
+                SyncDirection::Push => db.export_state(chrono::Utc::now()).map(Some),
+                SyncDirection::Pull => Ok(None),
+            };
+            match (services.sync.clone(), state) {
+                (None, _) => app.status_bar.set_message(StatusMessage::error(tr!(
+                    "status.sync-unconfigured",
+                    path = CONFIG_PATH
+                ))),
+                (_, Err(e)) => app.status_bar.set_message(StatusMessage::error(tr!(
+                    "status.sync-failed",
+                    error = format!("{:#}", e)
+                ))),
+                (Some(repo), Ok(state)) => {
+                    let tx = tx.clone();
                     tokio::spawn(async move {
-                        let result = fetch_all(&adaptors).await;
-                        let msg = if result.items.is_empty() {
-                            AppMessage::RefreshFailed("No items fetched".to_string())
-                        } else {
-                            AppMessage::RefreshComplete {
-                                items: result.items,
-                                diagnostics: result.diagnostics,
-                            }
-                        };
-                        let _ = tx.send(msg);
+                        let result = tokio::task::spawn_blocking(move || sync_with(&repo, state))
+                            .await
+                            .map_err(|e| e.to_string())
+                            .and_then(|result| result.map_err(|e| format!("{:#}", e)));
+                        tx.send(AppMessage::Synced(result)).await;
                     });
                 }
             }
+        }
 
-            // Handle other actions (like quit, open URL)
-            if !app.handle_action(&action) {
-                break;
+        // Pages are fetched following the crawl rules, which may pause
+        if let Some((id, url)) = app.take_content_request() {
+            if let Some((crawler, cache)) = services.content.clone() {
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let result = article_text(&url, &crawler, Some(&cache))
+                        .await
+                        .map_err(|e| format!("{:#}", e));
+                    tx.send(AppMessage::ContentFetched { id, result }).await;
+                });
             }
         }
+
+        for item in app.take_state_pushes() {
+            spawn_state_push(item, &services.sources, &tx);
+        }
+
+        // Fetch quotes for tickers in the newly selected article
+        if let (Some(symbols), Some(quote_adaptor)) =
+            (app.take_quote_request(), &services.quote_adaptor)
+        {
+            let tx = tx.clone();
+            let quote_adaptor = Arc::clone(quote_adaptor);
+            tokio::spawn(async move {
+                // Failures just leave the placeholder; the tape reports provider errors
+                if let Ok(quotes) = quote_adaptor.fetch_quotes(&symbols).await {
+                    tx.send(AppMessage::ArticleQuotes(quotes)).await;
+                }
+            });
+        }
     }
 
     // Cleanup
+    app.status_bar.clear_message();
+    let _ = save_status_log(&mut app, &db);
     terminal::disable_raw_mode()?;
-    execute!(terminal.backend_mut(), terminal::LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        event::DisableBracketedPaste,
+        event::DisableMouseCapture,
+        terminal::LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
+
+    if profile::is_enabled() {
+        print!("{}", profile::report(&profile::take()));
+    }
     Ok(())
 }
@@ -0,0 +1,3 @@
+pub mod session;
+
+pub use session::{configured_exchanges, session_summary, Exchange, SessionState, SessionStatus};
@@ -0,0 +1,334 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::config::{ExchangeConfig, MarketConfig};
+
+/// Built-in exchange sessions: (code, timezone, pre-open, open, close, post-close)
+/// Extended-hours bounds equal to open/close mean the exchange has no extended session.
+const BUILTIN_EXCHANGES: &[(&str, &str, &str, &str, &str, &str)] = &[
+    (
+        "NYSE",
+        "America/New_York",
+        "04:00",
+        "09:30",
+        "16:00",
+        "20:00",
+    ),
+    (
+        "NASDAQ",
+        "America/New_York",
+        "04:00",
+        "09:30",
+        "16:00",
+        "20:00",
+    ),
+    ("TSX", "America/Toronto", "09:30", "09:30", "16:00", "16:00"),
+    ("LSE", "Europe/London", "08:00", "08:00", "16:30", "16:30"),
+    ("XETRA", "Europe/Berlin", "08:00", "09:00", "17:30", "22:00"),
+    ("TSE", "Asia/Tokyo", "09:00", "09:00", "15:30", "15:30"),
+    ("HKEX", "Asia/Hong_Kong", "09:00", "09:30", "16:00", "16:10"),
+    (
+        "ASX",
+        "Australia/Sydney",
+        "07:00",
+        "10:00",
+        "16:00",
+        "16:12",
+    ),
+];
+
+/// Trading session state of an exchange at a point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    PreMarket,
+    Open,
+    AfterHours,
+    Closed,
+}
+
+/// Session state plus the time remaining until the next regular open
+#[derive(Debug, Clone)]
+pub struct SessionStatus {
+    pub state: SessionState,
+    pub until_close: Option<Duration>,
+    pub until_open: Option<Duration>,
+}
+
+/// Regular and extended trading hours of a single exchange, in its local timezone.
+/// Holidays are not modelled; only weekends are treated as closed days.
+#[derive(Debug, Clone)]
+pub struct Exchange {
+    pub code: String,
+    pub timezone: Tz,
+    pub pre_open: NaiveTime,
+    pub open: NaiveTime,
+    pub close: NaiveTime,
+    pub post_close: NaiveTime,
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .with_context(|| format!("Invalid time '{}', expected HH:MM", value))
+}
+
+impl Exchange {
+    /// Look up one of the built-in exchanges by code (case-insensitive)
+    pub fn builtin(code: &str) -> Option<Self> {
+        BUILTIN_EXCHANGES
+            .iter()
+            .find(|(c, ..)| c.eq_ignore_ascii_case(code))
+            .and_then(|(c, tz, pre, open, close, post)| {
+                Some(Self {
+                    code: c.to_string(),
+                    timezone: tz.parse().ok()?,
+                    pre_open: parse_time(pre).ok()?,
+                    open: parse_time(open).ok()?,
+                    close: parse_time(close).ok()?,
+                    post_close: parse_time(post).ok()?,
+                })
+            })
+    }
+
+    /// Build an exchange from a user-defined `[[market.custom]]` entry
+    pub fn from_config(cfg: &ExchangeConfig) -> Result<Self> {
+        let timezone: Tz = cfg.timezone.parse().map_err(|e| {
+            anyhow::anyhow!("{}: unknown timezone '{}': {}", cfg.code, cfg.timezone, e)
+        })?;
+        let open = parse_time(&cfg.open)?;
+        let close = parse_time(&cfg.close)?;
+        let pre_open = cfg
+            .pre_open
+            .as_deref()
+            .map(parse_time)
+            .transpose()?
+            .unwrap_or(open);
+        let post_close = cfg
+            .post_close
+            .as_deref()
+            .map(parse_time)
+            .transpose()?
+            .unwrap_or(close);
+
+        Ok(Self {
+            code: cfg.code.clone(),
+            timezone,
+            pre_open,
+            open,
+            close,
+            post_close,
+        })
+    }
+
+    fn is_trading_day(date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+    }
+
+    fn local_to_utc(&self, date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+        self.timezone
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    /// Next regular-session open strictly after `now`
+    fn next_open(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = now.with_timezone(&self.timezone).date_naive();
+        (0..8)
+            .filter_map(|offset| today.checked_add_signed(Duration::days(offset)))
+            .filter(|date| Self::is_trading_day(*date))
+            .filter_map(|date| self.local_to_utc(date, self.open))
+            .find(|open| *open > now)
+    }
+
+    /// Compute the session state of this exchange at `now`
+    pub fn status_at(&self, now: DateTime<Utc>) -> SessionStatus {
+        let local = now.with_timezone(&self.timezone);
+        let time = local.time();
+
+        let state = if !Self::is_trading_day(local.date_naive()) {
+            SessionState::Closed
+        } else if time >= self.open && time < self.close {
+            SessionState::Open
+        } else if time >= self.pre_open && time < self.open {
+            SessionState::PreMarket
+        } else if time >= self.close && time < self.post_close {
+            SessionState::AfterHours
+        } else {
+            SessionState::Closed
+        };
+
+        let until_close = if state == SessionState::Open {
+            self.local_to_utc(local.date_naive(), self.close)
+                .map(|close| close - now)
+        } else {
+            None
+        };
+
+        SessionStatus {
+            state,
+            until_close,
+            until_open: self.next_open(now).map(|open| open - now),
+        }
+    }
+}
+
+/// Format a duration compactly for the status bar ("2d 3h", "3h12m", "45m")
+pub fn format_countdown(d: Duration) -> String {
+    let minutes = d.num_minutes().max(0);
+    let (days, hours, mins) = (minutes / 1440, (minutes / 60) % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h{:02}m", hours, mins)
+    } else {
+        format!("{}m", mins)
+    }
+}
+
+/// One-line summary of all configured exchanges, e.g. "NYSE ● open 3h12m left | LSE ○ opens 14h05m"
+pub fn session_summary(exchanges: &[Exchange], now: DateTime<Utc>) -> String {
+    exchanges
+        .iter()
+        .map(|exchange| {
+            let status = exchange.status_at(now);
+            let opens = status
+                .until_open
+                .map(|d| format!("opens {}", format_countdown(d)))
+                .unwrap_or_default();
+            match status.state {
+                SessionState::Open => match status.until_close {
+                    Some(d) => format!("{} ● open {} left", exchange.code, format_countdown(d)),
+                    None => format!("{} ● open", exchange.code),
+                },
+                SessionState::PreMarket => format!("{} ◐ pre {}", exchange.code, opens),
+                SessionState::AfterHours => format!("{} ◑ post {}", exchange.code, opens),
+                SessionState::Closed => format!("{} ○ {}", exchange.code, opens),
+            }
+            .trim_end()
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Resolve `[market]` config into exchanges; custom definitions take precedence over built-ins
+pub fn configured_exchanges(cfg: &MarketConfig) -> Result<Vec<Exchange>> {
+    let mut exchanges = Vec::new();
+    for code in &cfg.exchanges {
+        if let Some(custom) = cfg
+            .custom
+            .iter()
+            .find(|c| c.code.eq_ignore_ascii_case(code))
+        {
+            exchanges.push(Exchange::from_config(custom)?);
+        } else {
+            let exchange = Exchange::builtin(code)
+                .with_context(|| format!("Unknown exchange '{}' in [market]", code))?;
+            exchanges.push(exchange);
+        }
+    }
+    Ok(exchanges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange(code: &str) -> Exchange {
+        Exchange::builtin(code).unwrap()
+    }
+
+    /// A fixed instant; 2026-10-16 is a Friday, with New York on EDT (UTC-4)
+    /// and London on BST (UTC+1)
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn pre_market_counts_down_to_the_open() {
+        // 08:00 in New York
+        let status = exchange("NYSE").status_at(at(16, 12, 0));
+        assert_eq!(status.state, SessionState::PreMarket);
+        assert_eq!(status.until_open, Some(Duration::minutes(90)));
+        assert_eq!(status.until_close, None);
+
+        // 08:30 in Frankfurt (CEST, UTC+2)
+        let status = exchange("XETRA").status_at(at(16, 6, 30));
+        assert_eq!(status.state, SessionState::PreMarket);
+        assert_eq!(status.until_open, Some(Duration::minutes(30)));
+    }
+
+    #[test]
+    fn open_counts_down_to_the_close() {
+        // 11:00 in New York; the next open is Monday's
+        let status = exchange("NYSE").status_at(at(16, 15, 0));
+        assert_eq!(status.state, SessionState::Open);
+        assert_eq!(status.until_close, Some(Duration::hours(5)));
+        assert_eq!(
+            status.until_open,
+            Some(Duration::days(2) + Duration::hours(22) + Duration::minutes(30))
+        );
+
+        // 10:00 in London
+        let status = exchange("LSE").status_at(at(16, 9, 0));
+        assert_eq!(status.state, SessionState::Open);
+        assert_eq!(
+            status.until_close,
+            Some(Duration::hours(6) + Duration::minutes(30))
+        );
+    }
+
+    #[test]
+    fn closed_after_the_session() {
+        // 18:00 in London, which has no after-hours session
+        let status = exchange("LSE").status_at(at(16, 17, 0));
+        assert_eq!(status.state, SessionState::Closed);
+        assert_eq!(status.until_close, None);
+        assert_eq!(
+            status.until_open,
+            Some(Duration::days(2) + Duration::hours(14))
+        );
+
+        // 17:00 in New York is after hours until 20:00
+        let status = exchange("NYSE").status_at(at(16, 21, 0));
+        assert_eq!(status.state, SessionState::AfterHours);
+    }
+
+    #[test]
+    fn weekends_roll_over_to_monday() {
+        // Saturday noon UTC: Monday 09:30 in New York, Monday 09:00 in Tokyo
+        let saturday = at(17, 12, 0);
+        let status = exchange("NYSE").status_at(saturday);
+        assert_eq!(status.state, SessionState::Closed);
+        assert_eq!(
+            status.until_open,
+            Some(Duration::days(2) + Duration::hours(1) + Duration::minutes(30))
+        );
+        let status = exchange("TSE").status_at(saturday);
+        assert_eq!(status.state, SessionState::Closed);
+        assert_eq!(
+            status.until_open,
+            Some(Duration::days(1) + Duration::hours(12))
+        );
+
+        // Sunday 23:30 UTC is already Monday morning in Tokyo
+        let sunday = at(18, 23, 30);
+        let status = exchange("TSE").status_at(sunday);
+        assert_eq!(status.state, SessionState::Closed);
+        assert_eq!(status.until_open, Some(Duration::minutes(30)));
+        let status = exchange("NYSE").status_at(sunday);
+        assert_eq!(status.state, SessionState::Closed);
+        assert_eq!(status.until_open, Some(Duration::hours(14)));
+    }
+
+    #[test]
+    fn summary_names_each_exchange() {
+        let exchanges = [exchange("NYSE"), exchange("LSE")];
+        assert_eq!(
+            session_summary(&exchanges, at(16, 12, 0)),
+            "NYSE ◐ pre opens 1h30m | LSE ● open 3h30m left"
+        );
+    }
+}
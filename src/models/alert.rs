@@ -0,0 +1,97 @@
+//! Alert keywords from `[alerts]`. Articles mentioning one are badged in the
+//! list, and a refresh that brings one in raises an alert that stays on the
+//! status line until dismissed.
+
+use super::NewsItem;
+
+/// Compiled `[alerts] keywords`
+#[derive(Debug, Clone, Default)]
+pub struct AlertKeywords {
+    /// Each keyword as configured, and lowercased without a leading `$`
+    keywords: Vec<(String, String)>,
+}
+
+impl AlertKeywords {
+    pub fn new(keywords: &[String]) -> Self {
+        let keywords = keywords
+            .iter()
+            .map(|keyword| keyword.trim())
+            .filter_map(|keyword| {
+                let needle = keyword.trim_start_matches('$').to_lowercase();
+                (!needle.is_empty()).then(|| (keyword.to_string(), needle))
+            })
+            .collect();
+        Self { keywords }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keywords.is_empty()
+    }
+
+    /// The first keyword `item` mentions, as configured. Keywords match
+    /// whole words, ignoring case and a trailing "s", in the title or
+    /// summary, or one of the item's tickers exactly; "$NVDA" and "NVDA"
+    /// are the same keyword.
+    pub fn matching(&self, item: &NewsItem) -> Option<&str> {
+        if self.keywords.is_empty() {
+            return None;
+        }
+        let title = item.title.to_lowercase();
+        let summary = item.summary.to_lowercase();
+        self.keywords
+            .iter()
+            .find(|(_, needle)| {
+                item.tickers.iter().any(|t| t.eq_ignore_ascii_case(needle))
+                    || mentions(&title, needle)
+                    || mentions(&summary, needle)
+            })
+            .map(|(keyword, _)| keyword.as_str())
+    }
+}
+
+/// Whether lowercased `text` has `needle` as a whole word, or followed by "s"
+fn mentions(text: &str, needle: &str) -> bool {
+    let mut from = 0;
+    while let Some(offset) = text[from..].find(needle) {
+        let start = from + offset;
+        let rest = &text[start + needle.len()..];
+        let rest = rest.strip_prefix('s').unwrap_or(rest);
+        let before = text[..start].chars().next_back();
+        let after = rest.chars().next();
+        if !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric) {
+            return true;
+        }
+        from = start + needle.chars().next().map_or(1, char::len_utf8);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(title: &str, tickers: &[&str]) -> NewsItem {
+        NewsItem {
+            title: title.to_string(),
+            tickers: tickers.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn keywords_match_whole_words() {
+        let alerts = AlertKeywords::new(&["$NVDA".to_string(), "rate cut".to_string()]);
+        assert_eq!(alerts.matching(&item("Why $nvda fell", &[])), Some("$NVDA"));
+        assert_eq!(
+            alerts.matching(&item("Chip stocks slide", &["NVDA"])),
+            Some("$NVDA")
+        );
+        assert_eq!(
+            alerts.matching(&item("Fed signals two Rate Cuts", &[])),
+            Some("rate cut")
+        );
+        assert_eq!(alerts.matching(&item("NVDAX launches", &[])), None);
+        assert_eq!(alerts.matching(&item("Corporate cutbacks", &[])), None);
+        assert!(AlertKeywords::new(&[" ".to_string()]).is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+/// Coin symbols with the names articles use for them. Symbols that are
+/// also common words (SOL, DOT, LINK) only match in capitals.
+const COINS: &[(&str, &[&str])] = &[
+    ("BTC", &["bitcoin", "bitcoins"]),
+    ("ETH", &["ethereum", "ether"]),
+    ("SOL", &["solana"]),
+    ("XRP", &["ripple"]),
+    ("BNB", &["binance coin"]),
+    ("DOGE", &["dogecoin"]),
+    ("ADA", &["cardano"]),
+    ("AVAX", &["avalanche"]),
+    ("DOT", &["polkadot"]),
+    ("LINK", &["chainlink"]),
+    ("LTC", &["litecoin"]),
+    ("TON", &["toncoin"]),
+    ("SHIB", &["shiba inu"]),
+    ("USDT", &["tether"]),
+    ("USDC", &["usd coin"]),
+];
+
+/// Coins mentioned in `texts` by symbol or name, in `COINS` order
+pub fn detect_coins(texts: &[&str]) -> Vec<String> {
+    let words: Vec<&str> = texts
+        .iter()
+        .flat_map(|text| text.split(|c: char| !c.is_alphanumeric() && c != '\''))
+        .filter(|word| !word.is_empty())
+        .collect();
+    let lower: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+    COINS
+        .iter()
+        .filter(|(symbol, names)| {
+            words.contains(symbol)
+                || names.iter().any(|name| {
+                    let parts: Vec<&str> = name.split(' ').collect();
+                    lower
+                        .windows(parts.len())
+                        .any(|window| window.iter().zip(&parts).all(|(w, p)| w == p))
+                })
+        })
+        .map(|(symbol, _)| symbol.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coins_match_by_symbol_or_name() {
+        assert_eq!(
+            detect_coins(&["Bitcoin tops $70K as ETH lags", "Solana fees spike"]),
+            ["BTC", "ETH", "SOL"]
+        );
+        // Lowercase symbols are ordinary words
+        assert!(detect_coins(&["Connect the dots with this link"]).is_empty());
+        assert_eq!(detect_coins(&["Shiba Inu burns tokens"]), ["SHIB"]);
+    }
+}
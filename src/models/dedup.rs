@@ -0,0 +1,264 @@
+//! Cross-source deduplication. When several sources carry the same story,
+//! the copy that arrived first is stored and the others are recorded against
+//! it, so the list shows the story once along with who else reported it.
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::story::keywords;
+use super::{NewsItem, SourceKind};
+
+/// How far apart two sources' copies of a story may be published
+pub const SAME_STORY_HOURS: i64 = 24;
+/// Headlines with fewer keywords only match by URL; short ones collide too easily
+const MIN_KEYWORDS: usize = 4;
+/// Query parameters that only say where a click came from
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "cmpid", "mod", "ncid", "ref", "src"];
+
+/// `url` without scheme, `www.`, fragment, tracking parameters or trailing
+/// slash, so syndicated copies of an article compare equal
+pub fn canonical_url(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let url = url.strip_prefix("www.").unwrap_or(url);
+    let url = url.split('#').next().unwrap_or(url);
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let query: Vec<&str> = query
+        .split('&')
+        .filter(|param| {
+            let name = param.split('=').next().unwrap_or(param);
+            !name.is_empty() && !name.starts_with("utm_") && !TRACKING_PARAMS.contains(&name)
+        })
+        .collect();
+    let (host, path) = path.split_once('/').unwrap_or((path, ""));
+    let mut canonical = format!("{}/{}", host.to_lowercase(), path.trim_end_matches('/'));
+    if !query.is_empty() {
+        canonical.push('?');
+        canonical.push_str(&query.join("&"));
+    }
+    canonical
+}
+
+/// Share of keywords two headlines have in common (Dice coefficient), from
+/// 0 to 1. Headlines too short to tell apart score 0.
+pub fn title_similarity(a: &str, b: &str) -> f32 {
+    similarity(&keywords(a), &keywords(b))
+}
+
+fn similarity(a: &[String], b: &[String]) -> f32 {
+    if a.len() < MIN_KEYWORDS || b.len() < MIN_KEYWORDS {
+        return 0.0;
+    }
+    let shared = a.iter().filter(|word| b.contains(word)).count();
+    2.0 * shared as f32 / (a.len() + b.len()) as f32
+}
+
+/// A stored article new ones are compared against
+#[derive(Debug)]
+struct Story {
+    id: String,
+    source: String,
+    url: String,
+    keywords: Vec<String>,
+    published: DateTime<Utc>,
+}
+
+/// Articles already kept, to find which story a new article repeats
+#[derive(Debug)]
+pub struct StoryIndex {
+    threshold: f32,
+    stories: Vec<Story>,
+}
+
+impl StoryIndex {
+    /// Headlines at least `threshold` similar count as the same story
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            stories: Vec::new(),
+        }
+    }
+
+    pub fn add(
+        &mut self,
+        id: &str,
+        source: &str,
+        title: &str,
+        url: &str,
+        published: DateTime<Utc>,
+    ) {
+        self.stories.push(Story {
+            id: id.to_string(),
+            source: source.to_string(),
+            url: canonical_url(url),
+            keywords: keywords(title),
+            published,
+        });
+    }
+
+    pub fn add_item(&mut self, item: &NewsItem) {
+        self.add(
+            &item.id,
+            &item.source,
+            &item.title,
+            &item.url,
+            item.published,
+        );
+    }
+
+    /// Id of the kept article from another source that `item` repeats, if
+    /// any. A re-fetch of an article its own source already has is not a
+    /// repeat, nor is anything from a remote server, which owns its items.
+    pub fn find(&self, item: &NewsItem) -> Option<&str> {
+        if item.source_kind == SourceKind::Remote {
+            return None;
+        }
+        let url = canonical_url(&item.url);
+        if self
+            .stories
+            .iter()
+            .any(|story| story.source == item.source && story.url == url)
+        {
+            return None;
+        }
+        let words = keywords(&item.title);
+        let window = Duration::hours(SAME_STORY_HOURS);
+        self.stories
+            .iter()
+            .filter(|story| story.source != item.source)
+            .filter(|story| (story.published - item.published).abs() <= window)
+            .find(|story| story.url == url || similarity(&story.keywords, &words) >= self.threshold)
+            .map(|story| story.id.as_str())
+    }
+}
+
+/// Another source's copy of a story, collapsed into the kept article
+#[derive(Debug, Clone)]
+pub struct Duplicate {
+    /// Id of the article kept for the story
+    pub kept: String,
+    pub item: NewsItem,
+}
+
+/// Collapse copies of the same story from different sources, keeping the
+/// earliest published. Returns the kept items in their original order and
+/// the copies that were collapsed.
+pub fn collapse_duplicates(
+    items: Vec<NewsItem>,
+    threshold: f32,
+) -> (Vec<NewsItem>, Vec<Duplicate>) {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|&i| items[i].published);
+
+    let mut index = StoryIndex::new(threshold);
+    let mut kept_into: Vec<Option<String>> = vec![None; items.len()];
+    for i in order {
+        match index.find(&items[i]) {
+            Some(kept) => kept_into[i] = Some(kept.to_string()),
+            None => index.add_item(&items[i]),
+        }
+    }
+
+    let mut kept = Vec::new();
+    let mut duplicates = Vec::new();
+    for (item, into) in items.into_iter().zip(kept_into) {
+        match into {
+            Some(kept) => duplicates.push(Duplicate { kept, item }),
+            None => kept.push(item),
+        }
+    }
+    (kept, duplicates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(source: &str, title: &str, url: &str, minutes: i64) -> NewsItem {
+        NewsItem {
+            id: format!("{}-{}", source, minutes),
+            source: source.to_string(),
+            title: title.to_string(),
+            url: url.to_string(),
+            published: DateTime::<Utc>::UNIX_EPOCH + Duration::minutes(minutes),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn urls_compare_without_tracking() {
+        assert_eq!(
+            canonical_url("https://www.Reuters.com/markets/fed-holds/?utm_source=rss&id=7#top"),
+            canonical_url("http://reuters.com/markets/fed-holds?id=7")
+        );
+        assert_ne!(
+            canonical_url("https://example.com/story?id=7"),
+            canonical_url("https://example.com/story?id=8")
+        );
+    }
+
+    #[test]
+    fn similar_headlines_score_high() {
+        let a = "Fed holds rates steady, signals two cuts later in 2024";
+        let b = "Fed holds rates steady and signals two cuts later in 2024";
+        assert_eq!(title_similarity(a, b), 1.0);
+        assert!(title_similarity(a, "Fed signals two cuts as inflation cools") < 0.85);
+        assert_eq!(title_similarity("Markets wrap", "Markets wrap"), 0.0);
+    }
+
+    #[test]
+    fn earliest_copy_is_kept() {
+        let title = "Boeing halts 737 MAX deliveries after door plug blowout";
+        let items = vec![
+            item("Bloomberg", title, "https://bloomberg.com/a", 30),
+            item("Reuters", title, "https://reuters.com/b", 10),
+            item(
+                "CNBC",
+                "Stocks close higher as Nvidia rallies again",
+                "https://cnbc.com/c",
+                20,
+            ),
+            // Same source, same headline: a repost, not this pipeline's job
+            item("Reuters", title, "https://reuters.com/b2", 40),
+            // The same URL syndicated under another headline
+            item(
+                "Yahoo Finance",
+                "Boeing stops deliveries",
+                "https://www.reuters.com/b?utm_medium=rss",
+                50,
+            ),
+            // Too long after the first copy
+            item("MarketWatch", title, "https://marketwatch.com/d", 60 * 30),
+        ];
+        let (kept, duplicates) = collapse_duplicates(items, 0.85);
+
+        let kept: Vec<&str> = kept.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(
+            kept,
+            ["Reuters-10", "CNBC-20", "Reuters-40", "MarketWatch-1800"]
+        );
+        let collapsed: Vec<(&str, &str)> = duplicates
+            .iter()
+            .map(|d| (d.item.source.as_str(), d.kept.as_str()))
+            .collect();
+        assert_eq!(
+            collapsed,
+            [("Bloomberg", "Reuters-10"), ("Yahoo Finance", "Reuters-10")]
+        );
+    }
+
+    #[test]
+    fn refetched_article_is_not_a_duplicate() {
+        let title = "Boeing halts 737 MAX deliveries after door plug blowout";
+        let mut index = StoryIndex::new(0.85);
+        index.add_item(&item("Reuters", title, "https://reuters.com/b", 10));
+        index.add_item(&item("Bloomberg", title, "https://bloomberg.com/a", 30));
+        assert_eq!(
+            index.find(&item("Bloomberg", title, "https://bloomberg.com/a/", 30)),
+            None
+        );
+        assert_eq!(
+            index.find(&item("CNBC", title, "https://cnbc.com/x", 45)),
+            Some("Reuters-10")
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+
+/// Words that mark a headline as announcing a scheduled event
+const EVENT_KEYWORDS: &[&str] = &[
+    "earnings",
+    "to report",
+    "results",
+    "release",
+    "meeting",
+    "decision",
+    "fomc",
+    "cpi",
+    "payrolls",
+    "jobs report",
+    "gdp",
+    "ipo",
+    "dividend",
+    "scheduled",
+    "expected on",
+];
+
+const MONTHS: &[&str] = &[
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Month names that are also everyday words ("shares may 10x", "march 5
+/// miles"), taken as months only when capitalized
+const MONTH_WORDS: &[&str] = &["may", "mar", "march"];
+
+/// Date of the event an item announces, e.g. "Apple to report earnings on
+/// May 2". Needs an event keyword and an explicit "<month> <day>[, <year>]"
+/// date; without a year the date closest to `published` is used.
+pub fn scheduled_date(texts: &[&str], published: DateTime<Utc>) -> Option<NaiveDate> {
+    let text = texts.join(" ");
+    let lower = text.to_lowercase();
+    if !EVENT_KEYWORDS.iter().any(|k| lower.contains(k)) {
+        return None;
+    }
+
+    let words: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.windows(2).enumerate().find_map(|(i, pair)| {
+        let month = month_number(pair[0])?;
+        let day: u32 = pair[1]
+            .trim_end_matches([',', '.', ':', ';'])
+            .parse()
+            .ok()?;
+        let year = words
+            .get(i + 2)
+            .map(|w| w.trim_end_matches([',', '.', ':', ';']))
+            .and_then(|w| w.parse::<i32>().ok())
+            .filter(|y| (2000..=2100).contains(y));
+        match year {
+            Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+            None => nearest_date(month, day, published.date_naive()),
+        }
+    })
+}
+
+/// 1-based month for a full or abbreviated month name ("sep", "Sept.", "September")
+fn month_number(word: &str) -> Option<u32> {
+    let original = word.trim_end_matches('.');
+    let word = original.to_lowercase();
+    let word = word.as_str();
+    if word.len() < 3 {
+        return None;
+    }
+    if MONTH_WORDS.contains(&word) && !original.starts_with(char::is_uppercase) {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|m| *m == word || (word.len() <= 4 && m.starts_with(word)))
+        .map(|i| i as u32 + 1)
+}
+
+/// `month`/`day` in the year around `reference`, whichever is closest
+fn nearest_date(month: u32, day: u32, reference: NaiveDate) -> Option<NaiveDate> {
+    (reference.year() - 1..=reference.year() + 1)
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+        .min_by_key(|date| (*date - reference).num_days().abs())
+        .filter(|date| (*date - reference).abs() <= Duration::days(183))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn published() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 4, 20, 12, 0, 0).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(y, m, d)
+    }
+
+    #[test]
+    fn finds_announced_dates() {
+        let found = |text: &str| scheduled_date(&[text], published());
+        assert_eq!(found("Apple to report earnings on May 2"), date(2024, 5, 2));
+        assert_eq!(
+            found("FOMC decision expected on june 12"),
+            date(2024, 6, 12)
+        );
+        assert_eq!(found("Q3 results (Sept. 30, 2025)"), date(2025, 9, 30));
+        // Without a year, the closest date to publication wins
+        assert_eq!(found("Earnings call Dec 28"), date(2023, 12, 28));
+        assert_eq!(found("Apple shares rise on May 2"), None);
+    }
+
+    #[test]
+    fn month_words_used_as_verbs_are_not_dates() {
+        let found = |text: &str| scheduled_date(&[text], published());
+        assert_eq!(found("Shares may 10x after earnings"), None);
+        assert_eq!(found("Earnings may 3 weeks late, sources say"), None);
+        assert_eq!(
+            found("Workers march 5 miles to the shareholder meeting"),
+            None
+        );
+        assert_eq!(found("Dividend cuts mar 2 quarters of results"), None);
+        // Capitalized, they still name the month
+        assert_eq!(found("Dividend payable March 5"), date(2024, 3, 5));
+        assert_eq!(found("Results due Mar. 28"), date(2024, 3, 28));
+    }
+}
@@ -1,7 +1,93 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, Utc};
+
+use super::NewsItem;
+
+/// Quick time filters, relative to the current time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeRange {
+    LastHour,
+    /// Since local midnight
+    Today,
+    /// Since Monday, local midnight
+    ThisWeek,
+}
+
+impl TimeRange {
+    pub fn label(self) -> &'static str {
+        match self {
+            TimeRange::LastHour => "Last hour",
+            TimeRange::Today => "Today",
+            TimeRange::ThisWeek => "This week",
+        }
+    }
+
+    /// Earliest publish time in the range as of `now`
+    pub fn start(self, now: DateTime<Local>) -> DateTime<Utc> {
+        let days_back = match self {
+            TimeRange::LastHour => return (now - Duration::hours(1)).to_utc(),
+            TimeRange::Today => 0,
+            TimeRange::ThisWeek => now.weekday().num_days_from_monday(),
+        };
+        let day = now.date_naive() - Duration::days(days_back.into());
+        day.and_hms_opt(0, 0, 0)
+            .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+            .map_or(now.to_utc() - Duration::days(days_back.into()), |m| {
+                m.to_utc()
+            })
+    }
+}
 
 #[derive(Clone, Debug, Default)]
 pub struct FilterState {
     pub sources: Vec<String>,
+    /// Tags the item must have one of (case-insensitive); empty allows all
+    pub tags: Vec<String>,
     pub date_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Quick filter, re-evaluated against the current time
+    pub time_range: Option<TimeRange>,
+}
+
+impl FilterState {
+    /// Whether `item` passes the filter, with time ranges taken as of `now`.
+    /// Source names match regardless of case or legacy prefix; an empty
+    /// source or tag list allows all.
+    pub fn matches(&self, item: &NewsItem, now: DateTime<Local>) -> bool {
+        let source_ok =
+            self.sources.is_empty() || self.sources.iter().any(|name| item.is_from(name));
+        let tag_ok = self.tags.is_empty()
+            || self
+                .tags
+                .iter()
+                .any(|tag| item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+        let date_ok = self
+            .date_range
+            .is_none_or(|(from, to)| item.published >= from && item.published <= to);
+        let time_ok = self
+            .time_range
+            .is_none_or(|range| item.published >= range.start(now));
+        source_ok && tag_ok && date_ok && time_ok
+    }
+
+    /// Add or remove a source filter. Returns whether it is now active.
+    pub fn toggle_source(&mut self, name: &str) -> bool {
+        toggle(&mut self.sources, name)
+    }
+
+    /// Add or remove a tag filter. Returns whether it is now active.
+    pub fn toggle_tag(&mut self, tag: &str) -> bool {
+        toggle(&mut self.tags, tag)
+    }
+}
+
+fn toggle(list: &mut Vec<String>, value: &str) -> bool {
+    match list.iter().position(|v| v == value) {
+        Some(index) => {
+            list.remove(index);
+            false
+        }
+        None => {
+            list.push(value.to_string());
+            true
+        }
+    }
 }
@@ -0,0 +1,238 @@
+use sha2::{Digest, Sha256};
+
+use super::source_id;
+
+/// Hex digits of an id's hashes shown in messages; ids keep the full hash
+const DISPLAY_HASH_LEN: usize = 16;
+/// Longest GUID kept verbatim; longer ones are hashed
+const MAX_GUID_LEN: usize = 16;
+
+/// Full SHA-256 of `value` as lowercase hex
+pub fn hash_hex(value: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(value.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Stable id for a feed entry: `<source>-guid-<guid>` for short opaque GUIDs,
+/// otherwise `<source>-hash-<hash>` of the GUID (permalinks and long GUIDs)
+/// or of the link when there is no usable GUID.
+pub fn feed_item_id(
+    source_name: &str,
+    guid: Option<&str>,
+    guid_is_permalink: bool,
+    url: &str,
+) -> String {
+    let identifier = match guid.map(str::trim).filter(|g| !g.is_empty()) {
+        Some(guid) if guid_is_permalink || guid.len() > MAX_GUID_LEN => {
+            format!("hash-{}", hash_hex(guid))
+        }
+        Some(guid) => {
+            let sanitized: String = guid
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                .collect();
+            if sanitized.is_empty() {
+                format!("hash-{}", hash_hex(url))
+            } else {
+                format!("guid-{}", sanitized)
+            }
+        }
+        None => format!("hash-{}", hash_hex(url)),
+    };
+
+    format!("{}-{}", source_id(source_name), identifier)
+}
+
+/// Stable id for an item from an API that assigns its own ids
+pub fn api_item_id(source_name: &str, native_id: impl std::fmt::Display) -> String {
+    format!("{}-{}", source_id(source_name), native_id)
+}
+
+/// The source's own id from an id made by [`api_item_id`], if the item came
+/// from `source_name`
+pub fn native_item_id<'a>(source_name: &str, item_id: &'a str) -> Option<&'a str> {
+    item_id
+        .strip_prefix(&source_id(source_name))
+        .and_then(|rest| rest.strip_prefix('-'))
+        .filter(|native| !native.is_empty())
+}
+
+/// Id for an item whose `id` is already taken by an item with a different
+/// URL. Deterministic, so re-fetching the item maps to the same row.
+pub fn disambiguate(id: &str, url: &str) -> String {
+    format!("{}~{}", id, hash_hex(url))
+}
+
+/// `id` with its hashes cut to their first [`DISPLAY_HASH_LEN`] digits, for
+/// messages
+pub fn display_id(id: &str) -> String {
+    let (base, suffix) = match id.split_once('~') {
+        Some((base, suffix)) => (base, Some(suffix)),
+        None => (id, None),
+    };
+    let mut shown = match base.split_once("-hash-") {
+        Some((source, hash)) => format!("{}-hash-{}", source, shorten(hash)),
+        None => base.to_string(),
+    };
+    if let Some(suffix) = suffix {
+        shown.push('~');
+        shown.push_str(shorten(suffix));
+    }
+    shown
+}
+
+fn shorten(hash: &str) -> &str {
+    hash.char_indices()
+        .nth(DISPLAY_HASH_LEN)
+        .map_or(hash, |(end, _)| &hash[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const LINK: &str = "https://www.cnbc.com/2024/05/01/stocks.html";
+
+    #[test]
+    fn hash_is_full_length() {
+        assert_eq!(hash_hex(LINK).len(), 64);
+        assert!(hash_hex(LINK).starts_with("981a26ee371aa247"));
+    }
+
+    #[test]
+    fn no_guid_hashes_link() {
+        assert_eq!(
+            feed_item_id("CNBC", None, false, LINK),
+            format!("cnbc-hash-{}", hash_hex(LINK))
+        );
+    }
+
+    #[test]
+    fn short_numeric_guid_is_kept() {
+        // CNBC and many CMS feeds use numeric ids
+        assert_eq!(
+            feed_item_id("CNBC", Some("107412345"), false, LINK),
+            "cnbc-guid-107412345"
+        );
+    }
+
+    #[test]
+    fn permalink_guid_is_hashed() {
+        let id = feed_item_id("MarketWatch", Some(LINK), true, "https://other.example/");
+        assert_eq!(id, format!("marketwatch-hash-{}", hash_hex(LINK)));
+    }
+
+    #[test]
+    fn long_guids_are_hashed() {
+        for guid in [
+            "urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a",
+            "tag:blogger.com,1999:blog-1234.post-5678",
+            "https://www.ft.com/content/0a1b2c3d-4e5f",
+        ] {
+            let id = feed_item_id("Financial Times", Some(guid), false, LINK);
+            assert_eq!(id, format!("financial-times-hash-{}", hash_hex(guid)));
+        }
+    }
+
+    #[test]
+    fn short_guid_is_sanitized() {
+        assert_eq!(
+            feed_item_id("Barrons", Some("ab/12:cd"), false, LINK),
+            "barrons-guid-ab12cd"
+        );
+    }
+
+    #[test]
+    fn unusable_guid_falls_back_to_link() {
+        let expected = format!("wall-street-journal-hash-{}", hash_hex(LINK));
+        for guid in ["", "   ", "#?!/"] {
+            assert_eq!(
+                feed_item_id("Wall Street Journal", Some(guid), false, LINK),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn surrounding_whitespace_is_ignored() {
+        assert_eq!(
+            feed_item_id("CNBC", Some(" 107412345\n"), false, LINK),
+            feed_item_id("CNBC", Some("107412345"), false, LINK)
+        );
+    }
+
+    #[test]
+    fn ids_are_scoped_by_source() {
+        let a = feed_item_id("CNBC", Some("1"), false, LINK);
+        let b = feed_item_id("Bloomberg", Some("1"), false, LINK);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn api_ids_keep_native_id() {
+        assert_eq!(api_item_id("Benzinga", 38812345), "benzinga-38812345");
+    }
+
+    #[test]
+    fn native_id_round_trips() {
+        let id = api_item_id("Tiny Tiny RSS", 42);
+        assert_eq!(native_item_id("Tiny Tiny RSS", &id), Some("42"));
+        assert_eq!(native_item_id("FreshRSS", &id), None);
+    }
+
+    #[test]
+    fn disambiguation_is_deterministic_and_distinct() {
+        let id = "cnbc-guid-1";
+        let a = disambiguate(id, "https://example.com/a?utm=1");
+        assert_eq!(
+            a,
+            format!("cnbc-guid-1~{}", hash_hex("https://example.com/a?utm=1"))
+        );
+        assert!(a.starts_with("cnbc-guid-1~82e12f865d80d382"));
+        assert_eq!(a, disambiguate(id, "https://example.com/a?utm=1"));
+        assert_ne!(a, disambiguate(id, LINK));
+    }
+
+    #[test]
+    fn displayed_ids_show_short_hashes() {
+        let id = feed_item_id("CNBC", None, false, LINK);
+        assert_eq!(id.len(), "cnbc-hash-".len() + 64);
+        assert_eq!(display_id(&id), "cnbc-hash-981a26ee371aa247");
+        assert_eq!(
+            display_id(&disambiguate("cnbc-guid-1", "https://example.com/a?utm=1")),
+            "cnbc-guid-1~82e12f865d80d382"
+        );
+        assert_eq!(display_id("benzinga-38812345"), "benzinga-38812345");
+    }
+
+    proptest! {
+        #[test]
+        fn feed_ids_are_stable_and_scoped(
+            source in "[A-Za-z ]{1,20}",
+            guid in proptest::option::of("\\PC{0,40}"),
+            permalink in any::<bool>(),
+            url in "\\PC{0,60}",
+        ) {
+            let id = feed_item_id(&source, guid.as_deref(), permalink, &url);
+            prop_assert_eq!(&id, &feed_item_id(&source, guid.as_deref(), permalink, &url));
+            let prefix = format!("{}-", source_id(&source));
+            let rest = id.strip_prefix(&prefix).unwrap();
+            prop_assert!(rest.starts_with("guid-") || rest.starts_with("hash-"));
+            // Only word characters and dashes, whatever the GUID held
+            prop_assert!(rest
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_'));
+        }
+
+        #[test]
+        fn guid_whitespace_never_changes_the_id(guid in "[a-z0-9]{1,16}", pad in "[ \t\n]{0,4}") {
+            let padded = format!("{}{}{}", pad, guid, pad);
+            prop_assert_eq!(
+                feed_item_id("CNBC", Some(&padded), false, LINK),
+                feed_item_id("CNBC", Some(&guid), false, LINK)
+            );
+        }
+    }
+}
@@ -1,5 +1,21 @@
 pub mod news_item;
+pub mod alert;
+pub mod coins;
+pub mod dedup;
+pub mod event;
 pub mod filter;
+pub mod id;
+pub mod quote;
+pub mod source;
+pub mod story;
 
-pub use news_item::NewsItem;
-pub use filter::FilterState;
+pub use news_item::{extract_tickers, ticker_filter, NewsItem, Revision, SourceKind};
+pub use alert::AlertKeywords;
+pub use coins::detect_coins;
+pub use dedup::{collapse_duplicates, Duplicate, StoryIndex};
+pub use event::scheduled_date;
+pub use filter::{FilterState, TimeRange};
+pub use id::{api_item_id, feed_item_id, native_item_id};
+pub use quote::Quote;
+pub use source::{normalize_source_name, publishing_cadence, source_id, Source, SourceStats};
+pub use story::FollowedStory;
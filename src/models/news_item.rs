@@ -1,13 +1,178 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::source_id;
+
+/// What kind of adaptor produced an item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    #[default]
+    Rss,
+    Api,
+    /// Pulled from an aggregator server that owns the read/starred state
+    Remote,
+}
+
+impl SourceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SourceKind::Rss => "rss",
+            SourceKind::Api => "api",
+            SourceKind::Remote => "remote",
+        }
+    }
+
+    /// Parse a stored value; unknown values fall back to RSS
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "api" => SourceKind::Api,
+            "remote" => SourceKind::Remote,
+            _ => SourceKind::Rss,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NewsItem {
     pub id: String,
     pub source: String,
+    /// Key of the [`Source`](super::Source) row this item belongs to
+    pub source_id: String,
     pub title: String,
     pub url: String,
     pub summary: String,
     pub published: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+
+    pub author: Option<String>,
+    /// Categories/tags reported by the source
+    pub tags: Vec<String>,
+    /// Ticker symbols the item is about (source-provided or cashtags)
+    pub tickers: Vec<String>,
+    pub image_url: Option<String>,
+    /// Full article body, when the source provides more than a summary
+    pub content: Option<String>,
+    /// Language code as reported by the source (e.g. "en-us")
+    pub language: Option<String>,
+    /// Source-specific relevance score (e.g. upvotes); 0 when unknown
+    pub score: f64,
+    /// Comments on the item's discussion page, for aggregators; 0 when unknown
+    pub comments: u32,
+    pub source_kind: SourceKind,
+    /// Date of the scheduled event the item announces (earnings, releases)
+    pub event_date: Option<NaiveDate>,
+    /// Title and summary before the source last edited them
+    pub previous: Option<Revision>,
+    /// Other sources that carried the same story, collapsed into this item
+    #[serde(default)]
+    pub also_reported_by: Vec<String>,
+
+    // User state, kept across re-fetches
+    pub read: bool,
+    pub starred: bool,
+}
+
+/// An earlier version of an item's headline, kept when a re-fetch changes it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    pub title: String,
+    pub summary: String,
+    /// When the changed version was first stored
+    pub revised_at: DateTime<Utc>,
+}
+
+impl NewsItem {
+    /// Whether the item comes from the source called `name`, however the
+    /// name is cased or prefixed
+    pub fn is_from(&self, name: &str) -> bool {
+        self.source_id == source_id(name)
+    }
+}
+
+/// Widely followed symbols recognized without a `$`, written in capitals.
+/// Symbols that are also common words or abbreviations (A, IT, ON, MS, BA)
+/// are left out; those need the cashtag.
+const KNOWN_TICKERS: &[&str] = &[
+    "AAPL", "MSFT", "NVDA", "AMZN", "GOOGL", "GOOG", "META", "TSLA", "AVGO", "AMD", "INTC", "NFLX",
+    "ORCL", "CRM", "ADBE", "QCOM", "CSCO", "IBM", "PYPL", "UBER", "JPM", "BAC", "WFC", "GS",
+    "BRK.B", "XOM", "CVX", "PFE", "MRK", "JNJ", "LLY", "UNH", "WMT", "COST", "PEP", "NKE", "SPY",
+    "QQQ",
+];
+
+/// Tickers mentioned in `texts` as cashtags ($AAPL, $BRK.B) or as a known
+/// symbol written out in capitals (NVDA), deduplicated in order
+pub fn extract_tickers(texts: &[&str]) -> Vec<String> {
+    let mut tickers: Vec<String> = Vec::new();
+    for text in texts {
+        let chars: Vec<char> = text.chars().collect();
+        for (i, c) in chars.iter().enumerate() {
+            // A ticker must not be glued to a preceding word character ("US$5")
+            if i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '$') {
+                continue;
+            }
+            let symbol = if *c == '$' {
+                let symbol: String = chars[i + 1..]
+                    .iter()
+                    .take_while(|ch| ch.is_ascii_uppercase() || **ch == '.')
+                    .collect();
+                let symbol = symbol.trim_end_matches('.').to_string();
+                let letters = symbol.chars().filter(|ch| ch.is_ascii_uppercase()).count();
+                if !(1..=6).contains(&letters) {
+                    continue;
+                }
+                symbol
+            } else if c.is_ascii_uppercase() {
+                let word: String = chars[i..]
+                    .iter()
+                    .take_while(|ch| ch.is_alphanumeric() || **ch == '.')
+                    .collect();
+                let word = word.trim_end_matches('.');
+                if !KNOWN_TICKERS.contains(&word) {
+                    continue;
+                }
+                word.to_string()
+            } else {
+                continue;
+            };
+            if !tickers.contains(&symbol) {
+                tickers.push(symbol);
+            }
+        }
+    }
+    tickers
+}
+
+/// The ticker a search word like `$aapl` filters to, in capitals; None for
+/// ordinary words and amounts like `$5`
+pub fn ticker_filter(word: &str) -> Option<String> {
+    let symbol = word.strip_prefix('$')?;
+    let valid = symbol.starts_with(|c: char| c.is_ascii_alphabetic())
+        && symbol
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.');
+    valid.then(|| symbol.to_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tickers_come_from_cashtags_and_known_symbols() {
+        assert_eq!(
+            extract_tickers(&["$BRK.B and NVDA rally.", "AMD's chips; $XYZ up"]),
+            ["BRK.B", "NVDA", "AMD", "XYZ"]
+        );
+        // Unknown capitals, title case and glued symbols are not tickers
+        assert!(extract_tickers(&["CEO says Nvidia beat; US$5 SAAPL"]).is_empty());
+    }
+
+    #[test]
+    fn dollar_words_filter_by_ticker() {
+        assert_eq!(ticker_filter("$brk.b").as_deref(), Some("BRK.B"));
+        assert_eq!(ticker_filter("$5"), None);
+        assert_eq!(ticker_filter("$"), None);
+        assert_eq!(ticker_filter("AAPL"), None);
+    }
 }
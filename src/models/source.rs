@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::SourceKind;
+
+/// Longest delay counted when averaging how late a source delivers items
+const MAX_LAG_SECS: f64 = 24.0 * 3600.0;
+/// Fewest articles a publishing cadence is learned from
+const MIN_CADENCE_ITEMS: usize = 5;
+
+/// A news source as stored in the database. Items reference it by `id`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Source {
+    /// Stable key derived from the name, see [`source_id`]
+    pub id: String,
+    /// Display name, as registered with the adaptor registry
+    pub name: String,
+    pub kind: SourceKind,
+    /// Feed or API endpoint, when the adaptor has a single one
+    pub url: Option<String>,
+    /// Optional grouping label (e.g. "crypto")
+    pub group: Option<String>,
+    pub enabled: bool,
+    /// Higher sorts first when sources are listed
+    pub priority: i32,
+    /// Display colour name or hex code (e.g. "cyan", "#ff8800")
+    pub color: Option<String>,
+    /// Last successful fetch
+    pub last_fetch: Option<DateTime<Utc>>,
+    pub stats: SourceStats,
+    /// URL the feed permanently redirects to, until `url` is updated
+    pub moved_to: Option<String>,
+    /// Rate-limited until then; refreshes skip the source before it
+    pub cooldown_until: Option<DateTime<Utc>>,
+}
+
+/// Fetch history of a source, accumulated over all refreshes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceStats {
+    pub fetches: u64,
+    pub successes: u64,
+    /// Items that were new when fetched
+    pub new_items: u64,
+    /// New items whose URL was already stored from another source
+    pub duplicates: u64,
+    /// Total delay between publishing and fetching the new items, each capped at a day
+    pub lag_secs: f64,
+}
+
+impl SourceStats {
+    /// Stats for a single fetch
+    pub fn fetch(success: bool) -> Self {
+        Self {
+            fetches: 1,
+            successes: success as u64,
+            ..Default::default()
+        }
+    }
+
+    /// Count a new item, published at `published` and fetched at `now`
+    pub fn add_item(&mut self, published: DateTime<Utc>, now: DateTime<Utc>, duplicate: bool) {
+        self.new_items += 1;
+        self.duplicates += duplicate as u64;
+        let lag = now.signed_duration_since(published).num_seconds() as f64;
+        self.lag_secs += lag.clamp(0.0, MAX_LAG_SECS);
+    }
+
+    pub fn success_rate(&self) -> f64 {
+        if self.fetches == 0 {
+            1.0
+        } else {
+            self.successes as f64 / self.fetches as f64
+        }
+    }
+
+    /// Average delay before new items were fetched
+    pub fn average_lag_secs(&self) -> f64 {
+        if self.new_items == 0 {
+            0.0
+        } else {
+            self.lag_secs / self.new_items as f64
+        }
+    }
+
+    pub fn duplicate_ratio(&self) -> f64 {
+        if self.new_items == 0 {
+            0.0
+        } else {
+            self.duplicates as f64 / self.new_items as f64
+        }
+    }
+
+    /// Score from 0 to 1: the success rate, reduced by up to half for items
+    /// that arrive a day late and by up to half for items other sources
+    /// already had. `None` until the source has been fetched.
+    pub fn reliability(&self) -> Option<f64> {
+        if self.fetches == 0 {
+            return None;
+        }
+        let freshness = 1.0 - 0.5 * self.average_lag_secs() / MAX_LAG_SECS;
+        let originality = 1.0 - 0.5 * self.duplicate_ratio();
+        Some(self.success_rate() * freshness * originality)
+    }
+}
+
+impl Source {
+    pub fn new(name: &str, kind: SourceKind) -> Self {
+        Self {
+            id: source_id(name),
+            name: normalize_source_name(name).to_string(),
+            kind,
+            enabled: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Typical gap between a source's articles: the median gap between
+/// consecutive publish times, so one quiet weekend doesn't skew it.
+/// Articles sharing a timestamp (a batch, or a feed without times) add no
+/// gap. `None` with fewer than a handful of articles.
+pub fn publishing_cadence(published: &[DateTime<Utc>]) -> Option<Duration> {
+    let mut times = published.to_vec();
+    times.sort_unstable();
+    let mut gaps: Vec<i64> = times
+        .windows(2)
+        .map(|pair| pair[1].signed_duration_since(pair[0]).num_seconds())
+        .filter(|gap| *gap > 0)
+        .collect();
+    if gaps.len() + 1 < MIN_CADENCE_ITEMS {
+        return None;
+    }
+    gaps.sort_unstable();
+    Some(Duration::from_secs(gaps[gaps.len() / 2] as u64))
+}
+
+/// Display form of a source name. Older builds stored RSS sources as
+/// "RSS_<name>"; the prefix and surrounding whitespace are dropped.
+pub fn normalize_source_name(name: &str) -> &str {
+    let name = name.trim();
+    name.strip_prefix("RSS_").unwrap_or(name).trim()
+}
+
+/// Stable id for a source name: normalized, lowercased, spaces replaced with
+/// dashes. "MarketWatch", "RSS_MarketWatch" and "marketwatch" share an id.
+pub fn source_id(name: &str) -> String {
+    normalize_source_name(name).to_lowercase().replace(' ', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn cadence_is_the_median_gap() {
+        let at = |mins: i64| {
+            Utc.with_ymd_and_hms(2024, 3, 14, 9, 0, 0).unwrap() + chrono::Duration::minutes(mins)
+        };
+        assert_eq!(publishing_cadence(&[at(0), at(10), at(20)]), None);
+
+        // Every 10 minutes, then a night's silence, in no particular order
+        let times = [at(40), at(0), at(10), at(20), at(30), at(30), at(900)];
+        assert_eq!(publishing_cadence(&times), Some(Duration::from_secs(600)));
+    }
+}
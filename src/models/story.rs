@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+
+use super::NewsItem;
+
+/// Words too common to tell stories apart
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "that", "this", "after", "over", "into", "amid", "are",
+    "was", "were", "has", "have", "had", "its", "his", "her", "their", "will", "would", "could",
+    "says", "said", "new", "more", "than", "about", "as", "but", "not", "out", "off", "what",
+    "why", "how", "who", "when", "here", "now", "week", "today", "year", "news", "report",
+    "update", "live",
+];
+/// Shared keywords needed for an article to count as the same story
+const MIN_SHARED_KEYWORDS: usize = 2;
+/// Share of the shorter keyword set that must overlap
+const MIN_OVERLAP: f64 = 0.5;
+
+/// A story the user follows, identified by the keywords and tickers of the
+/// article it was followed from
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowedStory {
+    /// Database row id; 0 until stored
+    pub id: i64,
+    /// Id of the article the story was followed from
+    pub item_id: String,
+    pub title: String,
+    pub keywords: Vec<String>,
+    pub tickers: Vec<String>,
+    pub followed_at: DateTime<Utc>,
+}
+
+impl FollowedStory {
+    pub fn from_item(item: &NewsItem) -> Self {
+        Self {
+            id: 0,
+            item_id: item.id.clone(),
+            title: item.title.clone(),
+            keywords: keywords(&item.title),
+            tickers: item.tickers.clone(),
+            followed_at: Utc::now(),
+        }
+    }
+
+    /// Whether `item` covers this story: it mentions one of the story's
+    /// tickers and shares a keyword, or shares most of the story's keywords
+    pub fn matches(&self, item: &NewsItem) -> bool {
+        if item.id == self.item_id {
+            return true;
+        }
+        let words = keywords(&item.title);
+        let shared = words.iter().filter(|w| self.keywords.contains(w)).count();
+        if shared > 0 && item.tickers.iter().any(|t| self.tickers.contains(t)) {
+            return true;
+        }
+        let smaller = words.len().min(self.keywords.len());
+        shared >= MIN_SHARED_KEYWORDS && shared as f64 >= smaller as f64 * MIN_OVERLAP
+    }
+}
+
+/// Distinct lowercase words of a headline that can identify a story
+pub fn keywords(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if word.chars().count() < 3 || STOPWORDS.contains(&word.as_str()) {
+            continue;
+        }
+        if !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, title: &str, tickers: &[&str]) -> NewsItem {
+        NewsItem {
+            id: id.to_string(),
+            title: title.to_string(),
+            tickers: tickers.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn related_headlines_match() {
+        let story = FollowedStory::from_item(&item(
+            "a",
+            "Boeing halts 737 MAX deliveries after door plug blowout",
+            &["BA"],
+        ));
+        assert!(story.matches(&item("b", "FAA grounds Boeing 737 MAX 9 jets", &[])));
+        assert!(story.matches(&item("c", "Boeing shares slide", &["BA"])));
+        assert!(!story.matches(&item("d", "Airbus deliveries rise in March", &[])));
+        assert!(!story.matches(&item("e", "Stocks slide after jobs report", &["SPY"])));
+    }
+
+    #[test]
+    fn keywords_skip_short_and_common_words() {
+        assert_eq!(
+            keywords("The Fed and the ECB: rates on hold"),
+            ["fed", "ecb", "rates", "hold"]
+        );
+    }
+}
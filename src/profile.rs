@@ -0,0 +1,198 @@
+//! Timings of startup and refresh for `news-hub --profile`. Recording is
+//! off until [`enable`] is called; then each [`span`] adds one entry when it
+//! ends, and [`report`] sums them per phase and source so the feeds that
+//! make a refresh feel slow stand out.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TIMINGS: Mutex<Vec<Timing>> = Mutex::new(Vec::new());
+
+/// What was being timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// A startup step, in the order it ran
+    Startup,
+    /// One source's whole fetch, parsing included
+    Fetch,
+    /// Turning a feed body into items
+    Parse,
+    /// Storing one source's fetched items
+    Insert,
+}
+
+#[derive(Debug, Clone)]
+pub struct Timing {
+    pub phase: Phase,
+    /// Startup step or source name
+    pub label: String,
+    pub elapsed: Duration,
+}
+
+/// Start recording timings
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn record(phase: Phase, label: &str, elapsed: Duration) {
+    if is_enabled() {
+        TIMINGS.lock().unwrap().push(Timing {
+            phase,
+            label: label.to_string(),
+            elapsed,
+        });
+    }
+}
+
+/// Time from now until the returned guard is dropped
+pub fn span(phase: Phase, label: &str) -> Span {
+    Span {
+        phase,
+        label: is_enabled().then(|| label.to_string()),
+        start: Instant::now(),
+    }
+}
+
+/// Records its timing when dropped; does nothing while recording is off
+pub struct Span {
+    phase: Phase,
+    label: Option<String>,
+    start: Instant,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(label) = &self.label {
+            record(self.phase, label, self.start.elapsed());
+        }
+    }
+}
+
+/// Everything recorded so far, leaving the record empty
+pub fn take() -> Vec<Timing> {
+    std::mem::take(&mut *TIMINGS.lock().unwrap())
+}
+
+/// Timings of one source, summed over its fetches
+#[derive(Debug, Default)]
+struct SourceTimes {
+    fetches: u32,
+    fetch: Duration,
+    slowest: Duration,
+    parse: Duration,
+    insert: Duration,
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{} ms", duration.as_millis())
+}
+
+/// Startup steps in order, then sources by their slowest fetch, slowest
+/// first, with average times per fetch
+pub fn report(timings: &[Timing]) -> String {
+    let mut out = String::new();
+    let startup: Vec<&Timing> = timings
+        .iter()
+        .filter(|t| t.phase == Phase::Startup)
+        .collect();
+    if !startup.is_empty() {
+        let total: Duration = startup.iter().map(|t| t.elapsed).sum();
+        let _ = writeln!(out, "Startup: {}", millis(total));
+        for timing in startup {
+            let _ = writeln!(out, "  {:<26} {:>10}", timing.label, millis(timing.elapsed));
+        }
+    }
+
+    let mut sources: Vec<(&str, SourceTimes)> = Vec::new();
+    for timing in timings.iter().filter(|t| t.phase != Phase::Startup) {
+        let index = match sources.iter().position(|(name, _)| *name == timing.label) {
+            Some(index) => index,
+            None => {
+                sources.push((&timing.label, SourceTimes::default()));
+                sources.len() - 1
+            }
+        };
+        let times = &mut sources[index].1;
+        match timing.phase {
+            Phase::Fetch => {
+                times.fetches += 1;
+                times.fetch += timing.elapsed;
+                times.slowest = times.slowest.max(timing.elapsed);
+            }
+            Phase::Parse => times.parse += timing.elapsed,
+            Phase::Insert => times.insert += timing.elapsed,
+            Phase::Startup => {}
+        }
+    }
+    if sources.is_empty() {
+        return out;
+    }
+    sources.sort_by_key(|(_, times)| std::cmp::Reverse(times.slowest));
+
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    let _ = writeln!(
+        out,
+        "{:<28} {:>7} {:>10} {:>10} {:>10} {:>10}",
+        "SOURCE", "FETCHES", "FETCH", "SLOWEST", "PARSE", "INSERT"
+    );
+    for (name, times) in &sources {
+        let average = |total: Duration| millis(total / times.fetches.max(1));
+        let _ = writeln!(
+            out,
+            "{:<28} {:>7} {:>10} {:>10} {:>10} {:>10}",
+            name,
+            times.fetches,
+            average(times.fetch),
+            millis(times.slowest),
+            average(times.parse),
+            average(times.insert)
+        );
+    }
+    out.push_str("\nFETCH includes PARSE; times are averages per fetch\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(phase: Phase, label: &str, ms: u64) -> Timing {
+        Timing {
+            phase,
+            label: label.to_string(),
+            elapsed: Duration::from_millis(ms),
+        }
+    }
+
+    #[test]
+    fn report_puts_the_slowest_source_first() {
+        let report = report(&[
+            timing(Phase::Startup, "database load", 40),
+            timing(Phase::Startup, "first render", 10),
+            timing(Phase::Fetch, "CNBC", 300),
+            timing(Phase::Parse, "CNBC", 20),
+            timing(Phase::Insert, "CNBC", 6),
+            timing(Phase::Fetch, "Benzinga", 2000),
+            timing(Phase::Fetch, "CNBC", 500),
+            timing(Phase::Insert, "CNBC", 4),
+        ]);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "Startup: 50 ms");
+        assert!(lines[1].starts_with("  database load"));
+        assert!(lines[5].starts_with("Benzinga"));
+        let cnbc: Vec<&str> = lines[6].split_whitespace().collect();
+        assert_eq!(
+            cnbc,
+            ["CNBC", "2", "400", "ms", "500", "ms", "10", "ms", "5", "ms"]
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use anyhow::{Context, Result};
+use mlua::{Function, HookTriggers, Lua, RegistryKey, Table};
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::models::NewsItem;
+
+/// VM instructions a script may run per load or hook call before it is
+/// stopped, so a runaway loop can't freeze the UI
+const INSTRUCTION_LIMIT: u32 = 1_000_000;
+/// Instructions between checks of the limit
+const INSTRUCTION_CHECK: u32 = 1_000;
+
+/// Hooks a script may define as globals:
+///   filter(item) -> bool            hide articles for which this is falsy
+///   score(item) -> number           articles are listed by descending total score
+///   format_row(item, row) -> string replace the list row (`row` is the default text)
+struct Script {
+    filter: Option<Hook>,
+    score: Option<Hook>,
+    format_row: Option<Hook>,
+}
+
+/// A hook function, switched off once it runs past [`INSTRUCTION_LIMIT`]
+/// until the scripts are reloaded
+struct Hook {
+    function: RegistryKey,
+    stopped: Cell<bool>,
+}
+
+/// Lua scripts loaded from a directory. Each `*.lua` file runs in its own
+/// environment so scripts can define the same hooks without clobbering each other.
+pub struct ScriptEngine {
+    lua: Lua,
+    dir: PathBuf,
+    scripts: Vec<Script>,
+    /// Runtime errors since the last `take_errors`, deduplicated
+    errors: RefCell<Vec<String>>,
+    /// Instruction checks left for the running load or call
+    checks_left: Rc<Cell<u32>>,
+    /// `format_row` results by item id, with the fingerprint of the item and
+    /// row they were made from
+    rows: RefCell<HashMap<String, (u64, Option<String>)>>,
+}
+
+impl ScriptEngine {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let checks_left = Rc::new(Cell::new(0));
+        Self {
+            lua: limited_lua(&checks_left),
+            dir: dir.into(),
+            scripts: Vec::new(),
+            errors: RefCell::new(Vec::new()),
+            checks_left,
+            rows: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of successfully loaded scripts
+    pub fn len(&self) -> usize {
+        self.scripts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    /// (Re)load every script in the directory with a fresh interpreter.
+    /// A missing directory means no scripts. Returns per-script load errors.
+    pub fn reload(&mut self) -> Vec<String> {
+        self.scripts.clear();
+        self.errors.borrow_mut().clear();
+        self.rows.borrow_mut().clear();
+        self.lua = limited_lua(&self.checks_left);
+
+        let mut paths: Vec<PathBuf> = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "lua"))
+                .collect(),
+            Err(_) => return Vec::new(),
+        };
+        // Deterministic order: formatters are tried in file name order
+        paths.sort();
+
+        let mut errors = Vec::new();
+        for path in paths {
+            match self.load_script(&path) {
+                Ok(script) => self.scripts.push(script),
+                Err(e) => errors.push(format!("Script {}: {:#}", path.display(), e)),
+            }
+        }
+        errors
+    }
+
+    fn load_script(&self, path: &Path) -> Result<Script> {
+        let source = std::fs::read_to_string(path).context("Failed to read script")?;
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        // Unknown globals fall through to the shared standard library
+        let env = self.lua.create_table()?;
+        let meta = self.lua.create_table()?;
+        meta.set("__index", self.lua.globals())?;
+        env.set_metatable(Some(meta));
+
+        self.checks_left.set(INSTRUCTION_LIMIT / INSTRUCTION_CHECK);
+        self.lua
+            .load(&source)
+            .set_name(format!("={}", name))
+            .set_environment(env.clone())
+            .exec()
+            .map_err(|e| anyhow::anyhow!(first_line(&e)))?;
+
+        let hook = |name: &str| -> Result<Option<Hook>> {
+            let f: Option<Function> = env.get(name)?;
+            Ok(match f {
+                Some(f) => Some(Hook {
+                    function: self.lua.create_registry_value(f)?,
+                    stopped: Cell::new(false),
+                }),
+                None => None,
+            })
+        };
+        Ok(Script {
+            filter: hook("filter")?,
+            score: hook("score")?,
+            format_row: hook("format_row")?,
+        })
+    }
+
+    pub fn has_scores(&self) -> bool {
+        self.scripts.iter().any(|s| s.score.is_some())
+    }
+
+    /// True unless some script's filter rejects the item. Failing filters keep it.
+    pub fn filter(&self, item: &NewsItem) -> bool {
+        self.scripts
+            .iter()
+            .filter_map(|s| s.filter.as_ref())
+            // Lua truthiness: only nil and false reject
+            .all(|hook| self.call::<bool>(hook, item, None).unwrap_or(true))
+    }
+
+    /// Sum of all script scores for the item
+    pub fn score(&self, item: &NewsItem) -> f64 {
+        self.scripts
+            .iter()
+            .filter_map(|s| s.score.as_ref())
+            .filter_map(|hook| self.call::<Option<f64>>(hook, item, None).flatten())
+            .sum()
+    }
+
+    /// Row text from the first script whose formatter returns a string.
+    /// Rows are drawn every frame, so the text is kept until the item or its
+    /// default row changes.
+    pub fn format_row(&self, item: &NewsItem, default_row: &str) -> Option<String> {
+        let fingerprint = fingerprint(item, default_row);
+        if let Some((seen, row)) = self.rows.borrow().get(&item.id) {
+            if *seen == fingerprint {
+                return row.clone();
+            }
+        }
+        let row = self
+            .scripts
+            .iter()
+            .filter_map(|s| s.format_row.as_ref())
+            .find_map(|hook| {
+                self.call::<Option<String>>(hook, item, Some(default_row))
+                    .flatten()
+            });
+        self.rows
+            .borrow_mut()
+            .insert(item.id.clone(), (fingerprint, row.clone()));
+        row
+    }
+
+    /// Runtime errors collected since the last call
+    pub fn take_errors(&self) -> Vec<String> {
+        std::mem::take(&mut *self.errors.borrow_mut())
+    }
+
+    /// Call a hook, recording (not propagating) runtime errors
+    fn call<R>(&self, hook: &Hook, item: &NewsItem, row: Option<&str>) -> Option<R>
+    where
+        R: for<'lua> mlua::FromLuaMulti<'lua>,
+    {
+        if hook.stopped.get() {
+            return None;
+        }
+        self.checks_left.set(INSTRUCTION_LIMIT / INSTRUCTION_CHECK);
+        let result = self
+            .lua
+            .registry_value::<Function>(&hook.function)
+            .and_then(|f| f.call::<_, R>((self.item_table(item)?, row)));
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                if self.checks_left.get() == 0 {
+                    hook.stopped.set(true);
+                }
+                let message = format!("Script error: {}", first_line(&e));
+                let mut errors = self.errors.borrow_mut();
+                if !errors.contains(&message) {
+                    errors.push(message);
+                }
+                None
+            }
+        }
+    }
+
+    fn item_table(&self, item: &NewsItem) -> mlua::Result<Table<'_>> {
+        let table = self.lua.create_table()?;
+        table.set("id", item.id.as_str())?;
+        table.set("source", item.source.as_str())?;
+        table.set("source_id", item.source_id.as_str())?;
+        table.set("title", item.title.as_str())?;
+        table.set("url", item.url.as_str())?;
+        table.set("summary", item.summary.as_str())?;
+        table.set("published", item.published.timestamp())?;
+        table.set("tickers", item.tickers.clone())?;
+        table.set("tags", item.tags.clone())?;
+        table.set("author", item.author.as_deref())?;
+        table.set("language", item.language.as_deref())?;
+        table.set("score", item.score)?;
+        table.set("comments", item.comments)?;
+        table.set("source_kind", item.source_kind.as_str())?;
+        table.set("read", item.read)?;
+        table.set("starred", item.starred)?;
+        Ok(table)
+    }
+}
+
+/// An interpreter that stops whatever is running once `checks_left` runs out
+fn limited_lua(checks_left: &Rc<Cell<u32>>) -> Lua {
+    let lua = Lua::new();
+    let checks_left = Rc::clone(checks_left);
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(INSTRUCTION_CHECK),
+        move |_, _| match checks_left.get() {
+            0 => Err(mlua::Error::runtime(format!(
+                "stopped after {} instructions",
+                INSTRUCTION_LIMIT
+            ))),
+            left => {
+                checks_left.set(left - 1);
+                Ok(())
+            }
+        },
+    );
+    lua
+}
+
+/// Everything a hook sees of an item, to tell when a kept row is stale
+fn fingerprint(item: &NewsItem, row: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    row.hash(&mut hasher);
+    item.source.hash(&mut hasher);
+    item.source_id.hash(&mut hasher);
+    item.title.hash(&mut hasher);
+    item.url.hash(&mut hasher);
+    item.summary.hash(&mut hasher);
+    item.published.hash(&mut hasher);
+    item.tickers.hash(&mut hasher);
+    item.tags.hash(&mut hasher);
+    item.author.hash(&mut hasher);
+    item.language.hash(&mut hasher);
+    item.score.to_bits().hash(&mut hasher);
+    item.comments.hash(&mut hasher);
+    item.source_kind.as_str().hash(&mut hasher);
+    item.read.hash(&mut hasher);
+    item.starred.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Lua errors carry a stack traceback; the first line is the useful part
+fn first_line(e: &mlua::Error) -> String {
+    e.to_string().lines().next().unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine(dir: &Path, scripts: &[(&str, &str)]) -> (ScriptEngine, Vec<String>) {
+        for (name, source) in scripts {
+            std::fs::write(dir.join(name), source).unwrap();
+        }
+        let mut engine = ScriptEngine::new(dir);
+        let errors = engine.reload();
+        (engine, errors)
+    }
+
+    fn item(id: &str, title: &str) -> NewsItem {
+        NewsItem {
+            id: id.to_string(),
+            title: title.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stops_scripts_that_run_forever() {
+        let dir = tempfile::tempdir().unwrap();
+        let (engine, errors) = engine(
+            dir.path(),
+            &[
+                ("a.lua", "function filter(item) while true do end end"),
+                ("b.lua", "while true do end"),
+                ("c.lua", "function score(item) return 2 end"),
+            ],
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("stopped after"), "{}", errors[0]);
+
+        // The looping filter keeps the item and isn't called again
+        assert!(engine.filter(&item("1", "One")));
+        assert_eq!(engine.take_errors().len(), 1);
+        assert!(engine.filter(&item("2", "Two")));
+        assert!(engine.take_errors().is_empty());
+        assert_eq!(engine.score(&item("1", "One")), 2.0);
+    }
+
+    #[test]
+    fn formats_each_row_once_until_it_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = "calls = 0
+            function format_row(item, row)
+                calls = calls + 1
+                return calls .. ' ' .. row
+            end";
+        let (mut engine, errors) = engine(dir.path(), &[("row.lua", script)]);
+        assert!(errors.is_empty());
+
+        let mut one = item("1", "One");
+        assert_eq!(engine.format_row(&one, "One").as_deref(), Some("1 One"));
+        assert_eq!(engine.format_row(&one, "One").as_deref(), Some("1 One"));
+        assert_eq!(
+            engine.format_row(&item("2", "Two"), "Two").as_deref(),
+            Some("2 Two")
+        );
+
+        one.read = true;
+        assert_eq!(engine.format_row(&one, "One").as_deref(), Some("3 One"));
+        assert_eq!(engine.format_row(&one, "Uno").as_deref(), Some("4 Uno"));
+
+        engine.reload();
+        assert_eq!(engine.format_row(&one, "Uno").as_deref(), Some("1 Uno"));
+    }
+}
@@ -0,0 +1,313 @@
+//! Sync through a git repository of plain text files, for `:sync pull` and
+//! `:sync push`. Each machine only ever writes its own two files, its
+//! subscriptions as `<machine>.opml` and its article state as
+//! `<machine>.state.json`, so git never merges one file from two machines.
+//! Pulling merges the other machines' files into this one instead: their
+//! state newest-change-wins (see [`crate::db::state`]), their feeds by adding
+//! the ones not configured here to config.toml. Unsubscribing doesn't
+//! propagate.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::{parse_opml, plan_import, Config, OpmlImport, SyncConfig};
+use crate::db::state::StateFile;
+
+const STATE_SUFFIX: &str = ".state.json";
+const OPML_SUFFIX: &str = ".opml";
+
+/// Which way `:sync` goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    Pull,
+    Push,
+}
+
+impl SyncDirection {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pull" => Some(Self::Pull),
+            "push" => Some(Self::Push),
+            _ => None,
+        }
+    }
+}
+
+/// What a sync did
+#[derive(Debug)]
+pub enum SyncOutcome {
+    Pushed {
+        /// Whether this machine's files had changed since the last push
+        committed: bool,
+        /// Whether the repository has a remote the commit went to
+        pushed: bool,
+    },
+    Pulled {
+        /// Other machines' article state, for the database to merge
+        states: Vec<StateFile>,
+        /// Other machines' feeds missing here, for config.toml
+        feeds: OpmlImport,
+    },
+}
+
+/// This machine's view of the sync repository
+#[derive(Debug, Clone)]
+pub struct SyncRepo {
+    dir: PathBuf,
+    machine: String,
+}
+
+impl SyncRepo {
+    /// None while `[sync] dir` is unset
+    pub fn from_config(config: &SyncConfig) -> Option<Self> {
+        let dir = config.dir.as_deref().filter(|dir| !dir.is_empty())?;
+        Some(Self::new(dir, &config.machine()))
+    }
+
+    pub fn new(dir: impl Into<PathBuf>, machine: &str) -> Self {
+        Self {
+            dir: dir.into(),
+            machine: machine.to_string(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn state_path(&self) -> PathBuf {
+        self.dir.join(format!("{}{}", self.machine, STATE_SUFFIX))
+    }
+
+    fn opml_path(&self) -> PathBuf {
+        self.dir.join(format!("{}{}", self.machine, OPML_SUFFIX))
+    }
+
+    fn check_repo(&self) -> Result<()> {
+        if self.dir.join(".git").exists() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "{} is not a git repository; clone your sync repository there or run `git init` in it",
+                self.dir.display()
+            ))
+        }
+    }
+
+    /// Write this machine's files, commit them if they changed, and push
+    /// when the repository has a remote. Runs git, so it blocks.
+    pub fn push(&self, state: &StateFile, opml: &str) -> Result<SyncOutcome> {
+        self.check_repo()?;
+        let state_path = self.state_path();
+        if !same_state(&state_path, state) {
+            state.write(&state_path)?;
+        }
+        let opml_path = self.opml_path();
+        // The export's date changes every time; only the feeds matter
+        let old_opml = fs::read_to_string(&opml_path).unwrap_or_default();
+        if without_date(&old_opml) != without_date(opml) {
+            fs::write(&opml_path, opml)
+                .with_context(|| format!("Failed to write {}", opml_path.display()))?;
+        }
+
+        let files = [file_name(&state_path), file_name(&opml_path)];
+        self.git(&["add", "--", &files[0], &files[1]])?;
+        let changed = !self
+            .git(&["status", "--porcelain", "--", &files[0], &files[1]])?
+            .trim()
+            .is_empty();
+        if changed {
+            let message = format!("news-hub state from {}", self.machine);
+            self.git(&["commit", "-m", &message, "--", &files[0], &files[1]])?;
+        }
+        let pushed = self.has_remote()?;
+        if pushed {
+            self.git(&["push"])?;
+        }
+        Ok(SyncOutcome::Pushed {
+            committed: changed,
+            pushed,
+        })
+    }
+
+    /// Pull when the repository has a remote, then read the other machines'
+    /// article state and the feeds they subscribe to that `config` lacks.
+    /// Runs git, so it blocks.
+    pub fn pull(&self, config: &Config) -> Result<SyncOutcome> {
+        self.check_repo()?;
+        if self.has_remote()? {
+            self.git(&["pull", "--rebase", "--autostash"])?;
+        }
+
+        let mut states = Vec::new();
+        for path in self.other_machines(STATE_SUFFIX)? {
+            states.extend(StateFile::read(&path)?);
+        }
+        let mut feeds = Vec::new();
+        for path in self.other_machines(OPML_SUFFIX)? {
+            let xml =
+                fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+            let parsed =
+                parse_opml(&xml).with_context(|| format!("Failed to parse {}", path.display()))?;
+            feeds.extend(parsed);
+        }
+        Ok(SyncOutcome::Pulled {
+            states,
+            feeds: plan_import(&feeds, config),
+        })
+    }
+
+    /// Files other machines wrote with the given suffix, by name
+    fn other_machines(&self, suffix: &str) -> Result<Vec<PathBuf>> {
+        let own = format!("{}{}", self.machine, suffix);
+        let mut paths: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to list {}", self.dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                let name = file_name(path);
+                name.ends_with(suffix) && name != own
+            })
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn has_remote(&self) -> Result<bool> {
+        Ok(!self.git(&["remote"])?.trim().is_empty())
+    }
+
+    /// Run git in the repository, returning its output; fails with git's
+    /// own message
+    fn git(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.dir)
+            .args(args)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("git {} failed: {}", args[0], stderr.trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Whether the state file at `path` already holds `state`, apart from when
+/// it was exported
+fn same_state(path: &Path, state: &StateFile) -> bool {
+    let Ok(Some(old)) = StateFile::read(path) else {
+        return false;
+    };
+    let (Ok(old), Ok(new)) = (serde_json::to_value(&old), serde_json::to_value(state)) else {
+        return false;
+    };
+    old["items"] == new["items"] && old["deleted"] == new["deleted"]
+}
+
+/// OPML without its `dateCreated` line
+fn without_date(opml: &str) -> String {
+    opml.lines()
+        .filter(|line| !line.trim_start().starts_with("<dateCreated>"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::export_opml;
+    use crate::db::sqlite::NewsDB;
+    use crate::models::NewsItem;
+    use chrono::Utc;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn pushed_state_and_feeds_reach_the_other_machine() {
+        let root = tempfile::tempdir().unwrap();
+        git(root.path(), &["init", "--quiet", "--bare", "shared.git"]);
+        for machine in ["desktop", "laptop"] {
+            git(root.path(), &["clone", "--quiet", "shared.git", machine]);
+            let dir = root.path().join(machine);
+            git(&dir, &["config", "user.name", "news-hub"]);
+            git(&dir, &["config", "user.email", "news-hub@example.com"]);
+        }
+        let desktop = SyncRepo::new(root.path().join("desktop"), "desktop");
+        let laptop = SyncRepo::new(root.path().join("laptop"), "laptop");
+
+        let db = NewsDB::new(":memory:").unwrap();
+        db.insert(&NewsItem {
+            id: "a".to_string(),
+            source: "CNBC".to_string(),
+            url: "https://example.com/a".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        db.set_read("a", true, Utc::now()).unwrap();
+        let toml: crate::config::TomlConfig = toml::from_str(
+            r#"
+            [[feed]]
+            kind = "rss"
+            url = "https://example.org/feed"
+            name = "Example"
+            "#,
+        )
+        .unwrap();
+        let config = Config {
+            feed: toml.feed,
+            ..Config::default()
+        };
+        let opml = export_opml(&config, Utc::now());
+        let state = db.export_state(Utc::now()).unwrap();
+
+        let pushed = desktop.push(&state, &opml).unwrap();
+        assert!(matches!(
+            pushed,
+            SyncOutcome::Pushed {
+                committed: true,
+                pushed: true
+            }
+        ));
+        // Nothing changed but the export time
+        let again = desktop.push(&db.export_state(Utc::now()).unwrap(), &opml);
+        assert!(matches!(
+            again.unwrap(),
+            SyncOutcome::Pushed {
+                committed: false,
+                ..
+            }
+        ));
+
+        let SyncOutcome::Pulled { states, feeds } = laptop.pull(&Config::default()).unwrap() else {
+            panic!("expected a pull");
+        };
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].items[0].item.id, "a");
+        let added: Vec<&str> = feeds.added.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(added, ["Example"]);
+
+        // A machine never reads back its own files
+        let SyncOutcome::Pulled { states, .. } = desktop.pull(&config).unwrap() else {
+            panic!("expected a pull");
+        };
+        assert!(states.is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+//! Fixtures shared by the unit tests: a fixed instant to date things
+//! against, a clock standing at it and plain articles.
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::clock::ManualClock;
+
+/// The instant fixtures are dated against
+pub fn fixed_now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 3, 14, 15, 0, 0).unwrap()
+}
+
+/// A clock standing at [`fixed_now`] until moved
+pub fn manual_clock() -> ManualClock {
+    ManualClock::new(fixed_now())
+}
+
+/// Unread CNBC articles `u0`, `u1`, ..., newest first a minute apart
+#[cfg(feature = "tui")]
+pub fn articles(count: i64) -> Vec<crate::models::NewsItem> {
+    (0..count)
+        .map(|i| crate::models::NewsItem {
+            id: format!("u{}", i),
+            source: "CNBC".to_string(),
+            source_id: "cnbc".to_string(),
+            title: format!("Story {}", i),
+            url: format!("https://example.com/{}", i),
+            published: fixed_now() - chrono::Duration::minutes(i),
+            updated_at: fixed_now(),
+            ..Default::default()
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+use anyhow::{anyhow, Result};
+use ratatui::layout::Constraint;
+
+use crate::config::{ColumnConfig, ColumnKind};
+
+/// A compiled news list column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub kind: ColumnKind,
+    pub width: Constraint,
+}
+
+impl Column {
+    fn new(kind: ColumnKind) -> Self {
+        let width = match kind {
+            ColumnKind::Time => Constraint::Length(7),
+            ColumnKind::Source => Constraint::Length(18),
+            ColumnKind::Tickers => Constraint::Length(12),
+            ColumnKind::Score => Constraint::Length(5),
+            ColumnKind::Comments => Constraint::Length(5),
+            // The title takes whatever the other columns leave
+            ColumnKind::Title => Constraint::Fill(1),
+        };
+        Self { kind, width }
+    }
+
+    /// Time, title, source: the layout without a `[list]` section
+    pub fn defaults() -> Vec<Column> {
+        [ColumnKind::Time, ColumnKind::Title, ColumnKind::Source]
+            .into_iter()
+            .map(Column::new)
+            .collect()
+    }
+
+    pub fn from_config(cfg: &ColumnConfig) -> Result<Self> {
+        let mut column = Column::new(cfg.name);
+        match cfg.width {
+            Some(0) => {
+                return Err(anyhow!(
+                    "List column '{}': width must be at least 1",
+                    cfg.name.as_str()
+                ))
+            }
+            Some(width) => column.width = Constraint::Length(width),
+            None => {}
+        }
+        Ok(column)
+    }
+}
+
+/// Compile the configured columns, collecting errors for invalid and repeated
+/// ones. An empty list keeps the default layout.
+pub fn compile_columns(configs: &[ColumnConfig]) -> (Vec<Column>, Vec<String>) {
+    let mut columns: Vec<Column> = Vec::new();
+    let mut errors = Vec::new();
+    for cfg in configs {
+        if columns.iter().any(|c| c.kind == cfg.name) {
+            errors.push(format!(
+                "List column '{}' is listed more than once",
+                cfg.name.as_str()
+            ));
+            continue;
+        }
+        match Column::from_config(cfg) {
+            Ok(column) => columns.push(column),
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+    if columns.is_empty() {
+        columns = Column::defaults();
+    }
+    (columns, errors)
+}
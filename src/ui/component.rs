@@ -1,4 +1,7 @@
 use crate::models::FilterState;
+use crate::ui::components::FilterChip;
+use crate::ui::keymap::Command;
+use crate::ui::render_state::RenderState;
 use crate::ui::status_message::StatusMessage;
 use crossterm::event::Event;
 use ratatui::{layout::Rect, Frame};
@@ -8,10 +11,24 @@ use ratatui::{layout::Rect, Frame};
 pub enum Action {
     None,
     SelectionChanged(usize),
-    ArticleOpened(String), // URL
+    ViewChanged,                // The list switched views (archive, hide read, following)
+    ArticleOpened(String),      // URL
+    OpenAllRequested,           // Open every listed article in the browser
+    OpenSelected,               // Open the article shown in the detail pane
+    ArticleDeleted(String),     // Article id
+    ArchiveToggled(String),     // Article id; archives unread articles, restores read ones
+    StarToggled(String),        // Article id; stars it, or unstars it
+    TagToggled(String, String), // Article id and tag; adds the tag, or removes it
+    FollowToggled(String),      // Article id; follows its story, or unfollows it
+    PinToggled(String),         // Article id; pins it to compare against, or unpins it
     SearchQueryChanged(String),
     FilterApplied(FilterState),
+    FilterRemoved(FilterChip),
     RefreshRequested,
+    RefreshSourceRequested(String), // Source/adaptor name
+    ShareSelected(usize),           // Index of the share target
+    CommandEntered(String),         // A `:` command line, without the colon
+    Undo,
     Quit,
 
     // Status bar actions
@@ -24,11 +41,16 @@ pub trait Component {
     /// Handle input events and return an Action
     fn handle_event(&mut self, event: &Event) -> Action;
 
+    /// Handle a command bound to the key pressed; None if it doesn't apply here
+    fn handle_command(&mut self, _command: Command) -> Option<Action> {
+        None
+    }
+
     /// Update component state based on an Action from another component
     fn update(&mut self, action: &Action);
 
-    /// Render the component to the given area
-    fn render(&self, f: &mut Frame, area: Rect);
+    /// Render the component to the given area, reading app data from `state`
+    fn render(&self, f: &mut Frame, area: Rect, state: &RenderState);
 
     /// Check if this component currently has focus
     fn is_focused(&self) -> bool;
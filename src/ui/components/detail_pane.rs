@@ -1,113 +1,223 @@
+use crate::i18n::t;
 use crate::models::NewsItem;
+use crate::tr;
 use crate::ui::component::{Action, Component};
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crate::ui::diff::diff_spans;
+use crate::ui::keymap::Command;
+use crate::ui::render_state::RenderState;
+
+use super::news_list::wrap_text;
+use crossterm::event::Event;
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
+use std::cell::Cell;
 
+/// The selected article, read from the frame's `RenderState`
 pub struct DetailPaneComponent {
-    article: Option<NewsItem>,
+    /// Message id of the pane's title
+    title: &'static str,
     scroll_offset: u16,
     focused: bool,
+    /// Where the pane was last drawn, for routing the mouse
+    area: Cell<Rect>,
+}
+
+impl Default for DetailPaneComponent {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DetailPaneComponent {
     pub fn new() -> Self {
         Self {
-            article: None,
+            title: "title.detail",
             scroll_offset: 0,
             focused: false,
+            area: Cell::new(Rect::default()),
         }
     }
 
-    pub fn set_article(&mut self, article: NewsItem) {
-        self.article = Some(article);
-        self.scroll_offset = 0; // Reset scroll when new article is selected
+    /// Pane for the pinned article in a comparison, drawn from
+    /// [`RenderState::pinned_view`]
+    pub fn pinned() -> Self {
+        Self {
+            title: "title.pinned",
+            ..Self::new()
+        }
     }
-}
 
-impl Component for DetailPaneComponent {
-    fn handle_event(&mut self, event: &Event) -> Action {
-        if !self.focused {
-            return Action::None;
+    /// Lines the article body is scrolled down by
+    pub fn scroll_offset(&self) -> u16 {
+        self.scroll_offset
+    }
+
+    /// Scroll back to the top, e.g. when another article is selected
+    pub fn reset_scroll(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    /// Scroll the article body `lines` lines down or up
+    pub fn scroll(&mut self, down: bool, lines: u16) {
+        self.scroll_offset = if down {
+            self.scroll_offset.saturating_add(lines)
+        } else {
+            self.scroll_offset.saturating_sub(lines)
+        };
+    }
+
+    /// Whether `position` is on the pane as last drawn
+    pub fn contains(&self, position: Position) -> bool {
+        self.area.get().contains(position)
+    }
+
+    /// Title, source and time, tags and tickers; stays in place while the body scrolls
+    fn header_lines(article: &NewsItem, state: &RenderState) -> Vec<Line<'static>> {
+        let mut meta = vec![
+            article.source.clone(),
+            article.published.format("%Y-%m-%d %H:%M UTC").to_string(),
+        ];
+        if let Some(author) = &article.author {
+            meta.push(format!("by {}", author));
+        }
+        if let Some(language) = &article.language {
+            meta.push(language.clone());
         }
 
-        if let Event::Key(KeyEvent { code, .. }) = event {
-            match code {
-                KeyCode::Down | KeyCode::Char('j') => {
-                    self.scroll_offset = self.scroll_offset.saturating_add(1);
-                    Action::None
-                }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    self.scroll_offset = self.scroll_offset.saturating_sub(1);
-                    Action::None
-                }
-                KeyCode::PageDown => {
-                    self.scroll_offset = self.scroll_offset.saturating_add(10);
-                    Action::None
-                }
-                KeyCode::PageUp => {
-                    self.scroll_offset = self.scroll_offset.saturating_sub(10);
-                    Action::None
-                }
-                KeyCode::Char('o') | KeyCode::Enter => {
-                    if let Some(article) = &self.article {
-                        Action::ArticleOpened(article.url.clone())
+        let mut lines = vec![
+            Line::styled(
+                article.title.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::styled(meta.join(" · "), Style::default().fg(Color::Gray)),
+        ];
+        if !article.tags.is_empty() {
+            lines.push(Line::from(tr!(
+                "detail.tags",
+                tags = article.tags.join(", ")
+            )));
+        }
+        if !article.tickers.is_empty() {
+            lines.push(Self::quote_line(&article.tickers, state));
+        }
+        if article.starred {
+            lines.push(Line::styled(
+                tr!("detail.starred"),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        lines
+    }
+
+    /// "AAPL 189.23 ▲+1.20%" spans for each ticker, or a placeholder while quotes load
+    fn quote_line(tickers: &[String], state: &RenderState) -> Line<'static> {
+        let mut spans = vec![Span::raw(format!("{} ", t("detail.tickers")))];
+        for ticker in tickers {
+            spans.push(Span::styled(
+                format!("{} ", ticker),
+                Style::default().fg(Color::White),
+            ));
+            match state.article_quote(ticker) {
+                Some(q) => {
+                    let (arrow, color) = if q.change >= 0.0 {
+                        ("▲", Color::Green)
                     } else {
-                        Action::None
-                    }
+                        ("▼", Color::Red)
+                    };
+                    spans.push(Span::styled(
+                        format!(
+                            "{:.2} {}{:+.2} ({:+.2}%)",
+                            q.price, arrow, q.change, q.change_percent
+                        ),
+                        Style::default().fg(color),
+                    ));
                 }
-                _ => Action::None,
+                None => spans.push(Span::styled("…", Style::default().fg(Color::DarkGray))),
             }
-        } else {
-            Action::None
+            spans.push(Span::raw("  "));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Component for DetailPaneComponent {
+    fn handle_event(&mut self, _event: &Event) -> Action {
+        // Keys arrive as commands
+        Action::None
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        if !self.focused {
+            return None;
         }
+        match command {
+            Command::Down | Command::ScrollDown => self.scroll(true, 1),
+            Command::Up | Command::ScrollUp => self.scroll(false, 1),
+            Command::PageDown => self.scroll(true, 10),
+            Command::PageUp => self.scroll(false, 10),
+            Command::Open => return Some(Action::OpenSelected),
+            _ => return None,
+        }
+        Some(Action::None)
     }
 
-    fn update(&mut self, action: &Action) {
+    fn update(&mut self, _action: &Action) {
         // This is not great, but we defer handling of SelectionChanged
         // to the app, so that we don't need to get the actual article from NewsListComponent
         // For now, this is a placeholder
-        match action {
-            _ => {}
-        }
     }
 
-    fn render(&self, f: &mut Frame, area: Rect) {
-        let title = "Article Detail";
+    fn render(&self, f: &mut Frame, area: Rect, state: &RenderState) {
+        self.area.set(area);
+        let block = Block::default()
+            .title(t(self.title))
+            .borders(Borders::ALL)
+            .border_style(if self.focused {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            });
+        let inner = block.inner(area);
+        f.render_widget(block, area);
 
-        let content = if let Some(article) = &self.article {
-            format!(
-                "Title: {}\n\nSource: {}\nPublished: {}\n\nURL: {}\n\n{}\n\n---\n\nSummary:\n{}",
-                article.title,
-                article.source,
-                article.published.format("%Y-%m-%d %H:%M UTC"),
-                article.url,
-                "─".repeat(50),
-                article.summary
-            )
-        } else {
-            "No article selected\n\nSelect an article from the list to view details.".to_string()
+        let Some(article) = state.article else {
+            let placeholder = Paragraph::new(t("detail.none")).wrap(Wrap { trim: true });
+            f.render_widget(placeholder, inner);
+            return;
         };
 
-        let paragraph = Paragraph::new(content)
+        // The header keeps its height while the body scrolls, up to half the pane
+        let header = Self::header_lines(article, state);
+        let width = usize::from(inner.width.max(1));
+        let header_rows: usize = header
+            .iter()
+            .map(|line| {
+                let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+                wrap_text(&text, width, width).len()
+            })
+            .sum();
+        let header_height = (header_rows as u16 + 1).min(inner.height / 2).max(1);
+        let [header_area, body_area] =
+            Layout::vertical([Constraint::Length(header_height), Constraint::Min(0)]).areas(inner);
+
+        let header = Paragraph::new(header)
             .block(
                 Block::default()
-                    .title(title)
-                    .borders(Borders::ALL)
-                    .border_style(if self.focused {
-                        Style::default().fg(Color::Yellow)
-                    } else {
-                        Style::default()
-                    }),
+                    .borders(Borders::BOTTOM)
+                    .border_style(Style::default().fg(Color::DarkGray)),
             )
+            .wrap(Wrap { trim: true });
+        f.render_widget(header, header_area);
+
+        let body = Paragraph::new(body_lines(article))
             .wrap(Wrap { trim: true })
             .scroll((self.scroll_offset, 0));
-
-        f.render_widget(paragraph, area);
+        f.render_widget(body, body_area);
     }
 
     fn is_focused(&self) -> bool {
@@ -118,3 +228,47 @@ impl Component for DetailPaneComponent {
         self.focused = focused;
     }
 }
+
+/// Links, edits since the last fetch and the article text
+fn body_lines(article: &NewsItem) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(tr!("detail.url", url = article.url))];
+    if let Some(image_url) = &article.image_url {
+        lines.push(Line::from(tr!("detail.image", url = image_url)));
+    }
+    if !article.also_reported_by.is_empty() {
+        lines.push(Line::from(tr!(
+            "detail.also-reported",
+            sources = article.also_reported_by.join(", ")
+        )));
+    }
+    if let Some(previous) = &article.previous {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            tr!(
+                "detail.updated",
+                time = previous.revised_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+            Style::default().fg(Color::Yellow),
+        ));
+        if previous.title != article.title {
+            let mut spans = vec![Span::raw(format!("{} ", t("detail.earlier-title")))];
+            spans.extend(diff_spans(&previous.title, &article.title));
+            lines.push(Line::from(spans));
+        }
+        if previous.summary != article.summary {
+            let mut spans = vec![Span::raw(format!("{} ", t("detail.earlier-summary")))];
+            spans.extend(diff_spans(&previous.summary, &article.summary));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    // Prefer the full body when the source provides one
+    let (body_label, body) = match &article.content {
+        Some(content) => (t("detail.content"), content),
+        None => (t("detail.summary"), &article.summary),
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(body_label));
+    lines.extend(Text::from(body.clone()).lines);
+    lines
+}
@@ -0,0 +1,168 @@
+use crate::i18n::t;
+use crate::models::TimeRange;
+use crate::ui::component::{Action, Component};
+use crate::ui::components::ListView;
+use crate::ui::keymap::Command;
+use crate::ui::render_state::RenderState;
+use crossterm::event::Event;
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::cell::Cell;
+use unicode_width::UnicodeWidthStr;
+
+/// One active filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterChip {
+    Source(String),
+    Tag(String),
+    Time(TimeRange),
+    /// Read-state view other than "all"
+    ReadState(ListView),
+}
+
+impl FilterChip {
+    pub fn label(&self) -> String {
+        match self {
+            FilterChip::Source(name) => format!("source: {}", name),
+            FilterChip::Tag(tag) => format!("#{}", tag),
+            FilterChip::Time(range) => range.label().to_string(),
+            FilterChip::ReadState(ListView::Archive) => "read".to_string(),
+            FilterChip::ReadState(ListView::Following) => "following".to_string(),
+            FilterChip::ReadState(_) => "unread".to_string(),
+        }
+    }
+}
+
+/// Active filters shown as chips beside the search bar. When focused,
+/// ←/→ pick a chip and x/Delete removes it, leaving the others and the
+/// search query in place.
+pub struct FilterChipsComponent {
+    chips: Vec<FilterChip>,
+    selected_index: usize,
+    focused: bool,
+    /// Where the chips were last drawn, for routing mouse clicks
+    area: Cell<Rect>,
+}
+
+impl Default for FilterChipsComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FilterChipsComponent {
+    pub fn new() -> Self {
+        Self {
+            chips: Vec::new(),
+            selected_index: 0,
+            focused: false,
+            area: Cell::new(Rect::default()),
+        }
+    }
+
+    pub fn set_chips(&mut self, chips: Vec<FilterChip>) {
+        self.chips = chips;
+        if self.selected_index >= self.chips.len() {
+            self.selected_index = self.chips.len().saturating_sub(1);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chips.is_empty()
+    }
+
+    /// Whether `position` is on the chips as last drawn
+    pub fn contains(&self, position: Position) -> bool {
+        self.area.get().contains(position)
+    }
+
+    pub fn chips(&self) -> &[FilterChip] {
+        &self.chips
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    /// Columns needed to show every chip, borders included
+    pub fn width(&self) -> u16 {
+        let chips: usize = self.chips.iter().map(|chip| chip.label().width() + 3).sum();
+        chips as u16 + 2
+    }
+}
+
+impl Component for FilterChipsComponent {
+    fn handle_event(&mut self, _event: &Event) -> Action {
+        // Keys arrive as commands
+        Action::None
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        if !self.focused {
+            return None;
+        }
+        match command {
+            Command::Left => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+            }
+            Command::Right => {
+                let last = self.chips.len().saturating_sub(1);
+                self.selected_index = (self.selected_index + 1).min(last);
+            }
+            Command::RemoveFilter => {
+                let chip = self.chips.get(self.selected_index)?;
+                return Some(Action::FilterRemoved(chip.clone()));
+            }
+            _ => return None,
+        }
+        Some(Action::None)
+    }
+
+    fn update(&mut self, _action: &Action) {}
+
+    fn render(&self, f: &mut Frame, area: Rect, _state: &RenderState) {
+        self.area.set(area);
+        let mut spans = Vec::new();
+        for (i, chip) in self.chips.iter().enumerate() {
+            let style = if self.focused && i == self.selected_index {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            };
+            spans.push(Span::styled(format!(" {} ✕", chip.label()), style));
+            spans.push(Span::raw(" "));
+        }
+
+        let paragraph = Paragraph::new(Line::from(spans)).block(
+            Block::default()
+                .title(if self.focused {
+                    t("title.filters-focused")
+                } else {
+                    t("title.filters")
+                })
+                .borders(Borders::ALL)
+                .border_style(if self.focused {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                }),
+        );
+        f.render_widget(paragraph, area);
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
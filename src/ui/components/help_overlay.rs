@@ -0,0 +1,217 @@
+use crate::i18n::t;
+use crate::tr;
+use crate::ui::component::{Action, Component};
+use crate::ui::keymap::{help_sections, Command, KeyBindings};
+use crate::ui::render_state::RenderState;
+use crossterm::event::Event;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::cell::Cell;
+
+/// Widest the overlay gets, in columns
+const MAX_WIDTH: u16 = 70;
+
+/// Lines PgUp/PgDn move
+const PAGE_LINES: usize = 10;
+
+/// Modal cheat sheet of every key binding, grouped by pane. The rows are
+/// taken from the bindings when it opens, so rebound keys show as bound.
+pub struct HelpOverlayComponent {
+    /// Heading message id and (keys, message id) rows of each pane
+    sections: Vec<(&'static str, Vec<(String, &'static str)>)>,
+    scroll: usize,
+    visible: bool,
+    /// Rows the last render had room for, to stop scrolling at the end
+    shown_height: Cell<u16>,
+}
+
+impl Default for HelpOverlayComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HelpOverlayComponent {
+    pub fn new() -> Self {
+        Self {
+            sections: Vec::new(),
+            scroll: 0,
+            visible: false,
+            shown_height: Cell::new(0),
+        }
+    }
+
+    pub fn open(&mut self, bindings: &KeyBindings) {
+        self.sections = help_sections(bindings);
+        self.scroll = 0;
+        self.visible = true;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// The cheat sheet as plain text: a heading per pane, then one
+    /// "keys: description" line per binding
+    pub fn text_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (title, rows) in &self.sections {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("{}:", t(title)));
+            lines.extend(
+                rows.iter()
+                    .map(|(keys, message)| format!("  {}: {}", keys, t(message))),
+            );
+        }
+        lines
+    }
+
+    pub fn scroll_offset(&self) -> u16 {
+        self.scroll as u16
+    }
+
+    pub fn scroll(&mut self, down: bool, lines: usize) {
+        self.scroll = if down {
+            (self.scroll + lines).min(self.max_scroll())
+        } else {
+            self.scroll.saturating_sub(lines)
+        };
+    }
+
+    fn line_count(&self) -> usize {
+        // A blank line separates the sections
+        self.sections
+            .iter()
+            .map(|(_, rows)| rows.len() + 2)
+            .sum::<usize>()
+            .saturating_sub(1)
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.line_count()
+            .saturating_sub(self.shown_height.get() as usize)
+    }
+}
+
+impl Component for HelpOverlayComponent {
+    fn handle_event(&mut self, _event: &Event) -> Action {
+        // Keys arrive as commands
+        Action::None
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        if !self.visible {
+            return None;
+        }
+        match command {
+            Command::Dismiss | Command::Quit | Command::Help => self.close(),
+            Command::Down | Command::ScrollDown => self.scroll(true, 1),
+            Command::Up | Command::ScrollUp => self.scroll(false, 1),
+            Command::PageDown => self.scroll(true, PAGE_LINES),
+            Command::PageUp => self.scroll(false, PAGE_LINES),
+            _ => return None,
+        }
+        Some(Action::None)
+    }
+
+    fn update(&mut self, _action: &Action) {}
+
+    fn render(&self, f: &mut Frame, area: Rect, _state: &RenderState) {
+        if !self.visible {
+            return;
+        }
+
+        // Centered, as tall as the rows need within the terminal
+        let width = MAX_WIDTH.min(area.width);
+        let height = (self.line_count() as u16 + 2).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        self.shown_height.set(height.saturating_sub(2));
+
+        // Keys are padded to the widest in their section so descriptions line up
+        let mut lines: Vec<Line> = Vec::new();
+        for (title, rows) in &self.sections {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(
+                t(title).to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            let key_width = rows
+                .iter()
+                .map(|(keys, _)| keys.chars().count())
+                .max()
+                .unwrap_or(0);
+            lines.extend(rows.iter().map(|(keys, message)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("  {:<width$}", keys, width = key_width),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(format!("  {}", t(message))),
+                ])
+            }));
+        }
+
+        let scroll = self.scroll.min(self.max_scroll()) as u16;
+        let paragraph = Paragraph::new(lines).scroll((scroll, 0)).block(
+            Block::default()
+                .title(tr!("title.help"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(paragraph, popup);
+    }
+
+    fn is_focused(&self) -> bool {
+        self.visible
+    }
+
+    fn set_focus(&mut self, _focused: bool) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::keymap::compile_bindings;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn lists_rebound_keys_as_bound() {
+        let mut keys = BTreeMap::new();
+        keys.insert("archive".to_string(), vec!["a".to_string()]);
+        let (bindings, errors) = compile_bindings(&keys);
+        assert!(errors.is_empty());
+
+        let mut help = HelpOverlayComponent::new();
+        help.open(&bindings);
+        let lines = help.text_lines();
+        assert!(lines.contains(&"  a: Archive".to_string()));
+        assert!(lines.contains(&"  d: Delete".to_string()));
+        assert!(lines.contains(&"  ?: Help".to_string()));
+
+        assert!(matches!(
+            help.handle_command(Command::Help),
+            Some(Action::None)
+        ));
+        assert!(!help.is_visible());
+        assert!(help.handle_command(Command::Help).is_none());
+    }
+}
@@ -1,9 +1,17 @@
 pub mod news_list;
 pub mod detail_pane;
+pub mod filter_chips;
+pub mod help_overlay;
 pub mod search_bar;
+pub mod share_menu;
 pub mod status_bar;
+pub mod ticker_tape;
 
-pub use news_list::NewsListComponent;
+pub use news_list::{ListView, NewsListComponent};
 pub use detail_pane::DetailPaneComponent;
+pub use filter_chips::{FilterChip, FilterChipsComponent};
+pub use help_overlay::HelpOverlayComponent;
 pub use search_bar::SearchBarComponent;
+pub use share_menu::ShareMenuComponent;
 pub use status_bar::StatusBarComponent;
+pub use ticker_tape::TickerTapeComponent;
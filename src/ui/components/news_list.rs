@@ -1,20 +1,77 @@
-use crate::models::NewsItem;
+use crate::clock::{self, SharedClock};
+use crate::config::{ColumnKind, ListDensity, TimeFormat};
+use crate::i18n::t;
+use crate::models::{ticker_filter, AlertKeywords, FilterState, FollowedStory, NewsItem, Source};
+use crate::scripting::ScriptEngine;
+use crate::tr;
+use crate::ui::columns::Column;
 use crate::ui::component::{Action, Component};
-use chrono::Local;
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crate::ui::highlight::{highlight_spans, HighlightRule};
+use crate::ui::keymap::Command;
+use crate::ui::render_state::RenderState;
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::Event;
 use ratatui::{
-    layout::Rect,
+    layout::{Alignment, Constraint, Flex, Layout, Position, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, List, ListItem},
+    text::{Line, Span, Text},
+    widgets::{block::Title, Block, Borders, Cell, Row, Table},
     Frame,
 };
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Which articles the list shows, by read state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListView {
+    All,
+    /// Unread articles only (inbox zero mode)
+    Inbox,
+    /// Read articles only
+    Archive,
+    /// Coverage of followed stories
+    Following,
+}
 
 pub struct NewsListComponent {
     all_news: Vec<NewsItem>,      // Unfiltered news
     filtered_news: Vec<NewsItem>, // Filtered based on search query
     search_query: String,
+    /// Structured filters applied on top of the search query
+    filter: FilterState,
     selected_index: usize,
     focused: bool,
+    highlight_rules: Vec<HighlightRule>,
+    /// Articles mentioning one of these are badged
+    alerts: AlertKeywords,
+    scripts: Option<ScriptEngine>,
+    /// Source name colours, keyed by source id
+    source_colors: HashMap<String, Color>,
+    /// Source reliability scores, keyed by source id
+    source_reliability: HashMap<String, f64>,
+    /// Scale script scores by the source's reliability
+    reliability_weighting: bool,
+    view: ListView,
+    followed: Vec<FollowedStory>,
+    /// Whether read articles leave the main view
+    inbox_zero: bool,
+    time_format: TimeFormat,
+    columns: Vec<Column>,
+    density: ListDensity,
+    /// Reads the time row ages are computed against
+    clock: SharedClock,
+    /// Reference time for row ages, advanced by the app once a minute so
+    /// every row is labelled against the same clock
+    now: DateTime<Local>,
+    /// Last time any source refreshed successfully
+    last_refresh: Option<DateTime<Utc>>,
+    /// Ages at which the freshness indicator turns yellow, then red
+    stale_after: chrono::Duration,
+    very_stale_after: chrono::Duration,
+    /// Where the list was last drawn, for routing the mouse
+    area: std::cell::Cell<Rect>,
+    /// Index and height of each row as last drawn, top down, for clicks
+    drawn_rows: std::cell::RefCell<Vec<(usize, u16)>>,
 }
 
 impl NewsListComponent {
@@ -24,38 +81,434 @@ impl NewsListComponent {
             all_news: news,
             filtered_news,
             search_query: String::new(),
+            filter: FilterState::default(),
             selected_index: 0,
             focused: true,
+            highlight_rules: Vec::new(),
+            alerts: AlertKeywords::default(),
+            scripts: None,
+            source_colors: HashMap::new(),
+            source_reliability: HashMap::new(),
+            reliability_weighting: false,
+            view: ListView::All,
+            followed: Vec::new(),
+            inbox_zero: false,
+            time_format: TimeFormat::Relative,
+            columns: Column::defaults(),
+            density: ListDensity::Compact,
+            clock: clock::system(),
+            now: clock::system().local_now(),
+            last_refresh: None,
+            stale_after: chrono::Duration::minutes(60),
+            very_stale_after: chrono::Duration::minutes(240),
+            area: std::cell::Cell::new(Rect::default()),
+            drawn_rows: std::cell::RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Set keyword rules used to style matching terms in headlines
+    pub fn set_highlight_rules(&mut self, rules: Vec<HighlightRule>) {
+        self.highlight_rules = rules;
+    }
+
+    pub fn set_alerts(&mut self, alerts: AlertKeywords) {
+        self.alerts = alerts;
+    }
+
+    /// Colour source names by each source's configured colour. Unknown
+    /// colours are ignored (config check reports them).
+    pub fn set_sources(&mut self, sources: &[Source]) {
+        self.source_colors = sources
+            .iter()
+            .filter_map(|s| {
+                let color = Color::from_str(s.color.as_deref()?).ok()?;
+                Some((s.id.clone(), color))
+            })
+            .collect();
+        self.source_reliability = sources
+            .iter()
+            .filter_map(|s| Some((s.id.clone(), s.stats.reliability()?)))
+            .collect();
+        if self.reliability_weighting {
+            self.apply_filter();
         }
     }
 
+    /// Rank articles from unreliable sources lower when scripts score them
+    pub fn set_reliability_weighting(&mut self, enabled: bool) {
+        if self.reliability_weighting != enabled {
+            self.reliability_weighting = enabled;
+            self.apply_filter();
+        }
+    }
+
+    /// Script score, with positive scores scaled by the source's reliability
+    /// when weighting is on
+    fn weighted_score(&self, scripts: &ScriptEngine, item: &NewsItem) -> f64 {
+        let score = scripts.score(item);
+        match self.source_reliability.get(&item.source_id) {
+            Some(reliability) if self.reliability_weighting && score > 0.0 => score * reliability,
+            _ => score,
+        }
+    }
+
+    /// Hide read articles from the main view. The archive and following
+    /// views stay open if showing.
+    pub fn set_inbox_zero(&mut self, enabled: bool) {
+        self.inbox_zero = enabled;
+        if matches!(self.view, ListView::All | ListView::Inbox) {
+            self.view = self.main_view();
+        }
+        self.apply_filter();
+    }
+
+    pub fn set_time_format(&mut self, format: TimeFormat) {
+        self.time_format = format;
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<Column>) {
+        self.columns = columns;
+    }
+
+    pub fn density(&self) -> ListDensity {
+        self.density
+    }
+
+    pub fn set_density(&mut self, density: ListDensity) {
+        self.density = density;
+    }
+
+    /// Set the clock row ages are computed against
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.now = clock.local_now();
+        self.clock = clock;
+    }
+
+    /// Move row ages on to the clock's current time
+    pub fn advance_now(&mut self) {
+        self.now = self.clock.local_now();
+    }
+
+    pub fn set_last_refresh(&mut self, at: Option<DateTime<Utc>>) {
+        self.last_refresh = at;
+    }
+
+    /// Set the ages at which the freshness indicator turns yellow and red
+    pub fn set_staleness(
+        &mut self,
+        stale_after: chrono::Duration,
+        very_stale_after: chrono::Duration,
+    ) {
+        self.stale_after = stale_after;
+        self.very_stale_after = very_stale_after.max(stale_after);
+    }
+
+    /// Header spans with the age of the newest article and of the last
+    /// refresh, coloured by how stale each is
+    fn freshness(&self) -> Vec<Span<'static>> {
+        let newest = self.all_news.iter().map(|n| n.published).max();
+        let mut spans = Vec::new();
+        for (label, at) in [("newest", newest), ("refreshed", self.last_refresh)] {
+            let Some(at) = at else {
+                continue;
+            };
+            let age = self.now.signed_duration_since(at);
+            let color = if age >= self.very_stale_after {
+                Color::Red
+            } else if age >= self.stale_after {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            };
+            if !spans.is_empty() {
+                spans.push(Span::raw(" · "));
+            }
+            spans.push(Span::styled(
+                format!(
+                    "{} {}",
+                    label,
+                    time_label(at, self.now, TimeFormat::Relative)
+                ),
+                Style::default().fg(color),
+            ));
+        }
+        spans
+    }
+
+    pub fn view(&self) -> ListView {
+        self.view
+    }
+
+    pub fn set_view(&mut self, view: ListView) {
+        self.view = view;
+        self.selected_index = 0;
+        self.apply_filter();
+    }
+
+    fn main_view(&self) -> ListView {
+        if self.inbox_zero {
+            ListView::Inbox
+        } else {
+            ListView::All
+        }
+    }
+
+    pub fn set_followed(&mut self, stories: Vec<FollowedStory>) {
+        self.followed = stories;
+        if self.view == ListView::Following {
+            self.apply_filter();
+        }
+    }
+
+    /// The followed story `item` belongs to, if any
+    pub fn followed_story(&self, item: &NewsItem) -> Option<&FollowedStory> {
+        self.followed.iter().find(|story| story.matches(item))
+    }
+
+    /// Switch between the main view and coverage of followed stories
+    pub fn toggle_following_view(&mut self) {
+        let view = if self.view == ListView::Following {
+            self.main_view()
+        } else {
+            ListView::Following
+        };
+        self.set_view(view);
+    }
+
+    /// Hide read articles from the main view, or show them again
+    pub fn toggle_hide_read(&mut self) {
+        self.set_inbox_zero(!self.inbox_zero);
+    }
+
+    /// Switch between the main view and the archive of read articles
+    pub fn toggle_archive_view(&mut self) {
+        let view = if self.view == ListView::Archive {
+            self.main_view()
+        } else {
+            ListView::Archive
+        };
+        self.set_view(view);
+    }
+
+    /// The article with `id`, whether or not the list shows it
+    pub fn item(&self, id: &str) -> Option<&NewsItem> {
+        self.all_news.iter().find(|item| item.id == id)
+    }
+
+    /// Set an article's read state, returning the previous one. In the inbox
+    /// and archive views the article moves out of the list and the cursor
+    /// stays on the row that takes its place.
+    pub fn set_read(&mut self, id: &str, read: bool) -> Option<bool> {
+        let item = self.all_news.iter_mut().find(|item| item.id == id)?;
+        let was_read = std::mem::replace(&mut item.read, read);
+        self.apply_filter();
+        if self.selected_index >= self.filtered_news.len() {
+            self.selected_index = self.filtered_news.len().saturating_sub(1);
+        }
+        Some(was_read)
+    }
+
+    /// Star or unstar an article. Returns whether it was starred before.
+    pub fn set_starred(&mut self, id: &str, starred: bool) -> Option<bool> {
+        let item = self.all_news.iter_mut().find(|item| item.id == id)?;
+        let was_starred = std::mem::replace(&mut item.starred, starred);
+        self.apply_filter();
+        Some(was_starred)
+    }
+
+    /// Replace an article's tags, which may move it out of a tag filter
+    pub fn set_tags(&mut self, id: &str, tags: Vec<String>) {
+        let Some(item) = self.all_news.iter_mut().find(|item| item.id == id) else {
+            return;
+        };
+        item.tags = tags;
+        self.apply_filter();
+        if self.selected_index >= self.filtered_news.len() {
+            self.selected_index = self.filtered_news.len().saturating_sub(1);
+        }
+    }
+
+    /// Use Lua scripts for filtering, ordering and row formatting
+    pub fn set_scripts(&mut self, scripts: Option<ScriptEngine>) {
+        self.scripts = scripts;
+        self.apply_filter();
+    }
+
+    /// Reload scripts from disk and reapply them. Returns the number of
+    /// scripts loaded and any load errors, or None if scripting is disabled.
+    pub fn reload_scripts(&mut self) -> Option<(usize, Vec<String>)> {
+        let scripts = self.scripts.as_mut()?;
+        let errors = scripts.reload();
+        let loaded = scripts.len();
+        self.apply_filter();
+        Some((loaded, errors))
+    }
+
+    /// Script runtime errors since the last call
+    pub fn take_script_errors(&self) -> Vec<String> {
+        self.scripts
+            .as_ref()
+            .map(|s| s.take_errors())
+            .unwrap_or_default()
+    }
+
     pub fn set_news(&mut self, news: Vec<NewsItem>) {
         self.all_news = news;
         self.apply_filter();
         self.selected_index = 0;
     }
 
+    /// Replace the articles, keeping the selected one selected while it is
+    /// still listed. Returns how many of them weren't there before.
+    pub fn replace_news(&mut self, news: Vec<NewsItem>) -> usize {
+        let selected = self.selected_item().map(|item| item.id.clone());
+        let added = news
+            .iter()
+            .filter(|item| !self.all_news.iter().any(|old| old.id == item.id))
+            .count();
+        self.all_news = news;
+        self.apply_filter();
+        self.selected_index = selected
+            .and_then(|id| self.filtered_news.iter().position(|item| item.id == id))
+            .unwrap_or(0);
+        added
+    }
+
+    /// Articles the list currently shows, in display order
+    pub fn filtered_items(&self) -> &[NewsItem] {
+        &self.filtered_news
+    }
+
     pub fn selected_item(&self) -> Option<&NewsItem> {
         self.filtered_news.get(self.selected_index)
     }
 
+    /// Position of the selection among the listed articles
+    pub fn selected_position(&self) -> Option<usize> {
+        (self.selected_index < self.filtered_news.len()).then_some(self.selected_index)
+    }
+
+    /// An article's row in words rather than columns and glyphs, e.g.
+    /// "Apple beats on earnings. Reuters, 5m ago, unread, starred"
+    pub fn describe(&self, n: &NewsItem) -> String {
+        let mut parts = vec![
+            n.source.clone(),
+            time_label(n.published, self.now, self.time_format),
+        ];
+        if !n.read {
+            parts.push(tr!("focus.unread"));
+        }
+        if n.starred {
+            parts.push(tr!("focus.starred"));
+        }
+        if n.previous.is_some() {
+            parts.push(tr!("focus.updated"));
+        }
+        if let Some(keyword) = self.alerts.matching(n) {
+            parts.push(tr!("focus.alert", keyword = keyword));
+        }
+        if !n.also_reported_by.is_empty() {
+            parts.push(tr!(
+                "focus.also-reported",
+                sources = n.also_reported_by.join(", ")
+            ));
+        }
+        format!("{}. {}", n.title.trim_end_matches('.'), parts.join(", "))
+    }
+
+    /// Remove an article, returning it with its position in the unfiltered list
+    pub fn remove(&mut self, id: &str) -> Option<(NewsItem, usize)> {
+        let index = self.all_news.iter().position(|item| item.id == id)?;
+        let item = self.all_news.remove(index);
+        self.apply_filter();
+        // Keep the cursor on the row after the removed one, or the new last row
+        if self.selected_index >= self.filtered_news.len() {
+            self.selected_index = self.filtered_news.len().saturating_sub(1);
+        }
+        Some((item, index))
+    }
+
+    /// Put a removed article back at its old position and select it
+    pub fn restore(&mut self, item: NewsItem, index: usize) {
+        let id = item.id.clone();
+        self.all_news.insert(index.min(self.all_news.len()), item);
+        self.apply_filter();
+        if let Some(pos) = self.filtered_news.iter().position(|n| n.id == id) {
+            self.selected_index = pos;
+        }
+    }
+
     fn apply_filter(&mut self) {
+        let view = self.view;
+        let filter = &self.filter;
+        let followed = &self.followed;
+        let now = self.now;
+        let in_view = |item: &NewsItem| {
+            let read_ok = match view {
+                ListView::All => true,
+                ListView::Inbox => !item.read,
+                ListView::Archive => item.read,
+                ListView::Following => followed.iter().any(|story| story.matches(item)),
+            };
+            read_ok && filter.matches(item, now)
+        };
         if self.search_query.is_empty() {
-            self.filtered_news = self.all_news.clone();
+            self.filtered_news = self
+                .all_news
+                .iter()
+                .filter(|i| in_view(i))
+                .cloned()
+                .collect();
         } else {
-            let query_lower = self.search_query.to_lowercase();
+            // `$AAPL` words keep articles tagged with that ticker; the rest
+            // of the query is matched as typed
+            let tickers: Vec<String> = self
+                .search_query
+                .split_whitespace()
+                .filter_map(ticker_filter)
+                .collect();
+            let query_lower = if tickers.is_empty() {
+                self.search_query.to_lowercase()
+            } else {
+                self.search_query
+                    .split_whitespace()
+                    .filter(|word| ticker_filter(word).is_none())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_lowercase()
+            };
             self.filtered_news = self
                 .all_news
                 .iter()
+                .filter(|item| in_view(item))
+                .filter(|item| {
+                    tickers.iter().all(|ticker| {
+                        item.tickers.iter().any(|t| t.eq_ignore_ascii_case(ticker))
+                    })
+                })
                 .filter(|item| {
                     item.title.to_lowercase().contains(&query_lower)
                         || item.summary.to_lowercase().contains(&query_lower)
                         || item.source.to_lowercase().contains(&query_lower)
+                        || item.source_id.contains(&query_lower)
                 })
                 .cloned()
                 .collect();
         }
 
+        if let Some(scripts) = &self.scripts {
+            self.filtered_news.retain(|item| scripts.filter(item));
+            if scripts.has_scores() {
+                // Stable sort keeps newest-first order among equal scores
+                let mut scored: Vec<(f64, NewsItem)> = std::mem::take(&mut self.filtered_news)
+                    .into_iter()
+                    .map(|item| (self.weighted_score(scripts, &item), item))
+                    .collect();
+                scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+                self.filtered_news = scored.into_iter().map(|(_, item)| item).collect();
+            }
+        }
+
         // Reset selection if out of bounds
         if self.selected_index >= self.filtered_news.len() {
             self.selected_index = 0;
@@ -63,129 +516,399 @@ impl NewsListComponent {
     }
 
     /// Calculate the scroll offset to keep the selected item visible
-    /// Uses a centered approach: keeps selected item in the middle when possible
-    fn calculate_scroll_offset(&self, visible_height: usize) -> usize {
-        if self.filtered_news.len() <= visible_height {
-            return 0; // No scrolling needed
+    /// Uses a centered approach: keeps selected item in the middle when possible.
+    /// Rows can span several lines, so space is counted in lines.
+    fn calculate_scroll_offset(
+        &self,
+        visible_height: usize,
+        row_height: impl Fn(&NewsItem) -> usize,
+    ) -> usize {
+        let Some(selected) = self.selected_item() else {
+            return 0;
+        };
+
+        // Rows above the selection get half the lines it leaves free
+        let mut above = visible_height.saturating_sub(row_height(selected)) / 2;
+        let mut offset = self.selected_index;
+        while offset > 0 {
+            let height = row_height(&self.filtered_news[offset - 1]);
+            if height > above {
+                break;
+            }
+            above -= height;
+            offset -= 1;
+        }
+
+        // Near the bottom, scroll back so the last row ends the list
+        let mut below = 0;
+        for item in &self.filtered_news[offset..] {
+            below += row_height(item);
+            if below > visible_height {
+                return offset;
+            }
+        }
+        while offset > 0 {
+            let height = row_height(&self.filtered_news[offset - 1]);
+            if below + height > visible_height {
+                break;
+            }
+            below += height;
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// Move the selection one article down or up, if there is one
+    pub fn move_selection(&mut self, down: bool) -> Action {
+        let last = self.filtered_news.len().saturating_sub(1);
+        match down {
+            true if self.selected_index < last => self.selected_index += 1,
+            false if self.selected_index > 0 => self.selected_index -= 1,
+            _ => return Action::None,
+        }
+        Action::SelectionChanged(self.selected_index)
+    }
+
+    /// Whether `position` is on the list as last drawn
+    pub fn contains(&self, position: Position) -> bool {
+        self.area.get().contains(position)
+    }
+
+    /// Select the article drawn at `position`, if any
+    pub fn click(&mut self, position: Position) -> Action {
+        let area = self.area.get();
+        if !area.contains(position) {
+            return Action::None;
+        }
+        // Rows start below the top border
+        let mut top = area.y + 1;
+        for &(index, height) in self.drawn_rows.borrow().iter() {
+            if position.y < top {
+                break;
+            }
+            if position.y < top + height {
+                self.selected_index = index;
+                return Action::SelectionChanged(index);
+            }
+            top += height;
+        }
+        Action::None
+    }
+
+    /// One column of an article's row
+    fn cell(&self, n: &NewsItem, kind: ColumnKind) -> Cell<'static> {
+        match kind {
+            ColumnKind::Time => Cell::from(
+                Line::from(time_label(n.published, self.now, self.time_format))
+                    .alignment(Alignment::Right),
+            ),
+            ColumnKind::Source => {
+                let style = self
+                    .source_colors
+                    .get(&n.source_id)
+                    .map_or(Style::default(), |c| Style::default().fg(*c));
+                Cell::from(Span::styled(n.source.clone(), style))
+            }
+            ColumnKind::Tickers => Cell::from(n.tickers.join(" ")),
+            ColumnKind::Score if n.score == 0.0 => Cell::from(""),
+            ColumnKind::Score => {
+                Cell::from(Line::from(format!("{:.0}", n.score)).alignment(Alignment::Right))
+            }
+            ColumnKind::Comments if n.comments == 0 => Cell::from(""),
+            ColumnKind::Comments => {
+                Cell::from(Line::from(n.comments.to_string()).alignment(Alignment::Right))
+            }
+            // Multi-line, see title_lines
+            ColumnKind::Title => Cell::from(""),
         }
+    }
 
-        let max_scroll = self.filtered_news.len().saturating_sub(visible_height);
+    /// Title column lines for the current density. Scripts rewrite the
+    /// title; the other columns stay as configured.
+    fn title_lines(&self, n: &NewsItem, width: usize) -> Vec<Line<'static>> {
+        let scripted = self
+            .scripts
+            .as_ref()
+            .and_then(|scripts| scripts.format_row(n, &n.title));
+        let mut prefix = Vec::new();
+        if scripted.is_none() {
+            if self.alerts.matching(n).is_some() {
+                prefix.push(Span::styled(
+                    "⚑ ",
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            if n.starred {
+                prefix.push(Span::raw("★ "));
+            }
+            if n.previous.is_some() {
+                prefix.push(Span::styled(
+                    "✎ updated ",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+        }
+        let text = scripted.as_deref().unwrap_or(&n.title);
 
-        if self.selected_index < visible_height / 2 {
-            0 // Near top, don't scroll
-        } else if self.selected_index + visible_height / 2 >= self.filtered_news.len() {
-            max_scroll // Near bottom, scroll to end
+        let mut lines: Vec<Line<'static>> = if self.density == ListDensity::Wrapped {
+            let prefix_width: usize = prefix.iter().map(Span::width).sum();
+            wrap_text(text, width, width.saturating_sub(prefix_width))
+                .iter()
+                .map(|line| Line::from(highlight_spans(line, &self.highlight_rules)))
+                .collect()
         } else {
-            self.selected_index - visible_height / 2 // Center selected item
+            vec![Line::from(highlight_spans(text, &self.highlight_rules))]
+        };
+        if let Some(first) = lines.first_mut() {
+            prefix.append(&mut first.spans);
+            first.spans = prefix;
+        }
+
+        if self.density == ListDensity::Comfortable {
+            let summary = n
+                .summary
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .unwrap_or_default();
+            lines.push(Line::styled(
+                summary.to_string(),
+                Style::default().add_modifier(Modifier::DIM),
+            ));
         }
+        lines
     }
 }
 
-impl Component for NewsListComponent {
-    fn handle_event(&mut self, event: &Event) -> Action {
-        if !self.focused {
-            return Action::None;
+/// Greedy word wrap to `width` columns, `first_width` on the first line.
+/// Words longer than a line are split.
+pub(super) fn wrap_text(text: &str, width: usize, first_width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    let limit = |lines: &Vec<String>| if lines.is_empty() { first_width } else { width }.max(1);
+    for word in text.split_whitespace() {
+        let word_width = Span::raw(word).width();
+        let space = usize::from(!line.is_empty());
+        if line_width + space + word_width <= limit(&lines) {
+            if space == 1 {
+                line.push(' ');
+            }
+            line.push_str(word);
+            line_width += space + word_width;
+            continue;
         }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        for c in word.chars() {
+            let char_width = Span::raw(c.to_string()).width();
+            if line_width + char_width > limit(&lines) && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            line.push(c);
+            line_width += char_width;
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
 
-        if let Event::Key(KeyEvent { code, .. }) = event {
-            match code {
-                KeyCode::Down => {
-                    if self.selected_index < self.filtered_news.len().saturating_sub(1) {
-                        self.selected_index += 1;
-                        return Action::SelectionChanged(self.selected_index);
-                    }
-                }
-                KeyCode::Up => {
-                    if self.selected_index > 0 {
-                        self.selected_index -= 1;
-                        return Action::SelectionChanged(self.selected_index);
-                    }
-                }
-                KeyCode::Enter | KeyCode::Char('o') => {
-                    if let Some(item) = self.selected_item() {
-                        return Action::ArticleOpened(item.url.clone());
-                    }
-                }
-                _ => {}
+/// Publish time as shown in the list. Items stamped slightly ahead of
+/// `now` (clock skew, or fetched since the last tick) count as just published.
+fn time_label(published: DateTime<Utc>, now: DateTime<Local>, format: TimeFormat) -> String {
+    match format {
+        TimeFormat::Relative => {
+            let age = now
+                .signed_duration_since(published)
+                .max(chrono::Duration::zero());
+            if age.num_hours() < 1 {
+                format!("{}m ago", age.num_minutes())
+            } else if age.num_hours() < 24 {
+                format!("{}h ago", age.num_hours())
+            } else {
+                format!("{}d ago", age.num_days())
+            }
+        }
+        TimeFormat::Absolute => {
+            let local = published.with_timezone(&Local);
+            if local.date_naive() == now.date_naive() {
+                local.format("%H:%M").to_string()
+            } else {
+                local.format("%b %d").to_string()
             }
         }
+    }
+}
 
+impl Component for NewsListComponent {
+    fn handle_event(&mut self, _event: &Event) -> Action {
+        // Keys arrive as commands
         Action::None
     }
 
+    /// Selection commands also navigate results while the search bar has
+    /// focus, so they apply unfocused too
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        let action = match command {
+            Command::Down => self.move_selection(true),
+            Command::Up => self.move_selection(false),
+            Command::Open => Action::ArticleOpened(self.selected_item()?.url.clone()),
+            _ if !self.focused => return None,
+            Command::Delete => Action::ArticleDeleted(self.selected_item()?.id.clone()),
+            Command::Archive => Action::ArchiveToggled(self.selected_item()?.id.clone()),
+            Command::Star => Action::StarToggled(self.selected_item()?.id.clone()),
+            Command::Follow => Action::FollowToggled(self.selected_item()?.id.clone()),
+            Command::Pin => Action::PinToggled(self.selected_item()?.id.clone()),
+            Command::ArchiveView => {
+                self.toggle_archive_view();
+                Action::ViewChanged
+            }
+            Command::HideRead => {
+                self.toggle_hide_read();
+                Action::ViewChanged
+            }
+            Command::FollowingView => {
+                self.toggle_following_view();
+                Action::ViewChanged
+            }
+            _ => return None,
+        };
+        Some(action)
+    }
+
     fn update(&mut self, action: &Action) {
         match action {
-            Action::SelectionChanged(index) => {
-                if *index < self.filtered_news.len() {
-                    self.selected_index = *index;
-                }
+            Action::SelectionChanged(index) if *index < self.filtered_news.len() => {
+                self.selected_index = *index;
             }
             Action::SearchQueryChanged(query) => {
                 self.search_query = query.clone();
                 self.apply_filter();
             }
+            Action::FilterApplied(filter) => {
+                self.filter = filter.clone();
+                self.apply_filter();
+            }
             _ => {}
         }
     }
 
-    fn render(&self, f: &mut Frame, area: Rect) {
-        let title = if self.search_query.is_empty() {
-            format!("News Feed ({} articles)", self.filtered_news.len())
+    fn render(&self, f: &mut Frame, area: Rect, _state: &RenderState) {
+        self.area.set(area);
+        let name = match self.view {
+            ListView::All => t("title.news"),
+            ListView::Inbox => t("title.inbox"),
+            ListView::Archive => t("title.archive"),
+            ListView::Following => t("title.following"),
+        };
+        let in_view = match self.view {
+            ListView::All => self.all_news.len(),
+            ListView::Inbox => self.all_news.iter().filter(|n| !n.read).count(),
+            ListView::Archive => self.all_news.iter().filter(|n| n.read).count(),
+            ListView::Following => self
+                .all_news
+                .iter()
+                .filter(|n| self.followed_story(n).is_some())
+                .count(),
+        };
+        let title = if self.filtered_news.len() == in_view {
+            tr!("title.list", view = name, count = self.filtered_news.len())
         } else {
-            format!(
-                "News Feed ({}/{} filtered)",
-                self.filtered_news.len(),
-                self.all_news.len()
+            tr!(
+                "title.list-filtered",
+                view = name,
+                shown = self.filtered_news.len(),
+                total = in_view
             )
         };
 
         // Calculate visible height (minus 2 for borders)
         let visible_height = area.height.saturating_sub(2) as usize;
-        let scroll_offset = self.calculate_scroll_offset(visible_height);
+        let widths: Vec<Constraint> = self.columns.iter().map(|c| c.width).collect();
+
+        // Wrapped titles need the width the table will give their column
+        let inner = Rect::new(0, 0, area.width.saturating_sub(2), 1);
+        let title_width = Layout::horizontal(widths.clone())
+            .flex(Flex::Start)
+            .spacing(1)
+            .split(inner)
+            .iter()
+            .zip(&self.columns)
+            .find(|(_, column)| column.kind == ColumnKind::Title)
+            .map_or(0, |(rect, _)| rect.width as usize);
+        let row_height = |n: &NewsItem| match self.density {
+            ListDensity::Compact => 1,
+            ListDensity::Comfortable => 2,
+            ListDensity::Wrapped => self.title_lines(n, title_width).len(),
+        };
+        let scroll_offset = self.calculate_scroll_offset(visible_height, row_height);
 
-        let items: Vec<ListItem> = self
+        let mut lines_left = visible_height;
+        let mut drawn_rows = self.drawn_rows.borrow_mut();
+        drawn_rows.clear();
+        let rows: Vec<Row> = self
             .filtered_news
             .iter()
             .enumerate()
             .skip(scroll_offset)
-            .take(visible_height)
-            .map(|(i, n)| {
-                // CR jyuan: updated_at is not a great fallback for published date
-                let time_diff = Local::now().signed_duration_since(n.published);
-                let time_str = if time_diff.num_hours() < 1 {
-                    format!("{}m ago", time_diff.num_minutes())
-                } else if time_diff.num_hours() < 24 {
-                    format!("{}h ago", time_diff.num_hours())
-                } else {
-                    format!("{}d ago", time_diff.num_days())
-                };
+            .map_while(|(i, n)| {
+                if lines_left == 0 {
+                    return None;
+                }
+                let title_lines = self.title_lines(n, title_width);
+                let height = title_lines.len();
+                lines_left = lines_left.saturating_sub(height);
+                drawn_rows.push((i, height as u16));
 
-                let content = format!("{:<8} {}  —  {}", time_str, n.title, n.source);
+                let mut title_lines = Some(title_lines);
+                let cells: Vec<Cell> = self
+                    .columns
+                    .iter()
+                    .map(|column| match column.kind {
+                        ColumnKind::Title => {
+                            Cell::from(Text::from(title_lines.take().unwrap_or_default()))
+                        }
+                        kind => self.cell(n, kind),
+                    })
+                    .collect();
 
-                if i == self.selected_index {
-                    ListItem::new(content).style(
-                        Style::default()
-                            .bg(Color::DarkGray)
-                            .add_modifier(Modifier::BOLD),
-                    )
+                // Unread articles stand out, read ones are dimmed
+                let mut style = if n.read {
+                    Style::default().fg(Color::DarkGray)
                 } else {
-                    ListItem::new(content)
+                    Style::default().add_modifier(Modifier::BOLD)
+                };
+                if i == self.selected_index {
+                    style = style.bg(Color::DarkGray).add_modifier(Modifier::BOLD);
                 }
+                Some(Row::new(cells).height(height as u16).style(style))
             })
             .collect();
 
-        let list = List::new(items).block(
-            Block::default()
-                .title(title)
-                .borders(Borders::ALL)
-                .border_style(if self.focused {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
-                }),
-        );
+        // Freshness on the right of the header, when both fit
+        let title_width = title.chars().count();
+        let mut block = Block::default().title(title);
+        let freshness = Line::from(self.freshness());
+        if !freshness.spans.is_empty() && title_width + freshness.width() + 4 <= area.width as usize
+        {
+            block = block.title(Title::from(freshness).alignment(Alignment::Right));
+        }
+        let table = Table::new(rows, widths).block(block.borders(Borders::ALL).border_style(
+            if self.focused {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            },
+        ));
 
-        f.render_widget(list, area);
+        f.render_widget(table, area);
     }
 
     fn is_focused(&self) -> bool {
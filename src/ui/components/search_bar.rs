@@ -1,16 +1,29 @@
+use crate::i18n::t;
 use crate::ui::component::{Action, Component};
+use crate::ui::render_state::RenderState;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
-    layout::Rect,
+    layout::{Position, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use std::cell::Cell;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 pub struct SearchBarComponent {
     query: String,
-    cursor_pos: usize,
+    cursor_pos: usize, // In grapheme clusters, not bytes
     focused: bool,
+    /// Where the bar was last drawn, for routing mouse clicks
+    area: Cell<Rect>,
+}
+
+impl Default for SearchBarComponent {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SearchBarComponent {
@@ -19,9 +32,15 @@ impl SearchBarComponent {
             query: String::new(),
             cursor_pos: 0,
             focused: false,
+            area: Cell::new(Rect::default()),
         }
     }
 
+    /// Whether `position` is on the bar as last drawn
+    pub fn contains(&self, position: Position) -> bool {
+        self.area.get().contains(position)
+    }
+
     pub fn query(&self) -> &str {
         &self.query
     }
@@ -29,6 +48,71 @@ impl SearchBarComponent {
     pub fn is_empty(&self) -> bool {
         self.query.is_empty()
     }
+
+    fn grapheme_count(&self) -> usize {
+        self.query.graphemes(true).count()
+    }
+
+    /// Byte offset of the grapheme at `index` (or the end of the query)
+    fn byte_offset(&self, index: usize) -> usize {
+        self.query
+            .grapheme_indices(true)
+            .nth(index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.query.len())
+    }
+
+    /// Insert text at the cursor. The cursor is recomputed from the new prefix
+    /// because combining marks can merge into the preceding grapheme.
+    fn insert_str(&mut self, text: &str) {
+        let at = self.byte_offset(self.cursor_pos);
+        self.query.insert_str(at, text);
+        self.cursor_pos = self.query[..at + text.len()].graphemes(true).count();
+    }
+
+    /// Remove graphemes in `[start, end)` and leave the cursor at `start`
+    fn delete_range(&mut self, start: usize, end: usize) {
+        let (from, to) = (self.byte_offset(start), self.byte_offset(end));
+        self.query.drain(from..to);
+        self.cursor_pos = start;
+    }
+
+    /// Insert pasted text as a single line, dropping control characters
+    pub fn paste(&mut self, text: &str) -> Action {
+        let cleaned: String = text
+            .chars()
+            .map(|c| if c == '\n' || c == '\t' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+        let cleaned = cleaned.trim_end_matches(' ');
+        if cleaned.is_empty() {
+            return Action::None;
+        }
+        self.insert_str(cleaned);
+        self.query_changed()
+    }
+
+    /// Open the bar as a command line, replacing any search
+    pub fn start_command(&mut self) {
+        self.query = ":".to_string();
+        self.cursor_pos = 1;
+        self.focused = true;
+    }
+
+    /// Whether the query is a `:` command rather than a search
+    pub fn is_command(&self) -> bool {
+        self.query.starts_with(':')
+    }
+
+    /// The search to apply after an edit; a command being typed shows
+    /// every article
+    fn query_changed(&self) -> Action {
+        if self.is_command() {
+            Action::SearchQueryChanged(String::new())
+        } else {
+            Action::SearchQueryChanged(self.query.clone())
+        }
+    }
 }
 
 impl Component for SearchBarComponent {
@@ -37,43 +121,55 @@ impl Component for SearchBarComponent {
             return Action::None;
         }
 
+        if let Event::Paste(text) = event {
+            return self.paste(text);
+        }
+
         if let Event::Key(KeyEvent {
             code, modifiers, ..
         }) = event
         {
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
             match code {
-                KeyCode::Char(c) if modifiers.is_empty() => {
+                // Shift is part of text entry (capitals, symbols)
+                KeyCode::Char(c) if (*modifiers - KeyModifiers::SHIFT).is_empty() => {
                     // Insert character at cursor position
-                    self.query.insert(self.cursor_pos, *c);
-                    self.cursor_pos += 1;
-                    return Action::SearchQueryChanged(self.query.clone());
+                    self.insert_str(c.encode_utf8(&mut [0; 4]));
+                    return self.query_changed();
                 }
-                KeyCode::Backspace => {
-                    if self.cursor_pos > 0 {
-                        self.cursor_pos -= 1;
-                        self.query.remove(self.cursor_pos);
-                        return Action::SearchQueryChanged(self.query.clone());
-                    }
+                KeyCode::Backspace if self.cursor_pos > 0 => {
+                    self.delete_range(self.cursor_pos - 1, self.cursor_pos);
+                    return self.query_changed();
                 }
-                KeyCode::Delete => {
-                    if self.cursor_pos < self.query.len() {
-                        self.query.remove(self.cursor_pos);
-                        return Action::SearchQueryChanged(self.query.clone());
-                    }
+                KeyCode::Delete if self.cursor_pos < self.grapheme_count() => {
+                    self.delete_range(self.cursor_pos, self.cursor_pos + 1);
+                    return self.query_changed();
                 }
                 KeyCode::Left => {
                     self.cursor_pos = self.cursor_pos.saturating_sub(1);
                 }
-                KeyCode::Right => {
-                    if self.cursor_pos < self.query.len() {
-                        self.cursor_pos += 1;
-                    }
+                KeyCode::Right if self.cursor_pos < self.grapheme_count() => {
+                    self.cursor_pos += 1;
                 }
+                // Ctrl+A / Ctrl+E: readline-style start / end of line
                 KeyCode::Home => {
                     self.cursor_pos = 0;
                 }
+                KeyCode::Char('a') if ctrl => {
+                    self.cursor_pos = 0;
+                }
                 KeyCode::End => {
-                    self.cursor_pos = self.query.len();
+                    self.cursor_pos = self.grapheme_count();
+                }
+                KeyCode::Char('e') if ctrl => {
+                    self.cursor_pos = self.grapheme_count();
+                }
+                KeyCode::Enter if self.is_command() => {
+                    let command = self.query[1..].trim().to_string();
+                    self.query.clear();
+                    self.cursor_pos = 0;
+                    self.focused = false;
+                    return Action::CommandEntered(command);
                 }
                 KeyCode::Esc => {
                     // Clear search AND exit search mode
@@ -83,15 +179,23 @@ impl Component for SearchBarComponent {
                     return Action::SearchQueryChanged(String::new());
                 }
                 // Ctrl+W: Delete word backwards (common in CLI)
-                KeyCode::Char('w') if modifiers.contains(KeyModifiers::CONTROL) => {
-                    if self.cursor_pos > 0 {
-                        let before_cursor = &self.query[..self.cursor_pos];
-                        let trimmed = before_cursor.trim_end();
-                        let last_space = trimmed.rfind(' ').map(|i| i + 1).unwrap_or(0);
-                        self.query.drain(last_space..self.cursor_pos);
-                        self.cursor_pos = last_space;
-                        return Action::SearchQueryChanged(self.query.clone());
-                    }
+                KeyCode::Char('w') if ctrl && self.cursor_pos > 0 => {
+                    let prefix = &self.query[..self.byte_offset(self.cursor_pos)];
+                    let trimmed = prefix.trim_end();
+                    let word_start = trimmed.rfind(' ').map(|i| i + 1).unwrap_or(0);
+                    let start = self.query[..word_start].graphemes(true).count();
+                    self.delete_range(start, self.cursor_pos);
+                    return self.query_changed();
+                }
+                // Ctrl+U: delete to start of line
+                KeyCode::Char('u') if ctrl && self.cursor_pos > 0 => {
+                    self.delete_range(0, self.cursor_pos);
+                    return self.query_changed();
+                }
+                // Ctrl+K: delete to end of line
+                KeyCode::Char('k') if ctrl && self.cursor_pos < self.grapheme_count() => {
+                    self.delete_range(self.cursor_pos, self.grapheme_count());
+                    return self.query_changed();
                 }
                 // everything else passes through to focused component
                 // KeyCode::Up | KeyCode::Down | KeyCode::Enter | KeyCode::Tab
@@ -109,18 +213,21 @@ impl Component for SearchBarComponent {
         // SearchBar doesn't react to other component actions
     }
 
-    fn render(&self, f: &mut Frame, area: Rect) {
-        let title = if self.focused {
-            "Search [Esc to exit | Ctrl+W: delete word]"
+    fn render(&self, f: &mut Frame, area: Rect, _state: &RenderState) {
+        self.area.set(area);
+        let title = if self.focused && self.is_command() {
+            t("title.command")
+        } else if self.focused {
+            t("title.search")
         } else {
             ""
         };
 
         let display_text = if self.query.is_empty() {
             if self.focused {
-                "Type to search articles..."
+                t("search.placeholder")
             } else {
-                "Press / to focus and search"
+                t("search.idle")
             }
         } else {
             &self.query
@@ -134,23 +241,32 @@ impl Component for SearchBarComponent {
             Style::default().fg(Color::Gray)
         };
 
-        let paragraph = Paragraph::new(display_text).style(style).block(
-            Block::default()
-                .title(title)
-                .borders(Borders::ALL)
-                .border_style(if self.focused {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
-                }),
-        );
+        // Terminal columns before the cursor; wide glyphs (CJK, emoji) take two
+        let cursor_col = self.query[..self.byte_offset(self.cursor_pos)].width() as u16;
+        let inner_width = area.width.saturating_sub(2);
+        // Scroll horizontally so the cursor stays inside the box
+        let scroll_x = cursor_col.saturating_sub(inner_width.saturating_sub(1));
+
+        let paragraph = Paragraph::new(display_text)
+            .style(style)
+            .scroll((0, scroll_x))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(if self.focused {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    }),
+            );
 
         f.render_widget(paragraph, area);
 
         // Set cursor position when focused
         if self.focused {
             // Position cursor inside the block (accounting for border and padding)
-            let cursor_x = area.x + 1 + self.cursor_pos as u16;
+            let cursor_x = area.x + 1 + cursor_col - scroll_x;
             let cursor_y = area.y + 1;
 
             // Only set cursor if it's within the visible area
@@ -0,0 +1,183 @@
+use crate::tr;
+use crate::ui::component::{Action, Component};
+use crate::ui::keymap::Command;
+use crate::ui::render_state::RenderState;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+/// Widest the menu gets, in columns
+const MAX_WIDTH: u16 = 60;
+
+/// Modal list of share targets for the selected article. Picking one emits
+/// [`Action::ShareSelected`] with the target's index and closes the menu.
+/// j/k/q navigate and close by default, so targets can't use them as keys.
+pub struct ShareMenuComponent {
+    /// Shortcut key and name of each target, in menu order
+    entries: Vec<(Option<char>, String)>,
+    /// Title of the article being shared
+    article: String,
+    selected_index: usize,
+    visible: bool,
+}
+
+impl Default for ShareMenuComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShareMenuComponent {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            article: String::new(),
+            selected_index: 0,
+            visible: false,
+        }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<(Option<char>, String)>) {
+        self.entries = entries;
+        self.selected_index = 0;
+    }
+
+    pub fn open(&mut self, article: &str) {
+        self.article = article.to_string();
+        self.selected_index = 0;
+        self.visible = true;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Title of the article being shared
+    pub fn article(&self) -> &str {
+        &self.article
+    }
+
+    /// Shortcut key and name of each target, in menu order
+    pub fn entries(&self) -> &[(Option<char>, String)] {
+        &self.entries
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn select(&mut self, index: usize) -> Action {
+        self.close();
+        Action::ShareSelected(index)
+    }
+}
+
+impl Component for ShareMenuComponent {
+    fn handle_event(&mut self, event: &Event) -> Action {
+        if !self.visible {
+            return Action::None;
+        }
+        let Event::Key(KeyEvent {
+            code, modifiers, ..
+        }) = event
+        else {
+            return Action::None;
+        };
+        if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return Action::None;
+        }
+
+        // Navigation arrives as commands; the rest are target keys
+        if let KeyCode::Char(c) = code {
+            if let Some(index) = self.entries.iter().position(|(key, _)| *key == Some(*c)) {
+                return self.select(index);
+            }
+        }
+        Action::None
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        if !self.visible {
+            return None;
+        }
+        match command {
+            Command::Dismiss | Command::Quit => self.close(),
+            Command::Down | Command::ScrollDown => {
+                let last = self.entries.len().saturating_sub(1);
+                self.selected_index = (self.selected_index + 1).min(last);
+            }
+            Command::Up | Command::ScrollUp => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+            }
+            Command::Select if !self.entries.is_empty() => {
+                return Some(self.select(self.selected_index));
+            }
+            _ => return None,
+        }
+        Some(Action::None)
+    }
+
+    fn update(&mut self, _action: &Action) {}
+
+    fn render(&self, f: &mut Frame, area: Rect, _state: &RenderState) {
+        if !self.visible {
+            return;
+        }
+
+        // Centered, sized to the entries plus borders
+        let width = MAX_WIDTH.min(area.width);
+        let height = (self.entries.len() as u16 + 2).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, (key, name))| {
+                let key = key.map_or("   ".to_string(), |k| format!("[{}]", k));
+                let line = Line::from(vec![
+                    Span::styled(key, Style::default().fg(Color::Yellow)),
+                    Span::raw(format!(" {}", name)),
+                ]);
+                if i == self.selected_index {
+                    ListItem::new(line).style(
+                        Style::default()
+                            .bg(Color::DarkGray)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    ListItem::new(line)
+                }
+            })
+            .collect();
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("title.share", article = self.article))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(list, popup);
+    }
+
+    fn is_focused(&self) -> bool {
+        self.visible
+    }
+
+    fn set_focus(&mut self, _focused: bool) {}
+}
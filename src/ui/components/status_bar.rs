@@ -1,86 +1,492 @@
+use crate::app::RefreshReport;
+use crate::clock::{self, SharedClock};
+use crate::i18n::t;
+use crate::market::{session_summary, Exchange};
+use crate::tr;
 use crate::ui::component::{Action, Component};
+use crate::ui::keymap::{footer_hints, Command, KeyBindings, KeyContext};
+use crate::ui::render_state::RenderState;
 use crate::ui::status_message::{MessageLevel, StatusMessage};
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossterm::event::Event;
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
+use std::cell::Cell;
 use std::collections::VecDeque;
+use std::time::Duration;
 
 const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// Spinner for screen readers, which spell out braille cells
+const ASCII_SPINNER_FRAMES: &[&str] = &["-", "\\", "|", "/"];
+/// Shortest time a message stays up before a newer one replaces it
+const MIN_DISPLAY: Duration = Duration::from_secs(2);
+/// Messages waiting for their turn; beyond this the oldest go straight to history
+const MAX_PENDING: usize = 10;
+/// Shortest expanded view: one row between the borders
+const MIN_EXPANDED_HEIGHT: u16 = 3;
+/// Widest a toast gets, in columns
+const MAX_TOAST_WIDTH: u16 = 60;
 
+/// Shows messages by severity: info and success as a transient toast over
+/// the content, warnings and progress on the status line, errors on the
+/// status line plus a persistent badge that opens the errors view, and
+/// keyword alerts on the status line whenever nothing else is, until dismissed.
 pub struct StatusBarComponent {
     current_message: Option<StatusMessage>,
+    /// Info/success message shown as a toast
+    toast: Option<StatusMessage>,
+    /// Keyword alert not yet dismissed
+    alert: Option<StatusMessage>,
+    /// Errors not yet cleared, oldest first
+    errors: Vec<StatusMessage>,
+    show_errors: bool,
+    errors_scroll: usize,
+    /// Messages that arrived while the current one was still fresh
+    pending: VecDeque<StatusMessage>,
     message_history: VecDeque<StatusMessage>,
+    /// Messages kept in the history
+    max_history: usize,
+    /// History entries not yet written to the status log, when persisting
+    unsaved: Option<Vec<StatusMessage>>,
     show_history: bool,
+    /// Height of the status bar with history, a report or the errors expanded
+    expanded_height: u16,
+    /// Height the expanded view was last drawn at, which the terminal may
+    /// have cut short of `expanded_height`; 0 until it is first drawn
+    shown_height: Cell<u16>,
+    /// Where the status bar was last drawn, for routing the mouse
+    area: Cell<Rect>,
+    /// Display time for info, success, warning and error messages; None
+    /// keeps them up until dismissed
+    dismiss_after: [Option<Duration>; 4],
+    /// Refresh report expanded from its summary message
+    open_report: Option<RefreshReport>,
+    report_scroll: usize,
     focused: bool,
     history_scroll_offset: usize,
     spinner_frame: usize,
+    ascii_spinner: bool,
+    exchanges: Vec<Exchange>,
+    hints: String,
+    /// Times messages for display and dismissal
+    clock: SharedClock,
+}
+
+impl Default for StatusBarComponent {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StatusBarComponent {
     pub fn new() -> Self {
         Self {
             current_message: None,
+            toast: None,
+            alert: None,
+            errors: Vec::new(),
+            show_errors: false,
+            errors_scroll: 0,
+            pending: VecDeque::new(),
             message_history: VecDeque::new(),
+            max_history: 50,
+            unsaved: None,
             show_history: false,
+            expanded_height: 15,
+            shown_height: Cell::new(0),
+            area: Cell::new(Rect::default()),
+            dismiss_after: [
+                Some(Duration::from_secs(5)),
+                Some(Duration::from_secs(3)),
+                Some(Duration::from_secs(5)),
+                Some(Duration::from_secs(10)),
+            ],
+            open_report: None,
+            report_scroll: 0,
             focused: false,
             history_scroll_offset: 0,
             spinner_frame: 0,
+            ascii_spinner: false,
+            exchanges: Vec::new(),
+            hints: footer_hints(KeyContext::NewsList, &KeyBindings::default()),
+            clock: clock::system(),
         }
     }
 
-    /// Set a new status message
-    pub fn set_message(&mut self, message: StatusMessage) {
-        // Add previous message to history
-        if let Some(old_msg) = self.current_message.take() {
-            self.message_history.push_back(old_msg);
-            // Keep last 50 messages
-            if self.message_history.len() > 50 {
-                self.message_history.pop_front();
+    /// Set the key hints shown when there is no status message
+    pub fn set_hints(&mut self, hints: String) {
+        self.hints = hints;
+    }
+
+    /// Set the clock message ages and the market segment are read from
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Set the exchanges whose session state is shown in the right segment
+    pub fn set_exchanges(&mut self, exchanges: Vec<Exchange>) {
+        self.exchanges = exchanges;
+    }
+
+    /// Set how many messages the history keeps, dropping the oldest beyond that
+    pub fn set_history_size(&mut self, size: usize) {
+        self.max_history = size.max(1);
+        while self.message_history.len() > self.max_history {
+            self.message_history.pop_front();
+        }
+    }
+
+    /// Set the height of the expanded history, report and errors views
+    pub fn set_expanded_height(&mut self, height: u16) {
+        self.expanded_height = height.max(MIN_EXPANDED_HEIGHT);
+    }
+
+    /// Grow or shrink the expanded view by a row (+ and -), no taller than
+    /// the terminal leaves it
+    pub fn resize_expanded(&mut self, grow: bool) {
+        let shown = self.shown_height.get();
+        // Drawn shorter than asked for means the terminal is the limit
+        let limit = match shown {
+            0 => u16::MAX,
+            shown => shown.max(MIN_EXPANDED_HEIGHT),
+        };
+        let height = self.expanded_height.min(limit);
+        self.expanded_height = if grow {
+            (height + 1).min(limit)
+        } else {
+            height.saturating_sub(1).max(MIN_EXPANDED_HEIGHT)
+        };
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.show_history || self.open_report.is_some() || self.show_errors
+    }
+
+    /// Rows of messages or report lines the expanded view has room for, at
+    /// the height it was last drawn
+    fn visible_rows(&self) -> usize {
+        let height = match self.shown_height.get() {
+            0 => self.expanded_height,
+            shown => shown,
+        };
+        usize::from(height.saturating_sub(2))
+    }
+
+    /// Furthest a view of `lines` lines scrolls
+    fn max_scroll(&self, lines: usize) -> usize {
+        lines.saturating_sub(self.visible_rows())
+    }
+
+    /// Set how long messages of `level` stay up; None keeps them until
+    /// dismissed. Progress and alerts always stay.
+    pub fn set_dismiss_after(&mut self, level: MessageLevel, after: Option<Duration>) {
+        if let Some(slot) = dismiss_slot(level) {
+            self.dismiss_after[slot] = after;
+        }
+    }
+
+    /// Keep history entries for [`take_unsaved_history`](Self::take_unsaved_history)
+    pub fn set_persist(&mut self, persist: bool) {
+        if persist != self.unsaved.is_some() {
+            self.unsaved = persist.then(Vec::new);
+        }
+    }
+
+    pub fn history_size(&self) -> usize {
+        self.max_history
+    }
+
+    /// History entries added since the last call, oldest first
+    pub fn take_unsaved_history(&mut self) -> Vec<StatusMessage> {
+        self.unsaved
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Put messages from an earlier session at the start of the history
+    pub fn restore_history(&mut self, messages: Vec<StatusMessage>) {
+        for message in messages.into_iter().rev() {
+            self.message_history.push_front(message);
+        }
+        self.set_history_size(self.max_history);
+    }
+
+    /// Set a new status message. A repeat of the current, queued or just
+    /// dismissed message bumps its counter instead. Messages arriving while
+    /// the current one is under [`MIN_DISPLAY`] old wait their turn, except
+    /// errors and progress, which show at once. Sticky messages keep no
+    /// display time.
+    pub fn set_message(&mut self, mut message: StatusMessage) {
+        if let Some(slot) = dismiss_slot(message.level).filter(|_| !message.sticky) {
+            message.auto_dismiss_after = self.dismiss_after[slot];
+        }
+        message.timestamp = self.clock.now();
+        match message.level {
+            MessageLevel::Info | MessageLevel::Success => {
+                self.set_toast(message);
+                return;
+            }
+            MessageLevel::Alert => {
+                self.set_alert(message);
+                return;
+            }
+            MessageLevel::Error => self.record_error(&message),
+            MessageLevel::Warning | MessageLevel::Loading => {}
+        }
+
+        if let Some(current) = &mut self.current_message {
+            if current.is_repeat_of(&message) {
+                current.count += 1;
+                current.timestamp = message.timestamp;
+                current.report = message.report;
+                return;
+            }
+        } else if let Some(last) = self.message_history.back() {
+            if last.is_repeat_of(&message) {
+                let mut last = self.message_history.pop_back().unwrap_or(message.clone());
+                last.count += 1;
+                last.timestamp = self.clock.now();
+                last.report = message.report;
+                self.current_message = Some(last);
+                return;
+            }
+        }
+        if let Some(queued) = self.pending.iter_mut().find(|m| m.is_repeat_of(&message)) {
+            queued.count += 1;
+            queued.report = message.report;
+            return;
+        }
+
+        let urgent = matches!(message.level, MessageLevel::Error | MessageLevel::Loading);
+        if !urgent && self.is_current_fresh() {
+            self.pending.push_back(message);
+            if self.pending.len() > MAX_PENDING {
+                if let Some(skipped) = self.pending.pop_front() {
+                    self.push_history(skipped);
+                }
+            }
+            return;
+        }
+        self.show(message);
+    }
+
+    fn set_toast(&mut self, message: StatusMessage) {
+        if let Some(toast) = &mut self.toast {
+            if toast.is_repeat_of(&message) {
+                toast.count += 1;
+                toast.timestamp = message.timestamp;
+                return;
+            }
+        }
+        if let Some(old) = self.toast.replace(message) {
+            self.push_history(old);
+        }
+    }
+
+    fn set_alert(&mut self, message: StatusMessage) {
+        if let Some(alert) = &mut self.alert {
+            if alert.is_repeat_of(&message) {
+                alert.count += 1;
+                alert.timestamp = message.timestamp;
+                return;
+            }
+        }
+        if let Some(old) = self.alert.replace(message) {
+            self.push_history(old);
+        }
+    }
+
+    pub fn has_alert(&self) -> bool {
+        self.alert.is_some()
+    }
+
+    fn clear_alert(&mut self) {
+        if let Some(msg) = self.alert.take() {
+            self.push_history(msg);
+        }
+    }
+
+    /// Keep an error for the badge until the errors are cleared
+    fn record_error(&mut self, message: &StatusMessage) {
+        match self.errors.iter_mut().find(|e| e.is_repeat_of(message)) {
+            Some(error) => {
+                error.count += 1;
+                error.timestamp = message.timestamp;
             }
+            None => self.errors.push(message.clone()),
+        }
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_showing_errors(&self) -> bool {
+        self.show_errors
+    }
+
+    /// Open or close the errors view
+    pub fn toggle_errors(&mut self) {
+        let was_showing = self.show_errors;
+        self.collapse();
+        self.show_errors = !was_showing && !self.errors.is_empty();
+    }
+
+    /// Forget all errors, closing the errors view
+    pub fn clear_errors(&mut self) {
+        self.errors.clear();
+        self.show_errors = false;
+        self.errors_scroll = 0;
+    }
+
+    /// Close whichever of history, report and errors is expanded
+    fn collapse(&mut self) {
+        self.show_history = false;
+        self.reset_scroll();
+        self.open_report = None;
+        self.show_errors = false;
+        self.errors_scroll = 0;
+    }
+
+    /// Whether the current message was put up too recently to be replaced
+    fn is_current_fresh(&self) -> bool {
+        self.current_message.as_ref().is_some_and(|m| {
+            m.level != MessageLevel::Loading && m.age(self.clock.now()) < MIN_DISPLAY
+        })
+    }
+
+    fn show(&mut self, message: StatusMessage) {
+        if let Some(old_msg) = self.current_message.take() {
+            self.push_history(old_msg);
         }
         self.current_message = Some(message);
     }
 
+    fn push_history(&mut self, message: StatusMessage) {
+        if let Some(unsaved) = &mut self.unsaved {
+            unsaved.push(message.clone());
+        }
+        self.message_history.push_back(message);
+        if self.message_history.len() > self.max_history {
+            self.message_history.pop_front();
+        }
+    }
+
+    /// Update the text of the current loading message in place, so progress
+    /// updates don't flood the history. Starts a loading message if none is shown.
+    pub fn update_loading(&mut self, text: String) {
+        match &mut self.current_message {
+            Some(msg) if msg.level == MessageLevel::Loading => msg.text = text,
+            _ => self.set_message(StatusMessage::loading(text)),
+        }
+    }
+
     pub fn clear_message(&mut self) {
         if let Some(msg) = self.current_message.take() {
-            self.message_history.push_back(msg);
+            self.push_history(msg);
+        }
+    }
+
+    fn clear_toast(&mut self) {
+        if let Some(msg) = self.toast.take() {
+            self.push_history(msg);
         }
     }
 
     /// Scroll history up (newer messages)
     pub fn scroll_history_up(&mut self) {
-        if self.history_scroll_offset > 0 {
-            self.history_scroll_offset -= 1;
-        }
+        let max_scroll = self.max_scroll(self.message_history.len());
+        self.history_scroll_offset = self.history_scroll_offset.min(max_scroll).saturating_sub(1);
     }
 
     /// Scroll history down (older messages)
     pub fn scroll_history_down(&mut self) {
-        let max_scroll = self.message_history.len().saturating_sub(15);
-        if self.history_scroll_offset < max_scroll {
-            self.history_scroll_offset += 1;
+        let max_scroll = self.max_scroll(self.message_history.len());
+        self.history_scroll_offset = (self.history_scroll_offset + 1).min(max_scroll);
+    }
+
+    /// Scroll whichever expanded view is up by a line, down or up
+    pub fn scroll_expanded(&mut self, down: bool) {
+        if self.show_history {
+            if down {
+                self.scroll_history_down();
+            } else {
+                self.scroll_history_up();
+            }
+        } else if self.show_errors {
+            let max_scroll = self.max_scroll(self.errors.len());
+            self.errors_scroll = if down {
+                (self.errors_scroll + 1).min(max_scroll)
+            } else {
+                self.errors_scroll.min(max_scroll).saturating_sub(1)
+            };
+        } else {
+            self.scroll_report(down);
         }
     }
 
+    /// Whether `position` is on the status bar as last drawn
+    pub fn contains(&self, position: Position) -> bool {
+        self.area.get().contains(position)
+    }
+
     /// Reset scroll when closing history
     fn reset_scroll(&mut self) {
         self.history_scroll_offset = 0;
     }
 
-    /// Get the expanded height when history is shown
+    /// Get the expanded height when history or a report is shown
     pub fn get_height(&self) -> u16 {
-        if self.show_history {
-            15 // Expanded height
+        if self.is_expanded() {
+            self.expanded_height
         } else {
             3 // Normal height
         }
     }
 
+    /// Whether there is a refresh report to expand or one is expanded
+    pub fn has_report(&self) -> bool {
+        self.open_report.is_some()
+            || self
+                .current_message
+                .as_ref()
+                .is_some_and(|m| m.report.is_some())
+    }
+
+    pub fn is_showing_report(&self) -> bool {
+        self.open_report.is_some()
+    }
+
+    /// Expand the current message's report. It stays open when newer
+    /// messages arrive.
+    fn open_report(&mut self) {
+        let report = self
+            .current_message
+            .as_ref()
+            .and_then(|m| m.report.as_deref());
+        if let Some(report) = report.cloned() {
+            self.collapse();
+            self.open_report = Some(report);
+        }
+    }
+
+    fn scroll_report(&mut self, down: bool) {
+        let Some(report) = &self.open_report else {
+            return;
+        };
+        let max_scroll = self.max_scroll(report_lines(report).len());
+        self.report_scroll = if down {
+            (self.report_scroll + 1).min(max_scroll)
+        } else {
+            self.report_scroll.min(max_scroll).saturating_sub(1)
+        };
+    }
+
     /// Check if history is currently visible
     pub fn is_showing_history(&self) -> bool {
         self.show_history
@@ -88,68 +494,253 @@ impl StatusBarComponent {
 
     /// Toggle the visibility of the message history
     pub fn toggle_history(&mut self) {
-        self.show_history = !self.show_history;
-        if !self.show_history {
-            self.reset_scroll();
+        let was_showing = self.show_history;
+        self.collapse();
+        self.show_history = !was_showing;
+    }
+
+    /// Spin with plain ASCII characters instead of braille
+    pub fn set_ascii_spinner(&mut self, ascii: bool) {
+        self.ascii_spinner = ascii;
+        self.spinner_frame = 0;
+    }
+
+    fn spinner_frames(&self) -> &'static [&'static str] {
+        if self.ascii_spinner {
+            ASCII_SPINNER_FRAMES
+        } else {
+            SPINNER_FRAMES
         }
     }
 
     pub fn tick_spinner(&mut self) {
-        self.spinner_frame = (self.spinner_frame + 1) % SPINNER_FRAMES.len();
+        self.spinner_frame = (self.spinner_frame + 1) % self.spinner_frames().len();
     }
 
-    /// Check and auto-dismiss expired messages
-    pub fn check_auto_dismiss(&mut self) {
+    /// Check and auto-dismiss expired messages, and put up the next queued
+    /// one once the current has had its time. Returns true if the message changed
+    pub fn check_auto_dismiss(&mut self) -> bool {
+        if !self.pending.is_empty() && !self.is_current_fresh() {
+            if let Some(mut next) = self.pending.pop_front() {
+                // Its display time starts now
+                next.timestamp = self.clock.now();
+                self.show(next);
+                return true;
+            }
+        }
+        let now = self.clock.now();
+        let mut changed = false;
+        if self.toast.as_ref().is_some_and(|t| t.should_dismiss(now)) {
+            self.clear_toast();
+            changed = true;
+        }
         if let Some(msg) = &self.current_message {
-            if msg.should_dismiss() {
+            if msg.should_dismiss(now) {
                 self.clear_message();
+                changed = true;
             }
         }
+        changed
     }
 
     fn get_current_display_text(&self) -> Option<(String, MessageLevel)> {
-        self.current_message.as_ref().map(|msg| {
+        let message = self.current_message.as_ref().or(self.alert.as_ref());
+        message.map(|msg| {
             let text = if msg.level == MessageLevel::Loading {
-                format!("{} {}", SPINNER_FRAMES[self.spinner_frame], msg.text)
+                format!("{} {}", self.spinner_frames()[self.spinner_frame], msg.text)
             } else {
-                msg.text.clone()
+                msg.display_text()
             };
             (text, msg.level)
         })
     }
 
+    /// The status line in words, for the linear layout: the message and
+    /// toast with their level named instead of a glyph, the error count and
+    /// market sessions, or the key hints when there's no message
+    pub fn plain_status(&self) -> String {
+        let mut parts: Vec<String> = self
+            .get_current_display_text()
+            .into_iter()
+            .chain(self.toast.as_ref().map(|t| (t.display_text(), t.level)))
+            .map(|(text, level)| format!("{}: {}", level_name(level), text))
+            .collect();
+        match self.errors.len() {
+            0 => {}
+            1 => parts.push("1 error (E)".to_string()),
+            n => parts.push(format!("{} errors (E)", n)),
+        }
+        if parts.is_empty() {
+            parts.push(self.hints.clone());
+        }
+        let market = session_summary(&self.exchanges, self.clock.now());
+        if !market.is_empty() {
+            parts.push(market);
+        }
+        parts.join(". ")
+    }
+
+    /// Heading and lines of the expanded history, errors or report view,
+    /// newest message first, for the linear layout
+    pub fn plain_expanded(&self) -> Option<(String, Vec<String>)> {
+        let line = |msg: &StatusMessage| {
+            format!(
+                "{} {}: {}",
+                msg.timestamp.format("%H:%M:%S"),
+                level_name(msg.level),
+                msg.display_text()
+            )
+        };
+        if self.show_errors {
+            let heading = tr!("status.errors-heading", count = self.errors.len());
+            return Some((heading, self.errors.iter().rev().map(line).collect()));
+        }
+        if self.show_history {
+            let heading = tr!("status.history-heading", count = self.message_history.len());
+            let lines = self.message_history.iter().rev().map(line).collect();
+            return Some((heading, lines));
+        }
+        let report = self.open_report.as_ref()?;
+        let lines = report_lines(report)
+            .into_iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        Some((tr!("status.report-heading"), lines))
+    }
+
     fn render_status_line(&self, f: &mut Frame, area: Rect) {
         let (content, style) = if let Some((text, level)) = self.get_current_display_text() {
-            let color = match level {
-                MessageLevel::Info => Color::Gray,
-                MessageLevel::Success => Color::Green,
-                MessageLevel::Warning => Color::Yellow,
-                MessageLevel::Error => Color::Red,
-                MessageLevel::Loading => Color::Cyan,
-            };
+            let mut display_text = format!("{}{}", level_prefix(level), text);
+            if self.focused && self.has_report() {
+                display_text.push_str(" (Enter: details)");
+            }
+            let mut style = Style::default().fg(level_color(level));
+            if level == MessageLevel::Alert {
+                style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            }
+            (display_text, style)
+        } else {
+            // Show context-sensitive key hints when no status message
+            (self.hints.clone(), Style::default().fg(Color::Gray))
+        };
 
-            let prefix = match level {
-                MessageLevel::Success => "✓ ",
-                MessageLevel::Error => "✗ ",
-                MessageLevel::Warning => "⚠ ",
-                MessageLevel::Info => "ℹ ",
-                MessageLevel::Loading => "", // Spinner already shown
-            };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(if self.focused {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            });
+        let inner = block.inner(area);
+        f.render_widget(block, area);
 
-            let display_text = format!("{}{}", prefix, text);
-            (display_text, Style::default().fg(color))
-        } else {
-            // Show help text when no status message
-            let help_text = "/: Search | Tab: Switch | ↑/↓: Nav | Enter/o: Open | r: Refresh | Ctrl+H: Status History | q: Quit";
-            (help_text.to_string(), Style::default().fg(Color::Gray))
+        // Right segment: error badge and market session clock (omitted when
+        // no exchanges are configured)
+        let badge = match self.errors.len() {
+            0 => String::new(),
+            1 => "✗ 1 error (E) ".to_string(),
+            n => format!("✗ {} errors (E) ", n),
+        };
+        let market_text = session_summary(&self.exchanges, self.clock.now());
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(badge.chars().count() as u16),
+                Constraint::Length(market_text.chars().count() as u16),
+            ])
+            .split(inner);
+
+        f.render_widget(Paragraph::new(content).style(style), chunks[0]);
+        f.render_widget(
+            Paragraph::new(badge).style(Style::default().fg(Color::Red)),
+            chunks[1],
+        );
+        f.render_widget(
+            Paragraph::new(market_text)
+                .style(Style::default().fg(Color::Cyan))
+                .alignment(Alignment::Right),
+            chunks[2],
+        );
+    }
+
+    /// Draw the info/success toast in the bottom-right corner of `area`
+    pub fn render_toast(&self, f: &mut Frame, area: Rect) {
+        let Some(toast) = &self.toast else {
+            return;
+        };
+        let text = format!("{}{}", level_prefix(toast.level), toast.display_text());
+        let width = (Span::raw(text.as_str()).width() as u16 + 2)
+            .min(MAX_TOAST_WIDTH)
+            .min(area.width);
+        let height = 3.min(area.height);
+        let popup = Rect {
+            x: area.x + area.width - width,
+            y: area.y + area.height - height,
+            width,
+            height,
         };
+        let color = level_color(toast.level);
+        let paragraph = Paragraph::new(text)
+            .style(Style::default().fg(color))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(color)),
+            );
+        f.render_widget(Clear, popup);
+        f.render_widget(paragraph, popup);
+    }
 
-        let paragraph = Paragraph::new(content).style(style).block(
+    fn render_errors(&self, f: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = self
+            .errors
+            .iter()
+            .rev()
+            .map(|msg| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("[{}] ", msg.timestamp.format("%H:%M:%S")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(msg.display_text(), Style::default().fg(Color::Red)),
+                ])
+            })
+            .collect();
+        let scroll = self.errors_scroll.min(self.max_scroll(lines.len()));
+        let paragraph = Paragraph::new(lines).scroll((scroll as u16, 0)).block(
             Block::default()
+                .title(tr!("title.errors", count = self.errors.len()))
                 .borders(Borders::ALL)
-                .border_style(Style::default()),
+                .border_style(if self.focused {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                }),
         );
+        f.render_widget(paragraph, area);
+    }
 
+    fn render_report(&self, f: &mut Frame, area: Rect, report: &RefreshReport) {
+        let title = tr!(
+            "title.report",
+            time = report
+                .finished_at
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S")
+        );
+        let lines = report_lines(report);
+        let scroll = self.report_scroll.min(self.max_scroll(lines.len()));
+        let paragraph = Paragraph::new(lines).scroll((scroll as u16, 0)).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(if self.focused {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                }),
+        );
         f.render_widget(paragraph, area);
     }
 
@@ -157,49 +748,47 @@ impl StatusBarComponent {
         // Calculate how many lines we can fit (minus 2 for borders)
         let available_lines = area.height.saturating_sub(2) as usize;
         let total_messages = self.message_history.len();
+        // A taller view than when it was scrolled shows from further up
+        let offset = self
+            .history_scroll_offset
+            .min(self.max_scroll(total_messages));
 
         // Apply scroll offset
         let history_text: Vec<Line> = self
             .message_history
             .iter()
             .rev()
-            .skip(self.history_scroll_offset)
+            .skip(offset)
             .take(available_lines)
             .map(|msg| {
                 let time_str = msg.timestamp.format("%H:%M:%S");
-                let color = match msg.level {
-                    MessageLevel::Info => Color::Gray,
-                    MessageLevel::Success => Color::Green,
-                    MessageLevel::Warning => Color::Yellow,
-                    MessageLevel::Error => Color::Red,
-                    MessageLevel::Loading => Color::Cyan,
-                };
+                let color = level_color(msg.level);
                 Line::from(vec![
                     Span::styled(
                         format!("[{}] ", time_str),
                         Style::default().fg(Color::DarkGray),
                     ),
-                    Span::styled(msg.text.clone(), Style::default().fg(color)),
+                    Span::styled(msg.display_text(), Style::default().fg(color)),
                 ])
             })
             .collect();
 
         // Build title with scroll indicator
-        let can_scroll_up = self.history_scroll_offset > 0;
-        let can_scroll_down = self.history_scroll_offset + available_lines < total_messages;
+        let can_scroll_up = offset > 0;
+        let can_scroll_down = offset + available_lines < total_messages;
 
         let title = if can_scroll_up || can_scroll_down {
             let up_arrow = if can_scroll_up { "↑" } else { " " };
             let down_arrow = if can_scroll_down { "↓" } else { " " };
-            format!(
-                "Message History {} {}/{} {} (↑/↓: Scroll, Ctrl+H: Close)",
-                up_arrow,
-                self.history_scroll_offset + 1,
-                total_messages,
-                down_arrow
+            tr!(
+                "title.history-scroll",
+                up = up_arrow,
+                position = offset + 1,
+                total = total_messages,
+                down = down_arrow
             )
         } else {
-            "Message History (Ctrl+H to close)".to_string()
+            tr!("title.history")
         };
 
         let paragraph = Paragraph::new(history_text).block(
@@ -217,28 +806,119 @@ impl StatusBarComponent {
     }
 }
 
+/// Index into `dismiss_after` of the levels with a configurable display time
+fn dismiss_slot(level: MessageLevel) -> Option<usize> {
+    match level {
+        MessageLevel::Info => Some(0),
+        MessageLevel::Success => Some(1),
+        MessageLevel::Warning => Some(2),
+        MessageLevel::Error => Some(3),
+        MessageLevel::Loading | MessageLevel::Alert => None,
+    }
+}
+
+fn level_color(level: MessageLevel) -> Color {
+    match level {
+        MessageLevel::Info => Color::Gray,
+        MessageLevel::Success => Color::Green,
+        MessageLevel::Warning => Color::Yellow,
+        MessageLevel::Error => Color::Red,
+        MessageLevel::Loading => Color::Cyan,
+        MessageLevel::Alert => Color::Magenta,
+    }
+}
+
+fn level_name(level: MessageLevel) -> &'static str {
+    match level {
+        MessageLevel::Success => t("level.success"),
+        MessageLevel::Error => t("level.error"),
+        MessageLevel::Warning => t("level.warning"),
+        MessageLevel::Info => t("level.info"),
+        MessageLevel::Loading => t("level.loading"),
+        MessageLevel::Alert => t("level.alert"),
+    }
+}
+
+fn level_prefix(level: MessageLevel) -> &'static str {
+    match level {
+        MessageLevel::Success => "✓ ",
+        MessageLevel::Error => "✗ ",
+        MessageLevel::Warning => "⚠ ",
+        MessageLevel::Info => "ℹ ",
+        MessageLevel::Loading => "", // Spinner already shown
+        MessageLevel::Alert => "⚑ ",
+    }
+}
+
+/// Per-source breakdown: failures first, then sources with warnings
+fn report_lines(report: &RefreshReport) -> Vec<Line<'static>> {
+    let red = Style::default().fg(Color::Red);
+    let yellow = Style::default().fg(Color::Yellow);
+    let green = Style::default().fg(Color::Green);
+
+    let mut sources: Vec<_> = report.sources.iter().collect();
+    sources.sort_by_key(|s| (s.success, s.warnings.is_empty()));
+
+    let mut lines = vec![Line::from(report.summary())];
+    for source in sources {
+        if !source.success {
+            let mut spans = vec![
+                Span::styled(format!("✗ {}: ", source.source), red),
+                Span::raw(source.message.clone()),
+            ];
+            if let Some(error) = &source.error {
+                spans.push(Span::styled(
+                    format!(" ({})", error.category().hint()),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(spans));
+            continue;
+        }
+        let style = if source.warnings.is_empty() {
+            green
+        } else {
+            yellow
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("✓ {}: ", source.source), style),
+            Span::raw(format!("{} items", source.item_count)),
+        ]));
+        for warning in &source.warnings {
+            lines.push(Line::styled(format!("    ⚠ {}", warning), yellow));
+        }
+    }
+    for error in &report.db_errors {
+        lines.push(Line::from(vec![
+            Span::styled("✗ Database: ", red),
+            Span::raw(error.clone()),
+        ]));
+    }
+    lines
+}
+
 impl Component for StatusBarComponent {
-    fn handle_event(&mut self, event: &Event) -> Action {
-        // Only handle scroll events when BOTH focused AND history is showing
+    fn handle_event(&mut self, _event: &Event) -> Action {
+        // Keys arrive as commands
+        Action::None
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        // Only the expanded views take commands, once focused
         if !self.is_focused() {
-            return Action::None;
+            return None;
         }
-
-        if let Event::Key(KeyEvent { code, .. }) = event {
-            match code {
-                KeyCode::Up if self.show_history => {
-                    self.scroll_history_up();
-                    Action::None
-                }
-                KeyCode::Down if self.show_history => {
-                    self.scroll_history_down();
-                    Action::None
-                }
-                _ => Action::None,
+        match command {
+            Command::Up => self.scroll_expanded(false),
+            Command::Down => self.scroll_expanded(true),
+            Command::ClearErrors if self.show_errors => self.clear_errors(),
+            Command::Select | Command::Dismiss if self.open_report.is_some() => {
+                self.open_report = None;
             }
-        } else {
-            Action::None
+            Command::Select => self.open_report(),
+            _ => return None,
         }
+        Some(Action::None)
     }
 
     fn update(&mut self, action: &Action) {
@@ -246,16 +926,29 @@ impl Component for StatusBarComponent {
             Action::StatusMessage(msg) => {
                 self.set_message(msg.clone());
             }
+            // Transient messages go first; the alert once nothing else is up
             Action::DismissStatus => {
+                if self.current_message.is_none() && self.toast.is_none() {
+                    self.clear_alert();
+                }
                 self.clear_message();
+                self.clear_toast();
             }
             _ => {}
         }
     }
 
-    fn render(&self, f: &mut Frame, area: Rect) {
+    fn render(&self, f: &mut Frame, area: Rect, _state: &RenderState) {
+        self.area.set(area);
+        if self.is_expanded() {
+            self.shown_height.set(area.height);
+        }
         if self.show_history {
             self.render_history(f, area);
+        } else if self.show_errors {
+            self.render_errors(f, area);
+        } else if let Some(report) = &self.open_report {
+            self.render_report(f, area, report);
         } else {
             self.render_status_line(f, area);
         }
@@ -269,3 +962,146 @@ impl Component for StatusBarComponent {
         self.focused = focused;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, ManualClock};
+    use crate::test_support::manual_clock;
+
+    fn status_bar(clock: &ManualClock) -> StatusBarComponent {
+        let mut status = StatusBarComponent::new();
+        status.set_clock(clock.shared());
+        status
+    }
+
+    #[test]
+    fn messages_dismiss_once_their_time_is_up() {
+        let clock = manual_clock();
+        let mut status = status_bar(&clock);
+        status.set_message(StatusMessage::warning("Reuters: slow".to_string()));
+        status.set_message(StatusMessage::success("Saved".to_string()));
+
+        clock.advance(chrono::Duration::seconds(2));
+        assert!(!status.check_auto_dismiss());
+        // Success toasts go after 3s, warnings after 5s
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(status.check_auto_dismiss());
+        assert!(status.toast.is_none());
+        assert!(status.current_message.is_some());
+        clock.advance(chrono::Duration::seconds(2));
+        assert!(status.check_auto_dismiss());
+        assert!(status.current_message.is_none());
+    }
+
+    #[test]
+    fn queued_message_waits_for_the_current_one() {
+        let clock = manual_clock();
+        let mut status = status_bar(&clock);
+        status.set_message(StatusMessage::warning("First".to_string()));
+        status.set_message(StatusMessage::warning("Second".to_string()));
+        assert_eq!(status.pending.len(), 1);
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(!status.check_auto_dismiss());
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(status.check_auto_dismiss());
+        let current = status.current_message.as_ref().unwrap();
+        assert_eq!(current.text, "Second");
+        // Its display time starts when it is shown
+        assert_eq!(current.timestamp, clock.now());
+    }
+
+    #[test]
+    fn configured_and_sticky_messages_keep_their_time() {
+        let clock = manual_clock();
+        let mut status = status_bar(&clock);
+        status.set_dismiss_after(MessageLevel::Error, Some(Duration::from_secs(60)));
+        status.set_message(StatusMessage::error("Sync failed".to_string()));
+        clock.advance(chrono::Duration::seconds(59));
+        assert!(!status.check_auto_dismiss());
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(status.check_auto_dismiss());
+        // The badge keeps it either way
+        assert_eq!(status.error_count(), 1);
+
+        status.set_dismiss_after(MessageLevel::Error, None);
+        status.set_message(StatusMessage::error("Delete failed".to_string()));
+        status.set_message(StatusMessage::success("Saved".to_string()).sticky());
+        clock.advance(chrono::Duration::hours(1));
+        assert!(!status.check_auto_dismiss());
+        status.update(&Action::DismissStatus);
+        assert!(status.current_message.is_none() && status.toast.is_none());
+    }
+
+    #[test]
+    fn expanded_view_resizes_within_the_terminal() {
+        let clock = manual_clock();
+        let mut status = status_bar(&clock);
+        for i in 0..30 {
+            status.push_history(StatusMessage::info(format!("Message {}", i)));
+        }
+        status.toggle_history();
+        status.resize_expanded(true);
+        assert_eq!(status.get_height(), 16);
+
+        // Drawn at 10 rows, the terminal's limit: 8 messages show
+        status.shown_height.set(10);
+        status.resize_expanded(true);
+        assert_eq!(status.get_height(), 10);
+        for _ in 0..40 {
+            status.scroll_history_down();
+        }
+        assert_eq!(status.history_scroll_offset, 22);
+        status.resize_expanded(false);
+        assert_eq!(status.get_height(), 9);
+        for _ in 0..20 {
+            status.resize_expanded(false);
+        }
+        assert_eq!(status.get_height(), 3);
+    }
+
+    #[test]
+    fn wheel_scrolls_the_view_that_is_expanded() {
+        let clock = manual_clock();
+        let mut status = status_bar(&clock);
+        for i in 0..12 {
+            status.set_message(StatusMessage::error(format!("Feed {} failed", i)));
+        }
+        status.toggle_errors();
+        status.shown_height.set(6);
+        for _ in 0..20 {
+            status.scroll_expanded(true);
+        }
+        assert_eq!(status.errors_scroll, 8);
+        status.scroll_expanded(false);
+        assert_eq!((status.errors_scroll, status.history_scroll_offset), (7, 0));
+    }
+
+    #[test]
+    fn alert_stays_until_dismissed() {
+        let clock = manual_clock();
+        let mut status = status_bar(&clock);
+        status.set_message(StatusMessage::alert("NVDA: 2 new articles".to_string()));
+        status.set_message(StatusMessage::warning("Reuters: slow".to_string()));
+        clock.advance(chrono::Duration::minutes(10));
+        status.check_auto_dismiss();
+        assert!(status.current_message.is_none());
+        let (text, level) = status.get_current_display_text().unwrap();
+        assert_eq!(
+            (text.as_str(), level),
+            ("NVDA: 2 new articles", MessageLevel::Alert)
+        );
+
+        // Esc takes other messages down first
+        status.set_message(StatusMessage::warning("Reuters: slow".to_string()));
+        status.update(&Action::DismissStatus);
+        assert!(status.has_alert());
+        status.update(&Action::DismissStatus);
+        assert!(!status.has_alert());
+        assert_eq!(
+            status.message_history.back().unwrap().level,
+            MessageLevel::Alert
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use crate::i18n::t;
+use crate::models::Quote;
+use crate::ui::component::{Action, Component};
+use crate::ui::render_state::RenderState;
+use crossterm::event::Event;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+const SEPARATOR: &str = "   ";
+
+/// One-line scrolling quote tape rendered above the search bar; the quotes
+/// come from the frame's `RenderState`
+pub struct TickerTapeComponent {
+    enabled: bool,
+    offset: usize,
+}
+
+impl Default for TickerTapeComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TickerTapeComponent {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            offset: 0,
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Whether the tape takes up a row in the layout
+    pub fn is_visible(&self) -> bool {
+        self.enabled
+    }
+
+    /// Advance the tape by one column
+    pub fn tick(&mut self) {
+        self.offset = self.offset.wrapping_add(1);
+    }
+
+    /// Colored (text, color) segments for each quote, including trailing separators
+    fn segments(quotes: &[Quote]) -> Vec<(String, Color)> {
+        quotes
+            .iter()
+            .flat_map(|q| {
+                let (arrow, color) = if q.change >= 0.0 {
+                    ("▲", Color::Green)
+                } else {
+                    ("▼", Color::Red)
+                };
+                [
+                    (format!("{} ", q.symbol), Color::White),
+                    (
+                        format!("{:.2} {}{:+.2}%", q.price, arrow, q.change_percent),
+                        color,
+                    ),
+                    (SEPARATOR.to_string(), Color::Reset),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Component for TickerTapeComponent {
+    fn handle_event(&mut self, _event: &Event) -> Action {
+        // The tape is display-only
+        Action::None
+    }
+
+    fn update(&mut self, _action: &Action) {}
+
+    fn render(&self, f: &mut Frame, area: Rect, state: &RenderState) {
+        if state.tape_quotes.is_empty() {
+            let placeholder =
+                Paragraph::new(t("ticker.loading")).style(Style::default().fg(Color::DarkGray));
+            f.render_widget(placeholder, area);
+            return;
+        }
+
+        // Flatten segments into per-character colors so the tape can start mid-segment
+        let chars: Vec<(char, Color)> = Self::segments(state.tape_quotes)
+            .into_iter()
+            .flat_map(|(text, color)| text.chars().map(move |c| (c, color)).collect::<Vec<_>>())
+            .collect();
+
+        let start = self.offset % chars.len();
+        let mut spans: Vec<Span> = Vec::new();
+        let mut current = String::new();
+        let mut current_color = chars[start].1;
+
+        for i in 0..area.width as usize {
+            let (c, color) = chars[(start + i) % chars.len()];
+            if color != current_color {
+                spans.push(Span::styled(
+                    std::mem::take(&mut current),
+                    Style::default().fg(current_color),
+                ));
+                current_color = color;
+            }
+            current.push(c);
+        }
+        spans.push(Span::styled(current, Style::default().fg(current_color)));
+
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn is_focused(&self) -> bool {
+        false
+    }
+
+    fn set_focus(&mut self, _focused: bool) {}
+}
@@ -0,0 +1,84 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+/// Texts longer than this many words are shown as a whole replacement
+/// instead of being diffed word by word
+const MAX_DIFF_WORDS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Spans showing how `old` became `new`: removed words struck through in
+/// red, added words in green
+pub fn diff_spans(old: &str, new: &str) -> Vec<Span<'static>> {
+    let old: Vec<&str> = old.split_whitespace().collect();
+    let new: Vec<&str> = new.split_whitespace().collect();
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for (change, words) in word_diff(&old, &new) {
+        if !spans.is_empty() {
+            spans.push(Span::raw(" "));
+        }
+        let style = match change {
+            Change::Same => Style::default(),
+            Change::Removed => Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::CROSSED_OUT),
+            Change::Added => Style::default().fg(Color::Green),
+        };
+        spans.push(Span::styled(words.join(" "), style));
+    }
+    spans
+}
+
+/// Runs of unchanged, removed and added words, from the longest common
+/// subsequence of the two word lists
+fn word_diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Change, Vec<&'a str>)> {
+    let mut runs: Vec<(Change, Vec<&'a str>)> = Vec::new();
+    let mut push = |change: Change, word: &'a str| match runs.last_mut() {
+        Some((last, words)) if *last == change => words.push(word),
+        _ => runs.push((change, vec![word])),
+    };
+
+    if old.len() > MAX_DIFF_WORDS || new.len() > MAX_DIFF_WORDS {
+        old.iter().for_each(|w| push(Change::Removed, w));
+        new.iter().for_each(|w| push(Change::Added, w));
+        return runs;
+    }
+
+    // lcs[i][j]: common subsequence length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            push(Change::Same, old[i]);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            push(Change::Removed, old[i]);
+            i += 1;
+        } else {
+            push(Change::Added, new[j]);
+            j += 1;
+        }
+    }
+    old[i..].iter().for_each(|w| push(Change::Removed, w));
+    new[j..].iter().for_each(|w| push(Change::Added, w));
+    runs
+}
@@ -0,0 +1,373 @@
+//! Golden-file rendering tests. Each test draws a component with fixture
+//! data to a `TestBackend` and compares the buffer, text and styles, with
+//! `src/ui/golden/<name>.txt`. Run with `UPDATE_GOLDEN=1` to rewrite the
+//! files after an intended change, then review the diff.
+
+use std::path::PathBuf;
+
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::{Frame, Terminal};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::{App, TabComponent};
+use crate::db::sqlite::NewsDB;
+use crate::models::{NewsItem, Quote};
+use crate::test_support::{fixed_now, manual_clock};
+use crate::ui::components::FilterChip;
+use crate::ui::render_state::RenderState;
+use crate::ui::{
+    draw_ui, Component, DetailPaneComponent, FilterChipsComponent, NewsListComponent,
+    SearchBarComponent, ShareMenuComponent, StatusBarComponent, StatusMessage, TickerTapeComponent,
+};
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/golden")
+        .join(format!("{}.txt", name))
+}
+
+/// Draw into a `width`×`height` buffer
+fn render(width: u16, height: u16, draw: impl FnOnce(&mut Frame, Rect)) -> Buffer {
+    let mut term = Terminal::new(TestBackend::new(width, height)).unwrap();
+    term.draw(|f| {
+        let area = f.size();
+        draw(f, area)
+    })
+    .unwrap();
+    term.backend().buffer().clone()
+}
+
+/// The buffer's text, one line per row, followed by its styled runs as
+/// `row:start-end style`; unstyled cells are left out
+fn snapshot(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    let mut styles = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        // Columns still covered by the previous wide character
+        let mut skip = 0;
+        let mut run: Option<(u16, String)> = None;
+        for x in area.left()..=area.right() {
+            let style = (x < area.right())
+                .then(|| describe(buffer.get(x, y).style()))
+                .filter(|style| !style.is_empty());
+            if run.as_ref().map(|(_, s)| s) != style.as_ref() {
+                if let Some((start, s)) = run.take() {
+                    styles.push_str(&format!("{}:{}-{} {}\n", y, start, x - 1, s));
+                }
+                run = style.map(|s| (x, s));
+            }
+            if x == area.right() {
+                break;
+            }
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer.get(x, y).symbol();
+            skip = symbol.width().saturating_sub(1);
+            line.push_str(symbol);
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    if styles.is_empty() {
+        text
+    } else {
+        format!("{}\n{}", text, styles)
+    }
+}
+
+/// `fg=… bg=… MODIFIERS`, empty for the terminal's default style
+fn describe(style: Style) -> String {
+    let mut parts = Vec::new();
+    if let Some(fg) = style.fg.filter(|c| *c != Color::Reset) {
+        parts.push(format!("fg={:?}", fg));
+    }
+    if let Some(bg) = style.bg.filter(|c| *c != Color::Reset) {
+        parts.push(format!("bg={:?}", bg));
+    }
+    if style.add_modifier != Modifier::empty() {
+        parts.push(format!("{:?}", style.add_modifier));
+    }
+    parts.join(" ")
+}
+
+/// Compare `buffer` with the golden file `name`, or rewrite the file when
+/// `UPDATE_GOLDEN` is set
+fn assert_golden(name: &str, buffer: &Buffer) {
+    let actual = snapshot(buffer);
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing golden file {}; run with UPDATE_GOLDEN=1 to create it",
+            path.display()
+        )
+    });
+    if actual != expected {
+        let line = actual
+            .lines()
+            .zip(expected.lines())
+            .position(|(a, e)| a != e)
+            .unwrap_or_else(|| actual.lines().count().min(expected.lines().count()));
+        panic!(
+            "{} differs from {} at line {}\n--- expected\n{}\n--- actual\n{}",
+            name,
+            path.display(),
+            line + 1,
+            expected,
+            actual
+        );
+    }
+}
+
+fn articles() -> Vec<NewsItem> {
+    let item = |id: &str, source: &str, title: &str, minutes_ago: i64| NewsItem {
+        id: id.to_string(),
+        source: source.to_string(),
+        source_id: source.to_lowercase(),
+        title: title.to_string(),
+        url: format!("https://example.com/{}", id),
+        summary: format!("Summary of {}.", title),
+        published: fixed_now() - chrono::Duration::minutes(minutes_ago),
+        updated_at: fixed_now(),
+        ..Default::default()
+    };
+    let mut earnings = item("a1", "Reuters", "Apple beats on earnings", 5);
+    earnings.tickers = vec!["AAPL".to_string(), "MSFT".to_string()];
+    earnings.tags = vec!["earnings".to_string()];
+    earnings.author = Some("Jane Doe".to_string());
+    earnings.starred = true;
+    let mut fed = item("a2", "CNBC", "Fed holds rates steady", 90);
+    fed.read = true;
+    vec![
+        earnings,
+        fed,
+        item(
+            "a3",
+            "Hacker News",
+            "Show HN: A terminal news reader",
+            60 * 26,
+        ),
+    ]
+}
+
+fn quotes() -> Vec<Quote> {
+    vec![
+        Quote {
+            symbol: "AAPL".to_string(),
+            price: 189.23,
+            change: 1.2,
+            change_percent: 0.64,
+            as_of: fixed_now(),
+        },
+        Quote {
+            symbol: "SPY".to_string(),
+            price: 512.5,
+            change: -3.1,
+            change_percent: -0.6,
+            as_of: fixed_now(),
+        },
+    ]
+}
+
+fn news_list() -> NewsListComponent {
+    let mut list = NewsListComponent::new(articles());
+    list.set_clock(manual_clock().shared());
+    list
+}
+
+#[test]
+fn ticker_tape() {
+    let tape = TickerTapeComponent::new();
+    let quotes = quotes();
+    let state = RenderState {
+        tape_quotes: &quotes,
+        ..Default::default()
+    };
+    let buffer = render(60, 1, |f, area| tape.render(f, area, &state));
+    assert_golden("ticker_tape", &buffer);
+}
+
+#[test]
+fn search_bar() {
+    let mut search = SearchBarComponent::new();
+    search.set_focus(true);
+    search.paste("fed rates");
+    let buffer = render(40, 3, |f, area| {
+        search.render(f, area, &RenderState::default())
+    });
+    assert_golden("search_bar", &buffer);
+}
+
+#[test]
+fn filter_chips() {
+    let mut chips = FilterChipsComponent::new();
+    chips.set_chips(vec![
+        FilterChip::Source("Reuters".to_string()),
+        FilterChip::Tag("earnings".to_string()),
+    ]);
+    let buffer = render(chips.width(), 3, |f, area| {
+        chips.render(f, area, &RenderState::default())
+    });
+    assert_golden("filter_chips", &buffer);
+}
+
+#[test]
+fn news_list_with_read_and_starred_items() {
+    let list = news_list();
+    let buffer = render(70, 8, |f, area| {
+        list.render(f, area, &RenderState::default())
+    });
+    assert_golden("news_list", &buffer);
+}
+
+#[test]
+fn detail_pane_with_quotes() {
+    let pane = DetailPaneComponent::new();
+    let articles = articles();
+    let quotes = quotes();
+    let state = RenderState {
+        article: articles.first(),
+        // MSFT is still loading
+        article_quotes: vec![&quotes[0]],
+        ..Default::default()
+    };
+    let buffer = render(50, 14, |f, area| pane.render(f, area, &state));
+    assert_golden("detail_pane", &buffer);
+}
+
+#[test]
+fn status_bar_message() {
+    let mut status = StatusBarComponent::new();
+    status.set_message(StatusMessage::error("Reuters: timed out".to_string()));
+    let buffer = render(60, 3, |f, area| {
+        status.render(f, area, &RenderState::default())
+    });
+    assert_golden("status_bar", &buffer);
+}
+
+#[test]
+fn share_menu() {
+    let mut menu = ShareMenuComponent::new();
+    menu.set_entries(vec![
+        (Some('c'), "Copy link".to_string()),
+        (Some('s'), "Slack".to_string()),
+        (None, "Email".to_string()),
+    ]);
+    menu.open("Apple beats on earnings");
+    let buffer = render(50, 10, |f, area| {
+        menu.render(f, area, &RenderState::default())
+    });
+    assert_golden("share_menu", &buffer);
+}
+
+#[test]
+fn full_layout() {
+    let mut app = App::new(articles());
+    app.set_clock(manual_clock().shared());
+    let mut term = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    draw_ui(&mut term, &app).unwrap();
+    assert_golden("full_layout", term.backend().buffer());
+}
+
+#[test]
+fn compare_layout() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = NewsDB::new(dir.path().join("news.db").to_str().unwrap()).unwrap();
+    let mut app = App::new(articles());
+    app.set_clock(manual_clock().shared());
+    for code in [KeyCode::Char('p'), KeyCode::Down] {
+        let key = Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let action = app.handle_event(&key);
+        app.handle_action(&action, &db);
+    }
+    let mut term = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    draw_ui(&mut term, &app).unwrap();
+    assert_golden("compare_layout", term.backend().buffer());
+}
+
+#[test]
+fn screen_reader_layout() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = NewsDB::new(dir.path().join("news.db").to_str().unwrap()).unwrap();
+    let mut app = App::new(articles());
+    app.set_clock(manual_clock().shared());
+    app.set_screen_reader(true);
+    app.status_bar
+        .set_message(StatusMessage::warning("Reuters: slow".to_string()));
+    let down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    let action = app.handle_event(&down);
+    app.handle_action(&action, &db);
+    let mut term = Terminal::new(TestBackend::new(70, 16)).unwrap();
+    draw_ui(&mut term, &app).unwrap();
+    assert_golden("screen_reader", term.backend().buffer());
+}
+
+#[test]
+fn row_ages_follow_the_clock() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = NewsDB::new(dir.path().join("news.db").to_str().unwrap()).unwrap();
+    let clock = manual_clock();
+    let mut app = App::new(articles());
+    app.set_clock(clock.shared());
+    let mut term = Terminal::new(TestBackend::new(100, 20)).unwrap();
+
+    clock.advance(chrono::Duration::minutes(115));
+    assert!(app.tick(&db));
+    draw_ui(&mut term, &app).unwrap();
+    let text = snapshot(term.backend().buffer());
+    assert!(text.contains("2h ago"), "{}", text);
+    assert!(!text.contains("5m ago"), "{}", text);
+}
+
+#[test]
+fn clicks_select_rows_and_focus_panes() {
+    let mut app = App::new(articles());
+    app.set_clock(manual_clock().shared());
+    let mut term = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    draw_ui(&mut term, &app).unwrap();
+    let click = |column, row| {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    };
+
+    // Below the search bar and the list's top border, the third row
+    app.handle_event(&click(10, 6));
+    assert_eq!(app.news_list.selected_position(), Some(2));
+    assert_eq!(app.focused_component, TabComponent::NewsList);
+
+    app.handle_event(&click(80, 6));
+    assert_eq!(app.focused_component, TabComponent::DetailPane);
+    app.handle_event(&click(10, 1));
+    assert_eq!(app.focused_component, TabComponent::SearchBar);
+
+    // The empty rows under the list focus it without changing the selection
+    app.handle_event(&click(10, 12));
+    assert_eq!(app.focused_component, TabComponent::NewsList);
+    assert_eq!(app.news_list.selected_position(), Some(2));
+}
+
+#[test]
+fn too_small_terminal() {
+    let app = App::new(Vec::new());
+    let mut term = Terminal::new(TestBackend::new(30, 8)).unwrap();
+    draw_ui(&mut term, &app).unwrap();
+    assert_golden("too_small", term.backend().buffer());
+}
@@ -0,0 +1,181 @@
+use anyhow::{anyhow, Result};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+use std::str::FromStr;
+
+use crate::config::HighlightRuleConfig;
+
+/// A compiled keyword → style rule used when rendering headlines
+#[derive(Debug, Clone)]
+pub struct HighlightRule {
+    keyword: String, // ASCII-lowercased for case-insensitive matching
+    style: Style,
+    whole_word: bool,
+}
+
+impl HighlightRule {
+    pub fn from_config(cfg: &HighlightRuleConfig) -> Result<Self> {
+        if cfg.keyword.trim().is_empty() {
+            return Err(anyhow!("Highlight rule has an empty keyword"));
+        }
+
+        let mut style = Style::default();
+        if let Some(color) = &cfg.color {
+            let color = Color::from_str(color).map_err(|_| {
+                anyhow!(
+                    "Highlight rule '{}': unknown color '{}'",
+                    cfg.keyword,
+                    color
+                )
+            })?;
+            style = style.fg(color);
+        }
+        if cfg.bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+
+        Ok(Self {
+            keyword: cfg.keyword.to_ascii_lowercase(),
+            style,
+            whole_word: cfg.whole_word,
+        })
+    }
+
+    /// Byte offset of the first match in lowercased `haystack` at or after
+    /// `from`. Whole-word rules skip matches with a letter or digit next to
+    /// a letter or digit of the keyword.
+    fn find(&self, haystack: &str, from: usize) -> Option<usize> {
+        let mut pos = from;
+        while let Some(offset) = haystack[pos..].find(&self.keyword) {
+            let start = pos + offset;
+            let end = start + self.keyword.len();
+            if !self.whole_word
+                || (!joins(
+                    haystack[..start].chars().next_back(),
+                    self.keyword.chars().next(),
+                ) && !joins(
+                    haystack[end..].chars().next(),
+                    self.keyword.chars().next_back(),
+                ))
+            {
+                return Some(start);
+            }
+            pos = start + self.keyword.chars().next().map_or(1, char::len_utf8);
+        }
+        None
+    }
+}
+
+/// Whether two neighbouring characters belong to the same word
+fn joins(a: Option<char>, b: Option<char>) -> bool {
+    a.is_some_and(char::is_alphanumeric) && b.is_some_and(char::is_alphanumeric)
+}
+
+/// Compile all configured rules, collecting errors for invalid ones
+pub fn compile_rules(configs: &[HighlightRuleConfig]) -> (Vec<HighlightRule>, Vec<String>) {
+    let mut rules = Vec::new();
+    let mut errors = Vec::new();
+    for cfg in configs {
+        match HighlightRule::from_config(cfg) {
+            Ok(rule) => rules.push(rule),
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+    (rules, errors)
+}
+
+/// Split `text` into spans, styling every (case-insensitive) keyword match.
+/// Overlapping matches resolve to the earliest start, then the longest keyword.
+pub fn highlight_spans(text: &str, rules: &[HighlightRule]) -> Vec<Span<'static>> {
+    if rules.is_empty() {
+        return vec![Span::raw(text.to_string())];
+    }
+
+    // ASCII lowercasing keeps byte offsets aligned with the original text
+    let haystack = text.to_ascii_lowercase();
+    let mut spans = Vec::new();
+    let mut pos = 0;
+
+    while pos < text.len() {
+        let next_match = rules
+            .iter()
+            .filter_map(|rule| rule.find(&haystack, pos).map(|start| (start, rule)))
+            .min_by(|(a_start, a), (b_start, b)| {
+                a_start
+                    .cmp(b_start)
+                    .then(b.keyword.len().cmp(&a.keyword.len()))
+            });
+
+        match next_match {
+            Some((start, rule)) => {
+                let end = start + rule.keyword.len();
+                if start > pos {
+                    spans.push(Span::raw(text[pos..start].to_string()));
+                }
+                spans.push(Span::styled(text[start..end].to_string(), rule.style));
+                pos = end;
+            }
+            None => {
+                spans.push(Span::raw(text[pos..].to_string()));
+                break;
+            }
+        }
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(keyword: &str, whole_word: bool) -> HighlightRule {
+        let cfg = HighlightRuleConfig {
+            keyword: keyword.to_string(),
+            color: Some("green".to_string()),
+            bold: false,
+            whole_word,
+        };
+        HighlightRule::from_config(&cfg).unwrap()
+    }
+
+    /// The highlighted parts of `text`
+    fn matches(text: &str, rules: &[HighlightRule]) -> Vec<String> {
+        highlight_spans(text, rules)
+            .into_iter()
+            .filter(|span| span.style != Style::default())
+            .map(|span| span.content.into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn matches_whole_words_only() {
+        let rules = [rule("rate", true)];
+        assert!(matches("Corporate bonds rated higher", &rules).is_empty());
+        assert_eq!(
+            matches("Corporate RATE outlook: rate!", &rules),
+            ["RATE", "rate"]
+        );
+        let rules = [rule("upgrade", true)];
+        assert!(matches("Analyst upgrades", &rules).is_empty());
+        assert_eq!(matches("$NVDA up", &[rule("$nvda", true)]), ["$NVDA"]);
+        assert_eq!(matches("Guidance cut-off", &[rule("cut", true)]), ["cut"]);
+    }
+
+    #[test]
+    fn matches_inside_words_when_asked() {
+        let rules = [rule("rate", false)];
+        assert_eq!(matches("Corporate bonds", &rules), ["rate"]);
+    }
+
+    #[test]
+    fn overlapping_rules_take_the_earliest_then_longest() {
+        let rules = [rule("rate", true), rule("rate cut", true)];
+        assert_eq!(
+            matches("Fed rate cut; rate hold", &rules),
+            ["rate cut", "rate"]
+        );
+    }
+}
@@ -0,0 +1,1043 @@
+use crate::i18n;
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+
+/// Where a key binding applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Global,
+    NewsList,
+    DetailPane,
+    SearchBar,
+    FilterChips,
+    StatusHistory,
+    StatusReport,
+    StatusErrors,
+    ShareMenu,
+    Help,
+    /// Wherever an article is selected: the list, the article and the
+    /// search bar
+    Articles,
+    /// Lists an entry is picked from: the share menu and the refresh report
+    Menus,
+}
+
+impl KeyContext {
+    /// Message id of the context's heading in the key help
+    pub fn title(self) -> &'static str {
+        match self {
+            KeyContext::Global => "title.help-global",
+            KeyContext::NewsList => "title.help-list",
+            KeyContext::DetailPane => "title.help-detail",
+            KeyContext::SearchBar => "title.help-search",
+            KeyContext::FilterChips => "title.help-filters",
+            KeyContext::StatusHistory => "title.help-history",
+            KeyContext::StatusReport => "title.help-report",
+            KeyContext::StatusErrors => "title.help-errors",
+            KeyContext::ShareMenu => "title.help-share",
+            KeyContext::Help => "title.help",
+            KeyContext::Articles => "title.help-articles",
+            KeyContext::Menus => "title.help-menus",
+        }
+    }
+
+    /// Whether a key can mean something in both contexts at once
+    fn overlaps(self, other: KeyContext) -> bool {
+        self == other
+            || self == KeyContext::Global
+            || other == KeyContext::Global
+            || self.within(other)
+            || other.within(self)
+    }
+
+    /// Whether this context is one of those `group` stands for
+    fn within(self, group: KeyContext) -> bool {
+        match group {
+            KeyContext::Articles => matches!(
+                self,
+                KeyContext::NewsList | KeyContext::DetailPane | KeyContext::SearchBar
+            ),
+            KeyContext::Menus => matches!(self, KeyContext::ShareMenu | KeyContext::StatusReport),
+            _ => false,
+        }
+    }
+}
+
+/// What a key does, whichever key it is. `[keys]` binds commands to keys by
+/// the names in [`COMMANDS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Open,
+    Select,
+    Dismiss,
+    ScrollUp,
+    ScrollDown,
+    Left,
+    Right,
+    RemoveFilter,
+    Delete,
+    Archive,
+    Star,
+    ArchiveView,
+    HideRead,
+    Follow,
+    FollowingView,
+    Pin,
+    ClearErrors,
+    Search,
+    CommandLine,
+    NextPane,
+    PreviousPane,
+    Refresh,
+    RefreshSource,
+    LastHour,
+    Today,
+    ThisWeek,
+    AllTime,
+    FilterSource,
+    FilterTag,
+    Share,
+    OpenAll,
+    FullText,
+    Density,
+    Undo,
+    RepeatEdit,
+    RecordMacro,
+    PlayMacro,
+    History,
+    Errors,
+    Grow,
+    Shrink,
+    Help,
+    Quit,
+}
+
+/// Each command with its `[keys]` name, where it applies and its default keys
+pub const COMMANDS: &[(Command, &str, KeyContext, &[&str])] = &[
+    (Command::Up, "up", KeyContext::Global, &["Up"]),
+    (Command::Down, "down", KeyContext::Global, &["Down"]),
+    (
+        Command::PageUp,
+        "page_up",
+        KeyContext::DetailPane,
+        &["PageUp"],
+    ),
+    (
+        Command::PageDown,
+        "page_down",
+        KeyContext::DetailPane,
+        &["PageDown"],
+    ),
+    (Command::Open, "open", KeyContext::Articles, &["Enter", "o"]),
+    (Command::Select, "select", KeyContext::Menus, &["Enter"]),
+    (Command::Dismiss, "dismiss", KeyContext::Global, &["Esc"]),
+    (
+        Command::ScrollUp,
+        "scroll_up",
+        KeyContext::DetailPane,
+        &["k"],
+    ),
+    (
+        Command::ScrollDown,
+        "scroll_down",
+        KeyContext::DetailPane,
+        &["j"],
+    ),
+    (
+        Command::Left,
+        "left",
+        KeyContext::FilterChips,
+        &["Left", "h"],
+    ),
+    (
+        Command::Right,
+        "right",
+        KeyContext::FilterChips,
+        &["Right", "l"],
+    ),
+    (
+        Command::RemoveFilter,
+        "remove_filter",
+        KeyContext::FilterChips,
+        &["x", "Delete", "Backspace"],
+    ),
+    (Command::Delete, "delete", KeyContext::NewsList, &["d"]),
+    (Command::Archive, "archive", KeyContext::NewsList, &["e"]),
+    (Command::Star, "star", KeyContext::NewsList, &["*"]),
+    (
+        Command::ArchiveView,
+        "archive_view",
+        KeyContext::NewsList,
+        &["A"],
+    ),
+    (Command::HideRead, "hide_read", KeyContext::NewsList, &["H"]),
+    (Command::Follow, "follow", KeyContext::NewsList, &["F"]),
+    (Command::Pin, "pin", KeyContext::Global, &["p"]),
+    (
+        Command::FollowingView,
+        "following_view",
+        KeyContext::NewsList,
+        &["f"],
+    ),
+    (
+        Command::ClearErrors,
+        "clear_errors",
+        KeyContext::StatusErrors,
+        &["c"],
+    ),
+    (Command::Search, "search", KeyContext::Global, &["/"]),
+    (Command::CommandLine, "command", KeyContext::Global, &[":"]),
+    (Command::NextPane, "next_pane", KeyContext::Global, &["Tab"]),
+    (
+        Command::PreviousPane,
+        "previous_pane",
+        KeyContext::Global,
+        &["Shift+Tab"],
+    ),
+    (Command::Refresh, "refresh", KeyContext::Global, &["r"]),
+    (
+        Command::RefreshSource,
+        "refresh_source",
+        KeyContext::Global,
+        &["R"],
+    ),
+    (Command::LastHour, "last_hour", KeyContext::Global, &["1"]),
+    (Command::Today, "today", KeyContext::Global, &["2"]),
+    (Command::ThisWeek, "this_week", KeyContext::Global, &["3"]),
+    (Command::AllTime, "all_time", KeyContext::Global, &["0"]),
+    (
+        Command::FilterSource,
+        "filter_source",
+        KeyContext::Global,
+        &["s"],
+    ),
+    (Command::FilterTag, "filter_tag", KeyContext::Global, &["t"]),
+    (Command::Share, "share", KeyContext::Global, &["S"]),
+    (Command::OpenAll, "open_all", KeyContext::Global, &["O"]),
+    (Command::FullText, "full_text", KeyContext::Global, &["b"]),
+    (Command::Density, "density", KeyContext::Global, &["v"]),
+    (Command::Undo, "undo", KeyContext::Global, &["u"]),
+    (
+        Command::RepeatEdit,
+        "repeat_edit",
+        KeyContext::Global,
+        &["."],
+    ),
+    (
+        Command::RecordMacro,
+        "record_macro",
+        KeyContext::Global,
+        &["m"],
+    ),
+    (Command::PlayMacro, "play_macro", KeyContext::Global, &["@"]),
+    (Command::History, "history", KeyContext::Global, &["Ctrl+H"]),
+    (Command::Errors, "errors", KeyContext::Global, &["E"]),
+    (Command::Grow, "grow", KeyContext::Global, &["+", "="]),
+    (Command::Shrink, "shrink", KeyContext::Global, &["-"]),
+    (Command::Help, "help", KeyContext::Global, &["?"]),
+    (Command::Quit, "quit", KeyContext::Global, &["q", "Ctrl+C"]),
+];
+
+impl Command {
+    fn entry(self) -> &'static (Command, &'static str, KeyContext, &'static [&'static str]) {
+        COMMANDS
+            .iter()
+            .find(|(command, _, _, _)| *command == self)
+            .expect("every command is listed in COMMANDS")
+    }
+
+    /// Name of the command in `[keys]`
+    pub fn name(self) -> &'static str {
+        self.entry().1
+    }
+
+    /// Where the command applies; global ones apply everywhere
+    pub fn context(self) -> KeyContext {
+        self.entry().2
+    }
+
+    pub fn from_name(name: &str) -> Option<Command> {
+        COMMANDS
+            .iter()
+            .find(|(_, known, _, _)| *known == name)
+            .map(|(command, _, _, _)| *command)
+    }
+
+    /// Whether a key can't mean both this and `other`
+    fn shares_keys_with(self, other: Command) -> bool {
+        self.context().overlaps(other.context())
+    }
+}
+
+/// A key and the modifiers held with it. Shift is part of a character
+/// ('A', '@'), so it only counts for other keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            // Terminals send Ctrl+H, not Ctrl+Shift+h
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            code => code,
+        };
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code, modifiers }
+    }
+
+    /// Parse a key as written in `[keys]`: a character ("r", "A", "?"), a
+    /// key name ("Enter", "PageDown", "F5") or either after "Ctrl+", "Alt+"
+    /// or "Shift+"
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        // A lone "+" is the key itself
+        while let Some((prefix, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("unknown modifier '{}' in '{}'", prefix, text),
+            };
+            rest = key;
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "space" => KeyCode::Char(' '),
+                name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => bail!("unknown key '{}'", text),
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+
+    /// The chord as the footer shows it, e.g. "r", "↑" or "Ctrl+H"
+    pub fn label(&self) -> String {
+        let mut label = String::new();
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                label.push_str(name);
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => label.push_str("Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                label.push(c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => label.push(c),
+            KeyCode::Up => label.push('↑'),
+            KeyCode::Down => label.push('↓'),
+            KeyCode::Left => label.push('←'),
+            KeyCode::Right => label.push('→'),
+            KeyCode::PageUp => label.push_str("PgUp"),
+            KeyCode::PageDown => label.push_str("PgDn"),
+            KeyCode::BackTab => label.push_str("Shift+Tab"),
+            KeyCode::Delete => label.push_str("Del"),
+            KeyCode::F(n) => label.push_str(&format!("F{}", n)),
+            code => label.push_str(&format!("{:?}", code)),
+        }
+        label
+    }
+}
+
+impl From<&KeyEvent> for KeyChord {
+    fn from(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+/// Which keys trigger which commands: the defaults in [`COMMANDS`], with
+/// `[keys]` entries in place of theirs
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    keys: HashMap<Command, Vec<KeyChord>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = COMMANDS
+            .iter()
+            .map(|(command, _, _, defaults)| {
+                let chords = defaults
+                    .iter()
+                    .map(|key| KeyChord::parse(key).expect("default keys parse"))
+                    .collect();
+                (*command, chords)
+            })
+            .collect();
+        Self { keys }
+    }
+}
+
+impl KeyBindings {
+    /// Commands bound to `key`, in [`COMMANDS`] order. The focused pane
+    /// takes the first it knows, and the app the first global one after that.
+    pub fn commands(&self, key: &KeyEvent) -> Vec<Command> {
+        let chord = KeyChord::from(key);
+        COMMANDS
+            .iter()
+            .map(|(command, _, _, _)| *command)
+            .filter(|command| self.keys(*command).contains(&chord))
+            .collect()
+    }
+
+    pub fn keys(&self, command: Command) -> &[KeyChord] {
+        self.keys.get(&command).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether `command` still has its default keys
+    fn is_default(&self, command: Command) -> bool {
+        let defaults = command.entry().3;
+        let keys = self.keys(command);
+        keys.len() == defaults.len()
+            && defaults
+                .iter()
+                .zip(keys)
+                .all(|(default, key)| KeyChord::parse(default).is_ok_and(|d| d == *key))
+    }
+
+    /// Bind `command` to `keys` instead of its current keys. Other commands
+    /// that apply in the same place give those keys up.
+    pub fn bind(&mut self, command: Command, keys: Vec<KeyChord>) {
+        for (other, chords) in self.keys.iter_mut() {
+            if *other != command && other.shares_keys_with(command) {
+                chords.retain(|chord| !keys.contains(chord));
+            }
+        }
+        self.keys.insert(command, keys);
+    }
+
+    /// The keys of `commands` joined for a hint, e.g. "↑/↓"
+    fn label(&self, commands: &[Command]) -> String {
+        let mut labels: Vec<String> = Vec::new();
+        for chord in commands.iter().flat_map(|command| self.keys(*command)) {
+            let label = chord.label();
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+        labels.join("/")
+    }
+}
+
+/// One `[keys]` entry: the command named `name` and the keys it is bound to
+pub fn parse_binding(name: &str, keys: &[String]) -> Result<(Command, Vec<KeyChord>)> {
+    let command = Command::from_name(name).ok_or_else(|| {
+        let known: Vec<&str> = COMMANDS.iter().map(|(_, name, _, _)| *name).collect();
+        anyhow!(
+            "`keys.{}`: unknown command (available: {})",
+            name,
+            known.join(", ")
+        )
+    })?;
+    let chords = keys
+        .iter()
+        .map(|key| KeyChord::parse(key))
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| anyhow!("`keys.{}`: {}", name, e))?;
+    Ok((command, chords))
+}
+
+/// Bindings from `[keys]`, with an error for each entry left at its default
+/// keys: unknown commands and keys, and keys bound to two commands that
+/// apply in the same place
+pub fn compile_bindings(config: &BTreeMap<String, Vec<String>>) -> (KeyBindings, Vec<String>) {
+    let mut bindings = KeyBindings::default();
+    let mut errors = Vec::new();
+    let mut bound: Vec<(Command, KeyChord)> = Vec::new();
+    for (name, keys) in config {
+        let (command, chords) = match parse_binding(name, keys) {
+            Ok(binding) => binding,
+            Err(e) => {
+                errors.push(e.to_string());
+                continue;
+            }
+        };
+        let taken = bound
+            .iter()
+            .find(|(other, chord)| other.shares_keys_with(command) && chords.contains(chord));
+        if let Some((other, chord)) = taken {
+            errors.push(format!(
+                "`keys.{}`: {} is already bound to {}",
+                name,
+                chord.label(),
+                other.name()
+            ));
+            continue;
+        }
+        bound.extend(chords.iter().map(|chord| (command, *chord)));
+        bindings.bind(command, chords);
+    }
+    (bindings, errors)
+}
+
+/// A documented key binding, used to generate footer hints
+#[derive(Debug, Clone, Copy)]
+pub struct KeyHint {
+    pub context: KeyContext,
+    /// Commands whose keys these are, if they can be rebound
+    pub commands: &'static [Command],
+    /// The default keys as the footer shows them
+    pub keys: &'static str,
+    /// Message id of what the keys do
+    pub message: &'static str,
+}
+
+impl KeyHint {
+    /// What the keys do, in the current locale
+    pub fn description(&self) -> &'static str {
+        i18n::t(self.message)
+    }
+
+    /// The keys as bound now: the default text, or the configured keys once
+    /// any of the commands is rebound
+    pub fn keys(&self, bindings: &KeyBindings) -> String {
+        if self.commands.iter().all(|c| bindings.is_default(*c)) {
+            self.keys.to_string()
+        } else {
+            bindings.label(self.commands)
+        }
+    }
+}
+
+const fn hint(
+    context: KeyContext,
+    commands: &'static [Command],
+    keys: &'static str,
+    message: &'static str,
+) -> KeyHint {
+    KeyHint {
+        context,
+        commands,
+        keys,
+        message,
+    }
+}
+
+/// All key bindings, grouped by context in display order
+pub const KEYMAP: &[KeyHint] = &[
+    hint(
+        KeyContext::NewsList,
+        &[Command::Up, Command::Down],
+        "↑/↓",
+        "hint.nav",
+    ),
+    hint(
+        KeyContext::NewsList,
+        &[Command::Open],
+        "Enter/o",
+        "hint.open",
+    ),
+    hint(KeyContext::NewsList, &[Command::Delete], "d", "hint.delete"),
+    hint(
+        KeyContext::NewsList,
+        &[Command::Archive],
+        "e",
+        "hint.archive",
+    ),
+    hint(KeyContext::NewsList, &[Command::Star], "*", "hint.star"),
+    hint(
+        KeyContext::NewsList,
+        &[Command::ArchiveView],
+        "A",
+        "hint.inbox-archive",
+    ),
+    hint(
+        KeyContext::NewsList,
+        &[Command::HideRead],
+        "H",
+        "hint.hide-read",
+    ),
+    hint(
+        KeyContext::NewsList,
+        &[Command::Follow],
+        "F",
+        "hint.follow-story",
+    ),
+    hint(KeyContext::NewsList, &[Command::Pin], "p", "hint.compare"),
+    hint(
+        KeyContext::NewsList,
+        &[Command::FollowingView],
+        "f",
+        "hint.following",
+    ),
+    hint(
+        KeyContext::NewsList,
+        &[Command::FullText],
+        "b",
+        "hint.full-text",
+    ),
+    hint(
+        KeyContext::DetailPane,
+        &[
+            Command::Up,
+            Command::Down,
+            Command::ScrollDown,
+            Command::ScrollUp,
+        ],
+        "↑/↓/j/k",
+        "hint.scroll",
+    ),
+    hint(
+        KeyContext::DetailPane,
+        &[Command::PageUp, Command::PageDown],
+        "PgUp/PgDn",
+        "hint.page",
+    ),
+    hint(
+        KeyContext::DetailPane,
+        &[Command::Open],
+        "Enter/o",
+        "hint.open",
+    ),
+    hint(
+        KeyContext::DetailPane,
+        &[Command::FullText],
+        "b",
+        "hint.full-text",
+    ),
+    hint(KeyContext::SearchBar, &[], "Type", "hint.filter"),
+    hint(KeyContext::SearchBar, &[], "$TICKER", "hint.ticker-filter"),
+    hint(KeyContext::SearchBar, &[], "←/→", "hint.cursor"),
+    hint(KeyContext::SearchBar, &[], "Ctrl+A/E", "hint.start-end"),
+    hint(KeyContext::SearchBar, &[], "Ctrl+W", "hint.delete-word"),
+    hint(KeyContext::SearchBar, &[], "Ctrl+U/K", "hint.delete-to-end"),
+    hint(KeyContext::SearchBar, &[], "Ctrl+V", "hint.paste"),
+    hint(
+        KeyContext::SearchBar,
+        &[Command::Up, Command::Down],
+        "↑/↓",
+        "hint.nav-results",
+    ),
+    hint(
+        KeyContext::SearchBar,
+        &[Command::NextPane],
+        "Tab",
+        "hint.focus-list",
+    ),
+    hint(KeyContext::SearchBar, &[], "Esc", "hint.clear-and-exit"),
+    hint(
+        KeyContext::FilterChips,
+        &[Command::Left, Command::Right],
+        "←/→",
+        "hint.select",
+    ),
+    hint(
+        KeyContext::FilterChips,
+        &[Command::RemoveFilter],
+        "x/Del",
+        "hint.remove-filter",
+    ),
+    hint(
+        KeyContext::ShareMenu,
+        &[Command::Up, Command::Down],
+        "↑/↓",
+        "hint.nav",
+    ),
+    hint(
+        KeyContext::ShareMenu,
+        &[Command::Select],
+        "Enter/key",
+        "hint.share",
+    ),
+    hint(
+        KeyContext::ShareMenu,
+        &[Command::Dismiss],
+        "Esc",
+        "hint.close",
+    ),
+    hint(
+        KeyContext::Help,
+        &[Command::Up, Command::Down],
+        "↑/↓",
+        "hint.scroll",
+    ),
+    hint(
+        KeyContext::Help,
+        &[Command::PageUp, Command::PageDown],
+        "PgUp/PgDn",
+        "hint.page",
+    ),
+    hint(
+        KeyContext::Help,
+        &[Command::Dismiss, Command::Help],
+        "Esc/?",
+        "hint.close",
+    ),
+    hint(
+        KeyContext::StatusHistory,
+        &[Command::Up, Command::Down],
+        "↑/↓",
+        "hint.scroll-history",
+    ),
+    hint(
+        KeyContext::StatusHistory,
+        &[Command::Grow, Command::Shrink],
+        "+/-",
+        "hint.resize",
+    ),
+    hint(
+        KeyContext::StatusHistory,
+        &[Command::History],
+        "Ctrl+H",
+        "hint.close-history",
+    ),
+    hint(
+        KeyContext::StatusReport,
+        &[Command::Select],
+        "Enter",
+        "hint.details",
+    ),
+    hint(
+        KeyContext::StatusReport,
+        &[Command::Up, Command::Down],
+        "↑/↓",
+        "hint.scroll",
+    ),
+    hint(
+        KeyContext::StatusReport,
+        &[Command::Grow, Command::Shrink],
+        "+/-",
+        "hint.resize",
+    ),
+    hint(
+        KeyContext::StatusErrors,
+        &[Command::Up, Command::Down],
+        "↑/↓",
+        "hint.scroll",
+    ),
+    hint(
+        KeyContext::StatusErrors,
+        &[Command::Grow, Command::Shrink],
+        "+/-",
+        "hint.resize",
+    ),
+    hint(
+        KeyContext::StatusErrors,
+        &[Command::ClearErrors],
+        "c",
+        "hint.clear-errors",
+    ),
+    hint(
+        KeyContext::StatusErrors,
+        &[Command::Errors],
+        "E",
+        "hint.close",
+    ),
+    hint(KeyContext::Global, &[Command::Search], "/", "hint.search"),
+    hint(
+        KeyContext::Global,
+        &[Command::CommandLine],
+        ":",
+        "hint.command",
+    ),
+    hint(
+        KeyContext::Global,
+        &[Command::NextPane, Command::PreviousPane],
+        "Tab/Shift+Tab",
+        "hint.switch",
+    ),
+    hint(KeyContext::Global, &[Command::Refresh], "r", "hint.refresh"),
+    hint(
+        KeyContext::Global,
+        &[Command::RefreshSource],
+        "R",
+        "hint.refresh-source",
+    ),
+    hint(
+        KeyContext::Global,
+        &[Command::LastHour, Command::Today, Command::ThisWeek],
+        "1/2/3",
+        "hint.hour-today-week",
+    ),
+    hint(
+        KeyContext::Global,
+        &[Command::AllTime],
+        "0",
+        "hint.all-time",
+    ),
+    hint(
+        KeyContext::Global,
+        &[Command::FilterSource, Command::FilterTag],
+        "s/t",
+        "hint.filter-source-tag",
+    ),
+    hint(KeyContext::Global, &[Command::Share], "S", "hint.share"),
+    hint(
+        KeyContext::Global,
+        &[Command::OpenAll],
+        "O",
+        "hint.open-all-listed",
+    ),
+    hint(KeyContext::Global, &[Command::Density], "v", "hint.density"),
+    hint(KeyContext::Global, &[Command::Undo], "u", "hint.undo"),
+    hint(
+        KeyContext::Global,
+        &[Command::RepeatEdit],
+        ".",
+        "hint.repeat-edit",
+    ),
+    hint(
+        KeyContext::Global,
+        &[Command::RecordMacro, Command::PlayMacro],
+        "m/@",
+        "hint.record-play-macro",
+    ),
+    hint(
+        KeyContext::Global,
+        &[Command::History],
+        "Ctrl+H",
+        "hint.status-history",
+    ),
+    hint(KeyContext::Global, &[Command::Errors], "E", "hint.errors"),
+    hint(KeyContext::Global, &[Command::Help], "?", "hint.help"),
+    hint(
+        KeyContext::Global,
+        &[Command::Quit],
+        "q/Ctrl+C",
+        "hint.quit",
+    ),
+];
+
+/// Bindings for a single context, in keymap order
+pub fn bindings_for(context: KeyContext) -> impl Iterator<Item = &'static KeyHint> {
+    KEYMAP.iter().filter(move |h| h.context == context)
+}
+
+/// Contexts in the order the key help lists them
+const HELP_ORDER: &[KeyContext] = &[
+    KeyContext::NewsList,
+    KeyContext::DetailPane,
+    KeyContext::SearchBar,
+    KeyContext::FilterChips,
+    KeyContext::StatusHistory,
+    KeyContext::StatusReport,
+    KeyContext::StatusErrors,
+    KeyContext::ShareMenu,
+    KeyContext::Global,
+];
+
+/// Every binding for the key help, by context: the heading's message id and
+/// each binding's keys as `bindings` has them with its message id
+pub fn help_sections(bindings: &KeyBindings) -> Vec<(&'static str, Vec<(String, &'static str)>)> {
+    HELP_ORDER
+        .iter()
+        .map(|context| {
+            let rows = bindings_for(*context)
+                .map(|h| (h.keys(bindings), h.message))
+                .filter(|(keys, _)| !keys.is_empty())
+                .collect();
+            (context.title(), rows)
+        })
+        .collect()
+}
+
+/// Footer text for the focused context, with the keys as `bindings` has
+/// them. Global shortcuts are listed after the context's own bindings, except
+/// while typing in the search bar or in a menu where they don't apply.
+pub fn footer_hints(context: KeyContext, bindings: &KeyBindings) -> String {
+    let mut hints: Vec<(String, &KeyHint)> = bindings_for(context)
+        .map(|h| (h.keys(bindings), h))
+        .collect();
+    if !matches!(
+        context,
+        KeyContext::SearchBar | KeyContext::ShareMenu | KeyContext::Help | KeyContext::Global
+    ) {
+        // Skip globals already covered by the context (e.g. Ctrl+H in the history view)
+        let globals: Vec<(String, &KeyHint)> = bindings_for(KeyContext::Global)
+            .map(|g| (g.keys(bindings), g))
+            .filter(|(keys, _)| !hints.iter().any(|(h, _)| h == keys))
+            .collect();
+        hints.extend(globals);
+    }
+
+    hints
+        .iter()
+        // A command rebound to no keys has nothing to show
+        .filter(|(keys, _)| !keys.is_empty())
+        .map(|(keys, h)| format!("{}: {}", keys, h.description()))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(text: &str) -> KeyChord {
+        KeyChord::parse(text).unwrap()
+    }
+
+    fn config(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(name, keys)| {
+                let keys = keys.iter().map(|k| k.to_string()).collect();
+                (name.to_string(), keys)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_characters_names_and_modifiers() {
+        assert_eq!(
+            chord("r"),
+            KeyChord::new(KeyCode::Char('r'), KeyModifiers::NONE)
+        );
+        assert_eq!(chord("Shift+a"), chord("A"));
+        assert_eq!(chord("ctrl+h"), chord("Ctrl+H"));
+        assert_eq!(chord("Shift+Tab"), chord("BackTab"));
+        assert_eq!(
+            chord("+"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+        assert_eq!(chord("Ctrl++").label(), "Ctrl++");
+        assert_eq!(chord("Space").label(), "Space");
+        assert_eq!(chord("pgdn"), chord("PageDown"));
+        assert_eq!(
+            chord("F5"),
+            KeyChord::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert_eq!(chord("Alt+Enter").label(), "Alt+Enter");
+
+        assert!(KeyChord::parse("Hyper+x").is_err());
+        assert!(KeyChord::parse("F25").is_err());
+        assert!(KeyChord::parse("Bogus").is_err());
+        assert!(KeyChord::parse("").is_err());
+    }
+
+    #[test]
+    fn default_keys_mean_one_command_wherever_they_apply() {
+        let bindings = KeyBindings::default();
+        for (a, _, _, _) in COMMANDS {
+            for (b, _, _, _) in COMMANDS {
+                let shared = bindings
+                    .keys(*a)
+                    .iter()
+                    .any(|k| bindings.keys(*b).contains(k));
+                assert!(
+                    a == b || !shared || !a.shares_keys_with(*b),
+                    "{} and {} share a key",
+                    a.name(),
+                    b.name()
+                );
+            }
+        }
+
+        // Enter opens articles and picks menu entries, which never apply together
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(bindings.commands(&enter), [Command::Open, Command::Select]);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(bindings.commands(&ctrl_c), [Command::Quit]);
+    }
+
+    #[test]
+    fn reports_keys_bound_twice_where_they_apply() {
+        let (bindings, errors) = compile_bindings(&config(&[
+            ("archive", &["x"]),
+            ("refresh", &["x"]),
+            ("remove_filter", &["z"]),
+            ("scroll_down", &["z"]),
+        ]));
+        // Archive applies in the list, refresh everywhere: the later entry loses
+        assert_eq!(errors, ["`keys.refresh`: x is already bound to archive"]);
+        assert_eq!(bindings.keys(Command::Archive), [chord("x")]);
+        assert_eq!(bindings.keys(Command::Refresh), [chord("r")]);
+        // The filters and the article never take keys at the same time
+        assert_eq!(bindings.keys(Command::RemoveFilter), [chord("z")]);
+        assert_eq!(bindings.keys(Command::ScrollDown), [chord("z")]);
+    }
+
+    #[test]
+    fn reports_unknown_commands_and_keys() {
+        let (bindings, errors) =
+            compile_bindings(&config(&[("frobnicate", &["x"]), ("refresh", &["F99"])]));
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("`keys.frobnicate`: unknown command (available: up, "));
+        assert_eq!(errors[1], "`keys.refresh`: unknown key 'F99'");
+        assert_eq!(bindings.keys(Command::Refresh), [chord("r")]);
+    }
+
+    #[test]
+    fn binding_takes_the_key_from_commands_where_it_applies() {
+        let mut bindings = KeyBindings::default();
+        bindings.bind(Command::Refresh, vec![chord("d")]);
+        assert!(bindings.keys(Command::Delete).is_empty());
+
+        // The article's keys include opening it
+        bindings.bind(Command::ScrollDown, vec![chord("o")]);
+        assert_eq!(bindings.keys(Command::Open), [chord("Enter")]);
+
+        // Menus don't, so open keeps Enter
+        bindings.bind(Command::Select, vec![chord("Enter"), chord("Space")]);
+        assert_eq!(bindings.keys(Command::Open), [chord("Enter")]);
+
+        // Nor do the filters, so scroll_down keeps o
+        bindings.bind(Command::RemoveFilter, vec![chord("o")]);
+        assert_eq!(bindings.keys(Command::ScrollDown), [chord("o")]);
+    }
+
+    #[test]
+    fn footer_shows_the_keys_as_bound() {
+        let defaults = footer_hints(KeyContext::NewsList, &KeyBindings::default());
+        assert!(defaults.starts_with("↑/↓: Nav | Enter/o: Open | d: Delete"));
+        assert!(defaults.ends_with("q/Ctrl+C: Quit"));
+
+        let (bindings, errors) = compile_bindings(&config(&[
+            ("refresh", &["F5"]),
+            ("delete", &[]),
+            ("quit", &["Q"]),
+        ]));
+        assert!(errors.is_empty());
+        let footer = footer_hints(KeyContext::NewsList, &bindings);
+        assert!(footer.contains("F5: Refresh"));
+        assert!(!footer.contains("Delete"));
+        assert!(footer.ends_with("Q: Quit"));
+
+        // Typing in the search bar leaves the global keys out
+        let search = footer_hints(KeyContext::SearchBar, &bindings);
+        assert!(search.starts_with("Type: "));
+        assert!(!search.contains("Quit"));
+    }
+}
@@ -0,0 +1,301 @@
+//! Plain layout for terminal screen readers (`[app] screen_reader`, or
+//! `--screen-reader`). Every section is a run of full-width text lines that
+//! reads top to bottom: no borders, box drawing or decorative glyphs, states
+//! spelled out as words, and the terminal cursor left on the focused line,
+//! where screen readers look. The bottom lines announce focus and selection
+//! changes as they happen.
+
+use crate::app::{App, TabComponent};
+use crate::i18n::t;
+use crate::models::NewsItem;
+use crate::ui::render_state::RenderState;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Lines given to the status message, which may wrap
+const STATUS_HEIGHT: u16 = 2;
+/// Lines given to the latest announcement, which may wrap
+const ANNOUNCEMENT_HEIGHT: u16 = 2;
+
+/// Glyphs the regular layout and messages use, and what they read as
+const SPELLED_OUT: &[(&str, &str)] = &[
+    ("↑", "Up"),
+    ("↓", "Down"),
+    ("←", "Left"),
+    ("→", "Right"),
+    ("✓", ""),
+    ("✗", "Failed,"),
+    ("⚠", "Warning:"),
+    ("ℹ", ""),
+    ("⚑", "Alert:"),
+    ("★", "Starred"),
+    ("✎", ""),
+    ("▲", "up "),
+    ("▼", "down "),
+    ("●", ""),
+    ("◐", ""),
+    ("◑", ""),
+    ("○", ""),
+    ("·", ","),
+    ("—", ","),
+    ("×", "x"),
+    ("…", "..."),
+];
+
+/// `text` with glyphs replaced by words and runs of spaces collapsed
+pub fn spell_out(text: &str) -> String {
+    let mut text = text.to_string();
+    for (glyph, words) in SPELLED_OUT {
+        if text.contains(glyph) {
+            text = text.replace(glyph, &format!(" {} ", words));
+        }
+    }
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(" ,", ",")
+}
+
+/// Draw `app` with the plain layout
+pub fn render(f: &mut Frame, area: Rect, app: &App, state: &RenderState) {
+    let has_filters = !app.filter_chips.is_empty();
+    let fixed = 1 + u16::from(has_filters) + STATUS_HEIGHT + ANNOUNCEMENT_HEIGHT;
+    let rest = area.height.saturating_sub(fixed);
+    let list_height = (rest / 2).max(2);
+    let [search_area, filters_area, list_area, detail_area, status_area, announce_area] =
+        Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(u16::from(has_filters)),
+            Constraint::Length(list_height),
+            Constraint::Min(0),
+            Constraint::Length(STATUS_HEIGHT),
+            Constraint::Length(ANNOUNCEMENT_HEIGHT),
+        ])
+        .areas(area);
+
+    // Search or command line
+    let label = if app.search_bar.is_command() {
+        "Command: "
+    } else {
+        "Search: "
+    };
+    let query = app.search_bar.query();
+    f.render_widget(Paragraph::new(format!("{}{}", label, query)), search_area);
+    let mut cursor = None;
+    if app.focused_component == TabComponent::SearchBar {
+        let x = (label.width() + query.width()) as u16;
+        cursor = Some((search_area.x + x.min(search_area.width - 1), search_area.y));
+    }
+
+    if has_filters {
+        let labels: Vec<String> = app.filter_chips.chips().iter().map(|c| c.label()).collect();
+        let text = format!("Filters: {}", labels.join(", "));
+        f.render_widget(Paragraph::new(text), filters_area);
+        if app.focused_component == TabComponent::FilterChips {
+            cursor = Some((filters_area.x, filters_area.y));
+        }
+    }
+
+    let selected_row = render_list(f, list_area, app);
+    if app.focused_component == TabComponent::NewsList {
+        cursor = Some((list_area.x, selected_row));
+    }
+
+    // The overlays and the expanded status views take the article's place
+    let detail_lines = if app.help.is_visible() {
+        std::iter::once(t("title.help").to_string())
+            .chain(app.help.text_lines())
+            .collect()
+    } else if app.share_menu.is_visible() {
+        share_lines(app)
+    } else if let Some((heading, lines)) = app
+        .status_bar
+        .plain_expanded()
+        .filter(|_| app.focused_component == TabComponent::StatusBar)
+    {
+        std::iter::once(heading).chain(lines).collect()
+    } else if let Some(pinned) = state
+        .pinned_view()
+        .filter(|_| app.focused_component == TabComponent::PinnedPane)
+    {
+        // One article at a time: the pinned one while its pane has focus
+        pinned
+            .article
+            .map(|a| article_lines(a, &pinned))
+            .map(|lines| std::iter::once("Pinned".to_string()).chain(lines).collect())
+            .unwrap_or_default()
+    } else {
+        let compared = state
+            .pinned
+            .map(|p| format!("Compared with pinned: {}", p.title));
+        compared
+            .into_iter()
+            .chain(
+                state
+                    .article
+                    .map(|a| article_lines(a, state))
+                    .unwrap_or_default(),
+            )
+            .collect()
+    };
+    let scroll = if app.help.is_visible() {
+        app.help.scroll_offset()
+    } else if app.focused_component == TabComponent::DetailPane {
+        app.detail_pane.scroll_offset()
+    } else if app.focused_component == TabComponent::PinnedPane {
+        app.pinned_pane.scroll_offset()
+    } else {
+        0
+    };
+    let detail: Vec<Line> = detail_lines
+        .iter()
+        .map(|line| Line::from(spell_out(line)))
+        .collect();
+    f.render_widget(
+        Paragraph::new(detail)
+            .wrap(Wrap { trim: true })
+            .scroll((scroll, 0)),
+        detail_area,
+    );
+    if app.help.is_visible() && detail_area.height > 0 {
+        cursor = Some((detail_area.x, detail_area.y));
+    } else if app.share_menu.is_visible() {
+        let row = 1 + app.share_menu.selected_index() as u16;
+        cursor = Some((
+            detail_area.x,
+            detail_area.y + row.min(detail_area.height.saturating_sub(1)),
+        ));
+    } else if matches!(
+        app.focused_component,
+        TabComponent::DetailPane | TabComponent::PinnedPane | TabComponent::StatusBar
+    ) && detail_area.height > 0
+    {
+        cursor = Some((detail_area.x, detail_area.y));
+    }
+
+    let status = format!("Status: {}", spell_out(&app.status_bar.plain_status()));
+    f.render_widget(
+        Paragraph::new(status).wrap(Wrap { trim: true }),
+        status_area,
+    );
+
+    f.render_widget(
+        Paragraph::new(spell_out(app.announcement())).wrap(Wrap { trim: true }),
+        announce_area,
+    );
+
+    if let Some((x, y)) = cursor {
+        f.set_cursor(x, y);
+    }
+}
+
+/// A heading, then one line per article around the selection, which is
+/// marked with ">". Returns the row of the selection.
+fn render_list(f: &mut Frame, area: Rect, app: &App) -> u16 {
+    let list = &app.news_list;
+    let items = list.filtered_items();
+    let selected = list.selected_position();
+    let heading = match selected {
+        Some(index) => format!("Articles, {} of {}:", index + 1, items.len()),
+        None => "Articles: none".to_string(),
+    };
+
+    let rows = usize::from(area.height.saturating_sub(1));
+    let index = selected.unwrap_or(0);
+    let first = index
+        .saturating_sub(rows / 2)
+        .min(items.len().saturating_sub(rows));
+    let mut lines = vec![Line::from(heading)];
+    for (i, item) in items.iter().enumerate().skip(first).take(rows) {
+        let text = spell_out(&list.describe(item));
+        if Some(i) == selected {
+            lines.push(Line::styled(
+                format!("> {}", text),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            lines.push(Line::from(format!("  {}", text)));
+        }
+    }
+    f.render_widget(Paragraph::new(lines), area);
+    area.y + 1 + (index - first) as u16
+}
+
+/// The selected article, one fact per line
+fn article_lines(article: &NewsItem, state: &RenderState) -> Vec<String> {
+    let mut lines = vec![format!("Article: {}", article.title)];
+    let mut meta = vec![
+        article.source.clone(),
+        article.published.format("%Y-%m-%d %H:%M UTC").to_string(),
+    ];
+    if let Some(author) = &article.author {
+        meta.push(format!("by {}", author));
+    }
+    if article.starred {
+        meta.push("starred".to_string());
+    }
+    lines.push(meta.join(", "));
+    if !article.tags.is_empty() {
+        lines.push(format!("Tags: {}", article.tags.join(", ")));
+    }
+    if !article.tickers.is_empty() {
+        let quotes: Vec<String> = article
+            .tickers
+            .iter()
+            .map(|ticker| match state.article_quote(ticker) {
+                Some(q) => format!(
+                    "{} {:.2}, {} {:.2} percent",
+                    ticker,
+                    q.price,
+                    if q.change >= 0.0 { "up" } else { "down" },
+                    q.change_percent.abs()
+                ),
+                None => format!("{} loading", ticker),
+            })
+            .collect();
+        lines.push(format!("Tickers: {}", quotes.join("; ")));
+    }
+    if !article.also_reported_by.is_empty() {
+        lines.push(format!(
+            "Also reported by: {}",
+            article.also_reported_by.join(", ")
+        ));
+    }
+    if let Some(previous) = &article.previous {
+        lines.push(format!(
+            "Updated {}",
+            previous.revised_at.format("%Y-%m-%d %H:%M UTC")
+        ));
+        if previous.title != article.title {
+            lines.push(format!("Earlier title: {}", previous.title));
+        }
+    }
+    lines.push(format!("URL: {}", article.url));
+    let (label, body) = match &article.content {
+        Some(content) => (t("detail.content"), content),
+        None => (t("detail.summary"), &article.summary),
+    };
+    lines.push(label.to_string());
+    lines.extend(body.lines().map(str::to_string));
+    lines
+}
+
+/// The share menu's targets, the selection marked with ">"
+fn share_lines(app: &App) -> Vec<String> {
+    let menu = &app.share_menu;
+    let mut lines = vec![format!("Share {}:", menu.article())];
+    for (i, (key, name)) in menu.entries().iter().enumerate() {
+        let marker = if i == menu.selected_index() { ">" } else { " " };
+        match key {
+            Some(key) => lines.push(format!("{} {}: {}", marker, key, name)),
+            None => lines.push(format!("{} {}", marker, name)),
+        }
+    }
+    lines
+}
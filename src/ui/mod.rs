@@ -1,57 +1,170 @@
+pub mod columns;
 pub mod component;
 pub mod components;
+pub mod diff;
+#[cfg(test)]
+mod golden;
+pub mod highlight;
+pub mod keymap;
+pub mod linear;
+pub mod render_state;
 pub mod status_message;
 
 pub use component::{Action, Component};
-pub use components::{DetailPaneComponent, NewsListComponent, SearchBarComponent, StatusBarComponent};
+pub use components::{
+    DetailPaneComponent, FilterChipsComponent, HelpOverlayComponent, NewsListComponent,
+    SearchBarComponent, ShareMenuComponent, StatusBarComponent, TickerTapeComponent,
+};
+pub use render_state::RenderState;
 pub use status_message::{MessageLevel, StatusMessage};
 
-use crate::app::AppState;
+use crate::app::App;
+use crate::tr;
 use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    Terminal,
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Paragraph, Wrap},
+    Frame, Terminal,
 };
 use std::io;
 
-pub fn draw_ui(
-    term: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
-    search_bar: &SearchBarComponent,
-    news_list: &NewsListComponent,
-    detail_pane: &DetailPaneComponent,
-    status_bar: &StatusBarComponent,
-    _app_state: AppState,
-) -> io::Result<()> {
+/// Smallest terminal that fits the search bar, a usable content area, and the status line
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 12;
+
+const SEARCH_BAR_HEIGHT: u16 = 3;
+const MIN_CONTENT_HEIGHT: u16 = 5;
+const STATUS_LINE_HEIGHT: u16 = 3;
+
+/// Placeholder shown instead of the layout when the terminal is below the minimum size
+fn render_too_small(f: &mut Frame, area: Rect) {
+    let text = tr!(
+        "ui.too-small",
+        width = area.width,
+        height = area.height,
+        min_width = MIN_WIDTH,
+        min_height = MIN_HEIGHT
+    );
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow))
+        .wrap(Wrap { trim: true });
+
+    // Vertically center when there's room for both lines
+    let y_offset = area.height.saturating_sub(2) / 2;
+    let centered = Rect {
+        y: area.y + y_offset,
+        height: area.height - y_offset,
+        ..area
+    };
+    f.render_widget(paragraph, centered);
+}
+
+/// Draw one frame of `app`. Generic over the backend so frames can also be
+/// drawn to a `TestBackend`; ratatui only flushes cells that changed since
+/// the previous frame.
+pub fn draw_ui<B: Backend>(term: &mut Terminal<B>, app: &App) -> io::Result<()> {
+    let state = app.render_state();
+    let App {
+        ticker_tape,
+        search_bar,
+        filter_chips,
+        news_list,
+        detail_pane,
+        pinned_pane,
+        status_bar,
+        share_menu,
+        help,
+        ..
+    } = app;
     term.draw(|f| {
-        // Main vertical split: search bar + content area + status bar
-        let status_bar_height = status_bar.get_height();
+        let area = f.size();
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            render_too_small(f, area);
+            return;
+        }
+        if app.is_screen_reader() {
+            linear::render(f, area, app, &state);
+            return;
+        }
+
+        // Optional ticker tape row above everything else
+        let tape_height = if ticker_tape.is_visible() { 1 } else { 0 };
+        let outer_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(tape_height), Constraint::Min(0)].as_ref())
+            .split(area);
+
+        if ticker_tape.is_visible() {
+            ticker_tape.render(f, outer_chunks[0], &state);
+        }
+
+        // Main vertical split: search bar + content area + status bar.
+        // An expanded history never squeezes the content area below its minimum.
+        let max_status_height = outer_chunks[1]
+            .height
+            .saturating_sub(SEARCH_BAR_HEIGHT + MIN_CONTENT_HEIGHT)
+            .max(STATUS_LINE_HEIGHT);
+        let status_bar_height = status_bar.get_height().min(max_status_height);
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
                 [
-                    Constraint::Length(3),                    // Search bar
-                    Constraint::Min(5),                       // Content area
-                    Constraint::Length(status_bar_height),    // Status bar (dynamic)
+                    Constraint::Length(SEARCH_BAR_HEIGHT),              // Search bar
+                    Constraint::Min(MIN_CONTENT_HEIGHT),                // Content area
+                    Constraint::Length(status_bar_height),              // Status bar (dynamic)
                 ]
                 .as_ref(),
             )
-            .split(f.size());
+            .split(outer_chunks[1]);
 
-        // Render search bar at top
-        search_bar.render(f, main_chunks[0]);
+        // Render search bar at top, with active filter chips on its right
+        if filter_chips.is_empty() {
+            search_bar.render(f, main_chunks[0], &state);
+        } else {
+            let chips_width = filter_chips.width().min(main_chunks[0].width / 2);
+            let search_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0), Constraint::Length(chips_width)].as_ref())
+                .split(main_chunks[0]);
+            search_bar.render(f, search_chunks[0], &state);
+            filter_chips.render(f, search_chunks[1], &state);
+        }
 
-        // Content area horizontal split: news list (60%) + detail pane (40%)
-        let content_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
-            .split(main_chunks[1]);
+        // Content area horizontal split: news list (60%) + detail pane (40%),
+        // or while comparing, list (30%) + pinned article + selected article
+        if let Some(pinned_state) = state.pinned_view() {
+            let content_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Percentage(35),
+                        Constraint::Percentage(35),
+                    ]
+                    .as_ref(),
+                )
+                .split(main_chunks[1]);
+            news_list.render(f, content_chunks[0], &state);
+            pinned_pane.render(f, content_chunks[1], &pinned_state);
+            detail_pane.render(f, content_chunks[2], &state);
+        } else {
+            let content_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+                .split(main_chunks[1]);
+            news_list.render(f, content_chunks[0], &state);
+            detail_pane.render(f, content_chunks[1], &state);
+        }
 
-        // Render components
-        news_list.render(f, content_chunks[0]);
-        detail_pane.render(f, content_chunks[1]);
+        // Render status bar at bottom, its toasts over the content area
+        status_bar.render(f, main_chunks[2], &state);
+        status_bar.render_toast(f, main_chunks[1]);
 
-        // Render status bar at bottom
-        status_bar.render(f, main_chunks[2]);
+        // Overlays draw last, over everything else
+        share_menu.render(f, area, &state);
+        help.render(f, area, &state);
     })?;
     Ok(())
 }
@@ -0,0 +1,43 @@
+use crate::models::{NewsItem, Quote};
+
+/// Read-only view of the app data drawn in one frame. Built fresh by
+/// `App::render_state` before every draw, so components render what the app
+/// holds instead of copies they have to keep in sync. Components keep only
+/// their own view state (focus, scroll, selection).
+#[derive(Debug, Clone, Default)]
+pub struct RenderState<'a> {
+    /// Article shown in the detail pane
+    pub article: Option<&'a NewsItem>,
+    /// Latest known quote for each of the article's tickers, in ticker order;
+    /// tickers still loading are missing
+    pub article_quotes: Vec<&'a Quote>,
+    /// Quotes scrolling across the ticker tape
+    pub tape_quotes: &'a [Quote],
+    /// Article pinned to compare the selected one against
+    pub pinned: Option<&'a NewsItem>,
+    /// Latest known quote for each of the pinned article's tickers
+    pub pinned_quotes: Vec<&'a Quote>,
+}
+
+impl<'a> RenderState<'a> {
+    /// Quote for `symbol` among the article's quotes
+    pub fn article_quote(&self, symbol: &str) -> Option<&'a Quote> {
+        self.article_quotes
+            .iter()
+            .copied()
+            .find(|q| q.symbol == symbol)
+    }
+
+    /// The frame as the pinned article's pane draws it, with the pinned
+    /// article in the selected one's place
+    pub fn pinned_view(&self) -> Option<RenderState<'a>> {
+        let pinned = self.pinned?;
+        Some(RenderState {
+            article: Some(pinned),
+            article_quotes: self.pinned_quotes.clone(),
+            tape_quotes: self.tape_quotes,
+            pinned: None,
+            pinned_quotes: Vec::new(),
+        })
+    }
+}
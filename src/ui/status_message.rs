@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use std::time::Duration;
 
+use crate::app::RefreshReport;
+
 /// Severity level determines styling and persistence behavior
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MessageLevel {
@@ -9,6 +11,33 @@ pub enum MessageLevel {
     Warning,
     Error,
     Loading,
+    /// An alert keyword showed up; stays until dismissed
+    Alert,
+}
+
+impl MessageLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageLevel::Info => "info",
+            MessageLevel::Success => "success",
+            MessageLevel::Warning => "warning",
+            MessageLevel::Error => "error",
+            MessageLevel::Loading => "loading",
+            MessageLevel::Alert => "alert",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "info" => Some(MessageLevel::Info),
+            "success" => Some(MessageLevel::Success),
+            "warning" => Some(MessageLevel::Warning),
+            "error" => Some(MessageLevel::Error),
+            "loading" => Some(MessageLevel::Loading),
+            "alert" => Some(MessageLevel::Alert),
+            _ => None,
+        }
+    }
 }
 
 /// A status message to display in the status bar
@@ -16,66 +45,122 @@ pub enum MessageLevel {
 pub struct StatusMessage {
     pub level: MessageLevel,
     pub text: String,
+    /// When the status bar received the message, by its clock
     pub timestamp: DateTime<Utc>,
     pub auto_dismiss_after: Option<Duration>,
+    /// Stays up until dismissed, whatever the status bar's display times
+    pub sticky: bool,
+    /// How many times the message arrived; repeats are counted, not re-shown
+    pub count: usize,
+    /// Per-source breakdown the message summarizes, shown on Enter
+    pub report: Option<Box<RefreshReport>>,
 }
 
 impl StatusMessage {
-    pub fn info(text: String) -> Self {
+    /// The timestamp is left unset; the status bar stamps the message from
+    /// its clock when it arrives
+    fn new(level: MessageLevel, text: String, auto_dismiss_after: Option<Duration>) -> Self {
         Self {
-            level: MessageLevel::Info,
+            level,
             text,
-            timestamp: Utc::now(),
-            auto_dismiss_after: Some(Duration::from_secs(5)),
+            timestamp: DateTime::default(),
+            auto_dismiss_after,
+            count: 1,
+            report: None,
+            sticky: false,
         }
     }
 
+    pub fn info(text: String) -> Self {
+        Self::new(MessageLevel::Info, text, Some(Duration::from_secs(5)))
+    }
+
     pub fn success(text: String) -> Self {
-        Self {
-            level: MessageLevel::Success,
-            text,
-            timestamp: Utc::now(),
-            auto_dismiss_after: Some(Duration::from_secs(3)),
-        }
+        Self::new(MessageLevel::Success, text, Some(Duration::from_secs(3)))
     }
 
     pub fn warning(text: String) -> Self {
-        Self {
-            level: MessageLevel::Warning,
-            text,
-            timestamp: Utc::now(),
-            auto_dismiss_after: Some(Duration::from_secs(5)),
-        }
+        Self::new(MessageLevel::Warning, text, Some(Duration::from_secs(5)))
     }
 
     pub fn error(text: String) -> Self {
-        Self {
-            level: MessageLevel::Error,
-            text,
-            timestamp: Utc::now(),
-            auto_dismiss_after: None, // Errors persist
-        }
+        // Errors also stay behind the status bar's error badge until cleared
+        Self::new(MessageLevel::Error, text, Some(Duration::from_secs(10)))
     }
 
     pub fn loading(text: String) -> Self {
+        Self::new(MessageLevel::Loading, text, None)
+    }
+
+    pub fn alert(text: String) -> Self {
+        Self::new(MessageLevel::Alert, text, None)
+    }
+
+    /// A message from an earlier session, for the history only
+    pub fn restored(
+        level: MessageLevel,
+        text: String,
+        count: usize,
+        timestamp: DateTime<Utc>,
+    ) -> Self {
         Self {
-            level: MessageLevel::Loading,
+            level,
             text,
-            timestamp: Utc::now(),
+            timestamp,
             auto_dismiss_after: None,
+            count: count.max(1),
+            report: None,
+            sticky: false,
         }
     }
 
-    /// Check if message should be auto-dismissed based on age
-    pub fn should_dismiss(&self) -> bool {
+    /// Summary of a refresh batch; expandable unless everything went fine
+    pub fn report(report: RefreshReport) -> Self {
+        let text = report.summary();
+        let mut message = match report.level() {
+            MessageLevel::Success => Self::success(text),
+            MessageLevel::Error => Self::error(text),
+            _ => Self::warning(text),
+        };
+        if report.has_details() {
+            message.report = Some(Box::new(report));
+        }
+        message
+    }
+
+    /// Keep the message up until it is dismissed (Esc) or replaced
+    pub fn sticky(mut self) -> Self {
+        self.sticky = true;
+        self.auto_dismiss_after = None;
+        self
+    }
+
+    /// Check if message should be auto-dismissed based on its age at `now`
+    pub fn should_dismiss(&self, now: DateTime<Utc>) -> bool {
         if let Some(duration) = self.auto_dismiss_after {
-            let age = Utc::now()
-                .signed_duration_since(self.timestamp)
-                .to_std()
-                .unwrap_or(Duration::ZERO);
-            age >= duration
+            self.age(now) >= duration
         } else {
             false
         }
     }
+
+    pub fn age(&self, now: DateTime<Utc>) -> Duration {
+        now.signed_duration_since(self.timestamp)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Same level and text, so it can be counted instead of shown again
+    pub fn is_repeat_of(&self, other: &StatusMessage) -> bool {
+        self.level == other.level && self.text == other.text
+    }
+
+    /// Text with a repeat counter, e.g. "Dropped 3 unparsable items (×4)"
+    pub fn display_text(&self) -> String {
+        if self.count > 1 {
+            format!("{} (×{})", self.text, self.count)
+        } else {
+            self.text.clone()
+        }
+    }
 }
@@ -0,0 +1,32 @@
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+
+/// Watch `dir` (non-recursively) and call `on_change` when a created, modified
+/// or removed path passes `filter`. Watching stops when the handle is dropped.
+///
+/// Editors often save by writing a temp file and renaming it over the original,
+/// so callers interested in one file should watch its directory and filter by name.
+pub fn watch_dir(
+    dir: &Path,
+    filter: impl Fn(&Path) -> bool + Send + 'static,
+    on_change: impl Fn() + Send + 'static,
+) -> Result<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        // Reads (including our own reloads) must not trigger another reload
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        if event.paths.iter().any(|p| filter(p)) {
+            on_change();
+        }
+    })
+    .context("Failed to create file watcher")?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", dir.display()))?;
+    Ok(watcher)
+}